@@ -1,6 +1,12 @@
-use std::path::PathBuf;
+use std::{
+  path::{Path, PathBuf},
+  sync::Arc,
+};
 
-use rolldown_core::{Asset, BuildResult, BundlerCore};
+use rolldown_core::{
+  Asset, AssetHandler, BuildError, BuildMetrics, BuildResult, BundlerCore, ModuleGraphSnapshot,
+  ModuleInfo, ResolutionRecord, UsedSymbols,
+};
 use rolldown_plugin::BuildPlugin;
 use sugar_path::AsPath;
 
@@ -9,6 +15,7 @@ use crate::InputOptions;
 pub struct Bundler {
   core: BundlerCore,
   cwd: PathBuf,
+  root: PathBuf,
 }
 
 impl Bundler {
@@ -16,47 +23,116 @@ impl Bundler {
     Self::with_plugins(input_opts, vec![])
   }
 
+  /// A bundler running hooks of `plugins`, for Rust embedders supplying plugins without the node
+  /// binding. It's the same as `with_plugins`.
+  pub fn new_with_plugins(input_opts: InputOptions, plugins: Vec<Box<dyn BuildPlugin>>) -> Self {
+    Self::with_plugins(input_opts, plugins)
+  }
+
   pub fn with_plugins(input_opts: InputOptions, plugins: Vec<Box<dyn BuildPlugin>>) -> Self {
-    rolldown_tracing::enable_tracing_on_demand();
     let cwd = input_opts.cwd.clone();
+    let root = input_opts.root.clone().unwrap_or_else(|| cwd.clone());
 
     let bundler = BundlerCore::with_plugins(
       rolldown_core::BuildInputOptions {
         input: input_opts.input,
         treeshake: input_opts.treeshake,
+        drop_unused_entry_exports: input_opts.drop_unused_entry_exports,
         cwd: input_opts.cwd,
+        root: input_opts.root,
         is_external: input_opts.is_external,
         on_warn: input_opts.on_warn,
         shim_missing_exports: input_opts.shim_missing_exports,
         preserve_symlinks: input_opts.preserve_symlinks,
+        platform: input_opts.platform,
+        absolute_path_root: input_opts.absolute_path_root,
+        alias: input_opts.alias,
+        resolve_extensions: input_opts.resolve_extensions,
+        main_fields: input_opts.main_fields,
+        condition_names: input_opts.condition_names,
+        allow_exports_fallback: input_opts.allow_exports_fallback,
+        resolution_diagnostics: input_opts.resolution_diagnostics,
+        wasm_esm_integration: input_opts.wasm_esm_integration,
+        safe_hoisting: input_opts.safe_hoisting,
+        cache: input_opts.cache,
+        parse_recovery: input_opts.parse_recovery,
+        define: input_opts.define,
+        strict_define: input_opts.strict_define,
+        log_level: input_opts.log_level,
+        max_parallel_transforms: input_opts.max_parallel_transforms,
+        max_symbols: input_opts.max_symbols,
         builtins: rolldown_core::BuiltinsOptions {
           tsconfig: input_opts.builtins.tsconfig.unwrap_or_default(),
+          discover_tsconfig: input_opts.builtins.discover_tsconfig,
+          jsx: input_opts.builtins.jsx,
+          replace: input_opts.builtins.replace,
+          conditional_compilation: input_opts.builtins.conditional_compilation,
+          loaders: input_opts.builtins.loaders,
           ..Default::default()
         },
       },
       plugins,
     );
-    Self { cwd, core: bundler }
+    Self {
+      cwd,
+      root,
+      core: bundler,
+    }
+  }
+
+  /// The directory paths are displayed relative to. Render errors via `to_readable_string` with it.
+  pub fn root(&self) -> &Path {
+    &self.root
+  }
+
+  /// Metrics of the latest build, or `None` if nothing has been built yet.
+  pub fn metrics(&self) -> Option<&BuildMetrics> {
+    self.core.metrics()
+  }
+
+  /// Snapshot of the module graph of the latest build, or `None` if nothing has been built yet.
+  pub fn graph_snapshot(&self) -> Option<&ModuleGraphSnapshot> {
+    self.core.graph_snapshot()
+  }
+
+  /// How each specifier of the latest build is resolved, or `None` if `resolution_diagnostics` isn't
+  /// enabled.
+  pub fn resolution_report(&self) -> Option<&[ResolutionRecord]> {
+    self.core.resolution_report()
+  }
+
+  /// Top-level symbols kept by treeshaking in the latest build, or `None` if `treeshake` isn't
+  /// enabled or nothing has been built yet.
+  pub fn used_symbols(&self) -> Option<&UsedSymbols> {
+    self.core.used_symbols()
+  }
+
+  /// Info of the normal module with the id in the latest build, or `None` if it isn't in the graph.
+  pub fn module_info(&self, id: &str) -> Option<&ModuleInfo> {
+    self.core.module_info(id)
+  }
+
+  /// Drop modules cached by previous builds, so the next build reads and transforms all of them
+  /// again. No-op if `cache` isn't enabled.
+  pub fn clear_cache(&mut self) {
+    self.core.clear_cache();
   }
 
   pub async fn write(&mut self, output_options: crate::OutputOptions) -> BuildResult<Vec<Asset>> {
-    let dir = output_options.dir.clone().unwrap_or_else(|| {
-      self
-        .cwd
-        .as_path()
-        .join("dist")
-        .to_string_lossy()
-        .to_string()
+    let dir = self.output_dir(&output_options);
+    let on_asset = output_options.streaming.then(|| {
+      let dir = PathBuf::from(&dir);
+      Arc::new(move |asset: &Asset| write_asset(&dir, asset).map_err(BuildError::io_error))
+        as AssetHandler
     });
+    let streaming = output_options.streaming;
     let output = self
       .core
-      .build(rolldown_core::BuildOutputOptions {
-        entry_file_names: output_options.entry_file_names,
-        chunk_file_names: output_options.chunk_file_names,
-        format: output_options.format,
-        export_mode: output_options.export_mode,
-      })
+      .build(build_output_options(output_options, dir.clone(), on_asset))
       .await?;
+    if streaming {
+      return Ok(output);
+    }
 
     std::fs::create_dir_all(&dir).unwrap_or_else(|_| {
       panic!(
@@ -72,7 +148,7 @@ impl Bundler {
           std::fs::create_dir_all(p)?;
         }
       };
-      std::fs::write(dest, &chunk.content).unwrap_or_else(|_| {
+      std::fs::write(dest, chunk.bytes()).unwrap_or_else(|_| {
         panic!(
           "Failed to write file in {:?}",
           dir.as_path().join(&chunk.filename)
@@ -86,16 +162,71 @@ impl Bundler {
     &mut self,
     output_options: crate::OutputOptions,
   ) -> BuildResult<Vec<Asset>> {
+    let dir = self.output_dir(&output_options);
     let output = self
       .core
-      .build(rolldown_core::BuildOutputOptions {
-        entry_file_names: output_options.entry_file_names,
-        chunk_file_names: output_options.chunk_file_names,
-        format: output_options.format,
-        export_mode: output_options.export_mode,
-      })
+      .build(build_output_options(output_options, dir, None))
       .await?;
 
     Ok(output)
   }
+
+  /// `dir` of `output_options`, or `dist` in `cwd`. Both `write` and `generate` render with it, so
+  /// chunks referring to relative externals are the same whether they're written or not.
+  fn output_dir(&self, output_options: &crate::OutputOptions) -> String {
+    output_options.dir.clone().unwrap_or_else(|| {
+      self
+        .cwd
+        .as_path()
+        .join("dist")
+        .to_string_lossy()
+        .to_string()
+    })
+  }
+}
+
+fn build_output_options(
+  output_options: crate::OutputOptions,
+  dir: String,
+  on_asset: Option<AssetHandler>,
+) -> rolldown_core::BuildOutputOptions {
+  rolldown_core::BuildOutputOptions {
+    dir: Some(dir),
+    entry_file_names: output_options.entry_file_names,
+    chunk_file_names: output_options.chunk_file_names,
+    manual_chunks: output_options.manual_chunks,
+    split_directives: output_options.split_directives,
+    preserve_modules: output_options.preserve_modules,
+    file_name_conflict: output_options.file_name_conflict,
+    format: output_options.format,
+    export_mode: output_options.export_mode,
+    bom: output_options.bom,
+    banner: output_options.banner,
+    footer: output_options.footer,
+    intro: output_options.intro,
+    outro: output_options.outro,
+    amd: output_options.amd,
+    name: output_options.name,
+    mangle: output_options.mangle,
+    reserved_names: output_options.reserved_names,
+    minify: output_options.minify,
+    minify_options: output_options.minify_options,
+    api_surface: output_options.api_surface,
+    manifest: output_options.manifest,
+    codegen: output_options.codegen,
+    comments: output_options.comments,
+    global_this_polyfill: output_options.global_this_polyfill,
+    rewrite_import_meta_url: output_options.rewrite_import_meta_url,
+    import_map: output_options.import_map,
+    sourcemap: output_options.sourcemap,
+    on_asset,
+  }
+}
+
+fn write_asset(dir: &Path, asset: &Asset) -> std::io::Result<()> {
+  let dest = dir.join(&asset.filename);
+  if let Some(p) = dest.parent() {
+    std::fs::create_dir_all(p)?;
+  }
+  std::fs::write(dest, asset.bytes())
 }
@@ -1,5 +1,9 @@
+use std::collections::BTreeMap;
+
 use derivative::Derivative;
-pub use rolldown_core::TsConfig;
+pub use rolldown_core::{
+  DecoratorVersion, Jsx, JsxOptions, JsxRuntime, Loader, ReplaceOptions, TsConfig,
+};
 
 #[derive(Derivative)]
 #[derivative(Debug)]
@@ -7,12 +11,30 @@ pub struct BuiltinsOptions {
   /// None means disable the builtin
   /// None means default
   pub tsconfig: Option<TsConfig>,
+  /// Compile each TypeScript module with options of its nearest `tsconfig.json`, falling back to
+  /// `tsconfig` for options it doesn't set. Useful in monorepos whose packages have different
+  /// tsconfigs.
+  pub discover_tsconfig: bool,
+  pub jsx: Jsx,
+  /// None means disable the builtin
+  pub replace: Option<ReplaceOptions>,
+  /// Strip code between conditional compilation comments like `// @if DEV` and `// @endif` before
+  /// parsing, unless `DEV` is defined as a truthy value by `define`.
+  pub conditional_compilation: bool,
+  /// Loaders of extensions without the leading dot, like `md` to `Loader::Text`. They take
+  /// precedence over the loaders detected by extensions.
+  pub loaders: BTreeMap<String, Loader>,
 }
 
 impl Default for BuiltinsOptions {
   fn default() -> Self {
     Self {
       tsconfig: Some(Default::default()),
+      discover_tsconfig: false,
+      jsx: Default::default(),
+      replace: None,
+      conditional_compilation: false,
+      loaders: Default::default(),
     }
   }
 }
@@ -1,8 +1,12 @@
-use std::{path::PathBuf, sync::Arc};
+use std::{collections::HashMap, path::PathBuf, sync::Arc};
 
 use derivative::Derivative;
 use futures::{future, FutureExt};
-pub use rolldown_core::{InputItem, IsExternal, WarningHandler};
+pub use rolldown_core::{
+  external_by_patterns, InputItem, IsExternal, Platform, WarningHandler, DEFAULT_CONDITION_NAMES,
+  DEFAULT_RESOLVE_EXTENSIONS,
+};
+use rolldown_tracing::LevelFilter;
 mod builtins;
 pub use builtins::*;
 
@@ -11,14 +15,77 @@ pub use builtins::*;
 pub struct InputOptions {
   pub input: Vec<InputItem>,
   pub preserve_symlinks: bool,
+  /// Adds the condition of the platform, `browser` or `node`, to the default conditions of
+  /// resolving. Conditions set in `condition_names` are kept as they are.
+  pub platform: Option<Platform>,
+  /// Resolve server-absolute imports like `/src/main.js` in this directory first, instead of only in
+  /// the root of the file system.
+  pub absolute_path_root: Option<PathBuf>,
+  /// Replace specifiers before resolving, like `("@", "/project/src")` for `@/utils`. A key matches
+  /// the whole specifier or its leading path segments. Relative replacements are relative to `cwd`.
+  pub alias: Vec<(String, String)>,
+  /// Extensions tried in order for specifiers without one, like `.mjs`. Each must begin with a dot.
+  pub resolve_extensions: Vec<String>,
+  /// Fields of `package.json` tried in order for the entry of packages without `exports`. `None`
+  /// only tries `main`.
+  pub main_fields: Option<Vec<String>>,
+  /// Conditions of `exports`. The default ones follow the one of `platform` if it's set, while
+  /// others are used as they are. The `browser` field of packages is respected if they include
+  /// `browser`.
+  pub condition_names: Vec<String>,
+  /// Resolve subpaths blocked by `exports` of packages in the file system with a warning,
+  /// instead of failing.
+  pub allow_exports_fallback: bool,
+  /// Record how each specifier is resolved, and by which plugin or resolver. Read it via
+  /// `resolution_report` of the bundler after building.
+  pub resolution_diagnostics: bool,
+  /// Load `.wasm` modules like the ESM integration of WebAssembly, which exposes their exports as
+  /// named exports. The `.wasm` files are emitted as assets and instantiated by generated modules
+  /// with top-level await, so only the `es` format is supported.
+  pub wasm_esm_integration: bool,
+  /// Wrap modules that can't be safely hoisted into the scope of the chunk, like CommonJS modules
+  /// and scripts using `eval` or `with`, in functions registered in a runtime module registry.
+  /// Other modules are still hoisted.
+  pub safe_hoisting: bool,
+  /// Keep the transformed code of modules across builds of the same bundler, and reuse it if the
+  /// source is unchanged. Modules read from the disk are kept parsed as well, and reused without
+  /// reading them again if their modified time is unchanged. Plugins could opt out via
+  /// `should_transform_cached_module`.
+  pub cache: bool,
+  /// Warn about parse errors swc could recover from, like a legacy octal literal in strict mode, and
+  /// continue with the recovered AST instead of failing the build. Useful in watch mode.
+  pub parse_recovery: bool,
+  /// Replace free references of globals and their member expressions, like `process.env.NODE_ENV`,
+  /// with expressions in code, like `"\"production\""`. Unlike `builtins.replace`, it works on the
+  /// AST, so locals shadowing the globals are left alone, and replaced conditions are folded by
+  /// treeshaking. Names are tested by `builtins.conditional_compilation` as well.
+  pub define: HashMap<String, String>,
+  /// Fail the build if any key of `define` isn't referenced by any module, instead of warning about
+  /// it.
+  pub strict_define: bool,
   pub treeshake: bool,
+  /// Treat exports of user-defined entries like any other exports in treeshaking,
+  /// so unused ones get dropped. Useful for apps, but breaks the public API of libraries.
+  pub drop_unused_entry_exports: bool,
   pub cwd: PathBuf,
+  /// Paths in errors, warnings, source maps and comments of the output are displayed relative to it,
+  /// while `cwd` is still used for resolving. Defaults to `cwd`.
+  pub root: Option<PathBuf>,
   #[derivative(Debug = "ignore")]
   pub is_external: IsExternal,
   #[derivative(Debug = "ignore")]
   pub on_warn: WarningHandler,
   pub shim_missing_exports: bool,
   pub builtins: BuiltinsOptions,
+  /// Limit how many modules could be transformed by plugins at the same time, which is
+  /// separate from reading files. `None` means no limit.
+  pub max_parallel_transforms: Option<usize>,
+  /// Fail the build if the module graph has more top-level symbols than it, instead of letting
+  /// memory grow with huge graphs. `None` means no limit. See `BuildMetrics::symbols`.
+  pub max_symbols: Option<usize>,
+  /// Level of the logs emitted by builds of this bundler only, so other bundlers may use other
+  /// levels. `RUST_LOG` takes precedence over it. `None` leaves logs to the global subscriber.
+  pub log_level: Option<LevelFilter>,
 }
 
 pub fn default_warning_handler() -> WarningHandler {
@@ -32,12 +99,31 @@ impl Default for InputOptions {
     Self {
       input: Default::default(),
       preserve_symlinks: true,
+      platform: None,
+      absolute_path_root: None,
+      alias: Default::default(),
+      resolve_extensions: DEFAULT_RESOLVE_EXTENSIONS.map(ToString::to_string).to_vec(),
+      main_fields: None,
+      condition_names: DEFAULT_CONDITION_NAMES.map(ToString::to_string).to_vec(),
+      allow_exports_fallback: false,
+      resolution_diagnostics: false,
+      wasm_esm_integration: false,
+      safe_hoisting: false,
+      cache: false,
+      parse_recovery: false,
+      define: Default::default(),
+      strict_define: false,
       treeshake: true,
+      drop_unused_entry_exports: false,
       cwd: std::env::current_dir().unwrap(),
+      root: None,
       is_external: Arc::new(|_, _, _| future::ready(Ok(false)).boxed()),
       on_warn: default_warning_handler(),
       shim_missing_exports: false,
       builtins: Default::default(),
+      max_parallel_transforms: None,
+      max_symbols: None,
+      log_level: None,
     }
   }
 }
@@ -4,8 +4,17 @@ mod output_options;
 pub use {
   bundler::Bundler,
   input_options::{
-    default_warning_handler, BuiltinsOptions, InputItem, InputOptions, IsExternal, TsConfig,
+    default_warning_handler, external_by_patterns, BuiltinsOptions, DecoratorVersion, InputItem,
+    InputOptions, IsExternal, Jsx, JsxOptions, JsxRuntime, Loader, Platform, ReplaceOptions,
+    TsConfig, WarningHandler, DEFAULT_CONDITION_NAMES, DEFAULT_RESOLVE_EXTENSIONS,
   },
-  output_options::{ExportMode, FileNameTemplate, ModuleFormat, OutputOptions},
-  rolldown_core::{Asset, BuildResult},
+  output_options::{
+    AmdOptions, CodegenOptions, CodegenTarget, ExportMode, FileNameConflict, FileNameTemplate,
+    MinifyOptions, ModuleFormat, OutputComments, OutputOptions, SourceMapType,
+  },
+  rolldown_core::{
+    Asset, BuildMetrics, BuildResult, GraphDiff, ModuleGraphSnapshot, ModuleInfo, ResolutionRecord,
+    ResolutionSource, UsedSymbol, UsedSymbols,
+  },
+  rolldown_tracing::LevelFilter,
 };
@@ -1,14 +1,91 @@
+use std::collections::BTreeMap;
+
 use derivative::Derivative;
-pub use rolldown_core::{file_name::FileNameTemplate, ExportMode, ModuleFormat};
+pub use rolldown_core::{
+  file_name::FileNameTemplate, AmdOptions, CodegenOptions, CodegenTarget, ExportMode,
+  FileNameConflict, MinifyOptions, ModuleFormat, OutputComments, SourceMapType,
+};
 
 #[derive(Derivative)]
 #[derivative(Debug)]
 pub struct OutputOptions {
   pub dir: Option<String>,
+  /// File names of entry chunks. Besides `[name]`, `[hash]` is the content hash of the chunk, and
+  /// `[ext]` is `js`.
   pub entry_file_names: FileNameTemplate,
+  /// File names of chunks split from entries, like dynamic imports. Placeholders are the same as
+  /// the ones of `entry_file_names`.
   pub chunk_file_names: FileNameTemplate,
+  /// Put modules into chunks named by the keys, whatever chunks they would be in otherwise, like
+  /// `vendor` for `node_modules`. Modules are matched by their ids, or by globs of their paths
+  /// relative to `cwd`, like `node_modules/**`. Dependencies of matched modules go to the same
+  /// chunk, unless they're matched by other chunks. Entries and dynamically imported modules keep
+  /// their own chunks.
+  pub manual_chunks: Option<BTreeMap<String, Vec<String>>>,
+  /// Give each module with directives like `"use client"` its own chunk, so the directives apply to
+  /// the module only. Otherwise, directives are only emitted by chunks of modules with them as
+  /// entries, and dropped from modules hoisted into other chunks with a warning.
+  pub split_directives: bool,
+  /// Emit a chunk for each module, instead of hoisting modules into chunks of entries. Chunks are
+  /// named by `entry_file_names`, where `[name]` is the path of the module relative to the deepest
+  /// directory containing all of them, so the output keeps the directory structure, like
+  /// `lib/util.js`. It lets consumers of libraries treeshake them by files. `manual_chunks` is
+  /// ignored.
+  pub preserve_modules: bool,
+  /// What to do if multiple chunks are rendered to the same file name, which would overwrite each
+  /// other otherwise.
+  pub file_name_conflict: FileNameConflict,
   pub format: ModuleFormat,
   pub export_mode: ExportMode,
+  /// Prepend a UTF-8 BOM to generated chunks. It's skipped for chunks starting with a shebang.
+  pub bom: bool,
+  /// Code prepended to each chunk outside the wrapper of the format, like a license comment.
+  pub banner: Option<String>,
+  /// Code appended to each chunk outside the wrapper of the format. The `//# sourceMappingURL=`
+  /// comment still comes last.
+  pub footer: Option<String>,
+  /// Code prepended inside the wrapper of the format, like the function of `iife`. It follows the
+  /// `"use strict"` directive of `cjs` and `iife`.
+  pub intro: Option<String>,
+  /// Code appended inside the wrapper of the format.
+  pub outro: Option<String>,
+  /// Only used by the `amd` format.
+  pub amd: AmdOptions,
+  /// Name of the global variable holding exports of the entry, only used by the `iife` format.
+  pub name: Option<String>,
+  /// Shorten local names of generated chunks. Top level names are kept, since they may be exported.
+  pub mangle: bool,
+  /// Names that are never mangled, such as ones accessed by reflection or strings.
+  pub reserved_names: Vec<String>,
+  /// Run the minifier of swc over generated chunks, after treeshaking and scope hoisting. Unlike
+  /// `codegen.minify`, it rewrites the code as well, see `minify_options`.
+  pub minify: bool,
+  pub minify_options: MinifyOptions,
+  /// Emit `[name].api.json` for each entry chunk, which lists exports of the chunk.
+  pub api_surface: bool,
+  /// Emit `manifest.json`, which maps names of entries in `input` to the files of their chunks, and
+  /// chunks and css they import, like the manifest of Vite.
+  pub manifest: bool,
+  /// Control how generated chunks are printed.
+  pub codegen: CodegenOptions,
+  /// Comments of modules kept in generated chunks. It doesn't apply to `minify`.
+  pub comments: OutputComments,
+  /// Declare `globalThis` at the top of chunks referencing it, if `codegen.target` is older than
+  /// ES2020, which `globalThis` was introduced in.
+  pub global_this_polyfill: bool,
+  /// Replace `import.meta.url` with an equivalent of the format, since only ESM has `import.meta`.
+  /// It's derived from `__filename` for `cjs`, from the `module` dependency for `amd`, and from the
+  /// current script or the page for `iife`, which is best-effort.
+  pub rewrite_import_meta_url: bool,
+  /// URLs of external modules. If set, `[name].importmap.json` is emitted for each chunk of the
+  /// `esm` format, which maps externals imported by the chunk to their URLs.
+  pub import_map: Option<BTreeMap<String, String>>,
+  /// Emit source maps of generated chunks, which map them back to the transformed code of modules.
+  pub sourcemap: Option<SourceMapType>,
+  /// Write each asset to `dir` as soon as it's rendered, instead of after the whole output is
+  /// generated. Assets returned by `write` only have file names then, with empty `content`. It's
+  /// ignored by `generate`.
+  pub streaming: bool,
 }
 
 impl Default for OutputOptions {
@@ -16,9 +93,33 @@ impl Default for OutputOptions {
     Self {
       entry_file_names: FileNameTemplate::from("[name].js".to_string()),
       chunk_file_names: FileNameTemplate::from("[name]-[hash].js".to_string()),
+      manual_chunks: None,
+      split_directives: false,
+      preserve_modules: false,
+      file_name_conflict: Default::default(),
       dir: None,
       format: ModuleFormat::Esm,
       export_mode: ExportMode::Auto,
+      bom: false,
+      banner: None,
+      footer: None,
+      intro: None,
+      outro: None,
+      amd: Default::default(),
+      name: None,
+      mangle: false,
+      reserved_names: Default::default(),
+      minify: false,
+      minify_options: Default::default(),
+      api_surface: false,
+      manifest: false,
+      codegen: Default::default(),
+      comments: Default::default(),
+      global_this_polyfill: false,
+      rewrite_import_meta_url: true,
+      import_map: None,
+      sourcemap: None,
+      streaming: false,
     }
   }
 }
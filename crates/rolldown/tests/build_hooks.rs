@@ -0,0 +1,108 @@
+use std::sync::{Arc, Mutex};
+
+use rolldown::{Bundler, ModuleFormat, OutputOptions};
+use rolldown_plugin::{
+  async_trait, BuildEndReturn, BuildPlugin, BuildStartReturn, Context, PluginName,
+  ResolvedInputOptions,
+};
+
+mod common;
+use common::plugin_fixture_entry;
+
+/// Records calls of `build_start` and `build_end`.
+#[derive(Debug, Default)]
+struct RecordPlugin {
+  calls: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait::async_trait]
+impl BuildPlugin for RecordPlugin {
+  fn name(&self) -> PluginName {
+    std::borrow::Cow::Borrowed("test:record")
+  }
+
+  async fn build_start(
+    &self,
+    _ctx: &mut Context,
+    options: &ResolvedInputOptions,
+  ) -> BuildStartReturn {
+    let (name, import) = &options.input[0];
+    self
+      .calls
+      .lock()
+      .unwrap()
+      .push(format!("build_start {name} {import}"));
+    Ok(())
+  }
+
+  async fn build_end(
+    &self,
+    _ctx: &mut Context,
+    errors: Option<&rolldown_error::Errors>,
+  ) -> BuildEndReturn {
+    let errors = errors.map_or("ok".to_string(), |errors| {
+      errors
+        .iter()
+        .map(|err| err.kind.code())
+        .collect::<Vec<_>>()
+        .join(",")
+    });
+    self
+      .calls
+      .lock()
+      .unwrap()
+      .push(format!("build_end {errors}"));
+    Ok(())
+  }
+}
+
+async fn build(import: &str) -> Vec<String> {
+  build_with(import, OutputOptions::default()).await
+}
+
+async fn build_with(import: &str, output_options: OutputOptions) -> Vec<String> {
+  let plugin = RecordPlugin::default();
+  let calls = plugin.calls.clone();
+  let mut bundler = Bundler::with_plugins(
+    plugin_fixture_entry("build_hooks", import),
+    vec![Box::new(plugin)],
+  );
+  let _ = bundler.generate(output_options).await;
+  let calls = calls.lock().unwrap();
+  calls.clone()
+}
+
+#[tokio::test]
+async fn build_hooks_wrap_the_build() {
+  assert_eq!(
+    build("./main.js").await,
+    vec!["build_start main ./main.js", "build_end ok"]
+  );
+}
+
+#[tokio::test]
+async fn build_end_is_called_if_the_build_fails() {
+  assert_eq!(
+    build("./missing.js").await,
+    vec![
+      "build_start main ./missing.js",
+      "build_end UNRESOLVED_ENTRY"
+    ]
+  );
+}
+
+#[tokio::test]
+async fn build_end_receives_errors_of_rendering() {
+  let output_options = OutputOptions {
+    format: ModuleFormat::Iife,
+    name: Some("not an identifier".to_string()),
+    ..Default::default()
+  };
+  assert_eq!(
+    build_with("./main.js", output_options).await,
+    vec![
+      "build_start main ./main.js",
+      "build_end ILLEGAL_IDENTIFIER_AS_NAME"
+    ]
+  );
+}
@@ -0,0 +1,76 @@
+use rolldown::{Bundler, InputItem, InputOptions, OutputOptions};
+use rolldown_plugin::{
+  async_trait, BuildPlugin, Context, PluginName, TransformArgs, TransformReturn,
+};
+
+mod common;
+use common::plugin_fixture_input;
+
+const SETUP: &str = "globalThis.__setup = true;";
+
+/// Emits the same setup snippet for every module it transforms.
+#[derive(Debug)]
+struct SetupRuntime;
+
+#[async_trait::async_trait]
+impl BuildPlugin for SetupRuntime {
+  fn name(&self) -> PluginName {
+    std::borrow::Cow::Borrowed("test:setup-runtime")
+  }
+
+  async fn transform(&self, ctx: &mut Context, _args: &mut TransformArgs) -> TransformReturn {
+    ctx.emit_bundle_intro(SETUP);
+    Ok(None)
+  }
+}
+
+#[tokio::test]
+async fn bundle_intro_is_added_once_at_the_top() {
+  let mut bundler = Bundler::with_plugins(
+    plugin_fixture_input("bundle_intro"),
+    vec![Box::new(SetupRuntime)],
+  );
+  let output = bundler.generate(OutputOptions::default()).await.unwrap();
+  assert_eq!(output.len(), 1);
+  let content = &output[0].content;
+  assert_eq!(content.matches(SETUP).count(), 1, "{content}");
+  assert!(content.starts_with(SETUP), "{content}");
+  assert!(content.contains("console.log(a, b)"), "{content}");
+}
+
+#[tokio::test]
+async fn bundle_intro_is_added_again_from_the_cache() {
+  let mut bundler = Bundler::with_plugins(
+    InputOptions {
+      cache: true,
+      ..plugin_fixture_input("bundle_intro")
+    },
+    vec![Box::new(SetupRuntime)],
+  );
+  bundler.generate(OutputOptions::default()).await.unwrap();
+  let output = bundler.generate(OutputOptions::default()).await.unwrap();
+  let content = &output[0].content;
+  assert!(content.starts_with(SETUP), "{content}");
+}
+
+#[tokio::test]
+async fn bundle_intro_is_added_to_the_shared_chunk_evaluated_first() {
+  let mut input = plugin_fixture_input("bundle_intro");
+  input.input.push(InputItem {
+    name: "other".to_string(),
+    import: "./other.js".to_string(),
+  });
+  let mut bundler = Bundler::with_plugins(input, vec![Box::new(SetupRuntime)]);
+  let output = bundler.generate(OutputOptions::default()).await.unwrap();
+  assert_eq!(output.len(), 3);
+  let with_intro = output
+    .iter()
+    .filter(|asset| asset.content.contains(SETUP))
+    .collect::<Vec<_>>();
+  let [shared] = &with_intro[..] else {
+    panic!("{output:#?}");
+  };
+  let content = &shared.content;
+  assert!(content.starts_with(SETUP), "{content}");
+  assert!(!content.contains("console.log"), "{content}");
+}
@@ -1,10 +1,10 @@
-use std::{
-  path::{Path, PathBuf},
-  str::FromStr,
-};
+// Each test binary only uses some of the helpers.
+#![allow(dead_code)]
+
+use std::path::{Path, PathBuf};
 
 use rolldown::Bundler;
-use rolldown::{Asset, BuildResult, ExportMode, ModuleFormat, OutputOptions};
+use rolldown::{Asset, BuildResult, InputItem, InputOptions, OutputOptions};
 use rolldown_test_utils::tester::Tester;
 
 pub struct CompiledFixture {
@@ -24,7 +24,10 @@ impl CompiledFixture {
       .flat_map(|asset| {
         [
           format!("---------- {} ----------", asset.filename),
-          asset.content.trim().to_string(),
+          match &asset.binary {
+            Some(binary) => format!("<{} bytes>", binary.len()),
+            None => asset.content.trim().to_string(),
+          },
         ]
       })
       .chain(if self.tester.warnings.lock().unwrap().is_empty() {
@@ -66,9 +69,7 @@ pub async fn compile_fixture(test_config_path: &Path) -> CompiledFixture {
   let output = bundler
     .generate(OutputOptions {
       // dir: Some(fixture_path.join("dist").to_string_lossy().to_string()),
-      format: ModuleFormat::from_str(&tester.config.output.format).unwrap(),
-      export_mode: ExportMode::from_str(&tester.config.output.export_mode).unwrap(),
-      ..Default::default()
+      ..tester.output_options()
     })
     .await;
   let fixture_name = fixture_path
@@ -120,3 +121,27 @@ pub fn run_test(test_config_path: &Path) {
     insta::assert_snapshot!("output", compiled_fx.output_friendly_to_snapshot());
   });
 }
+
+/// `tests/plugins/<name>`, the fixture of tests that can't be expressed by `test.config.json`.
+pub fn plugin_fixture(name: &str) -> PathBuf {
+  PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+    .join("tests/plugins")
+    .join(name)
+}
+
+/// Input options bundling `main.js` of `tests/plugins/<name>` as the entry `main`.
+pub fn plugin_fixture_input(name: &str) -> InputOptions {
+  plugin_fixture_entry(name, "./main.js")
+}
+
+/// Input options bundling `import` of `tests/plugins/<name>` as the entry `main`.
+pub fn plugin_fixture_entry(name: &str, import: &str) -> InputOptions {
+  InputOptions {
+    input: vec![InputItem {
+      name: "main".to_string(),
+      import: import.to_string(),
+    }],
+    cwd: plugin_fixture(name),
+    ..Default::default()
+  }
+}
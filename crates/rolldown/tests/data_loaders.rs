@@ -0,0 +1,22 @@
+use rolldown::{Bundler, OutputOptions};
+
+mod common;
+use common::{plugin_fixture, plugin_fixture_entry};
+
+// The reason of the error comes from the YAML parser, so only the prefix is asserted here.
+#[tokio::test]
+async fn invalid_yaml_is_reported() {
+  let mut bundler = Bundler::new(plugin_fixture_entry("data_loaders", "./invalid.js"));
+  let errors = bundler
+    .generate(OutputOptions::default())
+    .await
+    .expect_err("Expected the YAML to fail to parse")
+    .into_vec();
+
+  assert_eq!(errors.len(), 1);
+  assert_eq!(errors[0].kind.code(), "PARSE_ERROR");
+  assert!(errors[0]
+    .kind
+    .to_readable_string(plugin_fixture("data_loaders"))
+    .starts_with(r#"Failed to parse "invalid.yaml" as YAML: "#));
+}
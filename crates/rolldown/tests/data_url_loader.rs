@@ -0,0 +1,98 @@
+use std::collections::BTreeMap;
+
+use rolldown::{BuiltinsOptions, Bundler, InputOptions, Loader, OutputOptions};
+use rolldown_plugin::{
+  async_trait, BuildPlugin, Context, LoadArgs, LoadOutput, LoadReturn, PluginName, TransformArgs,
+  TransformReturn,
+};
+
+mod common;
+use common::{plugin_fixture_entry, plugin_fixture_input};
+
+/// Loads `.svg` files as data URLs by switching the loader in `transform`.
+#[derive(Debug)]
+struct SvgAsDataUrl;
+
+#[async_trait::async_trait]
+impl BuildPlugin for SvgAsDataUrl {
+  fn name(&self) -> PluginName {
+    std::borrow::Cow::Borrowed("test:svg-as-data-url")
+  }
+
+  async fn transform(&self, _ctx: &mut Context, args: &mut TransformArgs) -> TransformReturn {
+    if args.id.to_string().ends_with(".svg") {
+      *args.loader = Loader::DataUrl;
+    }
+    Ok(None)
+  }
+}
+
+/// Loads `.png` files as data URLs, but returns code that isn't base64 encoded.
+#[derive(Debug)]
+struct InvalidDataUrl;
+
+#[async_trait::async_trait]
+impl BuildPlugin for InvalidDataUrl {
+  fn name(&self) -> PluginName {
+    std::borrow::Cow::Borrowed("test:invalid-data-url")
+  }
+
+  async fn load(&self, _ctx: &mut Context, args: &mut LoadArgs) -> LoadReturn {
+    Ok(args.id.to_string().ends_with(".png").then(|| LoadOutput {
+      code: r#"", alert("injected"), ""#.to_string(),
+      loader: Some(Loader::DataUrl),
+    }))
+  }
+}
+
+#[tokio::test]
+async fn binary_loaders_export_base64_content() {
+  let mut bundler = Bundler::new(InputOptions {
+    builtins: BuiltinsOptions {
+      loaders: BTreeMap::from([
+        ("png".to_string(), Loader::DataUrl),
+        ("bin".to_string(), Loader::Base64),
+      ]),
+      ..Default::default()
+    },
+    ..plugin_fixture_input("data_url_loader")
+  });
+  let output = bundler.generate(OutputOptions::default()).await.unwrap();
+
+  let content = &output[0].content;
+  assert!(
+    content.contains(r#""data:image/png;base64,iVBORw0KGgr/AA==""#),
+    "{content}"
+  );
+  assert!(content.contains(r#""3q2+7w==""#), "{content}");
+}
+
+#[tokio::test]
+async fn binary_loaders_set_by_transform_hooks_encode_the_source() {
+  let mut bundler = Bundler::with_plugins(
+    plugin_fixture_entry("data_url_loader", "./svg.js"),
+    vec![Box::new(SvgAsDataUrl)],
+  );
+  let output = bundler.generate(OutputOptions::default()).await.unwrap();
+
+  let content = &output[0].content;
+  assert!(
+    content.contains(r#""data:image/svg+xml;base64,PHN2Zy8+""#),
+    "{content}"
+  );
+}
+
+#[tokio::test]
+async fn binary_loaders_reject_sources_that_are_not_base64() {
+  let errors = Bundler::with_plugins(
+    plugin_fixture_entry("data_url_loader", "./png.js"),
+    vec![Box::new(InvalidDataUrl)],
+  )
+  .generate(OutputOptions::default())
+  .await
+  .expect_err("Expected the source to be rejected")
+  .into_vec();
+
+  assert_eq!(errors.len(), 1);
+  assert_eq!(errors[0].kind.code(), "PARSE_ERROR");
+}
@@ -0,0 +1,62 @@
+use rolldown::{BuiltinsOptions, Bundler, DecoratorVersion, InputOptions, OutputOptions, TsConfig};
+
+mod common;
+use common::plugin_fixture_entry;
+
+// Transformed decorators are mostly helpers of swc, so only whether they are transformed is asserted
+// instead of snapshotting the output. Kept decorators are covered by `fixtures/decorators`.
+async fn generate(import: &str, tsconfig: TsConfig) -> String {
+  let mut bundler = Bundler::new(InputOptions {
+    builtins: BuiltinsOptions {
+      tsconfig: Some(tsconfig),
+      ..Default::default()
+    },
+    ..plugin_fixture_entry("decorators", import)
+  });
+  let output = bundler.generate(OutputOptions::default()).await.unwrap();
+  assert_eq!(output.len(), 1);
+  output[0].content.clone()
+}
+
+#[tokio::test]
+async fn decorators_are_transformed_by_default() {
+  let content = generate("./main.ts", Default::default()).await;
+  assert!(!content.contains("@sealed"), "{content}");
+  assert!(content.contains("sealed"), "{content}");
+}
+
+#[tokio::test]
+async fn legacy_decorators() {
+  let content = generate(
+    "./main.ts",
+    TsConfig {
+      decorator_version: DecoratorVersion::Legacy,
+      ..Default::default()
+    },
+  )
+  .await;
+  assert!(!content.contains("@sealed"), "{content}");
+  assert!(content.contains("sealed"), "{content}");
+}
+
+#[tokio::test]
+async fn decorators_of_javascript_modules() {
+  let content = generate(
+    "./main.js",
+    TsConfig {
+      js_decorators: true,
+      ..Default::default()
+    },
+  )
+  .await;
+  assert!(!content.contains("@sealed"), "{content}");
+  assert!(content.contains("sealed"), "{content}");
+}
+
+#[tokio::test]
+async fn decorators_of_javascript_modules_are_opt_in() {
+  Bundler::new(plugin_fixture_entry("decorators", "./main.js"))
+    .generate(OutputOptions::default())
+    .await
+    .expect_err("Expected decorators of JavaScript modules to fail parsing");
+}
@@ -0,0 +1,47 @@
+use rolldown::{Bundler, OutputOptions};
+
+mod common;
+use common::{plugin_fixture, plugin_fixture_entry};
+
+// The output is covered by `fixtures/directives`, only the module info is asserted here.
+async fn generate(entry: &str) -> Bundler {
+  let mut bundler = Bundler::new(plugin_fixture_entry("directives", &format!("./{entry}")));
+  bundler.generate(OutputOptions::default()).await.unwrap();
+  bundler
+}
+
+#[tokio::test]
+async fn directives_of_entries_are_in_module_info() {
+  let bundler = generate("button.js").await;
+  let info = bundler
+    .module_info(
+      &plugin_fixture("directives")
+        .join("button.js")
+        .to_string_lossy(),
+    )
+    .unwrap();
+  assert!(info.is_entry);
+  assert_eq!(info.directives, vec!["use client".to_string()]);
+}
+
+#[tokio::test]
+async fn directives_of_hoisted_modules_are_in_module_info() {
+  let bundler = generate("main.js").await;
+  let info = bundler
+    .module_info(
+      &plugin_fixture("directives")
+        .join("button.js")
+        .to_string_lossy(),
+    )
+    .unwrap();
+  assert!(!info.is_entry);
+  assert_eq!(info.directives, vec!["use client".to_string()]);
+  let info = bundler
+    .module_info(
+      &plugin_fixture("directives")
+        .join("main.js")
+        .to_string_lossy(),
+    )
+    .unwrap();
+  assert!(info.directives.is_empty());
+}
@@ -0,0 +1,59 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+use rolldown::{Bundler, InputItem, InputOptions, OutputOptions};
+use rolldown_plugin::{
+  async_trait, BuildPlugin, Context, ExportsUsage, ExportsUsageArgs, ExportsUsageReturn, PluginName,
+};
+
+mod common;
+use common::plugin_fixture;
+
+#[derive(Debug, Default)]
+struct ExportsUsageCollector {
+  exports_usage: Arc<Mutex<BTreeMap<String, ExportsUsage>>>,
+}
+
+#[async_trait::async_trait]
+impl BuildPlugin for ExportsUsageCollector {
+  fn name(&self) -> PluginName {
+    std::borrow::Cow::Borrowed("test:exports-usage-collector")
+  }
+
+  async fn exports_usage(&self, _ctx: &mut Context, args: &ExportsUsageArgs) -> ExportsUsageReturn {
+    *self.exports_usage.lock().unwrap() = args
+      .exports_usage
+      .iter()
+      .map(|(id, usage)| (id.to_string(), usage.clone()))
+      .collect();
+    Ok(())
+  }
+}
+
+#[tokio::test]
+async fn exports_usage_marks_used_and_unused_exports() {
+  let cwd = plugin_fixture("exports_usage");
+  let collector = ExportsUsageCollector::default();
+  let exports_usage = collector.exports_usage.clone();
+  let mut bundler = Bundler::with_plugins(
+    InputOptions {
+      input: vec![InputItem {
+        name: "main".to_string(),
+        import: "./main.js".to_string(),
+      }],
+      cwd: cwd.clone(),
+      ..Default::default()
+    },
+    vec![Box::new(collector)],
+  );
+  bundler.generate(OutputOptions::default()).await.unwrap();
+
+  let exports_usage = exports_usage.lock().unwrap();
+  assert_eq!(
+    exports_usage.get(&*cwd.join("lib.js").to_string_lossy()),
+    Some(&ExportsUsage {
+      used: vec!["used".into()],
+      unused: vec!["unused".into()],
+    })
+  );
+}
@@ -0,0 +1,15 @@
+use rolldown::external_by_patterns;
+
+#[test]
+fn invalid_patterns_are_reported() {
+  let error = external_by_patterns(["^node:", "(unclosed"])
+    .err()
+    .expect("Expected the pattern to be rejected");
+  assert_eq!(error.kind.code(), "INVALID_OPTION");
+  assert!(
+    error
+      .to_string()
+      .starts_with(r#"Invalid "external" pattern "(unclosed": "#),
+    "{error}"
+  );
+}
@@ -0,0 +1,107 @@
+use rolldown::{Bundler, OutputOptions};
+use rolldown_plugin::{
+  async_trait, BuildPlugin, Context, External, PluginName, ResolveArgs, ResolveReturn, ResolvedId,
+};
+
+mod common;
+use common::{plugin_fixture, plugin_fixture_input};
+
+/// Resolves `./lib/dep.js` to an external absolute path.
+#[derive(Debug)]
+struct ExternalDepPlugin {
+  external: External,
+}
+
+#[async_trait::async_trait]
+impl BuildPlugin for ExternalDepPlugin {
+  fn name(&self) -> PluginName {
+    std::borrow::Cow::Borrowed("test:external-dep")
+  }
+
+  async fn resolve(&self, _ctx: &mut Context, args: &mut ResolveArgs) -> ResolveReturn {
+    if args.specifier == "./lib/dep.js" {
+      return Ok(Some(ResolvedId {
+        id: plugin_fixture("external_relative")
+          .join("lib/dep.js")
+          .to_string_lossy()
+          .to_string(),
+        external: self.external,
+      }));
+    }
+    Ok(None)
+  }
+}
+
+async fn build(external: External, dir: Option<&str>) -> String {
+  let mut bundler = Bundler::with_plugins(
+    plugin_fixture_input("external_relative"),
+    vec![Box::new(ExternalDepPlugin { external })],
+  );
+  let output = bundler
+    .generate(OutputOptions {
+      dir: dir.map(ToString::to_string),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+  assert_eq!(output.len(), 1);
+  output[0].content.clone()
+}
+
+#[tokio::test]
+async fn relative_externals_keep_relative_specifiers() {
+  let cwd = plugin_fixture("external_relative");
+  let content = build(External::Relative, Some(&cwd.to_string_lossy())).await;
+  assert!(
+    content.contains(r#"import { dep } from "./lib/dep.js";"#),
+    "{content}"
+  );
+}
+
+#[tokio::test]
+async fn relative_externals_are_relative_to_the_output_dir() {
+  let content = build(External::Relative, Some("dist")).await;
+  assert!(
+    content.contains(r#"import { dep } from "../lib/dep.js";"#),
+    "{content}"
+  );
+}
+
+#[tokio::test]
+async fn relative_externals_are_relative_to_dist_by_default() {
+  let content = build(External::Relative, None).await;
+  assert!(
+    content.contains(r#"import { dep } from "../lib/dep.js";"#),
+    "{content}"
+  );
+}
+
+#[tokio::test]
+async fn generate_and_write_render_the_same_code() {
+  let generated = build(External::Relative, None).await;
+
+  let mut bundler = Bundler::with_plugins(
+    plugin_fixture_input("external_relative"),
+    vec![Box::new(ExternalDepPlugin {
+      external: External::Relative,
+    })],
+  );
+  let written = bundler.write(Default::default()).await.unwrap();
+  std::fs::remove_dir_all(plugin_fixture("external_relative").join("dist")).unwrap();
+
+  assert_eq!(written.len(), 1);
+  assert_eq!(written[0].content, generated);
+}
+
+#[tokio::test]
+async fn absolute_externals_keep_absolute_ids() {
+  let id = plugin_fixture("external_relative").join("lib/dep.js");
+  let import = format!(r#"import {{ dep }} from "{}";"#, id.display());
+
+  let content = build(External::Absolute, None).await;
+  assert!(content.contains(&import), "{content}");
+
+  // `true` keeps ids as is too, even for relative specifiers.
+  let content = build(External::True, None).await;
+  assert!(content.contains(&import), "{content}");
+}
@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+
+use rolldown::{
+  Asset, BuildResult, Bundler, FileNameConflict, FileNameTemplate, InputItem, InputOptions,
+  OutputOptions,
+};
+use rolldown_plugin::{
+  async_trait, BuildPlugin, Context, PluginName, RenderChunkArgs, RenderChunkReturn,
+  RenderOutputOptions,
+};
+
+mod common;
+use common::{plugin_fixture, plugin_fixture_input};
+
+async fn generate(value: &str) -> Vec<Asset> {
+  let mut bundler = Bundler::new(InputOptions {
+    define: HashMap::from([("VALUE".to_string(), value.to_string())]),
+    ..plugin_fixture_input("file_names")
+  });
+  let mut output = bundler
+    .generate(OutputOptions {
+      entry_file_names: FileNameTemplate::new("[name]-[hash].[ext]".to_string()),
+      chunk_file_names: FileNameTemplate::new("chunks/[name]-[hash].[ext]".to_string()),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+  output.sort_by(|a, b| a.filename.cmp(&b.filename));
+  output
+}
+
+fn file_names(output: &[Asset]) -> Vec<&str> {
+  output.iter().map(|asset| asset.filename.as_str()).collect()
+}
+
+fn is_hashed(file_name: &str, prefix: &str) -> bool {
+  file_name
+    .strip_prefix(prefix)
+    .and_then(|rest| rest.strip_suffix(".js"))
+    .map_or(false, |hash| {
+      hash.len() == 8 && hash.chars().all(|c| c.is_ascii_hexdigit())
+    })
+}
+
+#[tokio::test]
+async fn placeholders_are_rendered() {
+  let output = generate("1").await;
+  let [lazy, main] = &output[..] else {
+    panic!("{:?}", file_names(&output));
+  };
+  assert!(
+    is_hashed(&lazy.filename, "chunks/lazy-"),
+    "{}",
+    lazy.filename
+  );
+  assert!(is_hashed(&main.filename, "main-"), "{}", main.filename);
+  // The entry imports the chunk by its final file name.
+  assert!(
+    main.content.contains(&format!("./{}", lazy.filename)),
+    "{}",
+    main.content
+  );
+  assert!(!main.content.contains("!~"), "{}", main.content);
+}
+
+#[tokio::test]
+async fn hashes_are_stable_and_follow_contents() {
+  let first = generate("1").await;
+  assert_eq!(file_names(&first), file_names(&generate("1").await));
+
+  // The entry is hashed again, since the file name of the chunk it imports changes.
+  let changed = generate("2").await;
+  assert_ne!(file_names(&first)[0], file_names(&changed)[0]);
+  assert_ne!(file_names(&first)[1], file_names(&changed)[1]);
+}
+
+/// Renders every chunk to the same code, so they get the same hash.
+#[derive(Debug)]
+struct SameCode;
+
+#[async_trait::async_trait]
+impl BuildPlugin for SameCode {
+  fn name(&self) -> PluginName {
+    std::borrow::Cow::Borrowed("test:same-code")
+  }
+
+  async fn render_chunk(
+    &self,
+    _ctx: &mut Context<&RenderOutputOptions>,
+    _args: &RenderChunkArgs,
+  ) -> RenderChunkReturn {
+    Ok(Some("console.log('same');\n".to_string()))
+  }
+}
+
+async fn generate_same_code(file_name_conflict: FileNameConflict) -> BuildResult<Vec<Asset>> {
+  let mut bundler = Bundler::with_plugins(
+    InputOptions {
+      input: ["a", "b"]
+        .into_iter()
+        .map(|name| InputItem {
+          name: name.to_string(),
+          import: format!("./{name}.js"),
+        })
+        .collect(),
+      cwd: plugin_fixture("file_name_conflict"),
+      ..Default::default()
+    },
+    vec![Box::new(SameCode)],
+  );
+  bundler
+    .generate(OutputOptions {
+      entry_file_names: FileNameTemplate::new("[hash].js".to_string()),
+      file_name_conflict,
+      ..Default::default()
+    })
+    .await
+}
+
+#[tokio::test]
+async fn same_hashes_are_derived_again() {
+  let output = generate_same_code(FileNameConflict::Suffix).await.unwrap();
+  let [first, second] = &output[..] else {
+    panic!("{:?}", file_names(&output));
+  };
+  assert!(is_hashed(&first.filename, ""), "{}", first.filename);
+  assert!(is_hashed(&second.filename, ""), "{}", second.filename);
+  assert_ne!(first.filename, second.filename);
+}
+
+#[tokio::test]
+async fn same_hashes_are_reported() {
+  let errors = generate_same_code(FileNameConflict::Error)
+    .await
+    .expect_err("Expected the conflict to be reported")
+    .into_vec();
+  assert_eq!(errors.len(), 1);
+  assert_eq!(errors[0].kind.code(), "FILE_NAME_CONFLICT");
+}
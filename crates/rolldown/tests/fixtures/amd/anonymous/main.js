@@ -0,0 +1,3 @@
+import { bar } from 'foo'
+console.log(bar)
+export const value = 1
@@ -0,0 +1,6 @@
+export default function greet() {}
+
+export const foo = 1
+
+const bar = 2
+export { bar }
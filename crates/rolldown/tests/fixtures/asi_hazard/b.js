@@ -0,0 +1,3 @@
+(function () {
+  console.log('b')
+})()
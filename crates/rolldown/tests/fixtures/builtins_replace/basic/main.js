@@ -0,0 +1,2 @@
+console.log(process.env.NODE_ENV);
+console.log(__VERSION__, __VERSION__SUFFIX);
@@ -0,0 +1,2 @@
+const name = 'app';
+console.log(<%name%>, name);
@@ -0,0 +1,2 @@
+process.env.DEBUG = "on";
+console.log(process.env.DEBUG);
@@ -0,0 +1 @@
+export { c } from './a.js'
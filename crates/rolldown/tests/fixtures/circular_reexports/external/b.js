@@ -0,0 +1 @@
+export { join } from './a.js'
@@ -0,0 +1,4 @@
+import './a.js'
+import { join } from './b.js'
+
+console.log(join)
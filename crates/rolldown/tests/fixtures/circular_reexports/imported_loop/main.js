@@ -0,0 +1,3 @@
+import { loop } from './x.js'
+
+console.log(loop)
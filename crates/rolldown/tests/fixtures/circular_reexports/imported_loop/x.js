@@ -0,0 +1,2 @@
+import './y.js'
+export { loop } from './z.js'
@@ -0,0 +1 @@
+export { loop } from './x.js'
@@ -0,0 +1,2 @@
+import { loop } from './y.js'
+export { loop }
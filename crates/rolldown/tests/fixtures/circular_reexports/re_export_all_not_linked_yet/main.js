@@ -0,0 +1,4 @@
+import './a.js'
+import { c } from './b.js'
+
+console.log(c)
@@ -0,0 +1 @@
+export { loop } from './y.js'
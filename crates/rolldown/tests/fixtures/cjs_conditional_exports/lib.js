@@ -0,0 +1,7 @@
+exports.mode = 'unknown'
+
+if (process.env.NODE_ENV === 'production') {
+  module.exports = { mode: 'production' }
+} else {
+  module.exports = { mode: 'development' }
+}
@@ -0,0 +1,3 @@
+const greeting = '你好'
+
+console.log(greeting)
@@ -0,0 +1,3 @@
+import pkg from 'pkg';
+import legacy from 'legacy';
+console.log(pkg, legacy);
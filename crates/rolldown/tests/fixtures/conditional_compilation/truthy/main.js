@@ -0,0 +1,4 @@
+// @if DEV
+console.log('dev')
+// @endif
+console.log('main')
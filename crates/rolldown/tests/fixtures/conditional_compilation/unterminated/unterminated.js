@@ -0,0 +1,3 @@
+console.log('main')
+  // @if DEV
+console.log('dev')
@@ -0,0 +1,5 @@
+import './greet.css'
+
+export function greet() {
+  console.log('hello')
+}
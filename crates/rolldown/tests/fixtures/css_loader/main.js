@@ -0,0 +1,4 @@
+import './styles.css'
+import { greet } from './greet.js'
+
+greet()
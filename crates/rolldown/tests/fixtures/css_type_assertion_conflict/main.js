@@ -0,0 +1,4 @@
+import sheet from './style.css' assert { type: 'css' };
+import './other.js';
+
+console.log(sheet)
@@ -0,0 +1,3 @@
+import sheet from './style.css' assert { type: 'css' };
+
+console.log(sheet)
@@ -0,0 +1,3 @@
+export { default as sheet } from './style.css' assert { type: 'css' };
+
+export const loadLazySheet = () => import('./lazy.css', { assert: { type: 'css' } });
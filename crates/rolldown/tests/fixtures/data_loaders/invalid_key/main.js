@@ -0,0 +1,3 @@
+import invalid from './invalid_key.yaml'
+
+console.log(invalid)
@@ -0,0 +1,4 @@
+import yaml, { name as yamlName, tags, ports } from './config.yaml'
+import toml, { name as tomlName, server } from './config.toml'
+
+console.log(yaml, yamlName, tags, ports, toml, tomlName, server)
@@ -0,0 +1,6 @@
+function sealed(target: any) {
+  Object.seal(target)
+}
+
+@sealed
+export class Greeter {}
@@ -0,0 +1,2 @@
+import { useState } from 'react'
+console.log(useState, 'a')
@@ -0,0 +1,9 @@
+// The parameter shadows the global `process`.
+export function readMode(process) {
+  return process.env.NODE_ENV;
+}
+
+if (process.env.NODE_ENV !== 'production') {
+  console.log('development only');
+}
+console.log(process.env['NODE_ENV'], VERSION);
@@ -0,0 +1,3 @@
+import { Button } from './button.js';
+
+console.log(Button());
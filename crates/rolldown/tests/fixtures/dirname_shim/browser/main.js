@@ -0,0 +1 @@
+console.log(__dirname, __filename)
@@ -0,0 +1 @@
+exports.bar = 123
@@ -0,0 +1 @@
+import('./foo.js').then((mod) => console.log(mod.default))
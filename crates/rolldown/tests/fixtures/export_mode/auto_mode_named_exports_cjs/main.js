@@ -0,0 +1,2 @@
+export const bar = 'bar'
+export const foo = 'foo'
@@ -0,0 +1 @@
+export const foo1 = 'foo1'
\ No newline at end of file
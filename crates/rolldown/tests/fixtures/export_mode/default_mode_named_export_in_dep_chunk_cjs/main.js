@@ -0,0 +1,3 @@
+import('./foo')
+
+export default 'hello, world'
\ No newline at end of file
@@ -0,0 +1,3 @@
+import { utils } from './index'
+
+console.log(utils.foo, utils.bar())
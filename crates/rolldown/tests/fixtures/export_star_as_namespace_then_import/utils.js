@@ -0,0 +1,5 @@
+export const foo = 1;
+
+export function bar() {
+  return 2;
+}
@@ -0,0 +1,3 @@
+export const zeta = 'a'
+export const shared = 'a'
+export const alpha = 'a'
@@ -0,0 +1,3 @@
+export const omega = 'b'
+export const shared = 'b'
+export const beta = 'b'
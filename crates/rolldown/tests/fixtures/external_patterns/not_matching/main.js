@@ -0,0 +1,5 @@
+import { readFileSync } from 'node:fs';
+import { helper } from '@scope/helper';
+import { local } from './local.js';
+
+console.log(readFileSync, helper, local);
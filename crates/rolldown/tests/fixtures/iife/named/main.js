@@ -0,0 +1,5 @@
+export const answer = 42
+
+export default function greet() {
+  return 'hello'
+}
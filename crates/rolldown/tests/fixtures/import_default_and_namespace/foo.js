@@ -0,0 +1,4 @@
+const value = 1;
+
+export { value as default };
+export const bar = 2;
@@ -0,0 +1,3 @@
+import def, * as ns from './foo'
+
+console.log(def, ns)
@@ -0,0 +1,7 @@
+import React from 'react'
+import { debounce } from 'lodash-es'
+import { version } from './util.js'
+
+console.log(React, debounce, version)
+
+export const loadDom = () => import('react-dom')
@@ -0,0 +1,6 @@
+console.log(new URL('./asset.txt', import.meta.url).href);
+
+// Bindings named like free variables the URL is derived from.
+export function urlOf(require, __filename, document, location) {
+  return import.meta.url;
+}
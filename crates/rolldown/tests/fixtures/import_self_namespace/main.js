@@ -0,0 +1,7 @@
+import * as ns from './main.js'
+
+export const foo = 1
+
+export function getFoo() {
+  return ns.foo
+}
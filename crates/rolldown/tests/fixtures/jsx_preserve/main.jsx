@@ -0,0 +1,5 @@
+function App() {
+  return <div className="app">hello</div>
+}
+
+console.log(App)
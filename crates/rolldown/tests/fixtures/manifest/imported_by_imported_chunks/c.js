@@ -0,0 +1,3 @@
+import { deep } from './deep.js'
+
+console.log('c', deep)
@@ -0,0 +1 @@
+export const deep = 'deep'
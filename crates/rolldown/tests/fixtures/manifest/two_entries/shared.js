@@ -0,0 +1,3 @@
+import { deep } from './deep.js'
+
+export const shared = `shared ${deep}`
@@ -0,0 +1,5 @@
+import lib, { format } from './node_modules/lib/index.js';
+import helper from './node_modules/helper/index.js';
+import { local } from './local.js';
+
+console.log(lib, format(local), helper());
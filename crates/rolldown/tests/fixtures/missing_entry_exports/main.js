@@ -0,0 +1,11 @@
+export const a = 1;
+
+export function b() {}
+
+export default 'c';
+
+export { shared } from './shared.js';
+
+export { join } from 'external';
+
+export * from 'external';
@@ -0,0 +1 @@
+export const c: string = 'c';
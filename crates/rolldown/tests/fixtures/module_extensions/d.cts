@@ -0,0 +1,5 @@
+interface D {
+  value: string;
+}
+
+export const d: D = { value: 'd' };
@@ -0,0 +1,6 @@
+import { a } from './a.mjs';
+import { b } from './b.cjs';
+import { c } from './c.mts';
+import { d } from './d.cts';
+
+console.log(a, b, c, d);
@@ -0,0 +1,3 @@
+import addon from './addon.node'
+
+console.log(addon)
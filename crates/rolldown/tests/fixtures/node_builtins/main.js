@@ -0,0 +1,5 @@
+import fs, { readFileSync } from 'node:fs';
+import { join } from 'path';
+import { EventEmitter } from 'node:events';
+
+console.log(fs, readFileSync, join, EventEmitter);
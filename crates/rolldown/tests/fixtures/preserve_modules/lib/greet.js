@@ -0,0 +1,5 @@
+import { upper } from '../utils/upper.js';
+
+export function greet(name) {
+  return `hello ${upper(name)}`;
+}
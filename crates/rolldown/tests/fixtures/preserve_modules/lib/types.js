@@ -0,0 +1 @@
+// Types only, which leave nothing to emit.
@@ -0,0 +1,4 @@
+import { greet } from './lib/index.js';
+import './lib/types.js';
+
+console.log(greet('world'));
@@ -0,0 +1,7 @@
+export function upper(value) {
+  return value.toUpperCase();
+}
+
+export function lower(value) {
+  return value.toLowerCase();
+}
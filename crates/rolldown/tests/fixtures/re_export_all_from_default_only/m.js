@@ -0,0 +1,4 @@
+const value = 1;
+console.log(value);
+
+export { value as default };
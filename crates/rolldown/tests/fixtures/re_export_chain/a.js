@@ -0,0 +1 @@
+export { y as z } from './b'
@@ -0,0 +1 @@
+export { x as y } from './c'
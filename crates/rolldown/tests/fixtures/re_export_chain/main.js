@@ -0,0 +1,3 @@
+import { z } from './a'
+
+console.log(z)
@@ -0,0 +1,4 @@
+const value = 'm';
+console.log(value);
+
+export { value as default };
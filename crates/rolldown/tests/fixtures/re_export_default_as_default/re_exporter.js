@@ -0,0 +1 @@
+export { default as default } from './m'
@@ -0,0 +1,2 @@
+exports.foo = 'foo';
+exports.bar = 'bar';
@@ -0,0 +1,3 @@
+import { ns } from './index.js'
+
+console.log(ns.foo, ns.bar, ns.default)
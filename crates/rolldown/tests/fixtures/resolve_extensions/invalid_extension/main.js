@@ -0,0 +1,2 @@
+import { value } from '../util';
+console.log(value);
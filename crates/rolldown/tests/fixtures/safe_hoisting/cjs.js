@@ -0,0 +1,3 @@
+exports.greet = function (who) {
+  return 'hello ' + who;
+};
@@ -0,0 +1,4 @@
+import cjs from './cjs.js';
+import { name } from './esm.js';
+
+console.log(cjs.greet(name));
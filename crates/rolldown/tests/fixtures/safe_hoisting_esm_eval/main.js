@@ -0,0 +1 @@
+export const value = eval('1 + 1');
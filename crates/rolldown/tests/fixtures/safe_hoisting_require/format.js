@@ -0,0 +1,3 @@
+module.exports = function (greeting, who) {
+  return greeting + ' ' + who;
+};
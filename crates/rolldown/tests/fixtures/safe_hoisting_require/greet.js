@@ -0,0 +1,5 @@
+const fmt = require('./format.js');
+
+exports.greet = function (who) {
+  return fmt('hello', who);
+};
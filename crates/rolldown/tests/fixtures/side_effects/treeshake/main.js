@@ -0,0 +1,8 @@
+import 'free-pkg/unused.js'
+import { used } from 'free-pkg'
+import 'globs-pkg/src/polyfill.js'
+import 'globs-pkg/src/plain.js'
+import 'globs-pkg/style.css.js'
+import 'plain-pkg/effect.js'
+
+console.log(used)
@@ -0,0 +1 @@
+export const token = Symbol('token');
@@ -0,0 +1 @@
+lib.js
\ No newline at end of file
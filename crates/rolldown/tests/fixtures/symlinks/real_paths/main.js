@@ -0,0 +1,4 @@
+import { token as direct } from '../lib.js';
+import { token as linked } from '../linked.js';
+
+console.log(direct === linked);
@@ -0,0 +1,3 @@
+import readme from './README.md'
+
+console.log(readme)
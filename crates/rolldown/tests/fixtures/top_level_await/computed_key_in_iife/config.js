@@ -0,0 +1,3 @@
+export class Config {
+  [await Promise.resolve('debug')]() {}
+}
@@ -0,0 +1,2 @@
+import { Config } from './config'
+console.log(new Config())
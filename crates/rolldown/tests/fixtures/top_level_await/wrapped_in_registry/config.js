@@ -0,0 +1 @@
+exports.value = await Promise.resolve(1)
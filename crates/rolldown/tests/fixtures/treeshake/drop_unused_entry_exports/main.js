@@ -0,0 +1,3 @@
+export const unused = 1;
+
+console.log('main')
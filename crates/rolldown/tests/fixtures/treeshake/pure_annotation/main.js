@@ -0,0 +1,5 @@
+import { create } from './create.js'
+
+const unused = /*#__PURE__*/ create()
+
+console.log('main')
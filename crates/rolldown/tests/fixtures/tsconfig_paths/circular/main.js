@@ -0,0 +1,5 @@
+import { app } from '@app/app'
+import { config } from '@app/config'
+import { shared } from 'shared/index.js'
+
+console.log(app, config, shared)
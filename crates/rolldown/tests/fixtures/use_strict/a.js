@@ -0,0 +1,2 @@
+"use strict";
+export const a = 'use strict'
@@ -0,0 +1,4 @@
+'use strict'
+import { a } from '../a'
+
+console.log(a)
@@ -0,0 +1,162 @@
+use std::path::PathBuf;
+
+use rolldown::{Bundler, FileNameConflict, OutputOptions};
+use rolldown_plugin::{async_trait, Asset, BuildPlugin, Context, GenerateBundleReturn, PluginName};
+
+mod common;
+use common::plugin_fixture_input;
+
+/// Emits `files.txt` listing the rendered assets, and appends a banner to them.
+#[derive(Debug)]
+struct ListFilesPlugin;
+
+#[async_trait::async_trait]
+impl BuildPlugin for ListFilesPlugin {
+  fn name(&self) -> PluginName {
+    std::borrow::Cow::Borrowed("test:list-files")
+  }
+
+  async fn generate_bundle(
+    &self,
+    ctx: &mut Context<Vec<Asset>>,
+    bundle: &mut Vec<Asset>,
+  ) -> GenerateBundleReturn {
+    let filenames = bundle
+      .iter()
+      .map(|asset| asset.filename.as_str())
+      .collect::<Vec<_>>()
+      .join("\n");
+    for asset in bundle.iter_mut() {
+      asset.content.insert_str(0, "/* banner */\n");
+    }
+    ctx.emit_file(Asset {
+      filename: "files.txt".to_string(),
+      content: filenames,
+      binary: None,
+    });
+    Ok(())
+  }
+}
+
+/// Emits a file named `main.js`, which is taken by the chunk.
+#[derive(Debug)]
+struct EmitMainPlugin;
+
+#[async_trait::async_trait]
+impl BuildPlugin for EmitMainPlugin {
+  fn name(&self) -> PluginName {
+    std::borrow::Cow::Borrowed("test:emit-main")
+  }
+
+  async fn generate_bundle(
+    &self,
+    ctx: &mut Context<Vec<Asset>>,
+    _bundle: &mut Vec<Asset>,
+  ) -> GenerateBundleReturn {
+    ctx.emit_file(Asset {
+      filename: "main.js".to_string(),
+      content: "emitted".to_string(),
+      binary: None,
+    });
+    Ok(())
+  }
+}
+
+fn bundler() -> Bundler {
+  Bundler::with_plugins(
+    plugin_fixture_input("generate_bundle"),
+    vec![Box::new(ListFilesPlugin)],
+  )
+}
+
+#[tokio::test]
+async fn generate_bundle_changes_and_emits_assets() {
+  let output = bundler().generate(Default::default()).await.unwrap();
+
+  assert_eq!(output.len(), 2);
+  assert_eq!(output[0].filename, "main.js");
+  assert!(output[0].content.starts_with("/* banner */\n"));
+  assert_eq!(output[1].filename, "files.txt");
+  assert_eq!(output[1].content, "main.js");
+}
+
+#[tokio::test]
+async fn emitted_assets_are_written() {
+  let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("generate_bundle");
+  if dir.exists() {
+    std::fs::remove_dir_all(&dir).unwrap();
+  }
+
+  bundler()
+    .write(OutputOptions {
+      dir: Some(dir.to_string_lossy().to_string()),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+  assert_eq!(
+    std::fs::read_to_string(dir.join("files.txt")).unwrap(),
+    "main.js"
+  );
+}
+
+#[tokio::test]
+async fn emitted_assets_are_written_when_streaming() {
+  let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("generate_bundle_streaming");
+  if dir.exists() {
+    std::fs::remove_dir_all(&dir).unwrap();
+  }
+
+  bundler()
+    .write(OutputOptions {
+      dir: Some(dir.to_string_lossy().to_string()),
+      streaming: true,
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+  assert_eq!(
+    std::fs::read_to_string(dir.join("files.txt")).unwrap(),
+    "main.js"
+  );
+}
+
+#[tokio::test]
+async fn emitted_assets_with_taken_names_are_suffixed() {
+  let output = Bundler::with_plugins(
+    plugin_fixture_input("generate_bundle"),
+    vec![Box::new(EmitMainPlugin)],
+  )
+  .generate(Default::default())
+  .await
+  .unwrap();
+
+  assert_eq!(output.len(), 2);
+  assert_eq!(output[0].filename, "main.js");
+  assert_eq!(output[1].filename, "main2.js");
+  assert_eq!(output[1].content, "emitted");
+}
+
+#[tokio::test]
+async fn emitted_assets_with_taken_names_could_fail_the_build() {
+  let errors = Bundler::with_plugins(
+    plugin_fixture_input("generate_bundle"),
+    vec![Box::new(EmitMainPlugin)],
+  )
+  .generate(OutputOptions {
+    file_name_conflict: FileNameConflict::Error,
+    ..Default::default()
+  })
+  .await
+  .expect_err("Expected the conflict to be reported")
+  .into_vec();
+
+  assert_eq!(errors.len(), 1);
+  assert_eq!(errors[0].kind.code(), "FILE_NAME_CONFLICT");
+  assert_eq!(
+    errors[0].kind.to_string(),
+    r#""main.js" emitted by plugin "test:emit-main" would overwrite another asset of the output."#
+  );
+}
@@ -0,0 +1,86 @@
+use std::sync::{Arc, Mutex};
+
+use rolldown::{Bundler, InputItem, InputOptions, OutputOptions};
+use rolldown_plugin::{
+  async_trait, BuildPlugin, Context, LoadArgs, LoadOutput, LoadReturn, PluginName,
+};
+
+mod common;
+use common::plugin_fixture;
+
+/// Serves `main.js` from memory, so the test could modify it between builds.
+#[derive(Debug)]
+struct InMemoryEntry {
+  code: Arc<Mutex<String>>,
+}
+
+#[async_trait::async_trait]
+impl BuildPlugin for InMemoryEntry {
+  fn name(&self) -> PluginName {
+    std::borrow::Cow::Borrowed("test:in-memory-entry")
+  }
+
+  async fn load(&self, _ctx: &mut Context, args: &mut LoadArgs) -> LoadReturn {
+    if args.id.as_ref().ends_with("main.js") {
+      return Ok(Some(LoadOutput {
+        code: self.code.lock().unwrap().clone(),
+        loader: None,
+      }));
+    }
+    Ok(None)
+  }
+}
+
+fn to_strings<T: ToString>(ids: &[T]) -> Vec<String> {
+  ids.iter().map(|id| id.to_string()).collect()
+}
+
+fn edges_to_strings<T: ToString>(edges: &[(T, T)]) -> Vec<(String, String)> {
+  edges
+    .iter()
+    .map(|(importer, importee)| (importer.to_string(), importee.to_string()))
+    .collect()
+}
+
+#[tokio::test]
+async fn diff_reports_changed_import() {
+  let cwd = plugin_fixture("graph_diff");
+  let code = Arc::new(Mutex::new(
+    std::fs::read_to_string(cwd.join("main.js")).unwrap(),
+  ));
+  let mut bundler = Bundler::with_plugins(
+    InputOptions {
+      input: vec![InputItem {
+        name: "main".to_string(),
+        import: "./main.js".to_string(),
+      }],
+      cwd: cwd.clone(),
+      ..Default::default()
+    },
+    vec![Box::new(InMemoryEntry { code: code.clone() })],
+  );
+  let id = |name: &str| cwd.join(name).to_string_lossy().to_string();
+
+  bundler.generate(OutputOptions::default()).await.unwrap();
+  let prev = bundler.graph_snapshot().unwrap().clone();
+
+  // Rebuilding without changes gives an empty diff.
+  bundler.generate(OutputOptions::default()).await.unwrap();
+  assert!(prev.diff(bundler.graph_snapshot().unwrap()).is_empty());
+
+  *code.lock().unwrap() = "import { b } from './b'\nconsole.log(b)\n".to_string();
+  bundler.generate(OutputOptions::default()).await.unwrap();
+  let diff = prev.diff(bundler.graph_snapshot().unwrap());
+
+  assert_eq!(to_strings(&diff.added_modules), vec![id("b.js")]);
+  assert_eq!(to_strings(&diff.removed_modules), vec![id("a.js")]);
+  assert_eq!(to_strings(&diff.changed_modules), vec![id("main.js")]);
+  assert_eq!(
+    edges_to_strings(&diff.added_edges),
+    vec![(id("main.js"), id("b.js"))]
+  );
+  assert_eq!(
+    edges_to_strings(&diff.removed_edges),
+    vec![(id("main.js"), id("a.js"))]
+  );
+}
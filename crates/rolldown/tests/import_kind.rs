@@ -0,0 +1,75 @@
+use std::sync::{Arc, Mutex};
+
+use rolldown::{Bundler, OutputOptions};
+use rolldown_plugin::{
+  async_trait, BuildPlugin, Context, External, ImportKind, LoadArgs, LoadOutput, LoadReturn,
+  PluginName, ResolveArgs, ResolveReturn, ResolvedId,
+};
+
+mod common;
+use common::plugin_fixture_input;
+
+#[derive(Debug, Default)]
+struct ImportKindPlugin {
+  entry_kinds: Arc<Mutex<Vec<ImportKind>>>,
+}
+
+#[async_trait::async_trait]
+impl BuildPlugin for ImportKindPlugin {
+  fn name(&self) -> PluginName {
+    std::borrow::Cow::Borrowed("test:import-kind")
+  }
+
+  async fn resolve(&self, _ctx: &mut Context, args: &mut ResolveArgs) -> ResolveReturn {
+    if args.importer.is_none() {
+      self.entry_kinds.lock().unwrap().push(args.kind);
+    }
+    if args.specifier != "target" {
+      return Ok(None);
+    }
+    let id = match args.kind {
+      ImportKind::DynamicImport => "\0dynamic-target",
+      ImportKind::Require => "\0required-target",
+      _ => "\0static-target",
+    };
+    Ok(Some(ResolvedId {
+      id: id.to_string(),
+      external: External::False,
+    }))
+  }
+
+  async fn load(&self, _ctx: &mut Context, args: &mut LoadArgs) -> LoadReturn {
+    let code = match args.id.as_ref() {
+      "\0static-target" => "export const target = 'static';",
+      "\0dynamic-target" => "export const target = 'dynamic';",
+      "\0required-target" => "export default 'required';",
+      _ => return Ok(None),
+    };
+    Ok(Some(LoadOutput {
+      code: code.to_string(),
+      loader: None,
+    }))
+  }
+}
+
+#[tokio::test]
+async fn resolve_hooks_receive_the_import_kind() {
+  let entry_kinds = Arc::new(Mutex::new(vec![]));
+  let mut bundler = Bundler::with_plugins(
+    plugin_fixture_input("import_kind"),
+    vec![Box::new(ImportKindPlugin {
+      entry_kinds: entry_kinds.clone(),
+    })],
+  );
+  let output = bundler.generate(OutputOptions::default()).await.unwrap();
+  let content = output
+    .iter()
+    .map(|asset| asset.content.as_str())
+    .collect::<Vec<_>>()
+    .join("\n");
+
+  assert!(content.contains("'static'"), "{content}");
+  assert!(content.contains("'dynamic'"), "{content}");
+  assert!(content.contains("'required'"), "{content}");
+  assert_eq!(*entry_kinds.lock().unwrap(), vec![ImportKind::Entry]);
+}
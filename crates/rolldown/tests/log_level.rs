@@ -0,0 +1,76 @@
+use std::sync::{Arc, Mutex};
+
+use rolldown::{Bundler, InputOptions, LevelFilter, OutputOptions};
+use rolldown_plugin::{
+  async_trait, BuildPlugin, BuildStartReturn, Context, PluginName, ResolvedInputOptions,
+};
+
+mod common;
+use common::plugin_fixture_input;
+
+#[tokio::test]
+async fn tracing_is_initialized_once_across_builds() {
+  for log_level in [LevelFilter::DEBUG, LevelFilter::TRACE] {
+    let mut bundler = Bundler::new(InputOptions {
+      log_level: Some(log_level),
+      ..plugin_fixture_input("log_level")
+    });
+    let output = bundler.generate(OutputOptions::default()).await.unwrap();
+    assert_eq!(output.len(), 1);
+  }
+}
+
+/// Records whether debug events of rolldown are enabled while the build runs.
+#[derive(Debug)]
+struct DebugEnabled(Arc<Mutex<Option<bool>>>);
+
+#[async_trait::async_trait]
+impl BuildPlugin for DebugEnabled {
+  fn name(&self) -> PluginName {
+    std::borrow::Cow::Borrowed("test:debug-enabled")
+  }
+
+  async fn build_start(
+    &self,
+    _ctx: &mut Context,
+    _options: &ResolvedInputOptions,
+  ) -> BuildStartReturn {
+    *self.0.lock().unwrap() = Some(tracing::enabled!(
+      target: "rolldown",
+      tracing::Level::DEBUG
+    ));
+    Ok(())
+  }
+}
+
+#[tokio::test]
+async fn each_bundler_logs_at_its_own_level() {
+  // `RUST_LOG` takes precedence over `log_level`.
+  if std::env::var("RUST_LOG").is_ok() {
+    return;
+  }
+
+  let debug_enabled = Arc::new(Mutex::new(None));
+  let mut debug = Bundler::with_plugins(
+    InputOptions {
+      log_level: Some(LevelFilter::DEBUG),
+      ..plugin_fixture_input("log_level")
+    },
+    vec![Box::new(DebugEnabled(debug_enabled.clone()))],
+  );
+  let warn_enabled = Arc::new(Mutex::new(None));
+  let mut warn = Bundler::with_plugins(
+    InputOptions {
+      log_level: Some(LevelFilter::WARN),
+      ..plugin_fixture_input("log_level")
+    },
+    vec![Box::new(DebugEnabled(warn_enabled.clone()))],
+  );
+
+  warn.generate(OutputOptions::default()).await.unwrap();
+  debug.generate(OutputOptions::default()).await.unwrap();
+  warn.generate(OutputOptions::default()).await.unwrap();
+
+  assert_eq!(*debug_enabled.lock().unwrap(), Some(true));
+  assert_eq!(*warn_enabled.lock().unwrap(), Some(false));
+}
@@ -0,0 +1,36 @@
+use rolldown::{Bundler, OutputOptions};
+
+mod common;
+use common::plugin_fixture_input;
+
+// Mangled names are picked by frequencies of characters in the chunk, so they aren't snapshotted in
+// fixtures, which would break on any change of the output.
+async fn generate(reserved_names: Vec<String>) -> String {
+  let mut bundler = Bundler::new(plugin_fixture_input("mangle"));
+  let output = bundler
+    .generate(OutputOptions {
+      mangle: true,
+      reserved_names,
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+  assert_eq!(output.len(), 1);
+  output[0].content.clone()
+}
+
+#[tokio::test]
+async fn local_names_are_shortened() {
+  let content = generate(vec![]).await;
+  assert!(!content.contains("reflectedName"));
+  assert!(!content.contains("temporaryValue"));
+  // Top level names might be exported, so they are kept.
+  assert!(content.contains("function describe("));
+}
+
+#[tokio::test]
+async fn reserved_names_survive_mangling() {
+  let content = generate(vec!["reflectedName".to_string()]).await;
+  assert!(content.contains("reflectedName"));
+  assert!(!content.contains("temporaryValue"));
+}
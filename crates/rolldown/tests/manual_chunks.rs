@@ -0,0 +1,26 @@
+use std::collections::BTreeMap;
+
+use rolldown::{Bundler, OutputOptions};
+
+mod common;
+use common::plugin_fixture_input;
+
+// The reason of the error comes from the glob parser, so it's not a fixture.
+#[tokio::test]
+async fn invalid_patterns_are_reported() {
+  let mut bundler = Bundler::new(plugin_fixture_input("manual_chunks"));
+  let errors = bundler
+    .generate(OutputOptions {
+      manual_chunks: Some(BTreeMap::from([(
+        "vendor".to_string(),
+        vec!["node_modules/[lib".to_string()],
+      )])),
+      ..Default::default()
+    })
+    .await
+    .expect_err("Expected the pattern to be reported")
+    .into_vec();
+
+  assert_eq!(errors.len(), 1);
+  assert_eq!(errors[0].kind.code(), "INVALID_OPTION");
+}
@@ -0,0 +1,58 @@
+use std::{
+  sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+  },
+  time::Duration,
+};
+
+use rolldown::{Bundler, InputOptions, OutputOptions};
+use rolldown_plugin::{
+  async_trait, BuildPlugin, Context, PluginName, TransformArgs, TransformReturn,
+};
+
+mod common;
+use common::plugin_fixture_input;
+
+#[derive(Debug, Default)]
+struct TransformCounter {
+  running: AtomicUsize,
+  max_running: AtomicUsize,
+}
+
+#[derive(Debug)]
+struct SlowTransformPlugin(Arc<TransformCounter>);
+
+#[async_trait::async_trait]
+impl BuildPlugin for SlowTransformPlugin {
+  fn name(&self) -> PluginName {
+    std::borrow::Cow::Borrowed("test:slow-transform")
+  }
+
+  async fn transform(&self, _ctx: &mut Context, _args: &mut TransformArgs) -> TransformReturn {
+    let running = self.0.running.fetch_add(1, Ordering::SeqCst) + 1;
+    self.0.max_running.fetch_max(running, Ordering::SeqCst);
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    self.0.running.fetch_sub(1, Ordering::SeqCst);
+    Ok(None)
+  }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn transforms_respect_max_parallel_transforms() {
+  let counter = Arc::new(TransformCounter::default());
+  let mut bundler = Bundler::with_plugins(
+    InputOptions {
+      max_parallel_transforms: Some(2),
+      ..plugin_fixture_input("max_parallel_transforms")
+    },
+    vec![Box::new(SlowTransformPlugin(counter.clone()))],
+  );
+  bundler.generate(OutputOptions::default()).await.unwrap();
+
+  let max_running = counter.max_running.load(Ordering::SeqCst);
+  assert!(
+    (1..=2).contains(&max_running),
+    "at most 2 transforms should run at the same time, but got {max_running}"
+  );
+}
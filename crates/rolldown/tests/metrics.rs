@@ -0,0 +1,42 @@
+use rolldown::{Bundler, InputOptions, OutputOptions};
+
+mod common;
+use common::plugin_fixture_input;
+
+#[tokio::test]
+async fn metrics_are_populated_after_build() {
+  let mut bundler = Bundler::new(plugin_fixture_input("metrics"));
+  assert!(bundler.metrics().is_none());
+
+  bundler.generate(OutputOptions::default()).await.unwrap();
+
+  let metrics = bundler.metrics().unwrap();
+  // `value` and `unused` of `lib.js` at least.
+  assert!(metrics.symbols >= 2);
+  // `value` imported by `main.js` is unioned with the one declared in `lib.js`.
+  assert!(metrics.union_find_size >= 2);
+}
+
+async fn build_with_max_symbols(max_symbols: usize) -> Result<(), Vec<&'static str>> {
+  let mut bundler = Bundler::new(InputOptions {
+    max_symbols: Some(max_symbols),
+    ..plugin_fixture_input("metrics")
+  });
+  match bundler.generate(OutputOptions::default()).await {
+    Ok(_) => Ok(()),
+    Err(errors) => Err(errors.into_vec().iter().map(|e| e.kind.code()).collect()),
+  }
+}
+
+#[tokio::test]
+async fn max_symbols_caps_the_graph() {
+  let mut bundler = Bundler::new(plugin_fixture_input("metrics"));
+  bundler.generate(OutputOptions::default()).await.unwrap();
+  let symbols = bundler.metrics().unwrap().symbols;
+
+  assert_eq!(build_with_max_symbols(symbols).await, Ok(()));
+  assert_eq!(
+    build_with_max_symbols(symbols - 1).await,
+    Err(vec!["TOO_MANY_SYMBOLS"])
+  );
+}
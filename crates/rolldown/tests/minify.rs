@@ -0,0 +1,60 @@
+use rolldown::{Bundler, InputOptions, MinifyOptions, OutputOptions};
+
+mod common;
+use common::plugin_fixture_input;
+
+// Only what's dropped or kept is asserted, since the exact output is up to the passes of swc.
+async fn generate(minify_options: MinifyOptions) -> String {
+  let mut bundler = Bundler::new(InputOptions {
+    // Treeshaking drops unreachable code as well, so it's left to the minifier here.
+    treeshake: false,
+    ..plugin_fixture_input("minify")
+  });
+  let output = bundler
+    .generate(OutputOptions {
+      minify: true,
+      minify_options,
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+  assert_eq!(output.len(), 1);
+  output[0].content.clone()
+}
+
+#[tokio::test]
+async fn chunks_are_compressed_and_mangled() {
+  let content = generate(MinifyOptions::default()).await;
+  assert!(!content.contains("unreachable"), "{content}");
+  assert!(!content.contains("localResult"), "{content}");
+  assert!(!content.contains('\n'), "{content}");
+  // Top level names might be exported, so they are kept.
+  assert!(content.contains("function readMain("), "{content}");
+}
+
+#[tokio::test]
+async fn names_renamed_by_scope_hoisting_are_kept_at_top_level() {
+  let content = generate(MinifyOptions::default()).await;
+  // `count` of `a.js` is renamed to avoid the one of the entry.
+  assert!(content.contains("count$1"), "{content}");
+  assert!(content.contains("function readA("), "{content}");
+}
+
+#[tokio::test]
+async fn passes_could_be_disabled() {
+  let content = generate(MinifyOptions {
+    mangle: false,
+    compress: true,
+  })
+  .await;
+  assert!(!content.contains("unreachable"), "{content}");
+  assert!(content.contains("localResult"), "{content}");
+
+  let content = generate(MinifyOptions {
+    mangle: true,
+    compress: false,
+  })
+  .await;
+  assert!(content.contains("unreachable"), "{content}");
+  assert!(!content.contains("localResult"), "{content}");
+}
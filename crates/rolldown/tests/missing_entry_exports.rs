@@ -0,0 +1,48 @@
+use std::sync::{Arc, Mutex};
+
+use futures::FutureExt;
+use rolldown::{Bundler, InputItem, InputOptions, ModuleFormat, OutputOptions};
+
+mod common;
+use common::plugin_fixture_input;
+
+async fn warnings_of(format: ModuleFormat) -> Vec<(&'static str, String)> {
+  let warnings = Arc::new(Mutex::new(vec![]));
+  let mut input_options = plugin_fixture_input("missing_entry_exports");
+  input_options.input.push(InputItem {
+    name: "other".to_string(),
+    import: "./other.js".to_string(),
+  });
+  let mut bundler = Bundler::new(InputOptions {
+    on_warn: {
+      let warnings = warnings.clone();
+      Arc::new(move |warning| {
+        warnings
+          .lock()
+          .unwrap()
+          .push((warning.kind.code(), warning.to_string()))
+      })
+    },
+    is_external: Arc::new(|specifier, _, _| {
+      let res = Ok(specifier == "external");
+      async { res }.boxed()
+    }),
+    ..input_options
+  });
+  bundler
+    .generate(OutputOptions {
+      format,
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+  let warnings = warnings.lock().unwrap().clone();
+  warnings
+}
+
+/// Export statements of the chunk are checked before they're rendered in the format. The `esm`
+/// output is covered by `fixtures/missing_entry_exports`.
+#[tokio::test]
+async fn entries_of_other_formats_are_validated_as_well() {
+  assert!(warnings_of(ModuleFormat::Cjs).await.is_empty());
+}
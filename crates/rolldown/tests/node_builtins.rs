@@ -0,0 +1,63 @@
+use std::sync::{Arc, Mutex};
+
+use rolldown::{external_by_patterns, Bundler, InputOptions, IsExternal, OutputOptions, Platform};
+
+mod common;
+use common::plugin_fixture_input;
+
+// The node platform is covered by `fixtures/node_builtins`, the stubs for browsers are asserted
+// here since their shims are printed in the order the stubs are loaded.
+async fn generate(is_external: Option<IsExternal>) -> (String, Vec<(&'static str, String)>) {
+  let warnings = Arc::new(Mutex::new(vec![]));
+  let options = plugin_fixture_input("node_builtins");
+  let mut bundler = Bundler::new(InputOptions {
+    platform: Some(Platform::Browser),
+    is_external: is_external.unwrap_or(options.is_external),
+    on_warn: {
+      let warnings = warnings.clone();
+      Arc::new(move |warning| {
+        warnings
+          .lock()
+          .unwrap()
+          .push((warning.kind.code(), warning.to_string()))
+      })
+    },
+    ..options
+  });
+  let output = bundler.generate(OutputOptions::default()).await.unwrap();
+  assert_eq!(output.len(), 1);
+  let mut warnings = warnings.lock().unwrap().clone();
+  // Stubs are loaded concurrently.
+  warnings.sort();
+  (output[0].content.clone(), warnings)
+}
+
+#[tokio::test]
+async fn node_builtins_are_polyfilled_or_stubbed_for_browsers() {
+  let (content, warnings) = generate(None).await;
+  assert!(!content.contains(r#"from "node:"#), "{content}");
+  assert!(!content.contains(r#"from "path""#), "{content}");
+  assert!(content.contains("function EventEmitter()"), "{content}");
+  // Named imports of stubs are shimmed, instead of failing the build.
+  assert!(content.contains("var readFileSync;"), "{content}");
+  assert!(content.contains("var join;"), "{content}");
+  assert_eq!(
+    warnings,
+    ["fs", "path"]
+      .map(|name| (
+        "NODE_BUILTIN_STUBBED",
+        format!(
+          r#""node:{name}" is a Node.js builtin, so it's replaced by an empty module for the browser platform. Install a polyfill package named "{name}" to bundle it instead."#
+        )
+      ))
+      .to_vec()
+  );
+}
+
+#[tokio::test]
+async fn is_external_takes_precedence_over_stubs() {
+  let (content, warnings) = generate(Some(external_by_patterns(["^node:fs$"]).unwrap())).await;
+  assert!(content.contains(r#"from "node:fs";"#), "{content}");
+  assert!(content.contains("function EventEmitter()"), "{content}");
+  assert_eq!(warnings.len(), 1, "{warnings:?}");
+}
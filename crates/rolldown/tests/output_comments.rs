@@ -0,0 +1,25 @@
+use rolldown::{Bundler, OutputComments, OutputOptions};
+
+mod common;
+use common::plugin_fixture_input;
+
+// Stripping comments by default is covered by `fixtures/output_comments`, where kept comments are
+// placed around the rewritten exports is up to swc, so only their presence is asserted here.
+#[tokio::test]
+async fn jsdoc_comments_are_kept() {
+  let mut bundler = Bundler::new(plugin_fixture_input("output_comments"));
+  let output = bundler
+    .generate(OutputOptions {
+      comments: OutputComments::Jsdoc,
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+  assert_eq!(output.len(), 1);
+  let content = &output[0].content;
+  assert!(
+    content.contains("/** Greets the given name. */"),
+    "{content}"
+  );
+  assert!(!content.contains("line comment"), "{content}");
+}
@@ -0,0 +1,72 @@
+use std::sync::{Arc, Mutex};
+
+use rolldown::{Bundler, InputOptions, OutputOptions};
+use rolldown_plugin::{
+  async_trait, BuildPlugin, Context, PluginName, TransformArgs, TransformReturn,
+};
+
+mod common;
+use common::plugin_fixture_input;
+
+/// Records transformed modules.
+#[derive(Debug, Default)]
+struct RecordTransformed {
+  transformed: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait::async_trait]
+impl BuildPlugin for RecordTransformed {
+  fn name(&self) -> PluginName {
+    std::borrow::Cow::Borrowed("test:record-transformed")
+  }
+
+  async fn transform(&self, _ctx: &mut Context, args: &mut TransformArgs) -> TransformReturn {
+    let file_name = args.id.to_string().rsplit('/').next().unwrap().to_string();
+    self.transformed.lock().unwrap().push(file_name);
+    Ok(None)
+  }
+}
+
+fn bundler() -> (Bundler, Arc<Mutex<Vec<String>>>) {
+  let plugin = RecordTransformed::default();
+  let transformed = plugin.transformed.clone();
+  let bundler = Bundler::with_plugins(
+    InputOptions {
+      cache: true,
+      ..plugin_fixture_input("parse_cache")
+    },
+    vec![Box::new(plugin)],
+  );
+  (bundler, transformed)
+}
+
+fn take_sorted(transformed: &Mutex<Vec<String>>) -> Vec<String> {
+  let mut transformed = std::mem::take(&mut *transformed.lock().unwrap());
+  transformed.sort();
+  transformed
+}
+
+#[tokio::test]
+async fn unmodified_modules_are_reused_across_builds() {
+  let (mut bundler, transformed) = bundler();
+
+  let first = bundler.generate(OutputOptions::default()).await.unwrap();
+  assert_eq!(take_sorted(&transformed), ["a.js", "main.js"]);
+
+  let second = bundler.generate(OutputOptions::default()).await.unwrap();
+  assert!(take_sorted(&transformed).is_empty());
+  assert_eq!(first[0].content, second[0].content);
+}
+
+#[tokio::test]
+async fn clear_cache_forces_a_full_rebuild() {
+  let (mut bundler, transformed) = bundler();
+
+  let first = bundler.generate(OutputOptions::default()).await.unwrap();
+  take_sorted(&transformed);
+
+  bundler.clear_cache();
+  let second = bundler.generate(OutputOptions::default()).await.unwrap();
+  assert_eq!(take_sorted(&transformed), ["a.js", "main.js"]);
+  assert_eq!(first[0].content, second[0].content);
+}
@@ -0,0 +1,48 @@
+use std::sync::{Arc, Mutex};
+
+use rolldown::{Bundler, InputOptions, OutputOptions};
+use rolldown_error::{Error, ErrorKind};
+
+mod common;
+use common::{plugin_fixture, plugin_fixture_input};
+
+// Both the error and the warning carry the reason from swc's parser, so they aren't fixtures.
+
+/// `main.js` has a legacy octal literal, which isn't allowed in strict mode.
+fn input_options(parse_recovery: bool, warnings: Arc<Mutex<Vec<Error>>>) -> InputOptions {
+  InputOptions {
+    parse_recovery,
+    on_warn: Arc::new(move |warning| warnings.lock().unwrap().push(warning)),
+    ..plugin_fixture_input("parse_recovery")
+  }
+}
+
+#[tokio::test]
+async fn recoverable_parse_error_fails_by_default() {
+  let mut bundler = Bundler::new(input_options(false, Default::default()));
+  let errors = bundler
+    .generate(OutputOptions::default())
+    .await
+    .expect_err("Expected the parse error to fail the build")
+    .into_vec();
+
+  assert_eq!(errors.len(), 1);
+  assert!(matches!(errors[0].kind, ErrorKind::ParseJsFailed { .. }));
+}
+
+#[tokio::test]
+async fn recoverable_parse_error_is_warned_under_recovery() {
+  let warnings = Arc::new(Mutex::new(vec![]));
+  let mut bundler = Bundler::new(input_options(true, warnings.clone()));
+  let output = bundler.generate(OutputOptions::default()).await.unwrap();
+  assert_eq!(output.len(), 1);
+  assert!(output[0].content.contains("console.log(mode)"));
+
+  let warnings = warnings.lock().unwrap();
+  assert_eq!(warnings.len(), 1);
+  assert_eq!(warnings[0].kind.code(), "PARSE_ERROR_RECOVERED");
+  assert!(warnings[0]
+    .kind
+    .to_readable_string(plugin_fixture("parse_recovery"))
+    .starts_with(r#"Recovered from a parse error at 1:21 of "main.js": "#));
+}
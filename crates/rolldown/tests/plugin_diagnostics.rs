@@ -0,0 +1,113 @@
+use std::sync::{Arc, Mutex};
+
+use rolldown::{Bundler, InputOptions};
+use rolldown_plugin::{
+  async_trait, BuildPlugin, BuildStartReturn, Context, PluginName, ResolvedInputOptions,
+  TransformArgs, TransformReturn,
+};
+
+mod common;
+use common::{plugin_fixture, plugin_fixture_input};
+
+/// Warns about `console.log`, and reports every `debugger` as an error.
+#[derive(Debug)]
+struct LintPlugin;
+
+#[async_trait::async_trait]
+impl BuildPlugin for LintPlugin {
+  fn name(&self) -> PluginName {
+    std::borrow::Cow::Borrowed("test:lint")
+  }
+
+  async fn transform(&self, ctx: &mut Context, args: &mut TransformArgs) -> TransformReturn {
+    for (line, code) in args.code.lines().enumerate() {
+      if code.contains("console.log") {
+        ctx.warn(rolldown_error::format_err!("line {}: console.log", line + 1).into());
+      }
+      if code.contains("debugger") {
+        ctx.error(rolldown_error::format_err!("line {}: debugger", line + 1).into());
+      }
+    }
+    Ok(None)
+  }
+}
+
+/// Reports an error of rolldown in `build_start`, which isn't called with a module.
+#[derive(Debug)]
+struct StartPlugin;
+
+#[async_trait::async_trait]
+impl BuildPlugin for StartPlugin {
+  fn name(&self) -> PluginName {
+    std::borrow::Cow::Borrowed("test:start")
+  }
+
+  async fn build_start(
+    &self,
+    ctx: &mut Context,
+    _options: &ResolvedInputOptions,
+  ) -> BuildStartReturn {
+    ctx.error(rolldown_error::Error::too_many_symbols(1, 0));
+    Ok(())
+  }
+}
+
+#[tokio::test]
+async fn plugins_emit_warnings_and_errors_of_modules() {
+  let cwd = plugin_fixture("plugin_diagnostics");
+  let warnings = Arc::new(Mutex::new(vec![]));
+  let mut bundler = Bundler::with_plugins(
+    InputOptions {
+      on_warn: {
+        let (warnings, cwd) = (warnings.clone(), cwd.clone());
+        Arc::new(move |warning| {
+          warnings
+            .lock()
+            .unwrap()
+            .push(warning.to_readable_string(&cwd))
+        })
+      },
+      ..plugin_fixture_input("plugin_diagnostics")
+    },
+    vec![Box::new(LintPlugin)],
+  );
+  let errors = bundler
+    .generate(Default::default())
+    .await
+    .expect_err("Expected errors emitted by the plugin to fail the build")
+    .into_vec();
+
+  assert_eq!(
+    *warnings.lock().unwrap(),
+    vec!["[plugin test:lint] main.js: line 3: console.log".to_string()]
+  );
+  assert_eq!(
+    errors
+      .iter()
+      .map(|error| error.to_readable_string(&cwd))
+      .collect::<Vec<_>>(),
+    vec![
+      "[plugin test:lint] dep.js: line 1: debugger",
+      "[plugin test:lint] dep.js: line 3: debugger"
+    ]
+  );
+}
+
+#[tokio::test]
+async fn codes_of_emitted_errors_are_kept() {
+  let errors = Bundler::with_plugins(
+    plugin_fixture_input("plugin_diagnostics"),
+    vec![Box::new(StartPlugin)],
+  )
+  .generate(Default::default())
+  .await
+  .expect_err("Expected the error emitted by the plugin to fail the build")
+  .into_vec();
+
+  assert_eq!(errors.len(), 1);
+  assert_eq!(errors[0].kind.code(), "TOO_MANY_SYMBOLS");
+  assert_eq!(
+    errors[0].to_string(),
+    r#"[plugin test:start] The module graph has 1 top-level symbols, which exceeds "maxSymbols" of 0."#
+  );
+}
@@ -0,0 +1,3 @@
+import invalid from './invalid.yaml'
+
+console.log(invalid)
@@ -0,0 +1,4 @@
+import logo from './logo.png';
+import raw from './logo.bin';
+
+console.log(logo, raw);
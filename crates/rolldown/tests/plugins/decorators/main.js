@@ -0,0 +1,8 @@
+function sealed(target) {
+  Object.seal(target)
+}
+
+@sealed
+class Greeter {}
+
+export { Greeter }
@@ -0,0 +1,2 @@
+import { used } from './lib'
+console.log(used)
@@ -0,0 +1,3 @@
+import { dep } from './lib/dep.js'
+
+console.log(dep)
@@ -0,0 +1 @@
+import('target').then(({ target }) => console.log(target));
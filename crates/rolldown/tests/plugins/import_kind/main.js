@@ -0,0 +1,5 @@
+import { target } from 'target';
+import './lazy.js';
+import './required.js';
+
+console.log(target);
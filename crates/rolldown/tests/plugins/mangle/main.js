@@ -0,0 +1,5 @@
+export function describe(user) {
+  const reflectedName = user.name
+  const temporaryValue = user.age + 1
+  return `${reflectedName}: ${temporaryValue}`
+}
@@ -0,0 +1 @@
+export const e = 'e';
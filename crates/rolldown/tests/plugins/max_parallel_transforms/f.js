@@ -0,0 +1 @@
+export const f = 'f';
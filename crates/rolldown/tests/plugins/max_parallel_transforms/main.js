@@ -0,0 +1,8 @@
+import { a } from './a'
+import { b } from './b'
+import { c } from './c'
+import { d } from './d'
+import { e } from './e'
+import { f } from './f'
+
+console.log(a, b, c, d, e, f)
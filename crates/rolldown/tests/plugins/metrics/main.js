@@ -0,0 +1,2 @@
+import { value } from './lib'
+console.log(value)
@@ -0,0 +1,6 @@
+const count = 1;
+
+export function readA() {
+  const localResult = count + 1;
+  return localResult;
+}
@@ -0,0 +1,11 @@
+import { readA } from './a.js';
+
+const count = 2;
+
+export function readMain() {
+  if (false) {
+    console.log('unreachable');
+  }
+  const localResult = count * 2;
+  return localResult + readA();
+}
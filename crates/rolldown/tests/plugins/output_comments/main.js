@@ -0,0 +1,7 @@
+// line comment
+const greeting = 'hello';
+
+/** Greets the given name. */
+export function greet(name) {
+  return `${greeting} ${name}`;
+}
@@ -0,0 +1,2 @@
+export const mode = 0644;
+console.log(mode);
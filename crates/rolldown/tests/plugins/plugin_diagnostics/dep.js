@@ -0,0 +1,3 @@
+debugger;
+export const dep = 'dep';
+debugger;
@@ -0,0 +1,3 @@
+import './dep.js';
+
+console.log('main');
@@ -0,0 +1,5 @@
+import { a } from './a'
+import React from 'react'
+import virtual from 'virtual'
+
+console.log(a, React, virtual)
@@ -0,0 +1,5 @@
+import found from './found'
+import missing from './missing'
+import notInstalled from 'not-installed'
+
+console.log(found, missing, notInstalled)
@@ -0,0 +1 @@
+export const present = 'present';
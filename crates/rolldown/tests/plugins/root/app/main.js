@@ -0,0 +1,3 @@
+import { missing } from './dep.js';
+
+console.log(missing);
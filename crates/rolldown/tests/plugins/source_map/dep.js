@@ -0,0 +1,2 @@
+export const dep = 'dep';
+console.log(dep);
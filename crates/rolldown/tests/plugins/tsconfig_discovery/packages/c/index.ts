@@ -0,0 +1,7 @@
+export class Point {
+  x = 1;
+  y: number;
+  constructor(y: number) {
+    this.y = y;
+  }
+}
@@ -0,0 +1,3 @@
+import { used } from './lib.js';
+
+console.log(used);
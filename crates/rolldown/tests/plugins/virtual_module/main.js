@@ -0,0 +1,3 @@
+import { msg } from 'virtual:msg'
+
+console.log(msg)
@@ -0,0 +1,4 @@
+import { msg } from 'virtual:msg'
+import { msg as msg2 } from 'virtual?msg'
+
+console.log(msg, msg2)
@@ -0,0 +1,3 @@
+import { msg } from './msg'
+
+console.log(msg)
@@ -0,0 +1,129 @@
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use rolldown::{Bundler, ModuleFormat, OutputOptions};
+use rolldown_plugin::{
+  async_trait, BuildPlugin, Context, PluginName, RenderChunkArgs, RenderChunkReturn,
+  RenderOutputOptions, RenderedChunk,
+};
+
+mod common;
+use common::{plugin_fixture, plugin_fixture_input};
+
+/// Prepends a banner depending on the output format, and records the output dir.
+#[derive(Debug, Default)]
+struct FormatBanner {
+  dirs: Arc<Mutex<Vec<Option<String>>>>,
+}
+
+#[async_trait::async_trait]
+impl BuildPlugin for FormatBanner {
+  fn name(&self) -> PluginName {
+    std::borrow::Cow::Borrowed("test:format-banner")
+  }
+
+  async fn render_chunk(
+    &self,
+    ctx: &mut Context<&RenderOutputOptions>,
+    args: &RenderChunkArgs,
+  ) -> RenderChunkReturn {
+    self.dirs.lock().unwrap().push(ctx.context.dir.clone());
+    let banner = match ctx.context.format {
+      ModuleFormat::Cjs => "'use strict';",
+      _ => "/* banner */",
+    };
+    Ok(Some(format!("{banner}\n{}", args.code)))
+  }
+}
+
+/// Records chunks passed to `render_chunk`.
+#[derive(Debug, Default)]
+struct RecordChunks {
+  chunks: Arc<Mutex<Vec<RenderedChunk>>>,
+}
+
+#[async_trait::async_trait]
+impl BuildPlugin for RecordChunks {
+  fn name(&self) -> PluginName {
+    std::borrow::Cow::Borrowed("test:record-chunks")
+  }
+
+  async fn render_chunk(
+    &self,
+    _ctx: &mut Context<&RenderOutputOptions>,
+    args: &RenderChunkArgs,
+  ) -> RenderChunkReturn {
+    self.chunks.lock().unwrap().push(args.chunk.clone());
+    Ok(None)
+  }
+}
+
+fn bundler(plugin: impl BuildPlugin + 'static) -> Bundler {
+  Bundler::with_plugins(plugin_fixture_input("render_chunk"), vec![Box::new(plugin)])
+}
+
+#[tokio::test]
+async fn render_chunk_branches_on_format() {
+  let mut bundler = bundler(FormatBanner::default());
+
+  let esm = bundler.generate(Default::default()).await.unwrap();
+  assert!(esm[0].content.starts_with("/* banner */\n"));
+
+  let cjs = bundler
+    .generate(OutputOptions {
+      format: ModuleFormat::Cjs,
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+  assert!(cjs[0].content.starts_with("'use strict';\n"));
+}
+
+#[tokio::test]
+async fn render_chunk_receives_output_dir() {
+  let dirs = Arc::new(Mutex::new(vec![]));
+  let mut bundler = bundler(FormatBanner { dirs: dirs.clone() });
+  let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR"))
+    .join("render_chunk")
+    .to_string_lossy()
+    .to_string();
+
+  bundler.generate(Default::default()).await.unwrap();
+  bundler
+    .write(OutputOptions {
+      dir: Some(dir.clone()),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+  // `generate` renders into `dist` in `cwd` by default, like `write`.
+  let default_dir = plugin_fixture("render_chunk")
+    .join("dist")
+    .to_string_lossy()
+    .to_string();
+  assert_eq!(*dirs.lock().unwrap(), vec![Some(default_dir), Some(dir)]);
+}
+
+#[tokio::test]
+async fn render_chunk_receives_the_chunk() {
+  let plugin = RecordChunks::default();
+  let chunks = plugin.chunks.clone();
+  let mut bundler = bundler(plugin);
+  bundler.generate(Default::default()).await.unwrap();
+
+  let chunks = chunks.lock().unwrap();
+  assert_eq!(chunks.len(), 1);
+  assert_eq!(chunks[0].filename, "main.js");
+  assert!(chunks[0].is_entry);
+  assert_eq!(
+    chunks[0]
+      .module_ids
+      .iter()
+      .map(|id| id.as_ref())
+      .collect::<Vec<_>>(),
+    vec![plugin_fixture("render_chunk")
+      .join("main.js")
+      .to_string_lossy()]
+  );
+}
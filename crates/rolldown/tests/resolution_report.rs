@@ -0,0 +1,104 @@
+use rolldown::{Bundler, InputItem, InputOptions, OutputOptions, ResolutionSource};
+use rolldown_plugin::{
+  async_trait, BuildPlugin, Context, External, LoadArgs, LoadOutput, LoadReturn, PluginName,
+  ResolveArgs, ResolveReturn, ResolvedId,
+};
+
+mod common;
+use common::plugin_fixture;
+
+const VIRTUAL_ID: &str = "\0virtual";
+
+#[derive(Debug)]
+struct VirtualPlugin;
+
+#[async_trait::async_trait]
+impl BuildPlugin for VirtualPlugin {
+  fn name(&self) -> PluginName {
+    std::borrow::Cow::Borrowed("test:virtual")
+  }
+
+  async fn resolve(&self, _ctx: &mut Context, args: &mut ResolveArgs) -> ResolveReturn {
+    if args.specifier == "virtual" {
+      return Ok(Some(ResolvedId {
+        id: VIRTUAL_ID.to_string(),
+        external: External::False,
+      }));
+    }
+    Ok(None)
+  }
+
+  async fn load(&self, _ctx: &mut Context, args: &mut LoadArgs) -> LoadReturn {
+    if args.id.as_ref() == VIRTUAL_ID {
+      return Ok(Some(LoadOutput {
+        code: "export default 'virtual'".to_string(),
+        loader: None,
+      }));
+    }
+    Ok(None)
+  }
+}
+
+fn bundler(resolution_diagnostics: bool) -> Bundler {
+  let cwd = plugin_fixture("resolution_report");
+  Bundler::with_plugins(
+    InputOptions {
+      input: vec![
+        InputItem {
+          name: "main".to_string(),
+          import: "./main.js".to_string(),
+        },
+        InputItem {
+          name: "pkg".to_string(),
+          import: "pkg".to_string(),
+        },
+      ],
+      cwd,
+      resolution_diagnostics,
+      ..Default::default()
+    },
+    vec![Box::new(VirtualPlugin)],
+  )
+}
+
+#[tokio::test]
+async fn report_is_absent_by_default() {
+  let mut bundler = bundler(false);
+  bundler.generate(OutputOptions::default()).await.unwrap();
+  assert!(bundler.resolution_report().is_none());
+}
+
+#[tokio::test]
+async fn report_records_source_of_each_resolution() {
+  let mut bundler = bundler(true);
+  bundler.generate(OutputOptions::default()).await.unwrap();
+  let report = bundler.resolution_report().unwrap();
+
+  let source_of = |specifier: &str| {
+    let record = report
+      .iter()
+      .find(|record| record.specifier == specifier)
+      .unwrap_or_else(|| panic!("{specifier} isn't recorded"));
+    assert!(record.resolved.is_ok(), "{record:?}");
+    record.source.clone().unwrap()
+  };
+
+  assert_eq!(report.len(), 5);
+  assert_eq!(source_of("./main.js"), ResolutionSource::Path);
+  assert_eq!(
+    source_of("pkg"),
+    ResolutionSource::NodeModules(Some("main".to_string()))
+  );
+  assert_eq!(source_of("./a"), ResolutionSource::Path);
+  assert_eq!(
+    source_of("virtual"),
+    ResolutionSource::ResolveHook("test:virtual".to_string())
+  );
+  assert_eq!(source_of("react"), ResolutionSource::Unresolved);
+
+  let entries = report
+    .iter()
+    .filter(|record| record.importer.is_none())
+    .count();
+  assert_eq!(entries, 2);
+}
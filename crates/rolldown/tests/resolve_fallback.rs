@@ -0,0 +1,71 @@
+use std::sync::{Arc, Mutex};
+
+use rolldown::{Bundler, OutputOptions};
+use rolldown_plugin::{
+  async_trait, BuildPlugin, Context, External, LoadArgs, LoadOutput, LoadReturn, PluginName,
+  ResolveArgs, ResolveReturn, ResolvedId,
+};
+
+mod common;
+use common::plugin_fixture_input;
+
+const STUB_PREFIX: &str = "\0stub:";
+
+/// Resolves anything unresolvable to a stub module.
+#[derive(Debug, Default)]
+struct StubPlugin {
+  stubbed: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait::async_trait]
+impl BuildPlugin for StubPlugin {
+  fn name(&self) -> PluginName {
+    std::borrow::Cow::Borrowed("test:stub")
+  }
+
+  async fn resolve_fallback(&self, _ctx: &mut Context, args: &mut ResolveArgs) -> ResolveReturn {
+    self
+      .stubbed
+      .lock()
+      .unwrap()
+      .push(args.specifier.to_string());
+    Ok(Some(ResolvedId {
+      id: format!("{STUB_PREFIX}{}", args.specifier),
+      external: External::False,
+    }))
+  }
+
+  async fn load(&self, _ctx: &mut Context, args: &mut LoadArgs) -> LoadReturn {
+    if let Some(specifier) = args.id.as_ref().strip_prefix(STUB_PREFIX) {
+      return Ok(Some(LoadOutput {
+        code: format!("export default 'stub of {specifier}'"),
+        loader: None,
+      }));
+    }
+    Ok(None)
+  }
+}
+
+#[tokio::test]
+async fn unresolvable_specifiers_fall_back_to_stubs() {
+  let plugin = StubPlugin::default();
+  let stubbed = plugin.stubbed.clone();
+  let mut bundler = Bundler::with_plugins(
+    plugin_fixture_input("resolve_fallback"),
+    vec![Box::new(plugin)],
+  );
+  let output = bundler.generate(OutputOptions::default()).await.unwrap();
+  assert_eq!(output.len(), 1);
+  let content = &output[0].content;
+
+  // Resolvable specifiers never reach the fallback.
+  let mut stubbed = stubbed.lock().unwrap().clone();
+  stubbed.sort();
+  assert_eq!(stubbed, vec!["./missing", "not-installed"]);
+
+  assert!(content.contains("'found'"), "{content}");
+  assert!(content.contains("'stub of ./missing'"), "{content}");
+  assert!(content.contains("'stub of not-installed'"), "{content}");
+  // `not-installed` would be external without the fallback.
+  assert!(!content.contains("import "), "{content}");
+}
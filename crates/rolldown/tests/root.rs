@@ -0,0 +1,79 @@
+use std::sync::{Arc, Mutex};
+
+use rolldown::{Bundler, InputItem, InputOptions, OutputOptions, SourceMapType};
+
+mod common;
+use common::plugin_fixture;
+
+// Fixtures resolve and display paths relative to their own directories, so the root differing
+// from the cwd is tested here.
+
+/// Modules are resolved in `app`, but displayed relative to its parent.
+fn input_options() -> InputOptions {
+  let root = plugin_fixture("root");
+  InputOptions {
+    input: vec![InputItem {
+      name: "main".to_string(),
+      import: "./main.js".to_string(),
+    }],
+    cwd: root.join("app"),
+    root: Some(root),
+    ..Default::default()
+  }
+}
+
+#[tokio::test]
+async fn errors_are_relative_to_root() {
+  let mut bundler = Bundler::new(input_options());
+  let errors = bundler
+    .generate(Default::default())
+    .await
+    .expect_err("Expected the missing export to be reported")
+    .into_vec();
+
+  assert_eq!(errors.len(), 1);
+  assert_eq!(
+    errors[0].to_readable_string(bundler.root()),
+    r#""missing" is not exported by "app/dep.js", imported by "app/main.js"."#
+  );
+}
+
+#[tokio::test]
+async fn warnings_and_source_maps_are_relative_to_root() {
+  let warnings = Arc::new(Mutex::new(vec![]));
+  let mut bundler = Bundler::new(InputOptions {
+    shim_missing_exports: true,
+    on_warn: {
+      let warnings = warnings.clone();
+      Arc::new(move |warning| warnings.lock().unwrap().push(warning.to_string()))
+    },
+    ..input_options()
+  });
+  let output = bundler
+    .generate(OutputOptions {
+      sourcemap: Some(SourceMapType::External),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+  assert!(warnings
+    .lock()
+    .unwrap()
+    .contains(&r#"Missing export "missing" has been shimmed in module "app/dep.js"."#.to_string()));
+
+  let chunk = output
+    .iter()
+    .find(|asset| asset.filename == "main.js")
+    .unwrap();
+  assert!(chunk.content.contains("// app/main.js"));
+  let source_map = output
+    .iter()
+    .find(|asset| asset.filename == "main.js.map")
+    .unwrap();
+  let source_map = serde_json::from_str::<serde_json::Value>(&source_map.content).unwrap();
+  assert!(source_map["sources"]
+    .as_array()
+    .unwrap()
+    .contains(&serde_json::json!("app/main.js")));
+}
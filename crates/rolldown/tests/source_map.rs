@@ -0,0 +1,162 @@
+use std::path::PathBuf;
+
+use rolldown::{
+  Asset, Bundler, InputItem, InputOptions, ModuleFormat, OutputOptions, SourceMapType,
+};
+
+async fn generate(format: ModuleFormat, sourcemap: SourceMapType) -> Vec<Asset> {
+  generate_with(OutputOptions {
+    format,
+    sourcemap: Some(sourcemap),
+    ..Default::default()
+  })
+  .await
+}
+
+async fn generate_with(output_options: OutputOptions) -> Vec<Asset> {
+  let mut bundler = Bundler::new(InputOptions {
+    input: vec![InputItem {
+      name: "main".to_string(),
+      import: "./main.js".to_string(),
+    }],
+    cwd: PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/plugins/source_map"),
+    ..Default::default()
+  });
+  bundler.generate(output_options).await.unwrap()
+}
+
+fn find<'a>(assets: &'a [Asset], filename: &str) -> Option<&'a Asset> {
+  assets.iter().find(|asset| asset.filename == filename)
+}
+
+const BASE64_CHARS: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+fn decode_vlq(segment: &str) -> Vec<i64> {
+  let mut values = vec![];
+  let (mut value, mut shift) = (0, 0);
+  for c in segment.chars() {
+    let digit = BASE64_CHARS.find(c).unwrap() as i64;
+    value += (digit & 31) << shift;
+    if digit & 32 == 0 {
+      values.push(if value & 1 == 1 {
+        -(value >> 1)
+      } else {
+        value >> 1
+      });
+      (value, shift) = (0, 0);
+    } else {
+      shift += 5;
+    }
+  }
+  values
+}
+
+/// Source index and original line of the first segment of each generated line.
+fn first_segments(mappings: &str) -> Vec<Option<(i64, i64)>> {
+  let (mut source, mut line) = (0, 0);
+  mappings
+    .split(';')
+    .map(|segments| {
+      let mut first = None;
+      for segment in segments.split(',').filter(|segment| !segment.is_empty()) {
+        let values = decode_vlq(segment);
+        if values.len() >= 4 {
+          source += values[1];
+          line += values[2];
+          first.get_or_insert((source, line));
+        }
+      }
+      first
+    })
+    .collect()
+}
+
+/// Statements of both modules are mapped back to their own lines.
+fn assert_mapped_back(code: &str, source_map: &str) {
+  let source_map: serde_json::Value = serde_json::from_str(source_map).unwrap();
+  assert_eq!(source_map["version"], 3);
+  let sources = source_map["sources"]
+    .as_array()
+    .unwrap()
+    .iter()
+    .map(|source| source.as_str().unwrap())
+    .collect::<Vec<_>>();
+  let segments = first_segments(source_map["mappings"].as_str().unwrap());
+
+  let original_of = |needle: &str| {
+    let line = code
+      .lines()
+      .position(|line| line.trim_start().starts_with(needle))
+      .unwrap_or_else(|| panic!("{needle} isn't generated:\n{code}"));
+    let (source, line) = segments[line].unwrap();
+    (sources[source as usize], line)
+  };
+  assert_eq!(original_of("console.log(dep)"), ("dep.js", 1));
+  assert_eq!(original_of("console.log('main'"), ("main.js", 2));
+}
+
+#[tokio::test]
+async fn external_source_map() {
+  let assets = generate(ModuleFormat::Esm, SourceMapType::External).await;
+
+  let chunk = find(&assets, "main.js").unwrap();
+  assert!(chunk
+    .content
+    .ends_with("//# sourceMappingURL=main.js.map\n"));
+  assert_mapped_back(
+    &chunk.content,
+    &find(&assets, "main.js.map").unwrap().content,
+  );
+}
+
+#[tokio::test]
+async fn hidden_source_map() {
+  let assets = generate(ModuleFormat::Esm, SourceMapType::Hidden).await;
+
+  let chunk = find(&assets, "main.js").unwrap();
+  assert!(!chunk.content.contains("//# sourceMappingURL="));
+  assert_mapped_back(
+    &chunk.content,
+    &find(&assets, "main.js.map").unwrap().content,
+  );
+}
+
+#[tokio::test]
+async fn inline_source_map() {
+  let assets = generate(ModuleFormat::Esm, SourceMapType::Inline).await;
+
+  let chunk = find(&assets, "main.js").unwrap();
+  assert!(chunk
+    .content
+    .contains("//# sourceMappingURL=data:application/json;charset=utf-8;base64,"));
+  assert!(find(&assets, "main.js.map").is_none());
+}
+
+#[tokio::test]
+async fn source_map_through_cjs_transform() {
+  let assets = generate(ModuleFormat::Cjs, SourceMapType::Hidden).await;
+
+  assert_mapped_back(
+    &find(&assets, "main.js").unwrap().content,
+    &find(&assets, "main.js.map").unwrap().content,
+  );
+}
+
+#[tokio::test]
+async fn footer_precedes_the_source_map_comment() {
+  let assets = generate_with(OutputOptions {
+    footer: Some("/*! footer */".to_string()),
+    sourcemap: Some(SourceMapType::External),
+    ..Default::default()
+  })
+  .await;
+
+  let chunk = find(&assets, "main.js").unwrap();
+  assert!(
+    chunk
+      .content
+      .ends_with("/*! footer */\n//# sourceMappingURL=main.js.map\n"),
+    "{}",
+    chunk.content
+  );
+}
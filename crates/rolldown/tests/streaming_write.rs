@@ -0,0 +1,177 @@
+use std::{
+  path::PathBuf,
+  sync::{Arc, Mutex},
+};
+
+use rolldown::{Bundler, InputItem, InputOptions, OutputOptions};
+use rolldown_core::{Asset, BuildInputOptions, BuildOutputOptions, BundlerCore};
+use rolldown_plugin::{
+  async_trait, BuildPlugin, Context, PluginName, RenderChunkArgs, RenderChunkReturn,
+  RenderOutputOptions,
+};
+
+mod common;
+use common::plugin_fixture;
+
+fn input() -> Vec<InputItem> {
+  ["a", "b"]
+    .into_iter()
+    .map(|name| InputItem {
+      name: name.to_string(),
+      import: format!("./{name}.js"),
+    })
+    .collect()
+}
+
+#[tokio::test]
+async fn assets_are_handed_over_as_rendered() {
+  let received = Arc::new(Mutex::new(vec![]));
+  let mut bundler = BundlerCore::new(BuildInputOptions {
+    input: input(),
+    cwd: plugin_fixture("streaming_write"),
+    ..Default::default()
+  });
+  let output = bundler
+    .build(BuildOutputOptions {
+      on_asset: Some({
+        let received = received.clone();
+        Arc::new(move |asset: &Asset| {
+          received
+            .lock()
+            .unwrap()
+            .push((asset.filename.clone(), asset.content.len()));
+          Ok(())
+        })
+      }),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+  let received = received.lock().unwrap();
+  // Two entries and the chunk of `shared.js`
+  assert_eq!(received.len(), 3);
+  assert!(received.iter().all(|(_, len)| *len > 0));
+  // Returned assets are in the order they were handed over, without contents.
+  assert_eq!(
+    output
+      .iter()
+      .map(|asset| asset.filename.as_str())
+      .collect::<Vec<_>>(),
+    received
+      .iter()
+      .map(|(filename, _)| filename.as_str())
+      .collect::<Vec<_>>()
+  );
+  assert!(output.iter().all(|asset| asset.content.is_empty()));
+}
+
+/// Records `render:<filename>` once a chunk is rendered.
+#[derive(Debug, Default)]
+struct RecordRender {
+  events: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait::async_trait]
+impl BuildPlugin for RecordRender {
+  fn name(&self) -> PluginName {
+    std::borrow::Cow::Borrowed("test:record-render")
+  }
+
+  async fn render_chunk(
+    &self,
+    _ctx: &mut Context<&RenderOutputOptions>,
+    args: &RenderChunkArgs,
+  ) -> RenderChunkReturn {
+    self
+      .events
+      .lock()
+      .unwrap()
+      .push(format!("render:{}", args.chunk.filename));
+    Ok(None)
+  }
+}
+
+#[tokio::test]
+async fn chunks_are_written_before_the_next_one_is_rendered() {
+  let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("streaming_write_order");
+  if dir.exists() {
+    std::fs::remove_dir_all(&dir).unwrap();
+  }
+  std::fs::create_dir_all(&dir).unwrap();
+
+  let plugin = RecordRender::default();
+  let events = plugin.events.clone();
+  let mut bundler = BundlerCore::with_plugins(
+    BuildInputOptions {
+      input: input(),
+      cwd: plugin_fixture("streaming_write"),
+      ..Default::default()
+    },
+    vec![Box::new(plugin)],
+  );
+  let output = bundler
+    .build(BuildOutputOptions {
+      // Hashes in file names would hold all chunks until they're rendered.
+      entry_file_names: "[name].js".to_string().into(),
+      chunk_file_names: "[name].js".to_string().into(),
+      on_asset: Some({
+        let events = events.clone();
+        let dir = dir.clone();
+        Arc::new(move |asset: &Asset| {
+          std::fs::write(dir.join(&asset.filename), &asset.content).unwrap();
+          events
+            .lock()
+            .unwrap()
+            .push(format!("write:{}", asset.filename));
+          Ok(())
+        })
+      }),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+  // Each chunk is written right after it's rendered, so at most one rendered chunk is held in
+  // memory at a time, and nothing is kept by the returned assets.
+  let events = events.lock().unwrap();
+  assert_eq!(events.len(), 6, "{events:?}");
+  for pair in events.chunks(2) {
+    let rendered = pair[0].strip_prefix("render:").unwrap();
+    assert_eq!(pair[1], format!("write:{rendered}"), "{events:?}");
+    assert!(dir.join(rendered).exists());
+  }
+  assert!(output.iter().all(|asset| asset.content.is_empty()));
+}
+
+#[tokio::test]
+async fn streaming_write_matches_generate() {
+  let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("streaming_write");
+  if dir.exists() {
+    std::fs::remove_dir_all(&dir).unwrap();
+  }
+
+  let mut bundler = Bundler::new(InputOptions {
+    input: input(),
+    cwd: plugin_fixture("streaming_write"),
+    ..Default::default()
+  });
+  let generated = bundler.generate(Default::default()).await.unwrap();
+  let written = bundler
+    .write(OutputOptions {
+      dir: Some(dir.to_string_lossy().to_string()),
+      streaming: true,
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+  assert_eq!(written.len(), generated.len());
+  assert!(written.iter().all(|asset| asset.content.is_empty()));
+  for asset in &generated {
+    assert_eq!(
+      std::fs::read_to_string(dir.join(&asset.filename)).unwrap(),
+      asset.content
+    );
+  }
+}
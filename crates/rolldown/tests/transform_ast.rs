@@ -0,0 +1,59 @@
+use rolldown::{Bundler, OutputOptions};
+use rolldown_plugin::{
+  async_trait, BuildPlugin, Context, PluginName, TransformArgs, TransformAstArgs,
+  TransformAstReturn, TransformReturn,
+};
+use swc_core::ecma::{
+  ast,
+  visit::{VisitMut, VisitMutWith},
+};
+
+mod common;
+use common::plugin_fixture_input;
+
+#[derive(Debug)]
+struct RenameFooToBar;
+
+impl VisitMut for RenameFooToBar {
+  fn visit_mut_ident(&mut self, ident: &mut ast::Ident) {
+    if &*ident.sym == "foo" {
+      ident.sym = "bar".into();
+    }
+  }
+}
+
+#[async_trait::async_trait]
+impl BuildPlugin for RenameFooToBar {
+  fn name(&self) -> PluginName {
+    std::borrow::Cow::Borrowed("test:rename-foo-to-bar")
+  }
+
+  async fn transform(&self, _ctx: &mut Context, args: &mut TransformArgs) -> TransformReturn {
+    // The source text should be untouched, the renaming only happens on the AST.
+    assert!(args.code.contains("foo"));
+    Ok(None)
+  }
+
+  async fn transform_ast(
+    &self,
+    _ctx: &mut Context,
+    args: &mut TransformAstArgs,
+  ) -> TransformAstReturn {
+    args.ast.visit_mut_with(&mut RenameFooToBar);
+    Ok(())
+  }
+}
+
+#[tokio::test]
+async fn transform_ast_mutates_the_parsed_module() {
+  let mut bundler = Bundler::with_plugins(
+    plugin_fixture_input("transform_ast"),
+    vec![Box::new(RenameFooToBar)],
+  );
+  let output = bundler.generate(OutputOptions::default()).await.unwrap();
+  assert_eq!(output.len(), 1);
+  let content = &output[0].content;
+  assert!(content.contains("const bar = 1;"), "{content}");
+  assert!(content.contains("console.log(bar);"), "{content}");
+  assert!(!content.contains("foo"), "{content}");
+}
@@ -0,0 +1,123 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use rolldown::{Bundler, InputOptions, Loader, OutputOptions};
+use rolldown_plugin::{
+  async_trait, BuildPlugin, Context, LoadArgs, LoadOutput, LoadReturn, PluginName,
+  ShouldTransformCachedModuleArgs, ShouldTransformCachedModuleReturn, TransformArgs,
+  TransformReturn,
+};
+
+mod common;
+use common::plugin_fixture_input;
+
+/// Records transformed modules, and asks for transforming `a.js` again even if it's cached.
+#[derive(Debug, Default)]
+struct InvalidateA {
+  transformed: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait::async_trait]
+impl BuildPlugin for InvalidateA {
+  fn name(&self) -> PluginName {
+    std::borrow::Cow::Borrowed("test:invalidate-a")
+  }
+
+  async fn transform(&self, _ctx: &mut Context, args: &mut TransformArgs) -> TransformReturn {
+    let file_name = args.id.to_string().rsplit('/').next().unwrap().to_string();
+    self.transformed.lock().unwrap().push(file_name);
+    Ok(None)
+  }
+
+  async fn should_transform_cached_module(
+    &self,
+    _ctx: &mut Context,
+    args: &ShouldTransformCachedModuleArgs,
+  ) -> ShouldTransformCachedModuleReturn {
+    Ok(args.id.to_string().ends_with("/a.js"))
+  }
+}
+
+/// Records transformed modules, and loads `b.js` as TypeScript once `as_ts` is set.
+#[derive(Debug, Default)]
+struct SwitchLoaderOfB {
+  transformed: Arc<Mutex<Vec<String>>>,
+  as_ts: Arc<AtomicBool>,
+}
+
+#[async_trait::async_trait]
+impl BuildPlugin for SwitchLoaderOfB {
+  fn name(&self) -> PluginName {
+    std::borrow::Cow::Borrowed("test:switch-loader-of-b")
+  }
+
+  async fn load(&self, _ctx: &mut Context, args: &mut LoadArgs) -> LoadReturn {
+    if !args.id.to_string().ends_with("/b.js") {
+      return Ok(None);
+    }
+    let loader = if self.as_ts.load(Ordering::SeqCst) {
+      Loader::Ts
+    } else {
+      Loader::Js
+    };
+    Ok(Some(LoadOutput {
+      code: "export const b = 'b'".to_string(),
+      loader: Some(loader),
+    }))
+  }
+
+  async fn transform(&self, _ctx: &mut Context, args: &mut TransformArgs) -> TransformReturn {
+    let file_name = args.id.to_string().rsplit('/').next().unwrap().to_string();
+    self.transformed.lock().unwrap().push(file_name);
+    Ok(None)
+  }
+}
+
+fn input_options(cache: bool) -> InputOptions {
+  InputOptions {
+    cache,
+    ..plugin_fixture_input("transform_cache")
+  }
+}
+
+#[tokio::test]
+async fn should_transform_cached_module_invalidates_cache() {
+  let plugin = InvalidateA::default();
+  let transformed = plugin.transformed.clone();
+  let mut bundler = Bundler::with_plugins(input_options(true), vec![Box::new(plugin)]);
+
+  let first = bundler.generate(OutputOptions::default()).await.unwrap();
+  let mut first_transformed = std::mem::take(&mut *transformed.lock().unwrap());
+  first_transformed.sort();
+  assert_eq!(first_transformed, ["a.js", "b.js", "main.js"]);
+
+  let second = bundler.generate(OutputOptions::default()).await.unwrap();
+  assert_eq!(*transformed.lock().unwrap(), ["a.js"]);
+  assert_eq!(first[0].content, second[0].content);
+}
+
+#[tokio::test]
+async fn modules_are_transformed_again_without_cache() {
+  let plugin = InvalidateA::default();
+  let transformed = plugin.transformed.clone();
+  let mut bundler = Bundler::with_plugins(input_options(false), vec![Box::new(plugin)]);
+
+  bundler.generate(OutputOptions::default()).await.unwrap();
+  bundler.generate(OutputOptions::default()).await.unwrap();
+  assert_eq!(transformed.lock().unwrap().len(), 6);
+}
+
+#[tokio::test]
+async fn modules_loaded_by_another_loader_are_transformed_again() {
+  let plugin = SwitchLoaderOfB::default();
+  let transformed = plugin.transformed.clone();
+  let as_ts = plugin.as_ts.clone();
+  let mut bundler = Bundler::with_plugins(input_options(true), vec![Box::new(plugin)]);
+
+  bundler.generate(OutputOptions::default()).await.unwrap();
+  transformed.lock().unwrap().clear();
+
+  as_ts.store(true, Ordering::SeqCst);
+  bundler.generate(OutputOptions::default()).await.unwrap();
+  assert_eq!(*transformed.lock().unwrap(), ["b.js"]);
+}
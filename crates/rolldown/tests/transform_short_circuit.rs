@@ -0,0 +1,71 @@
+use std::sync::{Arc, Mutex};
+
+use rolldown::{Bundler, OutputOptions};
+use rolldown_plugin::{
+  async_trait, BuildPlugin, Context, PluginName, TransformArgs, TransformReturn,
+};
+
+mod common;
+use common::plugin_fixture_input;
+
+/// Fully handles modules, so later transforms shouldn't touch its output.
+#[derive(Debug)]
+struct ExclusiveTransform;
+
+#[async_trait::async_trait]
+impl BuildPlugin for ExclusiveTransform {
+  fn name(&self) -> PluginName {
+    std::borrow::Cow::Borrowed("test:exclusive")
+  }
+
+  async fn transform(&self, _ctx: &mut Context, args: &mut TransformArgs) -> TransformReturn {
+    args.stop_further_transforms = true;
+    Ok(Some(args.code.replace("source", "compiled")))
+  }
+}
+
+#[derive(Debug, Default)]
+struct RecordingTransform {
+  transformed: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait::async_trait]
+impl BuildPlugin for RecordingTransform {
+  fn name(&self) -> PluginName {
+    std::borrow::Cow::Borrowed("test:recording")
+  }
+
+  async fn transform(&self, _ctx: &mut Context, args: &mut TransformArgs) -> TransformReturn {
+    self.transformed.lock().unwrap().push(args.id.to_string());
+    Ok(Some(args.code.replace("compiled", "mangled")))
+  }
+}
+
+#[tokio::test]
+async fn stopping_further_transforms_skips_later_plugins() {
+  let recording = RecordingTransform::default();
+  let transformed = recording.transformed.clone();
+  let mut bundler = Bundler::with_plugins(
+    plugin_fixture_input("transform_short_circuit"),
+    vec![Box::new(ExclusiveTransform), Box::new(recording)],
+  );
+  let output = bundler.generate(OutputOptions::default()).await.unwrap();
+  let content = &output[0].content;
+  assert!(content.contains("'compiled'"), "{content}");
+  assert!(transformed.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn transforms_run_in_plugin_order() {
+  let recording = RecordingTransform::default();
+  let transformed = recording.transformed.clone();
+  let mut bundler = Bundler::with_plugins(
+    plugin_fixture_input("transform_short_circuit"),
+    vec![Box::new(recording), Box::new(ExclusiveTransform)],
+  );
+  let output = bundler.generate(OutputOptions::default()).await.unwrap();
+  let content = &output[0].content;
+  // `compiled` isn't in the source yet when the recording plugin runs first.
+  assert!(content.contains("'compiled'"), "{content}");
+  assert_eq!(transformed.lock().unwrap().len(), 1);
+}
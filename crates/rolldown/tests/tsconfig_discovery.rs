@@ -0,0 +1,100 @@
+use rolldown::{BuiltinsOptions, Bundler, InputItem, InputOptions, OutputOptions, TsConfig};
+
+mod common;
+use common::plugin_fixture;
+
+// Class fields are compiled by swc, so the output is compared to the one of the global tsconfig
+// the discovered one should be equivalent to, instead of a snapshot.
+
+fn input_options(packages: &[&str], builtins: BuiltinsOptions) -> InputOptions {
+  InputOptions {
+    input: packages
+      .iter()
+      .map(|name| InputItem {
+        name: name.to_string(),
+        import: format!("./{name}/index.ts"),
+      })
+      .collect(),
+    cwd: plugin_fixture("tsconfig_discovery/packages"),
+    builtins,
+    ..Default::default()
+  }
+}
+
+async fn generate(packages: &[&str], builtins: BuiltinsOptions) -> Vec<(String, String)> {
+  let mut bundler = Bundler::new(input_options(packages, builtins));
+  let output = bundler.generate(OutputOptions::default()).await.unwrap();
+  let mut assets = output
+    .into_iter()
+    .map(|asset| (asset.filename, asset.content))
+    .collect::<Vec<_>>();
+  assets.sort();
+  assets
+}
+
+/// Output of the package compiled with a global tsconfig, without discovery.
+async fn generate_with_global_tsconfig(package: &str, use_define_for_class_fields: bool) -> String {
+  let assets = generate(
+    &[package],
+    BuiltinsOptions {
+      tsconfig: Some(TsConfig {
+        use_define_for_class_fields,
+        ..Default::default()
+      }),
+      ..Default::default()
+    },
+  )
+  .await;
+  assets.into_iter().next().unwrap().1
+}
+
+#[tokio::test]
+async fn modules_are_compiled_with_their_nearest_tsconfig() {
+  // The global tsconfig is the opposite of package `a`, so it must be overridden to match.
+  let assets = generate(
+    &["a", "b"],
+    BuiltinsOptions {
+      tsconfig: Some(TsConfig {
+        use_define_for_class_fields: false,
+        ..Default::default()
+      }),
+      discover_tsconfig: true,
+      ..Default::default()
+    },
+  )
+  .await;
+
+  assert_eq!(
+    assets,
+    vec![
+      (
+        "a.js".to_string(),
+        generate_with_global_tsconfig("a", true).await
+      ),
+      (
+        "b.js".to_string(),
+        generate_with_global_tsconfig("b", false).await
+      ),
+    ]
+  );
+}
+
+#[tokio::test]
+async fn options_of_extended_tsconfig_are_inherited() {
+  let assets = generate(
+    &["c"],
+    BuiltinsOptions {
+      discover_tsconfig: true,
+      ..Default::default()
+    },
+  )
+  .await;
+
+  assert_eq!(
+    assets,
+    vec![(
+      "c.js".to_string(),
+      generate_with_global_tsconfig("c", true).await
+    )]
+  );
+}
@@ -0,0 +1,26 @@
+use rolldown::{BuiltinsOptions, Bundler, InputOptions, OutputOptions, TsConfig};
+
+mod common;
+use common::plugin_fixture_input;
+
+// Resolving by `paths` and `baseUrl` is covered by `fixtures/tsconfig_paths`, the reason of the
+// missing file comes from the OS, so only its code is asserted here.
+#[tokio::test]
+async fn missing_config_file() {
+  let errors = Bundler::new(InputOptions {
+    builtins: BuiltinsOptions {
+      tsconfig: Some(TsConfig {
+        config_file: Some("missing.json".into()),
+        ..Default::default()
+      }),
+      ..Default::default()
+    },
+    ..plugin_fixture_input("tsconfig_paths")
+  })
+  .generate(OutputOptions::default())
+  .await
+  .expect_err("The config file doesn't exist")
+  .into_vec();
+  assert_eq!(errors.len(), 1);
+  assert_eq!(errors[0].kind.code(), "INVALID_OPTION");
+}
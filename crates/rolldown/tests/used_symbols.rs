@@ -0,0 +1,33 @@
+use rolldown::{Bundler, InputOptions, OutputOptions};
+
+mod common;
+use common::plugin_fixture_input;
+
+fn bundler(treeshake: bool) -> Bundler {
+  Bundler::new(InputOptions {
+    treeshake,
+    ..plugin_fixture_input("used_symbols")
+  })
+}
+
+#[tokio::test]
+async fn used_symbols_exclude_dead_declarations() {
+  let mut bundler = bundler(true);
+  bundler.generate(OutputOptions::default()).await.unwrap();
+  let used_symbols = bundler.used_symbols().unwrap();
+
+  let is_used = |module: &str, name: &str| {
+    used_symbols
+      .iter()
+      .any(|symbol| symbol.module.to_string().ends_with(module) && symbol.name == name)
+  };
+  assert!(is_used("lib.js", "used"), "{used_symbols:#?}");
+  assert!(!is_used("lib.js", "dead"), "{used_symbols:#?}");
+}
+
+#[tokio::test]
+async fn used_symbols_are_absent_without_treeshake() {
+  let mut bundler = bundler(false);
+  bundler.generate(OutputOptions::default()).await.unwrap();
+  assert!(bundler.used_symbols().is_none());
+}
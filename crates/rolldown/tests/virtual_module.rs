@@ -0,0 +1,96 @@
+use rolldown::{Bundler, OutputOptions, SourceMapType};
+use rolldown_plugin::{
+  async_trait, BuildPlugin, Context, External, LoadArgs, LoadOutput, LoadReturn, PluginName,
+  ResolveArgs, ResolveReturn, ResolvedId,
+};
+
+mod common;
+use common::plugin_fixture_input;
+
+/// Resolves `virtual:*` and `virtual?*` to virtual modules exporting their specifiers as `msg`.
+#[derive(Debug)]
+struct VirtualModulePlugin;
+
+#[async_trait::async_trait]
+impl BuildPlugin for VirtualModulePlugin {
+  fn name(&self) -> PluginName {
+    std::borrow::Cow::Borrowed("test:virtual-module")
+  }
+
+  async fn resolve(&self, _ctx: &mut Context, args: &mut ResolveArgs) -> ResolveReturn {
+    if args.specifier.starts_with("virtual") {
+      return Ok(Some(ResolvedId {
+        id: format!("\0{}", args.specifier),
+        external: External::False,
+      }));
+    }
+    Ok(None)
+  }
+
+  async fn load(&self, _ctx: &mut Context, args: &mut LoadArgs) -> LoadReturn {
+    if let Some(specifier) = args.id.as_ref().strip_prefix('\0') {
+      return Ok(Some(LoadOutput {
+        code: format!("export const msg = {specifier:?};"),
+        loader: None,
+      }));
+    }
+    Ok(None)
+  }
+}
+
+/// The generated code and the `sources` of its source map.
+async fn build(fixture: &str) -> (String, Vec<String>) {
+  let mut bundler = Bundler::with_plugins(
+    plugin_fixture_input(fixture),
+    vec![Box::new(VirtualModulePlugin)],
+  );
+  let output = bundler
+    .generate(OutputOptions {
+      sourcemap: Some(SourceMapType::Hidden),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+  assert_eq!(output.len(), 2);
+  let source_map: serde_json::Value = serde_json::from_str(&output[1].content).unwrap();
+  let sources = source_map["sources"]
+    .as_array()
+    .unwrap()
+    .iter()
+    .map(|source| source.as_str().unwrap().to_string())
+    .collect();
+  (output[0].content.clone(), sources)
+}
+
+#[tokio::test]
+async fn virtual_module_ids_are_stable_and_sanitized() {
+  let (first, first_sources) = build("virtual_module").await;
+  let (second, second_sources) = build("virtual_module").await;
+  assert_eq!(first, second);
+  assert_eq!(first_sources, second_sources);
+  assert!(first.contains("// _virtual_msg-8786fc12\n"), "{first}");
+  assert!(!first.contains('\0'), "{first}");
+  assert!(
+    first_sources
+      .iter()
+      .any(|source| source == "_virtual_msg-8786fc12"),
+    "{first_sources:?}"
+  );
+  assert!(
+    first_sources.iter().all(|source| !source.contains('\0')),
+    "{first_sources:?}"
+  );
+}
+
+#[tokio::test]
+async fn virtual_module_ids_sanitized_alike_stay_distinct() {
+  let (content, sources) = build("virtual_module_collision").await;
+  assert!(content.contains("// _virtual_msg-8786fc12\n"), "{content}");
+  assert!(content.contains("// _virtual_msg-16fd6ec3\n"), "{content}");
+  assert!(
+    sources
+      .iter()
+      .any(|source| source == "_virtual_msg-16fd6ec3"),
+    "{sources:?}"
+  );
+}
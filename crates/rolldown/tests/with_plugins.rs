@@ -0,0 +1,78 @@
+use std::sync::{Arc, Mutex};
+
+use rolldown::{Bundler, OutputOptions};
+use rolldown_plugin::{
+  async_trait, BuildPlugin, Context, LoadArgs, LoadReturn, PluginName, ResolveArgs, ResolveReturn,
+  TransformArgs, TransformReturn,
+};
+
+mod common;
+use common::plugin_fixture_input;
+
+/// Records called hooks, and uppercases the string in `msg.js`.
+#[derive(Debug, Default)]
+struct HookRecorder {
+  called_hooks: Arc<Mutex<Vec<String>>>,
+}
+
+impl HookRecorder {
+  fn record(&self, hook: &str, id: &str) {
+    let name = id.rsplit('/').next().unwrap_or(id);
+    self
+      .called_hooks
+      .lock()
+      .unwrap()
+      .push(format!("{hook}:{name}"));
+  }
+}
+
+#[async_trait::async_trait]
+impl BuildPlugin for HookRecorder {
+  fn name(&self) -> PluginName {
+    std::borrow::Cow::Borrowed("test:hook-recorder")
+  }
+
+  async fn resolve(&self, _ctx: &mut Context, args: &mut ResolveArgs) -> ResolveReturn {
+    self.record("resolve", args.specifier);
+    Ok(None)
+  }
+
+  async fn load(&self, _ctx: &mut Context, args: &mut LoadArgs) -> LoadReturn {
+    self.record("load", args.id.as_ref());
+    Ok(None)
+  }
+
+  async fn transform(&self, _ctx: &mut Context, args: &mut TransformArgs) -> TransformReturn {
+    self.record("transform", args.id.as_ref());
+    if args.id.as_ref().ends_with("msg.js") {
+      return Ok(Some(args.code.replace("hello", "HELLO")));
+    }
+    Ok(None)
+  }
+}
+
+#[tokio::test]
+async fn hooks_of_rust_plugins_run() {
+  let plugin = HookRecorder::default();
+  let called_hooks = plugin.called_hooks.clone();
+  let mut bundler =
+    Bundler::new_with_plugins(plugin_fixture_input("with_plugins"), vec![Box::new(plugin)]);
+  let output = bundler.generate(OutputOptions::default()).await.unwrap();
+  assert_eq!(output.len(), 1);
+  let content = &output[0].content;
+  assert!(content.contains("'HELLO'"), "{content}");
+
+  let mut called_hooks = called_hooks.lock().unwrap().clone();
+  called_hooks.sort();
+  assert_eq!(
+    called_hooks,
+    vec![
+      "load:main.js",
+      "load:msg.js",
+      "resolve:./main.js",
+      "resolve:./msg",
+      "transform:main.js",
+      "transform:msg.js",
+    ]
+  );
+}
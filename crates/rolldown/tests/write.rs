@@ -0,0 +1,36 @@
+use std::path::PathBuf;
+
+use rolldown::{Bundler, FileNameTemplate, InputItem, InputOptions, OutputOptions};
+
+mod common;
+use common::plugin_fixture;
+
+#[tokio::test]
+async fn write_creates_nested_dirs() {
+  let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("write_nested_dirs");
+  if dir.exists() {
+    std::fs::remove_dir_all(&dir).unwrap();
+  }
+
+  let mut bundler = Bundler::new(InputOptions {
+    input: vec![InputItem {
+      name: "file".to_string(),
+      import: "./main.js".to_string(),
+    }],
+    cwd: plugin_fixture("write_nested_dirs"),
+    ..Default::default()
+  });
+  let output = bundler
+    .write(OutputOptions {
+      dir: Some(dir.to_string_lossy().to_string()),
+      entry_file_names: FileNameTemplate::from("nested/deep/[name].js".to_string()),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+  assert_eq!(output.len(), 1);
+  assert_eq!(output[0].filename, "nested/deep/file.js");
+  assert!(dir.join("nested/deep").is_dir());
+  assert!(dir.join("nested/deep/file.js").is_file());
+}
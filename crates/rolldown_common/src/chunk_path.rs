@@ -0,0 +1,75 @@
+/// Compute the specifier used by the chunk `importer` to import the chunk `importee`. Both are
+/// file names relative to the output dir.
+///
+/// Import specifiers are always POSIX-style, so `\` in file names is treated as a separator too,
+/// no matter which OS we are running on.
+pub fn chunk_import_specifier(importer: &str, importee: &str) -> String {
+  fn segments(path: &str) -> Vec<&str> {
+    path
+      .split(['/', '\\'])
+      .filter(|seg| !seg.is_empty() && *seg != ".")
+      .collect()
+  }
+  let mut importer_dir = segments(importer);
+  // Remove the file name
+  importer_dir.pop();
+  let importee = segments(importee);
+  let (importee_dir, importee_file) = importee.split_at(importee.len().saturating_sub(1));
+
+  let common = importer_dir
+    .iter()
+    .zip(importee_dir)
+    .take_while(|(a, b)| a == b)
+    .count();
+
+  let mut specifier = if common == importer_dir.len() && importee_dir.get(common) != Some(&"..") {
+    "./".to_string()
+  } else {
+    "../".repeat(importer_dir.len() - common)
+  };
+  importee_dir[common..]
+    .iter()
+    .chain(importee_file)
+    .enumerate()
+    .for_each(|(idx, seg)| {
+      if idx > 0 {
+        specifier.push('/');
+      }
+      specifier.push_str(seg);
+    });
+  specifier
+}
+
+#[test]
+fn test_chunk_import_specifier() {
+  assert_eq!(
+    chunk_import_specifier("main.js", "shared.js"),
+    "./shared.js"
+  );
+  assert_eq!(
+    chunk_import_specifier("main.js", "chunks/shared.js"),
+    "./chunks/shared.js"
+  );
+  assert_eq!(
+    chunk_import_specifier("chunks/a.js", "chunks/b.js"),
+    "./b.js"
+  );
+  assert_eq!(
+    chunk_import_specifier("chunks/a.js", "main.js"),
+    "../main.js"
+  );
+  // Windows-style paths
+  assert_eq!(
+    chunk_import_specifier("main.js", "chunks\\nested\\shared.js"),
+    "./chunks/nested/shared.js"
+  );
+  assert_eq!(
+    chunk_import_specifier("entries\\main.js", "chunks\\shared.js"),
+    "../chunks/shared.js"
+  );
+  // Files outside of the output dir
+  assert_eq!(
+    chunk_import_specifier("main.js", "../lib/dep.js"),
+    "../lib/dep.js"
+  );
+}
@@ -0,0 +1,10 @@
+/// How an external module is referred to by chunks importing it.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum ExternalKind {
+  /// By its id as is, even if it's an absolute path.
+  Absolute,
+  /// By a path relative to the importing chunk if its id is an absolute path, like rollup does with
+  /// `makeAbsoluteExternalsRelative`. Chunks are located in `dir` of the output, or in `cwd` if it
+  /// isn't set.
+  Relative,
+}
@@ -1,6 +1,11 @@
 use std::borrow::Cow;
-use std::fmt::Display;
+use std::cmp::Ordering;
+use std::fmt::{Display, Write};
+use std::hash::{Hash, Hasher};
+use std::path::Path;
 
+use sha1::{Digest, Sha1};
+use sugar_path::{AsPath, SugarPath};
 use swc_core::ecma::atoms as swc_atoms;
 use swc_core::ecma::atoms::JsWord;
 mod union_find;
@@ -9,6 +14,17 @@ mod symbol;
 pub use symbol::*;
 mod loader;
 pub use loader::*;
+mod chunk_path;
+pub use chunk_path::*;
+mod module_format;
+pub use module_format::*;
+mod source_map_type;
+pub use source_map_type::*;
+mod external_kind;
+pub use external_kind::*;
+
+/// Length of hashes suffixing names sanitized by `sanitize_file_name`.
+const SANITIZED_HASH_LEN: usize = 8;
 
 #[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
 pub struct ChunkId(JsWord);
@@ -39,10 +55,39 @@ impl AsRef<str> for ChunkId {
   }
 }
 
-#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+/// How an external module is referred to isn't part of its identity, so importers referring to it
+/// differently still share the same module.
+#[derive(Debug, Clone)]
 pub struct ModuleId {
   value: JsWord,
-  is_external: bool,
+  external: Option<ExternalKind>,
+}
+
+impl PartialEq for ModuleId {
+  fn eq(&self, other: &Self) -> bool {
+    self.value == other.value && self.is_external() == other.is_external()
+  }
+}
+
+impl Eq for ModuleId {}
+
+impl Hash for ModuleId {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.value.hash(state);
+    self.is_external().hash(state);
+  }
+}
+
+impl PartialOrd for ModuleId {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl Ord for ModuleId {
+  fn cmp(&self, other: &Self) -> Ordering {
+    (&self.value, self.is_external()).cmp(&(&other.value, other.is_external()))
+  }
 }
 
 impl Display for ModuleId {
@@ -52,20 +97,75 @@ impl Display for ModuleId {
 }
 
 impl ModuleId {
+  /// External modules created here are referred to by their ids as is.
   pub fn new(value: impl Into<JsWord>, is_external: bool) -> Self {
+    Self::with_external(value, is_external.then_some(ExternalKind::Absolute))
+  }
+
+  pub fn with_external(value: impl Into<JsWord>, external: Option<ExternalKind>) -> Self {
     Self {
       value: value.into(),
-      is_external,
+      external,
     }
   }
 
   pub fn is_external(&self) -> bool {
-    self.is_external
+    self.external.is_some()
+  }
+
+  pub fn external_kind(&self) -> Option<ExternalKind> {
+    self.external
   }
 
   pub fn id(&self) -> &JsWord {
     &self.value
   }
+
+  /// Virtual modules created by plugins are prefixed with `\0`, which is a convention of rollup.
+  pub fn is_virtual(&self) -> bool {
+    self.value.starts_with('\0')
+  }
+
+  /// An id that stays the same across machines and builds, used to display the module in the output.
+  /// - Virtual modules use their ids as-is, but sanitized.
+  /// - Other modules use paths relative to `cwd`.
+  pub fn stable_id(&self, cwd: &Path) -> String {
+    if self.is_virtual() {
+      sanitize_file_name(&self.value)
+    } else {
+      self
+        .value
+        .as_path()
+        .relative(cwd)
+        .to_string_lossy()
+        .to_string()
+    }
+  }
+}
+
+/// Replace characters that are not allowed or meaningful in file names, such as `\0` of virtual modules.
+///
+/// Replacing is lossy, so a name with replaced characters is suffixed with a short hash of it, like
+/// `_virtual_msg-<hash>` for `\0virtual:msg`. Otherwise, `\0virtual?msg` would get the same name.
+pub fn sanitize_file_name(name: &str) -> String {
+  let sanitized = name
+    .chars()
+    .map(|c| match c {
+      '\0' | '?' | '*' | ':' | '<' | '>' | '|' | '"' => '_',
+      c => c,
+    })
+    .collect::<String>();
+  if sanitized == name {
+    return sanitized;
+  }
+  let hash = Sha1::digest(name.as_bytes())
+    .iter()
+    .take(SANITIZED_HASH_LEN / 2)
+    .fold(String::new(), |mut hex, byte| {
+      write!(hex, "{byte:02x}").unwrap();
+      hex
+    });
+  format!("{sanitized}-{hash}")
 }
 
 impl AsRef<str> for ModuleId {
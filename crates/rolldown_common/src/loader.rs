@@ -1,12 +1,47 @@
 use std::str::FromStr;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Loader {
   Js,
   Jsx,
   Ts,
   Tsx,
   Json,
+  /// Turn a css file into a module whose default export is a constructed `CSSStyleSheet`.
+  /// Only used for `import sheet from './foo.css' assert { type: 'css' }`.
+  CssStyleSheet,
+  /// Turn a css file into an empty module. The css is collected and emitted as a `.css` asset
+  /// alongside the chunk importing it.
+  Css,
+  /// Turn a file into a module whose default export is its content as a string.
+  Text,
+  /// Turn a YAML file into a module whose default export is the parsed value. Top-level keys that are
+  /// legal identifiers are also exported by name.
+  Yaml,
+  /// Like `Yaml`, but for TOML.
+  Toml,
+  /// Turn a file into a module whose default export is a `data:` URL of its content, with the MIME
+  /// type guessed by the extension. Files read from the disk are base64 encoded, so the code seen
+  /// by plugins is base64 and `load` hooks should return base64 as well.
+  DataUrl,
+  /// Like `DataUrl`, but the default export is the base64 content alone.
+  Base64,
+  /// Turn a `.wasm` file into a module instantiating it, whose exports are exported by name. The
+  /// file is emitted as an asset. Only used if `wasm_esm_integration` is enabled.
+  Wasm,
+  /// Turn a `.node` native addon into a module whose default export is the addon, loaded by
+  /// `createRequire`. The file is emitted as an asset, since addons can't be bundled.
+  NativeAddon,
+}
+
+impl Loader {
+  /// Binary loaders read files as base64 instead of UTF-8.
+  pub fn is_binary(&self) -> bool {
+    matches!(
+      self,
+      Self::DataUrl | Self::Base64 | Self::Wasm | Self::NativeAddon
+    )
+  }
 }
 
 impl FromStr for Loader {
@@ -18,6 +53,12 @@ impl FromStr for Loader {
       "jsx" => Ok(Self::Jsx),
       "ts" => Ok(Self::Ts),
       "tsx" => Ok(Self::Tsx),
+      "css" => Ok(Self::Css),
+      "text" => Ok(Self::Text),
+      "yaml" | "yml" => Ok(Self::Yaml),
+      "toml" => Ok(Self::Toml),
+      "dataurl" => Ok(Self::DataUrl),
+      "base64" => Ok(Self::Base64),
       _ => Err(format!("Unknown loader value \"{}\"", s)),
     }
   }
@@ -0,0 +1,53 @@
+use std::str::FromStr;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ModuleFormat {
+  Esm,
+  Cjs,
+  Amd,
+  /// A function expression called immediately, which returns exports of the entry. It's assigned to
+  /// `output.name` for `<script>` tags.
+  Iife,
+  // UMD,
+}
+
+impl ModuleFormat {
+  pub fn is_es(self) -> bool {
+    self == ModuleFormat::Esm
+  }
+
+  pub fn is_cjs(self) -> bool {
+    self == ModuleFormat::Cjs
+  }
+
+  pub fn is_amd(self) -> bool {
+    self == ModuleFormat::Amd
+  }
+
+  pub fn is_iife(self) -> bool {
+    self == ModuleFormat::Iife
+  }
+
+  pub fn as_str(self) -> &'static str {
+    match self {
+      ModuleFormat::Esm => "esm",
+      ModuleFormat::Cjs => "cjs",
+      ModuleFormat::Amd => "amd",
+      ModuleFormat::Iife => "iife",
+    }
+  }
+}
+
+impl FromStr for ModuleFormat {
+  type Err = String;
+
+  fn from_str(value: &str) -> Result<Self, Self::Err> {
+    match value {
+      "esm" => Ok(ModuleFormat::Esm),
+      "cjs" => Ok(ModuleFormat::Cjs),
+      "amd" => Ok(ModuleFormat::Amd),
+      "iife" => Ok(ModuleFormat::Iife),
+      _ => Err(format!("Invalid module format: {value}")),
+    }
+  }
+}
@@ -0,0 +1,25 @@
+use std::str::FromStr;
+
+/// How source maps of generated chunks are emitted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceMapType {
+  /// Embedded in the chunk as a data URL of `//# sourceMappingURL=`.
+  Inline,
+  /// Emitted as `[chunk].map` next to the chunk, which refers to it by `//# sourceMappingURL=`.
+  External,
+  /// Emitted as `[chunk].map` like `External`, but the chunk doesn't refer to it.
+  Hidden,
+}
+
+impl FromStr for SourceMapType {
+  type Err = String;
+
+  fn from_str(value: &str) -> Result<Self, Self::Err> {
+    match value {
+      "inline" => Ok(SourceMapType::Inline),
+      "external" => Ok(SourceMapType::External),
+      "hidden" => Ok(SourceMapType::Hidden),
+      _ => Err(format!("Invalid source map type: {value}")),
+    }
+  }
+}
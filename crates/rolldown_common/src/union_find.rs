@@ -22,76 +22,84 @@ impl UnifyKey for EnaKey {
   }
 }
 
-#[derive(Debug, Default)]
-pub struct UnionFind<Key: Eq + Hash + Clone + Debug> {
-  store: Mutex<InPlaceUnificationTable<EnaKey>>,
-  store_key_to_key: Mutex<FxHashMap<EnaKey, Key>>,
-  key_to_store_key: Mutex<FxHashMap<Key, EnaKey>>,
-  // stored: Vec<Key>,
+#[derive(Debug)]
+struct UnionFindInner<Key: Eq + Hash + Clone + Debug> {
+  /// `find` of ena does path compression, so the amortized cost of each operation is nearly constant.
+  store: InPlaceUnificationTable<EnaKey>,
+  // `EnaKey`s are allocated sequentially, so the index of a key is its `EnaKey`.
+  keys: Vec<Key>,
+  key_to_store_key: FxHashMap<Key, EnaKey>,
 }
 
-impl<Key: Eq + Hash + Clone + Debug> UnionFind<Key> {
+impl<Key: Eq + Hash + Clone + Debug> Default for UnionFindInner<Key> {
+  fn default() -> Self {
+    Self {
+      store: Default::default(),
+      keys: Default::default(),
+      key_to_store_key: Default::default(),
+    }
+  }
+}
+
+impl<Key: Eq + Hash + Clone + Debug> UnionFindInner<Key> {
   fn intern_key(&mut self, key: &Key) -> EnaKey {
-    *self
-      .key_to_store_key
-      .get_mut()
-      .unwrap()
-      .entry(key.clone())
-      .or_insert_with_key(|key| {
-        let ena_key = self.store.get_mut().unwrap().new_key(());
-        self
-          .store_key_to_key
-          .get_mut()
-          .unwrap()
-          .insert(ena_key, key.clone());
-        ena_key
-      })
+    if let Some(ena_key) = self.key_to_store_key.get(key) {
+      return *ena_key;
+    }
+    let ena_key = self.store.new_key(());
+    debug_assert_eq!(ena_key.index() as usize, self.keys.len());
+    self.keys.push(key.clone());
+    self.key_to_store_key.insert(key.clone(), ena_key);
+    ena_key
   }
 
-  fn intern_key_par(&self, key: &Key) -> EnaKey {
-    *self
-      .key_to_store_key
-      .lock()
-      .unwrap()
-      .entry(key.clone())
-      .or_insert_with_key(|key| {
-        let ena_key = self.store.lock().unwrap().new_key(());
-        self
-          .store_key_to_key
-          .lock()
-          .unwrap()
-          .insert(ena_key, key.clone());
-        ena_key
-      })
+  fn find_root(&mut self, key: &Key) -> &Key {
+    let ena_key = self.intern_key(key);
+    let ena_root = self.store.find(ena_key);
+    &self.keys[ena_root.index() as usize]
+  }
+}
+
+/// All the state lives behind one lock, so `find_root_par` only needs to lock once.
+#[derive(Debug, Default)]
+pub struct UnionFind<Key: Eq + Hash + Clone + Debug> {
+  inner: Mutex<UnionFindInner<Key>>,
+}
+
+impl<Key: Eq + Hash + Clone + Debug> UnionFind<Key> {
+  fn inner_mut(&mut self) -> &mut UnionFindInner<Key> {
+    self.inner.get_mut().unwrap()
   }
 
   pub fn union(&mut self, key1: &Key, key2: &Key) -> &mut Self {
-    let k1 = self.intern_key(key1);
-    let k2 = self.intern_key(key2);
-    self.store.get_mut().unwrap().union(k1, k2);
+    let inner = self.inner_mut();
+    let k1 = inner.intern_key(key1);
+    let k2 = inner.intern_key(key2);
+    inner.store.union(k1, k2);
     self
   }
 
   pub fn unioned(&mut self, key1: &Key, key2: &Key) -> bool {
-    let k1 = self.intern_key(key1);
-    let k2 = self.intern_key(key2);
-    self.store.get_mut().unwrap().unioned(k1, k2)
+    let inner = self.inner_mut();
+    let k1 = inner.intern_key(key1);
+    let k2 = inner.intern_key(key2);
+    inner.store.unioned(k1, k2)
   }
 
   pub fn find_root(&mut self, key: &Key) -> Option<&Key> {
-    let ena_key = self.intern_key(key);
-    let ena_root = self.store.get_mut().unwrap().find(ena_key);
-    self.store_key_to_key.get_mut().unwrap().get(&ena_root)
+    Some(self.inner_mut().find_root(key))
   }
 
   pub fn find_root_par(&self, key: &Key) -> Option<Key> {
-    let ena_key = self.intern_key_par(key);
-    let ena_root = self.store.lock().unwrap().find(ena_key);
-    self
-      .store_key_to_key
-      .lock()
-      .unwrap()
-      .get(&ena_root)
-      .cloned()
+    Some(self.inner.lock().unwrap().find_root(key).clone())
+  }
+
+  /// The number of keys ever added to the union-find.
+  pub fn len(&self) -> usize {
+    self.inner.lock().unwrap().keys.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
   }
 }
@@ -0,0 +1,30 @@
+use rolldown_common::UnionFind;
+
+/// Unions `0..n` into a single chain, then finds the root of every key.
+fn union_chain_and_find_all(n: usize) {
+  let mut uf = UnionFind::<usize>::default();
+  for i in 1..n {
+    uf.union(&(i - 1), &i);
+  }
+  let root = *uf.find_root(&0).unwrap();
+  for i in 0..n {
+    assert_eq!(uf.find_root_par(&i), Some(root));
+  }
+  assert_eq!(uf.len(), n);
+}
+
+#[test]
+fn keys_are_counted_once() {
+  let mut uf = UnionFind::<&str>::default();
+  assert!(uf.is_empty());
+  uf.union(&"a", &"b").union(&"b", &"c");
+  assert!(uf.unioned(&"a", &"c"));
+  assert!(!uf.unioned(&"a", &"d"));
+  assert_eq!(uf.len(), 4);
+}
+
+#[test]
+fn long_chains_share_one_root() {
+  // As many keys as the symbols of a huge graph.
+  union_chain_and_find_all(400_000);
+}
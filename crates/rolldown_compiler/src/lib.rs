@@ -1,10 +1,15 @@
-use std::{path::PathBuf, sync::Arc};
+use std::{
+  path::{Path, PathBuf},
+  sync::Arc,
+};
 
 use ast::EsVersion;
+use rolldown_common::ModuleId;
 use swc_common::{
   comments::Comments,
   errors::{ColorConfig, Handler},
-  FileName, SourceMap,
+  source_map::SourceMapGenConfig,
+  BytePos, FileName, LineCol, SourceMap,
 };
 use swc_core::{
   common::{self as swc_common, SourceFile},
@@ -36,22 +41,56 @@ impl Compiler {
     &self,
     ast: &ast::Module,
     comments: Option<&dyn Comments>,
+  ) -> anyhow::Result<String> {
+    self.print_with_config(ast, comments, Default::default())
+  }
+
+  pub fn print_with_config(
+    &self,
+    ast: &ast::Module,
+    comments: Option<&dyn Comments>,
+    cfg: swc_ecma_codegen::Config,
+  ) -> anyhow::Result<String> {
+    self.print_with_mappings(ast, comments, cfg, None)
+  }
+
+  /// Positions of printed tokens are pushed to `mappings` if it's `Some`, which could be turned
+  /// into a source map by `build_source_map`.
+  pub fn print_with_mappings(
+    &self,
+    ast: &ast::Module,
+    comments: Option<&dyn Comments>,
+    cfg: swc_ecma_codegen::Config,
+    mappings: Option<&mut Vec<(BytePos, LineCol)>>,
   ) -> anyhow::Result<String> {
     let mut output = Vec::new();
 
     let mut emitter = swc_ecma_codegen::Emitter {
-      cfg: swc_ecma_codegen::Config {
-        ..Default::default()
-      },
+      cfg,
       cm: self.cm.clone(),
       comments: Some(&comments),
-      wr: Box::new(JsWriter::new(self.cm.clone(), "\n", &mut output, None)),
+      wr: Box::new(JsWriter::new(self.cm.clone(), "\n", &mut output, mappings)),
     };
 
     emitter.emit_module(ast)?;
     String::from_utf8(output).map_err(Into::into)
   }
 
+  /// Build a v3 source map in JSON. Its `sources` are relative to `root`, and their contents are
+  /// inlined.
+  pub fn build_source_map(
+    &self,
+    mappings: &[(BytePos, LineCol)],
+    root: &Path,
+  ) -> anyhow::Result<String> {
+    let source_map = self
+      .cm
+      .build_source_map_with_config(mappings, None, RelativeSources { root });
+    let mut output = Vec::new();
+    source_map.to_writer(&mut output)?;
+    String::from_utf8(output).map_err(Into::into)
+  }
+
   pub fn print_module_item(
     &self,
     ast: &ast::ModuleItem,
@@ -105,6 +144,21 @@ impl Compiler {
   ) -> PResult<ast::Module> {
     let handler = Handler::with_tty_emitter(ColorConfig::Auto, true, false, Some(self.cm.clone()));
 
+    let (module, recovered_errors) = self.parse_recoverable(source_file, syntax, comments)?;
+    recovered_errors.into_iter().for_each(|e| {
+      e.into_diagnostic(&handler).emit();
+    });
+    Ok(module)
+  }
+
+  /// Errors swc recovered from, like a legacy octal literal in strict mode, are returned along with
+  /// the recovered AST instead of being emitted.
+  pub fn parse_recoverable(
+    &self,
+    source_file: Arc<SourceFile>,
+    syntax: Syntax,
+    comments: Option<&dyn Comments>,
+  ) -> PResult<(ast::Module, Vec<swc_ecma_parser::error::Error>)> {
     let lexer = Lexer::new(
       syntax,
       EsVersion::latest(),
@@ -112,11 +166,26 @@ impl Compiler {
       comments,
     );
     let mut parser = Parser::new_from(lexer);
-    parser.take_errors().into_iter().for_each(|e| {
-      e.into_diagnostic(&handler).emit();
-    });
     // To be clear, rolldown will always assume the input is a module
-    parser.parse_module()
+    let module = parser.parse_module()?;
+    Ok((module, parser.take_errors()))
+  }
+}
+
+struct RelativeSources<'a> {
+  root: &'a Path,
+}
+
+impl SourceMapGenConfig for RelativeSources<'_> {
+  fn file_name_to_source(&self, f: &FileName) -> String {
+    match f {
+      FileName::Real(path) => ModuleId::new(path.to_string_lossy(), false).stable_id(self.root),
+      _ => f.to_string(),
+    }
+  }
+
+  fn inline_sources_content(&self, _f: &FileName) -> bool {
+    true
   }
 }
 
@@ -1,18 +1,36 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use itertools::Itertools;
 use rayon::prelude::*;
-use rustc_hash::FxHashMap as HashMap;
+use rolldown_common::{ChunkId, ModuleId};
+use rolldown_error::Errors;
+use rolldown_plugin::RenderOutputOptions;
+use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
+use sugar_path::AsPath;
+use swc_core::ecma::{
+  ast,
+  atoms::{js_word, JsWord},
+  utils::find_pat_ids,
+};
 use tracing::instrument;
 
 use crate::{
-  Asset, BuildInputOptions, BuildOutputOptions, Chunk, CodeSplitter, FinalizeBundleContext, Graph,
-  ModuleRefMutById, SplitPointIdToChunkId, UnaryBuildResult,
+  content_hashes, find_hash_placeholder, hash_placeholder, is_legal, rehash,
+  replace_hash_placeholders, Asset, BuildError, BuildInputOptions, BuildOutputOptions, BuildResult,
+  Chunk, ChunkMappings, CodeSplitter, FileNameConflict, FinalizeBundleContext, Graph, ManualChunks,
+  ModuleRefMutById, Platform, SourceMapType, SplitPointIdToChunkId, UnaryBuildResult, COMPILER,
 };
 
+const UTF8_BOM: char = '\u{FEFF}';
+
 #[derive(Debug)]
 pub struct Bundle<'a> {
   pub input_options: &'a BuildInputOptions,
   pub output_options: &'a BuildOutputOptions,
   pub graph: &'a mut Graph,
   split_point_id_to_chunk_id: SplitPointIdToChunkId,
+  manual_chunks: ManualChunks,
 }
 
 impl<'a> Bundle<'a> {
@@ -26,20 +44,43 @@ impl<'a> Bundle<'a> {
       output_options,
       graph,
       split_point_id_to_chunk_id: Default::default(),
+      manual_chunks: Default::default(),
     }
   }
 
   #[instrument(skip_all)]
-  pub fn generate(&mut self) -> UnaryBuildResult<Vec<Asset>> {
+  pub async fn generate(&mut self) -> BuildResult<Vec<Asset>> {
+    if !self.output_options.format.is_es() {
+      self.validate_top_level_await()?;
+    }
+    if self.output_options.format.is_iife() {
+      self.validate_iife_name()?;
+    }
+
     let chunks = self.generate_chunks()?;
+    // The iife wrapper has no way to load other chunks.
+    if self.output_options.format.is_iife() && chunks.len() > 1 {
+      return Err(Errors::from(BuildError::code_splitting_unsupported(
+        self.output_options.format.as_str(),
+      )));
+    }
     let mut chunk_by_id = chunks
       .into_iter()
       .map(|c| (c.id.clone(), c))
       .collect::<HashMap<_, _>>();
 
-    chunk_by_id.values_mut().par_bridge().for_each(|chunk| {
-      chunk.gen_file_name(self.output_options);
-    });
+    let hashes_file_names = self.output_options.entry_file_names.contains_hash()
+      || self.output_options.chunk_file_names.contains_hash();
+    let chunks_len = chunk_by_id.len();
+    for (index, chunk) in chunk_by_id.values_mut().enumerate() {
+      let placeholder = if hashes_file_names {
+        hash_placeholder(index, chunks_len)?
+      } else {
+        String::new()
+      };
+      chunk.gen_file_name(self.output_options, &placeholder);
+    }
+    self.resolve_file_name_conflicts(chunk_by_id.values_mut().collect())?;
 
     let mut module_mut_ref_by_id = self
       .graph
@@ -69,6 +110,10 @@ impl<'a> Bundle<'a> {
       })
       .collect::<Vec<_>>();
 
+    let output_dir = match &self.output_options.dir {
+      Some(dir) => self.input_options.cwd.join(dir),
+      None => self.input_options.cwd.clone(),
+    };
     chunk_and_modules.into_iter().par_bridge().try_for_each(
       |(chunk, module_mut_ref_by_id)| -> UnaryBuildResult<()> {
         chunk.finalize(FinalizeBundleContext {
@@ -76,45 +121,755 @@ impl<'a> Bundle<'a> {
           uf: &self.graph.uf,
           output_options: self.output_options,
           split_point_id_to_chunk_id: &self.split_point_id_to_chunk_id,
+          manual_chunks: &self.manual_chunks,
           chunk_filename_by_id: &chunk_filename_by_id,
           unresolved_ctxt: self.graph.unresolved_ctxt,
+          output_dir: &output_dir,
         })
       },
     )?;
 
-    let chunks = chunk_by_id
-      .values()
-      .map(|chunk| {
-        let code = chunk.render(
-          crate::RenderContext {},
-          self.graph,
-          self.input_options,
-          self.output_options,
+    let plugin_driver = self.graph.build_plugin_driver.clone();
+    let plugin_driver = plugin_driver.read().await;
+    let bundle_intros = plugin_driver.bundle_intros();
+    let bundle_intro_chunks = if bundle_intros.is_empty() {
+      Default::default()
+    } else {
+      self.bundle_intro_chunks(&chunk_by_id)
+    };
+    let render_output_options = RenderOutputOptions {
+      format: self.output_options.format,
+      dir: self.output_options.dir.clone(),
+      sourcemap: self.output_options.sourcemap,
+    };
+
+    // Hashes in file names are known once all chunks are rendered, so chunks are held until then.
+    let mut assets = vec![];
+    let mut rendered = vec![];
+    for chunk in chunk_by_id.values() {
+      self.warn_dropped_directives(chunk);
+      let (code, mappings) = chunk.render(
+        crate::RenderContext {
+          bundle_intros: if bundle_intro_chunks.contains(&chunk.id) {
+            &bundle_intros
+          } else {
+            &[]
+          },
+          comments: self.output_options.comments,
+        },
+        self.graph,
+        self.input_options,
+        self.output_options,
+      )?;
+      let rendered_chunk = chunk.to_rendered_chunk(&self.graph.module_by_id);
+      let code = plugin_driver
+        .render_chunk(&rendered_chunk, code, &render_output_options)
+        .await?;
+      if hashes_file_names {
+        rendered.push((chunk.id.clone(), code, mappings));
+      } else {
+        assets.extend(self.emit_chunk(chunk, code, mappings)?);
+      }
+    }
+    if hashes_file_names {
+      self.resolve_hashes(&mut chunk_by_id, &mut rendered)?;
+      for (id, code, mappings) in rendered {
+        assets.extend(self.emit_chunk(&chunk_by_id[&id], code, mappings)?);
+      }
+    }
+
+    assets.extend(
+      chunk_by_id
+        .values()
+        .filter_map(|chunk| self.render_css(chunk))
+        .map(|asset| self.emit(asset))
+        .try_collect::<Vec<_>>()?,
+    );
+
+    assets.extend(
+      chunk_by_id
+        .values()
+        .flat_map(|chunk| self.render_emitted_files(chunk))
+        .map(|asset| self.emit(asset))
+        .try_collect::<Vec<_>>()?,
+    );
+
+    if self.output_options.api_surface {
+      assets.extend(
+        chunk_by_id
+          .values()
+          .filter(|chunk| chunk.is_user_defined_entry)
+          .map(|chunk| self.emit(self.render_api_surface(chunk)))
+          .try_collect::<Vec<_>>()?,
+      );
+    }
+
+    if let Some(urls) = &self.output_options.import_map {
+      if self.output_options.format.is_es() {
+        assets.extend(
+          chunk_by_id
+            .values()
+            .map(|chunk| self.emit(self.render_import_map(chunk, urls)))
+            .try_collect::<Vec<_>>()?,
         );
+      }
+    }
+
+    if self.output_options.manifest {
+      assets.push(self.emit(self.render_manifest(&chunk_by_id))?);
+    }
+
+    plugin_driver
+      .generate_bundle(&mut assets, |bundle, asset, plugin| {
+        self.emit_plugin_file(bundle, asset, plugin)
+      })
+      .await?;
+
+    Ok(assets)
+  }
+
+  /// The source map and the BOM don't affect the hash in the file name, so they're added after it's
+  /// resolved.
+  fn emit_chunk(
+    &self,
+    chunk: &Chunk,
+    mut code: String,
+    mut mappings: Option<ChunkMappings>,
+  ) -> UnaryBuildResult<Vec<Asset>> {
+    let filename = chunk.filename.clone().unwrap();
+    if chunk.is_user_defined_entry {
+      self.validate_entry_exports(chunk, &filename);
+    }
+    if self.output_options.bom {
+      // BOM before shebang makes the file not executable
+      if code.starts_with("#!") {
+        (self.input_options.on_warn)(BuildError::bom_with_shebang(filename.clone()));
+      } else {
+        code.insert(0, UTF8_BOM);
+        // It's a column of the first line, which mappings of the line are moved by.
+        if let Some(mappings) = &mut mappings {
+          mappings.prepend(&UTF8_BOM.to_string());
+        }
+      }
+    }
+    let source_map = self
+      .output_options
+      .sourcemap
+      .zip(mappings)
+      .map(|(source_map_type, mappings)| {
+        self.render_source_map(source_map_type, &mut code, &filename, &mappings)
+      })
+      .transpose()?
+      .flatten();
+    let mut assets = vec![self.emit(Asset {
+      content: code,
+      filename,
+      binary: None,
+    })?];
+    if let Some(source_map) = source_map {
+      assets.push(self.emit(source_map)?);
+    }
+    Ok(assets)
+  }
 
-        code.map(|code| Asset {
-          content: code,
-          filename: chunk.filename.clone().unwrap(),
+  /// `//# sourceMappingURL=` is appended to `code` unless the source map is hidden. The source map
+  /// is returned as an asset named `[chunk].map` unless it's inlined.
+  fn render_source_map(
+    &self,
+    source_map_type: SourceMapType,
+    code: &mut String,
+    filename: &str,
+    mappings: &ChunkMappings,
+  ) -> UnaryBuildResult<Option<Asset>> {
+    let content =
+      COMPILER.build_source_map(&mappings.mappings, self.input_options.display_root())?;
+    let map_filename = format!("{filename}.map");
+    let url = match source_map_type {
+      SourceMapType::Inline => Some(format!(
+        "data:application/json;charset=utf-8;base64,{}",
+        STANDARD.encode(&content)
+      )),
+      // The source map is next to the chunk.
+      SourceMapType::External => map_filename
+        .as_path()
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string()),
+      SourceMapType::Hidden => None,
+    };
+    if let Some(url) = url {
+      if !code.ends_with('\n') {
+        code.push('\n');
+      }
+      code.push_str(&format!("//# sourceMappingURL={url}\n"));
+    }
+
+    Ok((source_map_type != SourceMapType::Inline).then(|| Asset {
+      filename: map_filename,
+      content,
+      binary: None,
+    }))
+  }
+
+  /// Hands the asset over to `on_asset` right after it's rendered, and drops its content, so only
+  /// one asset is held in memory at a time.
+  fn emit(&self, mut asset: Asset) -> UnaryBuildResult<Asset> {
+    if let Some(on_asset) = &self.output_options.on_asset {
+      on_asset(&asset)?;
+      asset.content = String::new();
+      asset.binary = None;
+    }
+    Ok(asset)
+  }
+
+  /// Files emitted by plugins in `generate_bundle` mustn't overwrite assets in `bundle` either.
+  fn emit_plugin_file(
+    &self,
+    bundle: &[Asset],
+    mut asset: Asset,
+    plugin: &str,
+  ) -> UnaryBuildResult<Asset> {
+    let is_taken = |file_name: &str| bundle.iter().any(|taken| taken.filename == file_name);
+    if is_taken(&asset.filename) {
+      match self.output_options.file_name_conflict {
+        FileNameConflict::Error => {
+          return Err(BuildError::emitted_file_name_conflict(
+            asset.filename,
+            plugin.to_string(),
+          ));
+        }
+        FileNameConflict::Suffix => {
+          asset.filename = (2..)
+            .map(|index| suffixed_file_name(&asset.filename, index))
+            .find(|name| !is_taken(name))
+            .unwrap();
+        }
+      }
+    }
+    self.emit(asset)
+  }
+
+  /// Chunks are visited in the execution order of their entries, so the result is deterministic.
+  /// The first chunk keeps its file name, and later ones are either suffixed or reported.
+  fn resolve_file_name_conflicts(&self, mut chunks: Vec<&mut Chunk>) -> UnaryBuildResult<()> {
+    chunks.sort_by_key(|chunk| self.graph.module_by_id[&chunk.entry].exec_order());
+
+    let mut first_entry_by_file_name = HashMap::default();
+    let mut used_file_names = chunks
+      .iter()
+      .map(|chunk| chunk.filename.clone().unwrap())
+      .collect::<HashSet<_>>();
+    for chunk in chunks {
+      let file_name = chunk.filename.clone().unwrap();
+      let Some(first_entry) = first_entry_by_file_name.get(&file_name) else {
+        first_entry_by_file_name.insert(file_name, chunk.entry.clone());
+        continue;
+      };
+      match self.output_options.file_name_conflict {
+        FileNameConflict::Error => {
+          return Err(BuildError::file_name_conflict(
+            file_name,
+            vec![
+              first_entry.as_path().to_path_buf(),
+              chunk.entry.as_path().to_path_buf(),
+            ],
+          ));
+        }
+        FileNameConflict::Suffix => {
+          let unique_file_name = (2..)
+            .map(|index| suffixed_file_name(&file_name, index))
+            .find(|name| !used_file_names.contains(name))
+            .unwrap();
+          used_file_names.insert(unique_file_name.clone());
+          chunk.filename = Some(unique_file_name);
+        }
+      }
+    }
+    Ok(())
+  }
+
+  /// Replace placeholders of hashes in file names of chunks, and in the code referring to them.
+  /// Chunks of the same content get the same hash, so conflicts are resolved again after hashing,
+  /// where names without hashes are taken first. A suffixed name would change the code referring to
+  /// it, so the hash is derived again instead.
+  fn resolve_hashes(
+    &self,
+    chunk_by_id: &mut HashMap<ChunkId, Chunk>,
+    rendered: &mut [(ChunkId, String, Option<ChunkMappings>)],
+  ) -> UnaryBuildResult<()> {
+    let mut hashes = {
+      let code_by_placeholder = rendered
+        .iter()
+        .filter_map(|(id, code, _)| {
+          let placeholder = find_hash_placeholder(chunk_by_id[id].filename.as_deref().unwrap())?;
+          Some((placeholder.to_string(), code.as_str()))
         })
+        .collect();
+      content_hashes(&code_by_placeholder)
+    };
+
+    let mut chunks = chunk_by_id.values().collect::<Vec<_>>();
+    chunks.sort_by_key(|chunk| self.graph.module_by_id[&chunk.entry].exec_order());
+    let mut first_entry_by_file_name = chunks
+      .iter()
+      .filter(|chunk| find_hash_placeholder(chunk.filename.as_deref().unwrap()).is_none())
+      .map(|chunk| (chunk.filename.clone().unwrap(), &chunk.entry))
+      .collect::<HashMap<_, _>>();
+    for chunk in chunks {
+      let file_name = chunk.filename.as_deref().unwrap();
+      let Some(placeholder) = find_hash_placeholder(file_name) else {
+        continue;
+      };
+      loop {
+        let hashed_file_name = replace_hash_placeholders(file_name, &hashes);
+        let Some(first_entry) = first_entry_by_file_name.get(&hashed_file_name) else {
+          first_entry_by_file_name.insert(hashed_file_name, &chunk.entry);
+          break;
+        };
+        match self.output_options.file_name_conflict {
+          FileNameConflict::Error => {
+            return Err(BuildError::file_name_conflict(
+              hashed_file_name,
+              vec![
+                first_entry.as_path().to_path_buf(),
+                chunk.entry.as_path().to_path_buf(),
+              ],
+            ));
+          }
+          FileNameConflict::Suffix => {
+            let hash = hashes.get_mut(placeholder).unwrap();
+            *hash = rehash(hash);
+          }
+        }
+      }
+    }
+
+    for (_, code, _) in rendered.iter_mut() {
+      *code = replace_hash_placeholders(code, &hashes);
+    }
+    for chunk in chunk_by_id.values_mut() {
+      chunk.filename = chunk
+        .filename
+        .as_deref()
+        .map(|filename| replace_hash_placeholders(filename, &hashes));
+    }
+    Ok(())
+  }
+
+  /// Css of modules loaded by `Loader::Css` is concatenated in execution order. Their modules are
+  /// empty, so imports of them are dropped from the JavaScript output.
+  fn render_css(&self, chunk: &Chunk) -> Option<Asset> {
+    let mut modules = chunk
+      .modules
+      .iter()
+      .filter_map(|id| self.graph.module_by_id.get(id))
+      .filter_map(|module| module.as_norm())
+      .filter(|module| module.css.is_some())
+      .collect::<Vec<_>>();
+    if modules.is_empty() {
+      return None;
+    }
+    modules.sort_by_key(|module| module.exec_order);
+
+    let content = modules
+      .into_iter()
+      .filter_map(|module| module.css.as_deref())
+      .collect::<Vec<_>>()
+      .join("\n");
+
+    Some(Asset {
+      filename: css_file_name(chunk),
+      content,
+      binary: None,
+    })
+  }
+
+  /// Files of modules loaded by `Loader::Wasm` and `Loader::NativeAddon`, which are loaded by their
+  /// modules relative to the chunk.
+  fn render_emitted_files(&self, chunk: &Chunk) -> Vec<Asset> {
+    chunk
+      .modules
+      .iter()
+      .filter_map(|id| self.graph.module_by_id.get(id))
+      .filter_map(|module| module.as_norm())
+      .flat_map(|module| {
+        let wasm = module.wasm.iter().map(|wasm| (&wasm.filename, &wasm.bytes));
+        let addon = module
+          .addon
+          .iter()
+          .map(|addon| (&addon.filename, &addon.bytes));
+        wasm.chain(addon)
+      })
+      .map(|(filename, bytes)| Asset {
+        filename: filename.clone(),
+        content: String::new(),
+        binary: Some(bytes.clone()),
       })
-      .try_collect::<Vec<_>>()?;
+      .collect()
+  }
 
-    Ok(chunks)
+  /// Only externals with URLs are listed, including the ones imported dynamically. Imports of
+  /// external modules are kept as they are, so browsers resolve them via the import map.
+  fn render_import_map(&self, chunk: &Chunk, urls: &BTreeMap<String, String>) -> Asset {
+    let imports = chunk
+      .modules
+      .iter()
+      .filter_map(|id| self.graph.module_by_id.get(id))
+      .filter_map(|module| module.as_norm())
+      .filter(|module| module.is_included())
+      .flat_map(|module| module.dependencies.iter().chain(&module.dyn_dependencies))
+      .filter(|id| id.is_external())
+      .filter_map(|id| {
+        urls
+          .get_key_value(&**id.id())
+          .map(|(specifier, url)| (specifier.as_str(), url.as_str()))
+      })
+      .collect::<BTreeMap<_, _>>();
+
+    let content = serde_json::to_string_pretty(&serde_json::json!({ "imports": imports }))
+      .expect("Import map should be serializable");
+
+    Asset {
+      filename: sibling_file_name(chunk, "importmap.json"),
+      content,
+      binary: None,
+    }
+  }
+
+  /// Entries are keyed by their names in `input`. Imports of an entry chunk are all chunks loaded
+  /// along with it, including the ones imported by its imported chunks.
+  fn render_manifest(&self, chunk_by_id: &HashMap<ChunkId, Chunk>) -> Asset {
+    let chunk_by_entry = chunk_by_id
+      .values()
+      .filter(|chunk| chunk.is_user_defined_entry)
+      .map(|chunk| (&chunk.entry, chunk))
+      .collect::<HashMap<_, _>>();
+    let chunk_by_module = chunk_by_id
+      .values()
+      .flat_map(|chunk| chunk.modules.iter().map(move |id| (id, chunk)))
+      .collect::<HashMap<_, _>>();
+
+    let manifest = self
+      .input_options
+      .input
+      .iter()
+      .zip(&self.graph.entries)
+      .filter_map(|(item, entry)| {
+        let chunk = chunk_by_entry.get(entry)?;
+        let modules = chunk
+          .modules
+          .iter()
+          .filter_map(|id| self.graph.module_by_id.get(id))
+          .filter_map(|module| module.as_norm())
+          .collect::<Vec<_>>();
+        let mut imports = BTreeSet::new();
+        let mut visited = HashSet::from_iter([&chunk.id]);
+        let mut importers = vec![*chunk];
+        while let Some(importer) = importers.pop() {
+          for imported in self.imported_chunks(importer, &chunk_by_module) {
+            if visited.insert(&imported.id) {
+              imports.extend(imported.filename.as_deref());
+              importers.push(imported);
+            }
+          }
+        }
+        let css = modules
+          .iter()
+          .any(|module| module.css.is_some())
+          .then(|| css_file_name(chunk));
+        Some((
+          item.name.as_str(),
+          serde_json::json!({
+            "file": chunk.filename.as_deref().unwrap(),
+            "imports": imports,
+            "css": css.into_iter().collect::<Vec<_>>(),
+          }),
+        ))
+      })
+      .collect::<BTreeMap<_, _>>();
+
+    Asset {
+      filename: "manifest.json".to_string(),
+      content: serde_json::to_string_pretty(&manifest).expect("Manifest should be serializable"),
+      binary: None,
+    }
+  }
+
+  /// Chunks that snippets emitted via `ctx.emit_bundle_intro` are prepended to, which are the
+  /// chunks evaluated first by exec order among the chunks loaded along with each entry. Entries
+  /// sharing the chunk evaluated first run the snippets once.
+  fn bundle_intro_chunks(&self, chunk_by_id: &HashMap<ChunkId, Chunk>) -> HashSet<ChunkId> {
+    let chunk_by_module = chunk_by_id
+      .values()
+      .flat_map(|chunk| chunk.modules.iter().map(move |id| (id, chunk)))
+      .collect::<HashMap<_, _>>();
+    let first_exec_order = |chunk: &Chunk| {
+      chunk
+        .modules
+        .iter()
+        .filter_map(|id| self.graph.module_by_id.get(id))
+        .map(|module| module.exec_order())
+        .min()
+        .unwrap_or(usize::MAX)
+    };
+
+    chunk_by_id
+      .values()
+      .filter(|chunk| chunk.is_user_defined_entry)
+      .map(|chunk| {
+        let mut first = chunk;
+        let mut visited = HashSet::from_iter([&chunk.id]);
+        let mut importers = vec![chunk];
+        while let Some(importer) = importers.pop() {
+          if first_exec_order(importer) < first_exec_order(first) {
+            first = importer;
+          }
+          for imported in self.imported_chunks(importer, &chunk_by_module) {
+            if visited.insert(&imported.id) {
+              importers.push(imported);
+            }
+          }
+        }
+        first.id.clone()
+      })
+      .collect()
+  }
+
+  /// Chunks containing static imports of the included modules of `chunk`.
+  fn imported_chunks<'c>(
+    &'c self,
+    chunk: &'c Chunk,
+    chunk_by_module: &'c HashMap<&'c ModuleId, &'c Chunk>,
+  ) -> impl Iterator<Item = &'c Chunk> {
+    chunk
+      .modules
+      .iter()
+      .filter_map(|id| self.graph.module_by_id.get(id))
+      .filter_map(|module| module.as_norm())
+      .filter(|module| module.is_included())
+      .flat_map(|module| module.dependencies.iter())
+      .filter(|id| !id.is_external() && !chunk.modules.contains(id))
+      .filter_map(|id| chunk_by_module.get(id).copied())
+  }
+
+  /// Exports of an entry chunk are the `linked_exports` of its entry module.
+  fn render_api_surface(&self, chunk: &Chunk) -> Asset {
+    let entry = self.graph.module_by_id[&chunk.entry].expect_norm();
+    let mut names = entry.linked_exports.keys().collect::<Vec<_>>();
+    names.sort();
+    let exports = names
+      .into_iter()
+      .map(|name| {
+        let kind = if name == &js_word!("default") {
+          "default"
+        } else {
+          "named"
+        };
+        serde_json::json!({ "name": &**name, "kind": kind })
+      })
+      .collect::<Vec<_>>();
+
+    let chunk_filename = chunk.filename.as_deref().unwrap();
+    let content = serde_json::to_string_pretty(&serde_json::json!({
+      "chunk": chunk_filename,
+      "exports": exports,
+    }))
+    .expect("Api surface should be serializable");
+
+    Asset {
+      filename: sibling_file_name(chunk, "api.json"),
+      content,
+      binary: None,
+    }
+  }
+
+  /// Warn about `linked_exports` of the entry module missing from the export statements generated
+  /// for its chunk, which breaks importers of the chunk. Names of bindings owned by external modules
+  /// can't be resolved to statements of the chunk, like ones of `export * from "external"`, so
+  /// they're skipped.
+  fn validate_entry_exports(&self, chunk: &Chunk, filename: &str) {
+    let emitted = exported_names(
+      chunk
+        .before_module_items
+        .iter()
+        .chain(&chunk.after_module_items),
+    );
+    let entry = self.graph.module_by_id[&chunk.entry].expect_norm();
+    let missing = entry
+      .linked_exports
+      .iter()
+      .filter(|(name, spec)| !spec.owner.is_external() && !emitted.contains(*name))
+      .map(|(name, _)| name)
+      .sorted()
+      .map(|name| name.to_string())
+      .collect::<Vec<_>>();
+    if !missing.is_empty() {
+      (self.input_options.on_warn)(BuildError::missing_entry_exports(
+        entry.id.as_ref(),
+        filename.to_string(),
+        missing,
+      ));
+    }
+  }
+
+  /// Only the entry of a chunk emits its directives, so ones of other modules in the chunk are
+  /// dropped, like `"use client"` of a component imported by the entry.
+  fn warn_dropped_directives(&self, chunk: &Chunk) {
+    chunk
+      .modules
+      .iter()
+      .filter(|id| chunk.is_manual || **id != chunk.entry)
+      .filter_map(|id| self.graph.module_by_id[id].as_norm())
+      .sorted_by_key(|module| module.exec_order)
+      .for_each(|module| {
+        for directive in &module.directives {
+          (self.input_options.on_warn)(BuildError::module_level_directive(
+            module.id.as_ref(),
+            directive.to_string(),
+          ));
+        }
+      });
+  }
+
+  /// The name is declared by `var <name> = ...` of the iife wrapper.
+  fn validate_iife_name(&self) -> UnaryBuildResult<()> {
+    match self.output_options.name.as_deref() {
+      Some(name) if !is_legal(name) => Err(BuildError::illegal_identifier_as_name(name)),
+      _ => Ok(()),
+    }
+  }
+
+  /// Modules are concatenated with scope hoisting, so a top-level `await` runs in the top level
+  /// of the chunk, which keeps the execution order. This only works for formats supporting it.
+  fn validate_top_level_await(&self) -> UnaryBuildResult<()> {
+    let module_using_tla = self
+      .graph
+      .module_by_id
+      .values()
+      .filter_map(|m| m.as_norm())
+      .filter(|m| m.has_top_level_await && m.is_included())
+      .min_by_key(|m| m.exec_order);
+
+    if let Some(module) = module_using_tla {
+      return Err(BuildError::invalid_tla_format(
+        self.output_options.format.as_str(),
+        module.id.as_ref(),
+      ));
+    }
+    Ok(())
   }
 
   #[instrument(skip_all)]
   fn generate_chunks(&mut self) -> UnaryBuildResult<Vec<Chunk>> {
-    let code_splitter =
-      CodeSplitter::new(self.graph.entries.clone(), self.graph, self.input_options);
+    let code_splitter = CodeSplitter::new(
+      self.graph.entries.clone(),
+      self.graph,
+      self.input_options,
+      self.output_options,
+    );
     let chunk_graph = code_splitter.split()?;
     chunk_graph.chunk_by_id.values().for_each(|chunk| {
       chunk.modules.iter().for_each(|module_id| {
         let module = self.graph.module_by_id.get(module_id).unwrap();
         chunk.runtime_helpers.extend_from(module.runtime_helpers());
+        let uses_any_global = |names: &[&str]| {
+          module.as_norm().map_or(false, |module| {
+            module.is_included()
+              && names
+                .iter()
+                .any(|name| module.visited_global_names.contains(&JsWord::from(*name)))
+          })
+        };
+        // ESM has no `__dirname` and `__filename`, so they are derived from `import.meta.url` by
+        // `node:` modules, which only exist in Node.js.
+        if self.output_options.format.is_es()
+          && self.input_options.platform != Some(Platform::Browser)
+          && uses_any_global(&["__dirname", "__filename"])
+        {
+          chunk.runtime_helpers.dirname_shim();
+        }
+        if self.output_options.global_this_polyfill
+          && !self.output_options.codegen.target.has_global_this()
+          && uses_any_global(&["globalThis"])
+        {
+          chunk.runtime_helpers.global_this_polyfill();
+        }
       });
     });
     self.split_point_id_to_chunk_id = chunk_graph.split_point_to_chunk;
 
-    Ok(chunk_graph.chunk_by_id.into_values().collect())
+    let chunks = chunk_graph.chunk_by_id.into_values().collect::<Vec<_>>();
+    self.manual_chunks = ManualChunks::new(
+      chunk_graph.manual_chunk_by_module,
+      &chunks,
+      self.graph,
+      self.output_options.preserve_modules,
+    );
+    Ok(chunks)
   }
 }
+
+fn css_file_name(chunk: &Chunk) -> String {
+  sibling_file_name(chunk, "css")
+}
+
+/// The suffix goes before the extension, like `main2.js`.
+fn suffixed_file_name(file_name: &str, index: usize) -> String {
+  let (stem, ext) = file_name.split_at(
+    file_name
+      .rfind('.')
+      .filter(|dot| !file_name[*dot..].contains('/'))
+      .unwrap_or(file_name.len()),
+  );
+  format!("{stem}{index}{ext}")
+}
+
+/// File name of an asset emitted next to the chunk, with `extension` instead of `.js`.
+fn sibling_file_name(chunk: &Chunk, extension: &str) -> String {
+  let chunk_filename = chunk.filename.as_deref().unwrap();
+  format!(
+    "{}.{extension}",
+    chunk_filename.strip_suffix(".js").unwrap_or(chunk_filename)
+  )
+}
+
+/// Names exported by export declarations of the items. Names of `export * from` are unknown.
+fn exported_names<'i>(items: impl Iterator<Item = &'i ast::ModuleItem>) -> HashSet<JsWord> {
+  let name_of = |name: &ast::ModuleExportName| match name {
+    ast::ModuleExportName::Ident(ident) => ident.sym.clone(),
+    ast::ModuleExportName::Str(string) => string.value.clone(),
+  };
+  let mut names = HashSet::default();
+  items
+    .filter_map(|item| item.as_module_decl())
+    .for_each(|decl| match decl {
+      ast::ModuleDecl::ExportNamed(export) => {
+        names.extend(export.specifiers.iter().map(|spec| match spec {
+          ast::ExportSpecifier::Named(spec) => {
+            name_of(spec.exported.as_ref().unwrap_or(&spec.orig))
+          }
+          ast::ExportSpecifier::Namespace(spec) => name_of(&spec.name),
+          ast::ExportSpecifier::Default(spec) => spec.exported.sym.clone(),
+        }));
+      }
+      ast::ModuleDecl::ExportDecl(export) => match &export.decl {
+        ast::Decl::Class(class) => {
+          names.insert(class.ident.sym.clone());
+        }
+        ast::Decl::Fn(func) => {
+          names.insert(func.ident.sym.clone());
+        }
+        decl => {
+          names.extend(
+            find_pat_ids::<_, ast::Id>(decl)
+              .into_iter()
+              .map(|(name, _)| name),
+          );
+        }
+      },
+      ast::ModuleDecl::ExportDefaultDecl(_) | ast::ModuleDecl::ExportDefaultExpr(_) => {
+        names.insert(js_word!("default"));
+      }
+      _ => {}
+    });
+  names
+}
@@ -1,22 +1,30 @@
 use std::sync::Arc;
 
+use rolldown_error::CWD;
+pub use rolldown_plugin::Asset;
 use rolldown_plugin::BuildPlugin;
-use tracing::instrument;
+use tracing::{instrument, instrument::WithSubscriber, Dispatch};
 
 use crate::{
-  BuildInputOptions, BuildOutputOptions, BuildPluginDriver, BuildResult, Bundle, Graph,
-  SharedBuildInputOptions, SharedBuildPluginDriver,
+  BuildInputOptions, BuildMetrics, BuildOutputOptions, BuildPluginDriver, BuildResult, Bundle,
+  Graph, ModuleGraphSnapshot, ModuleInfo, ResolutionRecord, SharedBuildInputOptions,
+  SharedBuildPluginDriver, SharedParseCache, SharedTransformCache, UsedSymbols,
 };
 
 pub struct BundlerCore {
   input_options: SharedBuildInputOptions,
+  /// Entered around builds if `log_level` is set. Otherwise, events go to the global subscriber.
+  dispatch: Option<Dispatch>,
   plugin_driver: SharedBuildPluginDriver,
-}
-
-#[derive(Debug)]
-pub struct Asset {
-  pub filename: String,
-  pub content: String,
+  metrics: Option<BuildMetrics>,
+  graph_snapshot: Option<ModuleGraphSnapshot>,
+  resolution_report: Option<Vec<ResolutionRecord>>,
+  used_symbols: Option<UsedSymbols>,
+  module_infos: Vec<ModuleInfo>,
+  /// `Some` if `cache` is enabled. It outlives builds, unlike the graph.
+  transform_cache: Option<SharedTransformCache>,
+  /// `Some` if `cache` is enabled. It outlives builds, unlike the graph.
+  parse_cache: Option<SharedParseCache>,
 }
 
 impl BundlerCore {
@@ -25,21 +33,133 @@ impl BundlerCore {
   }
 
   pub fn with_plugins(input_opts: BuildInputOptions, plugins: Vec<Box<dyn BuildPlugin>>) -> Self {
-    rolldown_tracing::enable_tracing_on_demand();
+    let dispatch = input_opts.log_level.map(rolldown_tracing::scoped_dispatch);
+    if dispatch.is_none() {
+      rolldown_tracing::enable_tracing_on_demand();
+    }
+    let transform_cache = input_opts.cache.then(Default::default);
+    let parse_cache = input_opts.cache.then(Default::default);
+    let input_opts = with_relative_warnings(input_opts);
+    let plugin_driver = BuildPluginDriver::new(plugins, input_opts.on_warn.clone()).into_shared();
     Self {
       input_options: Arc::new(input_opts),
-      plugin_driver: BuildPluginDriver::new(plugins).into_shared(),
+      dispatch,
+      plugin_driver,
+      metrics: None,
+      graph_snapshot: None,
+      resolution_report: None,
+      used_symbols: None,
+      module_infos: vec![],
+      transform_cache,
+      parse_cache,
     }
   }
 
-  #[instrument(skip_all)]
+  /// Drop modules cached by previous builds, so the next build reads and transforms all of them
+  /// again. No-op if `cache` isn't enabled.
+  pub fn clear_cache(&mut self) {
+    if self.input_options.cache {
+      self.transform_cache = Some(Default::default());
+      self.parse_cache = Some(Default::default());
+    }
+  }
+
+  /// Metrics of the latest build, or `None` if the module graph has never been generated.
+  pub fn metrics(&self) -> Option<&BuildMetrics> {
+    self.metrics.as_ref()
+  }
+
+  /// Snapshot of the module graph of the latest build. Compare it with the one of a previous build
+  /// via `ModuleGraphSnapshot::diff` to know what has changed.
+  pub fn graph_snapshot(&self) -> Option<&ModuleGraphSnapshot> {
+    self.graph_snapshot.as_ref()
+  }
+
+  /// How each specifier of the latest build is resolved, or `None` if `resolution_diagnostics` isn't
+  /// enabled. It's kept even if the build fails, so unresolved entries could be diagnosed.
+  pub fn resolution_report(&self) -> Option<&[ResolutionRecord]> {
+    self.resolution_report.as_deref()
+  }
+
+  /// Top-level symbols kept by treeshaking in the latest build, or `None` if `treeshake` isn't
+  /// enabled or nothing has been built yet.
+  pub fn used_symbols(&self) -> Option<&UsedSymbols> {
+    self.used_symbols.as_ref()
+  }
+
+  /// Info of the normal module with the id in the latest build, or `None` if it isn't in the graph.
+  pub fn module_info(&self, id: &str) -> Option<&ModuleInfo> {
+    self
+      .module_infos
+      .binary_search_by(|info| info.id.as_ref().cmp(id))
+      .ok()
+      .map(|index| &self.module_infos[index])
+  }
+
+  /// `build_end` hooks are called no matter whether the build succeeds, with errors of any phase.
   pub async fn build(&mut self, output_opts: BuildOutputOptions) -> BuildResult<Vec<Asset>> {
+    match self.dispatch.clone() {
+      Some(dispatch) => {
+        self
+          .build_and_end(output_opts)
+          .with_subscriber(dispatch)
+          .await
+      }
+      None => self.build_and_end(output_opts).await,
+    }
+  }
+
+  #[instrument(skip_all)]
+  async fn build_and_end(&mut self, output_opts: BuildOutputOptions) -> BuildResult<Vec<Asset>> {
     tracing::debug!("{:#?}", self.input_options);
     tracing::debug!("{:#?}", output_opts);
-    let mut graph = Graph::new(self.plugin_driver.clone(), self.input_options.clone());
-    graph.generate_module_graph().await?;
-    let mut bundle = Bundle::new(&self.input_options, &output_opts, &mut graph);
-    let assets = bundle.generate()?;
+    let built = self.build_inner(&output_opts).await;
+    let ended = self
+      .plugin_driver
+      .read()
+      .await
+      .build_end(built.as_ref().err())
+      .await;
+    match (built, ended) {
+      (Err(mut errors), Err(errors_of_build_end)) => {
+        errors.extend(errors_of_build_end.into_vec());
+        Err(errors)
+      }
+      (built, ended) => ended.and(built),
+    }
+  }
+
+  async fn build_inner(&mut self, output_opts: &BuildOutputOptions) -> BuildResult<Vec<Asset>> {
+    let mut graph = Graph::new(
+      self.plugin_driver.clone(),
+      self.input_options.clone(),
+      self.transform_cache.clone(),
+      self.parse_cache.clone(),
+      output_opts.comments,
+    );
+    let generated = graph.generate_module_graph().await;
+    self.resolution_report = graph
+      .resolution_report
+      .as_ref()
+      .map(|report| report.records());
+    generated?;
+    self.metrics = Some(graph.metrics.clone());
+    self.graph_snapshot = Some(ModuleGraphSnapshot::new(&graph));
+    self.used_symbols = self
+      .input_options
+      .treeshake
+      .then(|| UsedSymbols::new(&graph));
+    self.module_infos = ModuleInfo::collect(&graph);
+    let mut bundle = Bundle::new(&self.input_options, output_opts, &mut graph);
+    let assets = bundle.generate().await?;
     Ok(assets)
   }
 }
+
+/// Call `on_warn` with paths in warnings displayed relative to `display_root`.
+fn with_relative_warnings(mut input_opts: BuildInputOptions) -> BuildInputOptions {
+  let on_warn = input_opts.on_warn.clone();
+  let root = input_opts.display_root().to_path_buf();
+  input_opts.on_warn = Arc::new(move |warning| CWD.set(&root, || on_warn(warning)));
+  input_opts
+}
@@ -1,14 +1,24 @@
-use std::{collections::HashSet, path::PathBuf};
+use std::{
+  collections::{BTreeMap, BTreeSet, HashSet},
+  path::{Path, PathBuf},
+};
 
 use hashlink::LinkedHashSet;
 use itertools::Itertools;
 use rayon::prelude::{IntoParallelIterator, IntoParallelRefIterator, ParallelIterator};
-use rolldown_common::{ChunkId, ExportedSpecifier, ImportedSpecifier, ModuleId, Symbol, UnionFind};
+use rolldown_common::{
+  chunk_import_specifier, ChunkId, ExportedSpecifier, ExternalKind, ImportedSpecifier, ModuleId,
+  Symbol, UnionFind,
+};
+use rolldown_plugin::RenderedChunk;
 use rolldown_runtime_helpers::RuntimeHelpers;
 use rolldown_swc_visitors::FinalizeContext;
 use rustc_hash::{FxHashMap, FxHashSet};
+use sugar_path::{AsPath, SugarPath};
 use swc_core::{
-  common::{comments::SingleThreadedComments, util::take::Take, Mark, SyntaxContext, GLOBALS},
+  common::{
+    comments::SingleThreadedComments, util::take::Take, Mark, SourceFile, SyntaxContext, GLOBALS,
+  },
   ecma::{
     ast::{self, Id, Ident},
     atoms::{js_word, JsWord},
@@ -19,9 +29,10 @@ use swc_core::{
 use tracing::instrument;
 
 use crate::{
-  file_name, norm_or_ext::NormOrExt, preset_of_used_names, BuildError, BuildInputOptions,
-  BuildOutputOptions, ExportMode, Graph, MergedExports, ModuleById, ModuleRefMutById,
-  SplitPointIdToChunkId, UnaryBuildResult, COMPILER,
+  file_name, norm_or_ext::NormOrExt, preset_of_used_names, rewrite_emitted_file_url, BuildError,
+  BuildInputOptions, BuildOutputOptions, ChunkMappings, ExportMode, Graph, ManualChunks,
+  MergedExports, ModuleById, ModuleRefMutById, OutputComments, SplitPointIdToChunkId,
+  UnaryBuildResult, COMPILER, SOURCE_MAP,
 };
 
 pub struct Chunk {
@@ -34,6 +45,14 @@ pub struct Chunk {
   pub(crate) after_module_items: Vec<ast::ModuleItem>,
   pub(crate) runtime_helpers: RuntimeHelpers,
   pub(crate) is_user_defined_entry: bool,
+  /// The chunk is created by `manual_chunks` or `preserve_modules`, whose `entry` is merely its
+  /// first module.
+  pub(crate) is_manual: bool,
+  /// Consumers of `cjs` chunks tell an exported `default` from the whole `exports` by the
+  /// `__esModule` marker, which is unnecessary if the chunk doesn't export `default`. It's only
+  /// omitted for user-defined entries, since other chunks are required by the interop helpers of
+  /// chunks importing them, which rely on the marker.
+  pub(crate) has_default_export: bool,
 }
 
 impl Chunk {
@@ -48,17 +67,28 @@ impl Chunk {
       filename: None,
       runtime_helpers: Default::default(),
       is_user_defined_entry,
+      is_manual: false,
+      has_default_export: false,
     }
   }
 
-  pub(crate) fn gen_file_name(&mut self, output_options: &BuildOutputOptions) {
-    self.filename = Some(
-      output_options
-        .entry_file_names
-        .render(file_name::RenderOptions {
-          name: Some(self.id.as_ref()),
-        }),
-    )
+  /// `[hash]` is rendered as `hash_placeholder`, which is replaced after the chunk is rendered.
+  pub(crate) fn gen_file_name(
+    &mut self,
+    output_options: &BuildOutputOptions,
+    hash_placeholder: &str,
+  ) {
+    // Like rollup, modules preserved as chunks are named as entries.
+    let template = if self.is_user_defined_entry || output_options.preserve_modules {
+      &output_options.entry_file_names
+    } else {
+      &output_options.chunk_file_names
+    };
+    self.filename = Some(template.render(file_name::RenderOptions {
+      name: Some(self.id.as_ref()),
+      hash: Some(hash_placeholder),
+      ext: Some("js"),
+    }))
   }
 
   fn ordered_modules<'m>(&self, module_by_id: &'m ModuleById) -> Vec<&'m NormOrExt> {
@@ -73,6 +103,20 @@ impl Chunk {
     modules
   }
 
+  pub(crate) fn to_rendered_chunk(&self, module_by_id: &ModuleById) -> RenderedChunk {
+    RenderedChunk {
+      filename: self.filename.clone().unwrap(),
+      is_entry: self.is_user_defined_entry,
+      module_ids: self
+        .ordered_modules(module_by_id)
+        .into_iter()
+        .filter_map(|m| m.as_norm())
+        .map(|m| m.id.clone())
+        .collect(),
+    }
+  }
+
+  /// Mappings of the generated code are returned if `sourcemap` is enabled.
   #[instrument(skip_all)]
   pub(crate) fn render(
     &self,
@@ -80,7 +124,7 @@ impl Chunk {
     graph: &Graph,
     input_options: &BuildInputOptions,
     output_options: &BuildOutputOptions,
-  ) -> UnaryBuildResult<String> {
+  ) -> UnaryBuildResult<(String, Option<ChunkMappings>)> {
     let mut runtime_code = self.runtime_helpers.generate_helpers().join("\n");
     runtime_code.push('\n');
 
@@ -96,41 +140,267 @@ impl Chunk {
       .map(|item| COMPILER.print_module_item(item, None).unwrap())
       .join("\n");
 
-    let code = self
+    let mut mappings = output_options
+      .sourcemap
+      .is_some()
+      .then(ChunkMappings::default);
+
+    let mut code = before_code + runtime_code.as_ref();
+    if let Some(mappings) = &mut mappings {
+      mappings.push(&code, vec![]);
+    }
+    self
       .ordered_modules(&graph.module_by_id)
       .iter()
       .filter_map(|m| m.as_norm())
       .filter(|m| m.is_included())
-      .map(|module| module.render(&ctx, input_options))
-      .collect::<Vec<_>>()
-      .join("\n");
+      .enumerate()
+      .for_each(|(index, module)| {
+        let mut module_mappings = mappings
+          .as_ref()
+          .filter(|_| module.maps_to_source)
+          .map(|_| vec![]);
+        let module_code = module.render(&ctx, input_options, module_mappings.as_mut());
+        let separator = if index == 0 { "" } else { "\n" };
+        let separator = if is_asi_hazard(&code, &module_code) {
+          format!(";{separator}")
+        } else {
+          separator.to_string()
+        };
+        if let Some(mappings) = &mut mappings {
+          mappings.push(&separator, vec![]);
+          mappings.push(&module_code, module_mappings.unwrap_or_default());
+        }
+        code.push_str(&separator);
+        code.push_str(&module_code);
+      });
+    code.push_str(&after_code);
 
-    let mut code = before_code + runtime_code.as_ref() + code.as_ref() + after_code.as_ref();
-
-    if output_options.format.is_cjs() {
-      // Workaround for cjs output
-      let comments = SingleThreadedComments::default();
-      let fm = COMPILER.create_source_file(PathBuf::from(self.id.value().to_string()), code);
-      let mut program = COMPILER
-        .parse_with_comments(
-          fm.clone(),
-          swc_core::ecma::parser::Syntax::Es(Default::default()),
-          Some(&comments),
-        )
-        .map_err(|e| BuildError::parse_js_failed(fm.clone(), e))?;
+    // The chunk is parsed only once for all the passes working on the AST, and printed again once.
+    let mut import_meta_url = None;
+    if !output_options.format.is_es()
+      || output_options.mangle
+      || output_options.minify
+      || output_options.codegen.is_customized()
+    {
+      (code, mappings, import_meta_url) = self.transform_program(code, mappings, output_options)?;
+    }
+
+    if let Some(intro) = &output_options.intro {
+      let intro = format!("{intro}\n");
+      // The directive has no mappings, so it doesn't matter that the intro follows it.
+      if let Some(mappings) = &mut mappings {
+        mappings.prepend(&intro);
+      }
+      code.insert_str(use_strict_len(&code), &intro);
+    }
+    if !ctx.bundle_intros.is_empty() {
+      let bundle_intro = ctx
+        .bundle_intros
+        .iter()
+        .map(|intro| format!("{intro}\n"))
+        .collect::<String>();
+      // Inserted after the intro to precede it. Like the intro, it has no mappings.
+      if let Some(mappings) = &mut mappings {
+        mappings.prepend(&bundle_intro);
+      }
+      code.insert_str(use_strict_len(&code), &bundle_intro);
+    }
+    if let Some(outro) = &output_options.outro {
+      append_line(&mut code, outro);
+    }
+
+    if output_options.format.is_iife() {
+      let (head, tail) = self.iife_wrapper(output_options, import_meta_url.as_ref());
+      if let Some(mappings) = &mut mappings {
+        mappings.prepend(&head);
+      }
+      code = head + &code + &tail;
+    }
+
+    let directives = self.directives(graph);
+    if !directives.is_empty() {
+      // Like the intro, the directives have no mappings.
+      if let Some(mappings) = &mut mappings {
+        mappings.prepend(&directives);
+      }
+      code.insert_str(use_strict_len(&code), &directives);
+    }
+
+    if let Some(banner) = &output_options.banner {
+      let banner = format!("{banner}\n");
+      if let Some(mappings) = &mut mappings {
+        mappings.prepend(&banner);
+      }
+      code.insert_str(0, &banner);
+    }
+    if let Some(footer) = &output_options.footer {
+      append_line(&mut code, footer);
+    }
+
+    // Prepended last, since it must precede anything else, including the banner.
+    if let Some(shebang) = self.shebang(graph) {
+      let shebang = format!("#!{shebang}\n");
+      if let Some(mappings) = &mut mappings {
+        mappings.prepend(&shebang);
+      }
+      code.insert_str(0, &shebang);
+    }
+    Ok((code, mappings))
+  }
+
+  /// The shebang of the entry, like `/usr/bin/env node`, which makes the chunk executable. Like
+  /// directives, a manual chunk has no shebang.
+  fn shebang<'g>(&self, graph: &'g Graph) -> Option<&'g JsWord> {
+    if self.is_manual {
+      return None;
+    }
+    graph.module_by_id[&self.entry]
+      .as_norm()
+      .and_then(|module| module.shebang.as_ref())
+  }
+
+  /// Directives of the entry, like `"use client"`, which must precede any other statement of the
+  /// chunk. A manual chunk has no meaningful entry, so it has no directives.
+  fn directives(&self, graph: &Graph) -> String {
+    if self.is_manual {
+      return String::new();
+    }
+    graph.module_by_id[&self.entry]
+      .as_norm()
+      .map_or_else(String::new, |module| {
+        module
+          .directives
+          .iter()
+          .map(|directive| format!("\"{directive}\";\n"))
+          .collect()
+      })
+  }
+
+  /// Passes working on the AST of the rendered chunk, like converting the format and minifying. The
+  /// chunk is printed again with `codegen` after them. The variable `import.meta.url` is rewritten
+  /// to is returned, which the iife wrapper declares.
+  fn transform_program(
+    &self,
+    code: String,
+    mappings: Option<ChunkMappings>,
+    output_options: &BuildOutputOptions,
+  ) -> UnaryBuildResult<(String, Option<ChunkMappings>, Option<JsWord>)> {
+    let comments = SingleThreadedComments::default();
+    let fm = COMPILER.create_source_file(PathBuf::from(self.id.value().to_string()), code);
+    let mut program = COMPILER
+      .parse_with_comments(
+        fm.clone(),
+        swc_core::ecma::parser::Syntax::Es(Default::default()),
+        Some(&comments),
+      )
+      .map_err(|e| BuildError::parse_js_failed(fm.clone(), e))?;
+
+    let mut import_meta_url = None;
+    if !output_options.format.is_es() {
+      // Workaround for cjs, amd and iife output. The iife format is based on cjs.
+      if output_options.rewrite_import_meta_url {
+        import_meta_url =
+          rolldown_swc_visitors::rewrite_import_meta_url(&mut program, output_options.format);
+      }
+
+      program = GLOBALS.set(&Default::default(), || {
+        if output_options.format.is_amd() {
+          rolldown_swc_visitors::to_amd(
+            program,
+            Mark::new(),
+            &comments,
+            output_options
+              .amd
+              .module_id(self.filename.as_ref().unwrap()),
+            output_options.amd.define.as_str().into(),
+            import_meta_url.take(),
+          )
+        } else {
+          rolldown_swc_visitors::to_cjs(
+            program,
+            Mark::new(),
+            &comments,
+            output_options.format.is_cjs()
+              && self.export_mode.is_default()
+              && self.is_user_defined_entry,
+            self.has_default_export || !self.is_user_defined_entry,
+          )
+        }
+      });
+    }
 
+    let compress = output_options.minify && output_options.minify_options.compress;
+    let mangle =
+      output_options.mangle || (output_options.minify && output_options.minify_options.mangle);
+    if compress || mangle {
+      // Top level names of iife are local to the function wrapping them, which is added later.
       program = GLOBALS.set(&Default::default(), || {
-        rolldown_swc_visitors::to_cjs(
+        rolldown_swc_visitors::minify(
           program,
-          Mark::new(),
+          SOURCE_MAP.clone(),
           &comments,
-          self.export_mode.is_default() && self.is_user_defined_entry,
+          compress,
+          mangle,
+          output_options
+            .reserved_names
+            .iter()
+            .map(|name| name.as_str().into())
+            .collect(),
+          output_options.format.is_iife(),
         )
       });
-
-      code = COMPILER.print(&program, Some(&comments))?;
     }
-    Ok(code)
+
+    // Whitespaces are omitted by the printer for minified chunks.
+    let mut cfg = output_options.codegen.to_swc_config();
+    cfg.minify |= output_options.minify;
+    let (code, mappings) = Self::print_again(&program, &comments, cfg, &fm, mappings)?;
+    Ok((code, mappings, import_meta_url))
+  }
+
+  /// Print `program` parsed from the generated code `fm`, and map the new code back to modules.
+  fn print_again(
+    program: &ast::Module,
+    comments: &SingleThreadedComments,
+    cfg: swc_core::ecma::codegen::Config,
+    fm: &SourceFile,
+    mappings: Option<ChunkMappings>,
+  ) -> UnaryBuildResult<(String, Option<ChunkMappings>)> {
+    let mut reprinted = mappings.as_ref().map(|_| vec![]);
+    let code = COMPILER.print_with_mappings(program, Some(comments), cfg, reprinted.as_mut())?;
+    let mappings =
+      mappings.map(|mappings| mappings.remap(fm, &code, reprinted.unwrap_or_default()));
+    Ok((code, mappings))
+  }
+
+  /// `code` is transformed to cjs already, so its exports are assigned to `exports`, which is
+  /// returned by the function instead. The code is wrapped by the returned head and tail.
+  ///
+  /// The URL of the script is passed as `import_meta_url`, so it's captured once while the script
+  /// is executing, without being shadowed by bindings of the chunk.
+  fn iife_wrapper(
+    &self,
+    output_options: &BuildOutputOptions,
+    import_meta_url: Option<&JsWord>,
+  ) -> (String, String) {
+    let returned = if self.export_mode.is_default() && self.is_user_defined_entry {
+      "exports.default"
+    } else {
+      "exports"
+    };
+    let (params, args) = match import_meta_url {
+      Some(import_meta_url) => (
+        format!("exports, {import_meta_url}"),
+        format!("{{}}, {}", rolldown_swc_visitors::CURRENT_SCRIPT_URL),
+      ),
+      None => ("exports".to_string(), "{}".to_string()),
+    };
+    let head = match &output_options.name {
+      Some(name) => format!("var {name} = (function({params}) {{\n"),
+      None => format!("(function({params}) {{\n"),
+    };
+    (head, format!("\nreturn {returned};\n}})({args});\n"))
   }
 
   /// Deconflicting is to rename identifiers to avoid conflicts.
@@ -151,6 +421,14 @@ impl Chunk {
       .collect::<FxHashSet<_>>();
 
     used_names.extend(preset_of_used_names(&ctx.output_options.format));
+    // Names declared by runtime helpers are in the top level of the chunk too.
+    used_names.extend(
+      self
+        .runtime_helpers
+        .used_names()
+        .into_iter()
+        .map(JsWord::from),
+    );
 
     let mut id_to_name = FxHashMap::default();
     let mut root_id_to_name = FxHashMap::default();
@@ -266,6 +544,8 @@ impl Chunk {
 
     let id_to_name = self.deconflict(&mut ctx);
 
+    let chunk_filename = self.filename.clone().unwrap();
+
     tracing::debug!("id_to_name: {:#?}", id_to_name);
 
     let ordered_modules = {
@@ -279,6 +559,7 @@ impl Chunk {
     {
       // Finalize module items in chunk
       let finalize_ctx = FinalizeContext {
+        chunk_filename: &chunk_filename,
         chunk_filename_by_id: ctx.chunk_filename_by_id,
         // Since there's no dynamic import expressions to rewrite, we can use empty set.
         resolved_ids: &Default::default(),
@@ -295,6 +576,7 @@ impl Chunk {
         .before_module_items
         .visit_mut_with(&mut rolldown_swc_visitors::finalizer(finalize_ctx));
       let finalize_ctx = FinalizeContext {
+        chunk_filename: &chunk_filename,
         chunk_filename_by_id: ctx.chunk_filename_by_id,
         // Since there's no dynamic import expressions to rewrite, we can use empty set.
         resolved_ids: &Default::default(),
@@ -316,6 +598,7 @@ impl Chunk {
       .filter_map(|m| m.as_norm_mut())
       .for_each(|m| {
         let finalize_ctx = FinalizeContext {
+          chunk_filename: &chunk_filename,
           chunk_filename_by_id: ctx.chunk_filename_by_id,
           resolved_ids: &m.resolved_module_ids,
           declared_scoped_names: &declared_scoped_names,
@@ -328,6 +611,15 @@ impl Chunk {
 
         m.ast
           .visit_mut_with(&mut rolldown_swc_visitors::finalizer(finalize_ctx));
+        let emitted_files = m
+          .wasm
+          .iter()
+          .map(|wasm| &wasm.filename)
+          .chain(m.addon.iter().map(|addon| &addon.filename));
+        for filename in emitted_files {
+          let specifier = chunk_import_specifier(&chunk_filename, filename);
+          rewrite_emitted_file_url(&mut m.ast, filename, &specifier);
+        }
       });
     Ok(())
   }
@@ -401,34 +693,98 @@ impl Chunk {
     let mut exports_in_scope: MergedExports = FxHashMap::default();
     let mut exports_out_scope: FxHashMap<&ModuleId, FxHashMap<&JsWord, &ExportedSpecifier>> =
       FxHashMap::default();
-    let re_export_all: FxHashSet<&ModuleId> = entry_module
-      .re_export_all
-      .iter()
-      .filter(|id| id.is_external())
-      .collect();
+    let mut re_export_all: FxHashSet<&ModuleId> = FxHashSet::default();
 
-    entry_module
-      .linked_exports
-      .iter()
-      .for_each(|(exported_name, spec)| {
-        if self.modules.contains(&spec.owner) && !spec.owner.is_external() {
-          exports_in_scope.insert(exported_name.clone(), spec.clone());
-        } else {
-          exports_out_scope
-            .entry(&spec.owner)
-            .or_default()
-            .insert(exported_name, spec);
-        }
-      });
+    if self.is_manual {
+      for (owner, name, exported_name) in ctx.manual_chunks.exports_of(&self.id) {
+        let spec = ctx
+          .modules
+          .get(owner)
+          .and_then(|module| module.as_norm())
+          .and_then(|module| module.find_exported(name))
+          .ok_or_else(|| {
+            BuildError::panic(format!(
+              "Cannot find export {name} of {owner} in Chunk({:?})",
+              self.id
+            ))
+          })?;
+        exports_in_scope.insert(exported_name.clone(), spec.clone());
+      }
+    }
+    let preserved_exports = ctx.manual_chunks.preserved_exports_of(&self.id);
+    if !self.is_manual || preserved_exports.is_some() {
+      re_export_all.extend(
+        entry_module
+          .re_export_all
+          .iter()
+          .filter(|id| id.is_external()),
+      );
+      entry_module
+        .linked_exports
+        .iter()
+        .filter(|(exported_name, _)| {
+          preserved_exports.map_or(true, |kept| kept.contains(*exported_name))
+        })
+        .for_each(|(exported_name, spec)| {
+          if self.modules.contains(&spec.owner) && !spec.owner.is_external() {
+            exports_in_scope.insert(exported_name.clone(), spec.clone());
+          } else {
+            exports_out_scope
+              .entry(&spec.owner)
+              .or_default()
+              .insert(exported_name, spec);
+          }
+        });
+    }
+
+    // Manual chunks are imported once, where the first of their modules is depended on.
+    let mut first_dep_by_manual_chunk: FxHashMap<&ChunkId, &ModuleId> = FxHashMap::default();
+    depended_modules.iter().for_each(|dep| {
+      if let Some(manual_chunk) = ctx.manual_chunks.chunk_of(dep) {
+        first_dep_by_manual_chunk
+          .entry(manual_chunk)
+          .or_insert(*dep);
+      }
+    });
+    // Ones only reached through bindings re-exported by modules out of them.
+    let manual_chunks_of_owners = imports_map
+      .keys()
+      .chain(exports_out_scope.keys())
+      .filter_map(|owner| ctx.manual_chunks.chunk_of(owner))
+      .filter(|manual_chunk| {
+        *manual_chunk != &self.id && !first_dep_by_manual_chunk.contains_key(manual_chunk)
+      })
+      .collect::<BTreeSet<_>>();
 
     // imports and re-exports
-    let module_items = depended_modules
+    let mut module_items = depended_modules
       .par_iter()
       .flat_map(|chunk_dep_id| {
+        if let Some(manual_chunk) = ctx.manual_chunks.chunk_of(chunk_dep_id) {
+          return if first_dep_by_manual_chunk.get(manual_chunk) == Some(chunk_dep_id) {
+            self.link_manual_chunk(
+              ctx.manual_chunks,
+              ctx.chunk_filename_by_id,
+              manual_chunk,
+              &imports_map,
+              &exports_out_scope,
+            )
+          } else {
+            vec![]
+          };
+        }
         let mut imported = false;
         let mut module_items = vec![];
-        let src = if chunk_dep_id.is_external() {
-          box quote_str!(chunk_dep_id.id())
+        let src = if let Some(external_kind) = chunk_dep_id.external_kind() {
+          let id = chunk_dep_id.id();
+          if external_kind == ExternalKind::Relative && id.as_path().is_absolute() {
+            box quote_str!(chunk_import_specifier(
+              self.filename.as_ref().unwrap(),
+              &id.as_path().relative(ctx.output_dir).to_string_lossy()
+            ))
+          } else {
+            box quote_str!(id)
+          }
         } else {
           let dep_chunk_id = ctx
             .split_point_id_to_chunk_id
@@ -441,7 +797,10 @@ impl Chunk {
               )
             });
           let imported_chunk_filename = ctx.chunk_filename_by_id.get(dep_chunk_id).unwrap();
-          box quote_str!(format!("./{imported_chunk_filename}"))
+          box quote_str!(chunk_import_specifier(
+            self.filename.as_ref().unwrap(),
+            imported_chunk_filename
+          ))
         };
         if let Some(specifiers) = imports_map.get(chunk_dep_id) {
           let mut specifiers = specifiers
@@ -550,11 +909,30 @@ impl Chunk {
         module_items
       })
       .collect::<Vec<_>>();
+    manual_chunks_of_owners
+      .into_iter()
+      .for_each(|manual_chunk| {
+        module_items.extend(self.link_manual_chunk(
+          ctx.manual_chunks,
+          ctx.chunk_filename_by_id,
+          manual_chunk,
+          &imports_map,
+          &exports_out_scope,
+        ))
+      });
 
     self.before_module_items = module_items;
+    self.has_default_export = exports_in_scope.contains_key(&js_word!("default"));
 
     if self.is_user_defined_entry {
       self.validate_export_mode(ctx.output_options, &exports_in_scope)?;
+      let is_iife_without_name =
+        ctx.output_options.format.is_iife() && ctx.output_options.name.is_none();
+      if is_iife_without_name && !exports_in_scope.is_empty() {
+        return Err(BuildError::missing_name_option_for_iife_export(
+          self.entry.as_ref(),
+        ));
+      }
     }
 
     if !exports_in_scope.is_empty() {
@@ -569,13 +947,116 @@ impl Chunk {
     Ok(())
   }
 
+  /// Imports and re-exports of bindings from a manual chunk, by the names it exports them as.
+  fn link_manual_chunk(
+    &self,
+    manual_chunks: &ManualChunks,
+    chunk_filename_by_id: &FxHashMap<ChunkId, String>,
+    manual_chunk: &ChunkId,
+    imports_map: &FxHashMap<&ModuleId, HashSet<&ImportedSpecifier>>,
+    exports_out_scope: &FxHashMap<&ModuleId, FxHashMap<&JsWord, &ExportedSpecifier>>,
+  ) -> Vec<ast::ModuleItem> {
+    let src = box quote_str!(chunk_import_specifier(
+      self.filename.as_ref().unwrap(),
+      chunk_filename_by_id.get(manual_chunk).unwrap()
+    ));
+    let is_in_manual_chunk = |owner: &ModuleId| manual_chunks.chunk_of(owner) == Some(manual_chunk);
+    let exported_name = |owner: &ModuleId, name: &JsWord| {
+      manual_chunks
+        .exported_name(owner, name)
+        .cloned()
+        .unwrap_or_else(|| name.clone())
+    };
+    let mut module_items = vec![];
+
+    // Importers of a binding share the same symbol, so it's imported once.
+    let imported = imports_map
+      .iter()
+      .filter(|(owner, _)| is_in_manual_chunk(owner))
+      .flat_map(|(owner, specs)| {
+        specs
+          .iter()
+          .map(move |spec| (exported_name(owner, &spec.imported), *spec))
+      })
+      .collect::<BTreeMap<_, _>>();
+    if !imported.is_empty() {
+      module_items.push(ast::ModuleItem::ModuleDecl(ast::ModuleDecl::Import(
+        ast::ImportDecl {
+          src: src.clone(),
+          specifiers: imported
+            .into_iter()
+            .map(|(imported, spec)| {
+              if imported == js_word!("default") {
+                ast::ImportSpecifier::Default(ast::ImportDefaultSpecifier {
+                  local: spec.imported_as.clone().to_id().into(),
+                  span: Default::default(),
+                })
+              } else {
+                ast::ImportSpecifier::Named(ast::ImportNamedSpecifier {
+                  local: Ident::from(spec.imported_as.clone().to_id()),
+                  imported: Some(quote_ident!(imported).into()),
+                  span: Default::default(),
+                  is_type_only: false,
+                })
+              }
+            })
+            .collect(),
+          ..ast::ImportDecl::dummy()
+        },
+      )));
+    }
+
+    let re_exported = exports_out_scope
+      .iter()
+      .filter(|(owner, _)| is_in_manual_chunk(owner))
+      .flat_map(|(owner, specs)| {
+        specs
+          .iter()
+          .map(move |(exported, spec)| (*exported, exported_name(owner, &spec.exported_as)))
+      })
+      .sorted()
+      .collect_vec();
+    if !re_exported.is_empty() {
+      module_items.push(ast::ModuleItem::ModuleDecl(ast::ModuleDecl::ExportNamed(
+        ast::NamedExport {
+          src: Some(src.clone()),
+          span: Default::default(),
+          specifiers: re_exported
+            .into_iter()
+            .map(|(exported, orig)| {
+              ast::ExportSpecifier::Named(ast::ExportNamedSpecifier {
+                span: Default::default(),
+                exported: (exported != &orig).then(|| quote_ident!(exported.clone()).into()),
+                orig: ast::ModuleExportName::Ident(quote_ident!(orig)),
+                is_type_only: false,
+              })
+            })
+            .collect(),
+          type_only: false,
+          asserts: None,
+        },
+      )));
+    }
+
+    if module_items.is_empty() {
+      module_items.push(ast::ModuleItem::ModuleDecl(ast::ModuleDecl::Import(
+        ast::ImportDecl {
+          src,
+          specifiers: vec![],
+          ..ast::ImportDecl::dummy()
+        },
+      )));
+    }
+    module_items
+  }
+
   fn validate_export_mode(
     &mut self,
     output_options: &BuildOutputOptions,
     exports: &FxHashMap<JsWord, ExportedSpecifier>,
   ) -> UnaryBuildResult<()> {
     // validate export mode
-    if output_options.format.is_cjs() {
+    if output_options.format.is_cjs() || output_options.format.is_iife() {
       match output_options.export_mode {
         ExportMode::Default => {
           if !exports.contains_key(&js_word!("default")) || exports.len() != 1 {
@@ -621,14 +1102,70 @@ impl Chunk {
 }
 
 #[derive(Debug)]
-pub(crate) struct RenderContext {}
+pub(crate) struct RenderContext<'me> {
+  /// Snippets emitted by plugins via `ctx.emit_bundle_intro`, which are empty unless the chunk is
+  /// evaluated first.
+  pub bundle_intros: &'me [String],
+  pub comments: OutputComments,
+}
 
 pub(crate) struct FinalizeBundleContext<'me> {
   pub modules: ModuleRefMutById<'me>,
   pub split_point_id_to_chunk_id: &'me SplitPointIdToChunkId,
+  pub manual_chunks: &'me ManualChunks,
   pub chunk_filename_by_id: &'me FxHashMap<ChunkId, String>,
   pub uf: &'me UnionFind<Symbol>,
   // pub unresolved_mark: Mark,
   pub unresolved_ctxt: SyntaxContext,
   pub output_options: &'me BuildOutputOptions,
+  /// `dir` of the output resolved against `cwd`, or `cwd` if it isn't set. Relative externals are
+  /// referred to from chunks in it.
+  pub output_dir: &'me Path,
+}
+
+/// Length of the leading `"use strict"` directive of `code` emitted by the `cjs` transform, which
+/// must stay at the top. Line comments before it are counted too, since comments at the start of
+/// the chunk, like the `// main.js` of the first module, are printed before the directive.
+fn use_strict_len(code: &str) -> usize {
+  let mut comments_len = 0;
+  for line in code.split_inclusive('\n') {
+    if !line.starts_with("//") {
+      return ["\"use strict\";\n", "'use strict';\n"]
+        .into_iter()
+        .find(|directive| line == *directive)
+        .map_or(0, |directive| comments_len + directive.len());
+    }
+    comments_len += line.len();
+  }
+  0
+}
+
+fn append_line(code: &mut String, line: &str) {
+  if !code.is_empty() && !code.ends_with('\n') {
+    code.push('\n');
+  }
+  code.push_str(line);
+  code.push('\n');
+}
+
+/// Whether concatenating `next` to `prev` would continue the last statement of `prev`, like a module
+/// ending without a semicolon followed by one starting with `(`. Line comments are skipped, such as
+/// the leading `// path/to/module.js` of modules.
+fn is_asi_hazard(prev: &str, next: &str) -> bool {
+  let is_significant = |line: &&str| !line.is_empty() && !line.starts_with("//");
+  let last = prev
+    .lines()
+    .rev()
+    .map(str::trim)
+    .find(is_significant)
+    .and_then(|line| line.chars().last());
+  let first = next
+    .lines()
+    .map(str::trim)
+    .find(is_significant)
+    .and_then(|line| line.chars().next());
+  match (last, first) {
+    (Some(last), Some(first)) => last != ';' && matches!(first, '(' | '[' | '`' | '+' | '-' | '/'),
+    _ => false,
+  }
 }
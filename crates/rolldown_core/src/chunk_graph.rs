@@ -6,4 +6,5 @@ use crate::Chunk;
 pub(crate) struct ChunkGraph {
   pub(crate) chunk_by_id: FxHashMap<ChunkId, Chunk>,
   pub(crate) split_point_to_chunk: FxHashMap<ModuleId, ChunkId>,
+  pub(crate) manual_chunk_by_module: FxHashMap<ModuleId, ChunkId>,
 }
@@ -0,0 +1,92 @@
+use swc_core::common::{BytePos, LineCol, SourceFile};
+
+use crate::COMPILER;
+
+/// Mappings from positions in the generated code of a chunk to positions in the transformed code of
+/// modules. It's built up along with the generated code.
+#[derive(Debug, Default)]
+pub(crate) struct ChunkMappings {
+  pub(crate) mappings: Vec<(BytePos, LineCol)>,
+  /// End of the generated code so far.
+  line: u32,
+  col: u32,
+}
+
+impl ChunkMappings {
+  /// `code` is appended to the generated code, and `mappings` are relative to its start.
+  pub(crate) fn push(&mut self, code: &str, mappings: Vec<(BytePos, LineCol)>) {
+    self.mappings.extend(mappings.into_iter().map(|(pos, lc)| {
+      let col = if lc.line == 0 {
+        self.col + lc.col
+      } else {
+        lc.col
+      };
+      (
+        pos,
+        LineCol {
+          line: self.line + lc.line,
+          col,
+        },
+      )
+    }));
+    self.advance(code);
+  }
+
+  /// `code` is prepended to the generated code, which moves all the mappings.
+  pub(crate) fn prepend(&mut self, code: &str) {
+    let mut head = Self::default();
+    head.advance(code);
+    for (_, lc) in &mut self.mappings {
+      if lc.line == 0 {
+        lc.col += head.col;
+      }
+      lc.line += head.line;
+    }
+    if self.line == 0 {
+      self.col += head.col;
+    }
+    self.line += head.line;
+  }
+
+  /// The generated code is parsed again as `fm`, and printed to `code` with `reprinted` mappings,
+  /// which point to `fm`. They are mapped back to modules via the current mappings.
+  pub(crate) fn remap(
+    mut self,
+    fm: &SourceFile,
+    code: &str,
+    reprinted: Vec<(BytePos, LineCol)>,
+  ) -> Self {
+    self.mappings.sort_by_key(|(_, lc)| (lc.line, lc.col));
+    let mappings = reprinted
+      .into_iter()
+      .filter(|(pos, _)| (fm.start_pos..=fm.end_pos).contains(pos))
+      .filter_map(|(pos, lc)| {
+        let loc = COMPILER.cm.lookup_char_pos(pos);
+        let generated = ((loc.line - 1) as u32, loc.col.0 as u32);
+        let index = self
+          .mappings
+          .partition_point(|(_, lc)| (lc.line, lc.col) <= generated);
+        // Tokens of the previous code are mapped by the closest token before them in the same line.
+        let (original, found) = self.mappings.get(index.checked_sub(1)?)?;
+        (found.line == generated.0).then_some((*original, lc))
+      })
+      .collect();
+
+    let mut remapped = Self {
+      mappings,
+      ..Default::default()
+    };
+    remapped.advance(code);
+    remapped
+  }
+
+  fn advance(&mut self, code: &str) {
+    match code.rsplit_once('\n') {
+      Some((before, last)) => {
+        self.line += before.matches('\n').count() as u32 + 1;
+        self.col = last.chars().count() as u32;
+      }
+      None => self.col += code.chars().count() as u32,
+    }
+  }
+}
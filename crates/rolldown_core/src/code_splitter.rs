@@ -1,15 +1,19 @@
-use std::path::Component;
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
 
 use hashlink::LinkedHashSet;
 use itertools::Itertools;
 // use petgraph::stable_graph::NodeIndex;
-use rolldown_common::{ChunkId, ModuleId};
+use rolldown_common::{sanitize_file_name, ChunkId, ModuleId};
 use rustc_hash::{FxHashMap, FxHashSet};
 use sugar_path::{AsPath, SugarPath};
 use swc_core::ecma::atoms::JsWord;
 use tracing::instrument;
 
 pub fn uri_to_chunk_name(root: &str, uri: &str) -> String {
+  if uri.starts_with('\0') {
+    return sanitize_file_name(uri);
+  }
   let path = uri.as_path();
   let mut relatived = path.relative(root);
   let _ext = relatived
@@ -34,10 +38,43 @@ pub fn uri_to_chunk_name(root: &str, uri: &str) -> String {
   name
 }
 
-use crate::{BuildInputOptions, Chunk, ChunkGraph, Graph, UnaryBuildResult};
+/// Chunk names for `preserve_modules` keep the directories of modules relative to `root`, like
+/// `lib/util` for `<root>/lib/util.ts`.
+fn preserved_chunk_name(root: &Path, uri: &str) -> String {
+  if uri.starts_with('\0') {
+    return sanitize_file_name(uri);
+  }
+  let mut relative = uri.as_path().relative(root);
+  relative.set_extension("");
+  relative
+    .components()
+    .filter(|com| matches!(com, Component::Normal(_)))
+    .filter_map(|seg| seg.as_os_str().to_str())
+    .join("/")
+}
+
+/// The deepest directory containing all the modules, which isn't virtual.
+fn common_dir<'a>(ids: impl Iterator<Item = &'a ModuleId>) -> Option<PathBuf> {
+  let mut paths = ids
+    .filter(|id| !id.is_external() && id.as_path().is_absolute())
+    .map(|id| id.as_path());
+  let mut dir = paths.next()?.parent()?.to_path_buf();
+  for path in paths {
+    while !path.starts_with(&dir) {
+      dir = dir.parent()?.to_path_buf();
+    }
+  }
+  Some(dir)
+}
+
+use crate::{
+  BuildError, BuildInputOptions, BuildOutputOptions, Chunk, ChunkGraph, Graph, ManualChunkPatterns,
+  UnaryBuildResult,
+};
 
 pub(crate) struct CodeSplitter<'me> {
   opts: &'me BuildInputOptions,
+  output_options: &'me BuildOutputOptions,
   graph: &'me Graph,
   chunk_by_id: FxHashMap<ChunkId, Chunk>,
   entries: Vec<ModuleId>,
@@ -45,7 +82,13 @@ pub(crate) struct CodeSplitter<'me> {
   // chunk_relation_graph: ChunkRelationGraph,
   mod_to_chunks: FxHashMap<ModuleId, FxHashSet<ChunkId>>,
   // The order is only to make the output stable.
+  /// Modules with directives are split like dynamic imports if `split_directives` is enabled.
   dynamic_entries: LinkedHashSet<ModuleId>,
+  /// Modules placed by `manual_chunks`, which are left out when entries collect their modules.
+  manual_chunk_by_module: FxHashMap<ModuleId, ChunkId>,
+  /// `Some` if `preserve_modules` is enabled. Chunks are named by paths of their modules relative to
+  /// it.
+  preserve_modules_root: Option<PathBuf>,
 }
 
 impl<'me> CodeSplitter<'me> {
@@ -53,9 +96,19 @@ impl<'me> CodeSplitter<'me> {
     entries: Vec<ModuleId>,
     graph: &'me mut Graph,
     opts: &'me BuildInputOptions,
+    output_options: &'me BuildOutputOptions,
   ) -> Self {
+    let mut directive_modules = graph
+      .module_by_id
+      .values()
+      .filter_map(|m| m.as_norm())
+      .filter(|m| output_options.split_directives && !m.directives.is_empty())
+      .filter(|m| !entries.contains(&m.id))
+      .collect_vec();
+    directive_modules.sort_by_key(|m| m.exec_order);
     Self {
       opts,
+      output_options,
       graph,
       chunk_by_id: Default::default(),
       entries,
@@ -72,8 +125,20 @@ impl<'me> CodeSplitter<'me> {
         .flat_map(|m| m.dynamic_dependencies())
         // Ignore external module
         .filter(|m| !m.is_external())
+        .chain(directive_modules.into_iter().map(|m| &m.id))
         .cloned()
         .collect::<LinkedHashSet<_>>(),
+      manual_chunk_by_module: Default::default(),
+      preserve_modules_root: output_options
+        .preserve_modules
+        .then(|| common_dir(graph.module_by_id.keys()).unwrap_or_else(|| opts.cwd.clone())),
+    }
+  }
+
+  fn chunk_name_of(&self, module_id: &ModuleId) -> String {
+    match &self.preserve_modules_root {
+      Some(root) => preserved_chunk_name(root, module_id.as_ref()),
+      None => uri_to_chunk_name(&self.opts.cwd.to_string_lossy(), module_id.as_ref()),
     }
   }
 }
@@ -130,11 +195,7 @@ impl<'me> CodeSplitter<'me> {
   pub fn analyze_entries(&mut self, mut entries: Vec<ModuleId>, is_entry_chunk: bool) {
     while let Some(entry) = entries.pop() {
       let _exec_order = self.graph.module_by_id[&entry].exec_order();
-      let chunk = Chunk::new(
-        uri_to_chunk_name(&self.opts.cwd.to_string_lossy(), entry.as_ref()),
-        entry.clone(),
-        is_entry_chunk,
-      );
+      let chunk = Chunk::new(self.chunk_name_of(&entry), entry.clone(), is_entry_chunk);
       self
         .split_point_module_to_chunk
         .insert(entry.clone(), chunk.id.clone());
@@ -146,7 +207,9 @@ impl<'me> CodeSplitter<'me> {
       let mut visited_modules: FxHashSet<ModuleId> = Default::default();
       let mut stack = vec![entry];
       while let Some(module_id) = stack.pop() {
-        if visited_modules.contains(&module_id) {
+        if visited_modules.contains(&module_id)
+          || self.manual_chunk_by_module.contains_key(&module_id)
+        {
           continue;
         } else {
           visited_modules.insert(module_id.clone());
@@ -167,6 +230,137 @@ impl<'me> CodeSplitter<'me> {
     }
   }
 
+  /// Modules matched by `manual_chunks` and their dependencies are assigned to the chunks before
+  /// entries collect their modules, so they are neither duplicated in nor split from other chunks.
+  /// Dependencies go to the first chunk reaching them, in the order of names of the chunks.
+  ///
+  /// With `preserve_modules`, each module is assigned to a chunk of its own instead, and
+  /// `manual_chunks` is ignored.
+  fn assign_manual_chunks(&mut self) -> UnaryBuildResult<()> {
+    let patterns = match &self.output_options.manual_chunks {
+      Some(manual_chunks) if self.preserve_modules_root.is_none() => {
+        Some(ManualChunkPatterns::new(manual_chunks)?)
+      }
+      _ => None,
+    };
+    if patterns.is_none() && self.preserve_modules_root.is_none() {
+      return Ok(());
+    }
+    let graph = self.graph;
+    let split_points = self
+      .entries
+      .iter()
+      .chain(self.dynamic_entries.iter())
+      .collect::<FxHashSet<_>>();
+
+    let mut modules = graph
+      .module_by_id
+      .values()
+      .filter_map(|module| module.as_norm())
+      .filter(|module| !split_points.contains(&&module.id))
+      .collect_vec();
+    modules.sort_by_key(|module| module.exec_order);
+    let mut matched_by_chunk: BTreeMap<String, Vec<&ModuleId>> = BTreeMap::new();
+    for module in modules {
+      let Some(patterns) = &patterns else {
+        // Modules dropped by treeshaking have nothing to emit.
+        if !module.is_included() && graph.kept_exports(module).next().is_none() {
+          continue;
+        }
+        let name = self.chunk_name_of(&module.id);
+        matched_by_chunk.entry(name).or_default().push(&module.id);
+        continue;
+      };
+      let mut matched_chunks = patterns.matched_chunks(&module.id, &self.opts.cwd);
+      if let Some(chunk) = matched_chunks.next() {
+        if let Some(other_chunk) = matched_chunks.next() {
+          return Err(BuildError::manual_chunk_conflict(
+            module.id.as_ref(),
+            (chunk.to_string(), other_chunk.to_string()),
+          ));
+        }
+        matched_by_chunk
+          .entry(chunk.to_string())
+          .or_default()
+          .push(&module.id);
+      }
+    }
+
+    // Names of chunks of entries are taken, like `main`.
+    let taken_names = split_points
+      .iter()
+      .map(|id| self.chunk_name_of(id))
+      .collect::<FxHashSet<_>>();
+    let chunk_ids = matched_by_chunk
+      .keys()
+      .map(|name| {
+        let id = std::iter::once(name.to_string())
+          .chain((2..).map(|index| format!("{name}{index}")))
+          .find(|id| !taken_names.contains(id))
+          .unwrap();
+        (name, ChunkId::new(id))
+      })
+      .collect::<FxHashMap<_, _>>();
+
+    let mut manual_chunk_by_module = FxHashMap::default();
+    for (name, modules) in &matched_by_chunk {
+      for module_id in modules {
+        manual_chunk_by_module.insert((*module_id).clone(), chunk_ids[name].clone());
+      }
+    }
+    for (name, modules) in &matched_by_chunk {
+      let mut stack = modules.iter().rev().map(|id| (*id).clone()).collect_vec();
+      while let Some(module_id) = stack.pop() {
+        graph.module_by_id[&module_id]
+          .dependencies()
+          .iter()
+          .rev()
+          .for_each(|dep| {
+            let is_assignable = !dep.is_external()
+              && !split_points.contains(&dep)
+              && !manual_chunk_by_module.contains_key(dep);
+            if is_assignable {
+              manual_chunk_by_module.insert(dep.clone(), chunk_ids[name].clone());
+              stack.push(dep.clone());
+            }
+          });
+      }
+    }
+    self.manual_chunk_by_module = manual_chunk_by_module;
+    Ok(())
+  }
+
+  /// The first module of a manual chunk in execution order stands for its `entry`.
+  fn create_manual_chunks(&mut self) {
+    let mut modules_by_chunk: FxHashMap<&ChunkId, Vec<&ModuleId>> = FxHashMap::default();
+    self
+      .manual_chunk_by_module
+      .iter()
+      .for_each(|(module_id, chunk_id)| {
+        modules_by_chunk
+          .entry(chunk_id)
+          .or_default()
+          .push(module_id)
+      });
+
+    for (chunk_id, modules) in modules_by_chunk {
+      let entry = modules
+        .iter()
+        .min_by_key(|id| self.graph.module_by_id[**id].exec_order())
+        .unwrap();
+      let mut chunk = Chunk::new(chunk_id.clone(), (*entry).clone(), false);
+      chunk.is_manual = true;
+      for module_id in modules {
+        chunk.modules.insert(module_id.clone());
+        *self.mod_to_chunks.get_mut(module_id).unwrap() = FxHashSet::from_iter([chunk_id.clone()]);
+        self
+          .split_point_module_to_chunk
+          .insert(module_id.clone(), chunk_id.clone());
+      }
+      self.chunk_by_id.insert(chunk_id.clone(), chunk);
+    }
+  }
+
   fn collect_shared_modules(&self) -> Vec<ModuleId> {
     self
       .mod_to_chunks
@@ -206,6 +400,7 @@ impl<'me> CodeSplitter<'me> {
 
   #[instrument(skip_all)]
   pub(crate) fn split(mut self) -> UnaryBuildResult<ChunkGraph> {
+    self.assign_manual_chunks()?;
     self.analyze_entries(self.entries.clone(), true);
     self.analyze_entries(
       self.dynamic_entries.clone().into_iter().collect_vec(),
@@ -214,6 +409,7 @@ impl<'me> CodeSplitter<'me> {
     self.dynamic_entries.clone().iter().for_each(|entry| {
       self.remove_duplicated_module(entry);
     });
+    self.create_manual_chunks();
 
     let mut shared_modules = self.collect_shared_modules();
     while let Some(shared_module_id) = shared_modules.pop() {
@@ -229,6 +425,7 @@ impl<'me> CodeSplitter<'me> {
     Ok(ChunkGraph {
       chunk_by_id: self.chunk_by_id,
       split_point_to_chunk: self.split_point_module_to_chunk,
+      manual_chunk_by_module: self.manual_chunk_by_module,
     })
   }
 }
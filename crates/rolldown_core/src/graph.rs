@@ -1,10 +1,15 @@
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use derivative::Derivative;
 use itertools::Itertools;
 use rayon::prelude::{ParallelBridge, ParallelIterator};
-use rolldown_common::{ExportedSpecifier, ImportedSpecifier, ModuleId, Symbol, UnionFind};
-use rolldown_resolver::Resolver;
+use rolldown_common::{
+  ExportedSpecifier, ImportedSpecifier, ModuleId, ReExportedSpecifier, Symbol, UnionFind,
+};
+use rolldown_error::Errors;
+use rolldown_plugin::{ExportsUsage, ResolvedInputOptions};
+use rolldown_resolver::{Resolver, DEFAULT_CONDITION_NAMES, DEFAULT_EXTENSIONS};
 use rolldown_tracing::ContextedTracer;
 use rustc_hash::FxHashSet as HashSet;
 use rustc_hash::{FxHashMap, FxHashSet};
@@ -17,7 +22,11 @@ use crate::module_loader::ModuleLoader;
 use crate::{
   norm_or_ext::NormOrExt, normal_module::NormalModule, ModuleById, UnaryBuildResult, SWC_GLOBALS,
 };
-use crate::{BuildError, BuildResult, SharedBuildInputOptions, SharedBuildPluginDriver};
+use crate::{
+  stubbed_node_builtin, BuildError, BuildInputOptions, BuildMetrics, BuildResult, Definitions,
+  OutputComments, Replacer, ResolutionReport, SharedBuildInputOptions, SharedBuildPluginDriver,
+  SharedTransformCache, TsConfigFile,
+};
 
 #[derive(Derivative)]
 #[derivative(Debug)]
@@ -31,18 +40,37 @@ pub struct Graph {
   pub(crate) uf: UnionFind<Symbol>,
   pub(crate) build_plugin_driver: SharedBuildPluginDriver,
   pub(crate) used_symbols: HashSet<Symbol>,
+  /// Computed in treeshaking, since unused exports are dropped after that.
+  pub(crate) exports_usage: BTreeMap<ModuleId, ExportsUsage>,
+  /// Collected after linking, before treeshaking drops unused symbols.
+  pub(crate) metrics: BuildMetrics,
+  /// `Some` if `resolution_diagnostics` is enabled.
+  pub(crate) resolution_report: Option<Arc<ResolutionReport>>,
+  /// `Some` if `cache` is enabled.
+  pub(crate) transform_cache: Option<SharedTransformCache>,
+  /// `Some` if `cache` is enabled.
+  pub(crate) parse_cache: Option<SharedParseCache>,
+  /// Comments kept in the output. Leading comments of removed `export` keywords are only moved to
+  /// the declarations if any are kept.
+  pub(crate) comments: OutputComments,
 }
 
 impl Graph {
   pub(crate) fn new(
     build_plugin_driver: SharedBuildPluginDriver,
     input_options: SharedBuildInputOptions,
+    transform_cache: Option<SharedTransformCache>,
+    parse_cache: Option<SharedParseCache>,
+    comments: OutputComments,
   ) -> Self {
     let (unresolved_mark, unresolved_ctxt) = GLOBALS.set(&SWC_GLOBALS, || {
       let mark = Mark::new();
       let ctxt = SyntaxContext::empty().apply_mark(mark);
       (mark, ctxt)
     });
+    let resolution_report = input_options
+      .resolution_diagnostics
+      .then(|| Arc::new(ResolutionReport::default()));
 
     Self {
       input_options,
@@ -53,6 +81,12 @@ impl Graph {
       uf: Default::default(),
       build_plugin_driver,
       used_symbols: Default::default(),
+      exports_usage: Default::default(),
+      metrics: Default::default(),
+      resolution_report,
+      transform_cache,
+      parse_cache,
+      comments,
     }
   }
 
@@ -83,6 +117,29 @@ impl Graph {
       .unwrap_or_else(|| panic!("Expected NormalModule, got ExternalModule({id:?})"))
   }
 
+  /// Fails if there are more symbols than `max_symbols`.
+  fn collect_metrics(&mut self) -> UnaryBuildResult<()> {
+    let symbols = self
+      .module_by_id
+      .values()
+      .filter_map(|module| module.as_norm())
+      .map(|module| {
+        module.parts.declared_id_to_statement_parts.len() + module.extra_top_level_symbols.len()
+      })
+      .sum();
+    self.metrics = BuildMetrics {
+      symbols,
+      union_find_size: self.uf.len(),
+    };
+    tracing::debug!("build metrics: {:?}", self.metrics);
+    match self.input_options.max_symbols {
+      Some(max_symbols) if symbols > max_symbols => {
+        Err(BuildError::too_many_symbols(symbols, max_symbols))
+      }
+      _ => Ok(()),
+    }
+  }
+
   pub(crate) fn add_module(&mut self, module: NormOrExt) {
     debug_assert!(!self.module_by_id.contains_key(module.id()));
     self.module_by_id.insert(module.id().clone(), module);
@@ -260,6 +317,9 @@ impl Graph {
               .unwrap();
 
             let importer = importer.expect_norm_mut();
+            // Re-exports of the importee that aren't linked yet, since it's in a cycle with the
+            // importer and linked later.
+            let mut unlinked = vec![];
             match importee {
               NormOrExt::Normal(importee) => {
                 for spec in re_exports {
@@ -268,61 +328,50 @@ impl Graph {
                   if spec.imported == js_word!("*") {
                     importee.mark_namespace_id_referenced();
                   }
-                  if self.input_options.shim_missing_exports
-                    && shim_missing_export_if_needed(importee, &spec.imported)
-                  {
-                    (self.input_options.on_warn)(BuildError::shimmed_export(
-                      spec.imported.to_string(),
-                      importee_id.as_path().to_path_buf(),
-                    ));
-                  }
+                  shim_missing_export_if_needed(&self.input_options, importee, &spec.imported);
                   if let Some(original_spec) = importee.find_exported(&spec.imported) {
                     importer.add_to_linked_exports(spec.exported_as, original_spec.clone());
                   } else {
-                    return Err(BuildError::missing_export(
-                      spec.imported.to_string(),
-                      importer_id.as_ref(),
-                      importee_id.as_ref(),
-                    ));
+                    unlinked.push(spec);
                   }
                 }
               }
               NormOrExt::External(importee) => {
-                // We will transform
-                // ```js
-                // export { resolve } from 'path'
-                // ```
-                // to
-                // ```
-                // import { resolve } from 'path'
-                // export { resolve }
-                // ```
                 re_exports.into_iter().for_each(|spec| {
-                  let symbol_in_importer =
-                    importer.create_top_level_symbol(if spec.exported_as != js_word!("default") {
-                      &spec.exported_as
-                    } else {
-                      &spec.imported
-                    });
-
-                  importer.add_to_linked_imports(
-                    &importee.id,
-                    ImportedSpecifier {
-                      imported_as: symbol_in_importer.clone(),
-                      imported: spec.imported.clone(),
-                    },
-                  );
+                  re_export_from_external(importer, &importee.id, spec);
+                });
+              }
+            }
 
-                  importer.add_to_linked_exports(
-                    spec.exported_as.clone(),
-                    ExportedSpecifier {
+            for spec in unlinked {
+              let traced = Self::trace_re_export(
+                &self.module_by_id,
+                &importee_id,
+                &spec.imported,
+                &mut FxHashSet::default(),
+              )?
+              .ok_or_else(|| {
+                BuildError::missing_export(
+                  spec.imported.to_string(),
+                  importer_id.as_ref(),
+                  importee_id.as_ref(),
+                )
+              })?;
+              let importer = Self::fetch_normal_module_mut(&mut self.module_by_id, importer_id);
+              match traced {
+                TracedExport::Binding(original_spec) => {
+                  importer.add_to_linked_exports(spec.exported_as, original_spec);
+                }
+                TracedExport::External(external_id, imported) => {
+                  re_export_from_external(
+                    importer,
+                    &external_id,
+                    ReExportedSpecifier {
                       exported_as: spec.exported_as,
-                      local_id: symbol_in_importer,
-                      /// NOTE: This is a local export to importer
-                      owner: importer_id.clone(),
+                      imported,
                     },
-                  )
-                });
+                  );
+                }
               }
             }
             Ok(())
@@ -338,13 +387,20 @@ impl Graph {
             .cloned()
             .collect::<Vec<_>>();
 
+        // `linked_exports` are hash maps. Names are visited in sorted order, so the result doesn't
+        // rely on the iteration order of them.
         let non_conflicted_names = {
           use std::collections::hash_map::Entry;
           let mut tmp: FxHashMap<&JsWord, Option<&ExportedSpecifier>> = FxHashMap::default();
           importee_of_being_re_exported_all
             .iter()
             .filter_map(|importee_id| Self::fetch_module(&self.module_by_id, importee_id).as_norm())
-            .flat_map(|each_importee| each_importee.linked_exports.iter())
+            .flat_map(|each_importee| {
+              each_importee
+                .linked_exports
+                .iter()
+                .sorted_by_key(|(exported_name, _)| *exported_name)
+            })
             .for_each(|(exported_name, spec)| match tmp.entry(exported_name) {
               Entry::Occupied(mut entry) => {
                 match entry.get() {
@@ -403,6 +459,19 @@ impl Graph {
 
             match importee {
               NormOrExt::Normal(importee) => {
+                let has_only_default_export = importee.re_export_all.is_empty()
+                  && !importee.linked_exports.is_empty()
+                  && importee
+                    .linked_exports
+                    .keys()
+                    .all(|name| name == &js_word!("default"));
+                if has_only_default_export {
+                  (self.input_options.on_warn)(BuildError::re_export_all_from_default_only(
+                    importer_id.as_path().to_path_buf(),
+                    importee_id.as_path().to_path_buf(),
+                  ));
+                }
+
                 importee
                   .linked_exports
                   .clone()
@@ -423,6 +492,7 @@ impl Graph {
                     // Conflicted names should be hidden
                     non_conflicted_names.contains(exported_as)
                   })
+                  .sorted_by(|(a, _), (b, _)| a.cmp(b))
                   .for_each(|(exported_as, spec)| {
                     importer.add_to_linked_exports(exported_as, spec);
                   });
@@ -467,6 +537,92 @@ impl Graph {
       })
   }
 
+  /// What `name` exported by `id` refers to, following re-exports that aren't linked yet. Modules
+  /// importing each other could be linked before the ones they re-export from, like `b.js` with
+  /// `export { c } from './a.js'`, where `a.js` imports `b.js` and has `export { c } from './c.js'`.
+  ///
+  /// Named re-exports, `export * from` and imported bindings exported again, like
+  /// `import { c } from './c.js'; export { c }`, are followed. It's an error if they loop back
+  /// without reaching a binding, except for `export * from`, whose loops are skipped like in ESM.
+  fn trace_re_export(
+    module_by_id: &ModuleById,
+    id: &ModuleId,
+    name: &JsWord,
+    visiting: &mut FxHashSet<(ModuleId, JsWord)>,
+  ) -> UnaryBuildResult<Option<TracedExport>> {
+    let NormOrExt::Normal(module) = &module_by_id[id] else {
+      return Ok(Some(TracedExport::External(id.clone(), name.clone())));
+    };
+    let key = (id.clone(), name.clone());
+    if !visiting.insert(key.clone()) {
+      return Err(BuildError::circular_reexport(
+        name.to_string(),
+        id.as_ref().as_path().to_path_buf(),
+      ));
+    }
+
+    let traced = if let Some(spec) = module.find_exported(name) {
+      // Imports from external modules are left to `link_imports`, so are the ones not found, like
+      // imports of missing exports to shim.
+      let imported = module
+        .imports
+        .iter()
+        .filter(|(importee_id, _)| spec.owner == *id && !importee_id.is_external())
+        .find_map(|(importee_id, specs)| {
+          specs
+            .iter()
+            .find(|imported| imported.imported_as == spec.local_id)
+            .map(|imported| (importee_id, &imported.imported))
+        });
+      let traced = match imported {
+        Some((importee_id, imported)) => {
+          Self::trace_re_export(module_by_id, importee_id, imported, visiting)?
+        }
+        None => None,
+      };
+      traced.or_else(|| Some(TracedExport::Binding(spec.clone())))
+    } else if let Some((importee_id, imported)) =
+      module
+        .re_exported_ids
+        .iter()
+        .find_map(|(importee_id, specs)| {
+          specs
+            .iter()
+            .find(|spec| &spec.exported_as == name)
+            .map(|spec| (importee_id, &spec.imported))
+        })
+    {
+      Self::trace_re_export(module_by_id, importee_id, imported, visiting)?
+    } else if name == "default" {
+      // `export * from` doesn't re-export `default`
+      None
+    } else {
+      // Names of external modules are unknown, which are left to `external_modules_of_re_export_all`.
+      let mut found = None;
+      for importee_id in module.re_export_all.iter() {
+        if importee_id.is_external() || visiting.contains(&(importee_id.clone(), name.clone())) {
+          continue;
+        }
+        let Some(traced) = Self::trace_re_export(module_by_id, importee_id, name, visiting)? else {
+          continue;
+        };
+        match &found {
+          None => found = Some(traced),
+          Some(existing) if *existing == traced => {}
+          // Like conflicted names of `export * from`, ambiguous ones aren't exported.
+          Some(_) => {
+            found = None;
+            break;
+          }
+        }
+      }
+      found
+    };
+
+    visiting.remove(&key);
+    Ok(traced)
+  }
+
   /// two things
   /// 1. Union symbol
   /// 2. Generate real ImportedSpecifier for each import and add to `linked_imports`
@@ -501,14 +657,11 @@ impl Graph {
                 }
                 importee.suggest_name(&imported_spec.imported, imported_spec.imported_as.name());
 
-                if self.input_options.shim_missing_exports
-                  && shim_missing_export_if_needed(importee, &imported_spec.imported)
-                {
-                  (self.input_options.on_warn)(BuildError::shimmed_export(
-                    imported_spec.imported.to_string(),
-                    importee_id.as_path().to_path_buf(),
-                  ));
-                }
+                shim_missing_export_if_needed(
+                  &self.input_options,
+                  importee,
+                  &imported_spec.imported,
+                );
                 if let Some(exported_spec) =
                   importee.find_exported(&imported_spec.imported).cloned()
                 {
@@ -553,14 +706,11 @@ impl Graph {
                     importee.mark_namespace_id_referenced();
                   }
                   importee.suggest_name(&imported_spec.imported, imported_spec.imported_as.name());
-                  if self.input_options.shim_missing_exports
-                    && shim_missing_export_if_needed(importee, &imported_spec.imported)
-                  {
-                    (self.input_options.on_warn)(BuildError::shimmed_export(
-                      imported_spec.imported.to_string(),
-                      importee_id.as_path().to_path_buf(),
-                    ));
-                  }
+                  shim_missing_export_if_needed(
+                    &self.input_options,
+                    importee,
+                    &imported_spec.imported,
+                  );
                   if let Some(exported_spec) =
                     importee.find_exported(&imported_spec.imported).cloned()
                   {
@@ -679,30 +829,114 @@ impl Graph {
       });
   }
 
+  /// `build_start` hooks are called before anything else.
   #[instrument(skip_all)]
   pub(crate) async fn generate_module_graph(&mut self) -> BuildResult<()> {
-    let resolver = Arc::new(Resolver::with_cwd(
+    let options = ResolvedInputOptions {
+      input: self
+        .input_options
+        .input
+        .iter()
+        .map(|item| (item.name.clone(), item.import.clone()))
+        .collect(),
+      cwd: self.input_options.cwd.clone(),
+      treeshake: self.input_options.treeshake,
+    };
+    self
+      .build_plugin_driver
+      .read()
+      .await
+      .build_start(&options)
+      .await?;
+    self.generate_module_graph_inner().await
+  }
+
+  async fn generate_module_graph_inner(&mut self) -> BuildResult<()> {
+    if let Some(extension) = self
+      .input_options
+      .resolve_extensions
+      .iter()
+      .find(|extension| !extension.starts_with('.'))
+    {
+      return Err(BuildError::invalid_resolve_extension(extension).into());
+    }
+
+    let condition_names = match self.input_options.platform {
+      Some(platform) if self.input_options.condition_names == DEFAULT_CONDITION_NAMES => {
+        platform.condition_names()
+      }
+      _ => self.input_options.condition_names.clone(),
+    };
+    let mut resolver = Resolver::with_conditions(
       self.input_options.cwd.clone(),
       self.input_options.preserve_symlinks,
-    ));
+      condition_names,
+    );
+    if let Some(root) = &self.input_options.absolute_path_root {
+      resolver = resolver.with_absolute_path_root(root.clone());
+    }
+    if !self.input_options.alias.is_empty() {
+      resolver = resolver.with_alias(self.input_options.alias.clone());
+    }
+    if let Some(config_file) = &self.input_options.builtins.tsconfig.config_file {
+      let path = self.input_options.cwd.join(config_file);
+      resolver = resolver.with_tsconfig_paths(TsConfigFile::read(&path).await?.into_paths(&path));
+    }
+    if self.input_options.resolve_extensions != DEFAULT_EXTENSIONS {
+      resolver = resolver.with_extensions(self.input_options.resolve_extensions.clone());
+    }
+    if let Some(main_fields) = &self.input_options.main_fields {
+      resolver = resolver.with_main_fields(main_fields.clone());
+    }
+    if self.input_options.allow_exports_fallback {
+      resolver = resolver.with_exports_fallback(self.input_options.on_warn.clone());
+    }
+    let resolver = Arc::new(resolver);
+
+    let replacer = self
+      .input_options
+      .builtins
+      .replace
+      .as_ref()
+      .filter(|replace| !replace.values.is_empty())
+      .map(Replacer::new)
+      .transpose()?
+      .map(Arc::new);
+    let definitions = (!self.input_options.define.is_empty())
+      .then(|| Definitions::new(&self.input_options.define))
+      .transpose()?
+      .map(Arc::new);
 
     ModuleLoader::new(
       self,
       resolver,
       self.build_plugin_driver.clone(),
       self.input_options.clone(),
+      replacer,
+      definitions.clone(),
     )
     .fetch_all_modules()
     .await?;
+    if let Some(definitions) = definitions {
+      self.report_unused_definitions(&definitions)?;
+    }
 
     self.sort_modules();
     self.link()?;
     self.patch();
     tracing::trace!("graph after link and patch {:#?}", self);
+    self.collect_metrics()?;
 
     if self.input_options.treeshake {
       self.treeshake()?;
+      self
+        .build_plugin_driver
+        .read()
+        .await
+        .exports_usage(&self.exports_usage)
+        .await?;
     } else {
+      let keeps_comments = self.comments != OutputComments::None;
       self
         .module_by_id
         .values_mut()
@@ -711,7 +945,10 @@ impl Graph {
           match module {
             NormOrExt::Normal(module) => {
               // Because of scope hoisting, we need to remove export/import
-              rolldown_swc_visitors::remove_export_and_import(&mut module.ast);
+              rolldown_swc_visitors::remove_export_and_import(
+                &mut module.ast,
+                keeps_comments.then_some(&module.comments),
+              );
             }
             NormOrExt::External(_ext) => {}
           };
@@ -719,13 +956,90 @@ impl Graph {
     }
     Ok(())
   }
+
+  /// Unused keys of `define` are warned about, or fail the build if `strict_define` is enabled.
+  fn report_unused_definitions(&self, definitions: &Definitions) -> BuildResult<()> {
+    let unused = definitions
+      .unused_names()
+      .into_iter()
+      .map(BuildError::unused_define_key)
+      .collect_vec();
+    if self.input_options.strict_define && !unused.is_empty() {
+      return Err(Errors::from_vec(unused));
+    }
+    unused
+      .into_iter()
+      .for_each(|warning| (self.input_options.on_warn)(warning));
+    Ok(())
+  }
+}
+
+/// What a re-export traced by `Graph::trace_re_export` refers to.
+#[derive(Debug, PartialEq, Eq)]
+enum TracedExport {
+  Binding(ExportedSpecifier),
+  /// `imported` of the external module, whose bindings are unknown.
+  External(ModuleId, JsWord),
+}
+
+/// We will transform
+/// ```js
+/// export { resolve } from 'path'
+/// ```
+/// to
+/// ```
+/// import { resolve } from 'path'
+/// export { resolve }
+/// ```
+fn re_export_from_external(
+  importer: &mut NormalModule,
+  external_id: &ModuleId,
+  spec: ReExportedSpecifier,
+) {
+  let symbol_in_importer =
+    importer.create_top_level_symbol(if spec.exported_as != js_word!("default") {
+      &spec.exported_as
+    } else {
+      &spec.imported
+    });
+
+  importer.add_to_linked_imports(
+    external_id,
+    ImportedSpecifier {
+      imported_as: symbol_in_importer.clone(),
+      imported: spec.imported,
+    },
+  );
+
+  let owner = importer.id.clone();
+  importer.add_to_linked_exports(
+    spec.exported_as.clone(),
+    ExportedSpecifier {
+      exported_as: spec.exported_as,
+      local_id: symbol_in_importer,
+      // NOTE: This is a local export to importer
+      owner,
+    },
+  )
 }
 
-fn shim_missing_export_if_needed(importee: &mut NormalModule, imported_name: &JsWord) -> bool {
+/// Shim the export missing in `importee` if `shim_missing_exports` is enabled, with a warning.
+/// Exports of node builtins are unknown, so their stubs shim anything imported without warnings.
+fn shim_missing_export_if_needed(
+  input_options: &BuildInputOptions,
+  importee: &mut NormalModule,
+  imported_name: &JsWord,
+) {
   if importee.find_exported(imported_name).is_some() {
-    false
-  } else {
+    return;
+  }
+  if stubbed_node_builtin(&importee.id).is_some() {
+    importee.shim_missing_export(imported_name);
+  } else if input_options.shim_missing_exports {
     importee.shim_missing_export(imported_name);
-    true
+    (input_options.on_warn)(BuildError::shimmed_export(
+      imported_name.to_string(),
+      importee.id.as_path().to_path_buf(),
+    ));
   }
 }
@@ -0,0 +1,107 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use rolldown_common::ModuleId;
+
+use crate::{norm_or_ext::NormOrExt, Graph};
+
+/// The shape of a module graph, which outlives the build and could be compared with the next one.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ModuleGraphSnapshot {
+  modules: BTreeMap<ModuleId, ModuleSnapshot>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ModuleSnapshot {
+  /// `None` for external modules, whose source is unknown.
+  source_hash: Option<u64>,
+  /// Both static and dynamic dependencies.
+  dependencies: BTreeSet<ModuleId>,
+}
+
+/// Changes from a previous module graph to the next one. Edges are `(importer, importee)`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct GraphDiff {
+  pub added_modules: Vec<ModuleId>,
+  pub removed_modules: Vec<ModuleId>,
+  /// Modules existing in both graphs, but with different sources.
+  pub changed_modules: Vec<ModuleId>,
+  pub added_edges: Vec<(ModuleId, ModuleId)>,
+  pub removed_edges: Vec<(ModuleId, ModuleId)>,
+}
+
+impl GraphDiff {
+  pub fn is_empty(&self) -> bool {
+    self.added_modules.is_empty()
+      && self.removed_modules.is_empty()
+      && self.changed_modules.is_empty()
+      && self.added_edges.is_empty()
+      && self.removed_edges.is_empty()
+  }
+}
+
+impl ModuleGraphSnapshot {
+  pub(crate) fn new(graph: &Graph) -> Self {
+    let modules = graph
+      .module_by_id
+      .values()
+      .map(|module| {
+        let snapshot = ModuleSnapshot {
+          source_hash: match module {
+            NormOrExt::Normal(module) => Some(module.source_hash),
+            NormOrExt::External(_) => None,
+          },
+          dependencies: module
+            .dependencies()
+            .iter()
+            .chain(module.dynamic_dependencies())
+            .cloned()
+            .collect(),
+        };
+        (module.id().clone(), snapshot)
+      })
+      .collect();
+    Self { modules }
+  }
+
+  /// Compare with the `next` snapshot. Results are sorted, so they are stable across runs.
+  pub fn diff(&self, next: &Self) -> GraphDiff {
+    let mut diff = GraphDiff::default();
+
+    for (id, prev_module) in &self.modules {
+      match next.modules.get(id) {
+        Some(next_module) => {
+          if prev_module.source_hash != next_module.source_hash {
+            diff.changed_modules.push(id.clone());
+          }
+        }
+        None => diff.removed_modules.push(id.clone()),
+      }
+    }
+    diff.added_modules = next
+      .modules
+      .keys()
+      .filter(|id| !self.modules.contains_key(id))
+      .cloned()
+      .collect();
+
+    let prev_edges = self.edges();
+    let next_edges = next.edges();
+    diff.removed_edges = prev_edges.difference(&next_edges).cloned().collect();
+    diff.added_edges = next_edges.difference(&prev_edges).cloned().collect();
+
+    diff
+  }
+
+  fn edges(&self) -> BTreeSet<(ModuleId, ModuleId)> {
+    self
+      .modules
+      .iter()
+      .flat_map(|(importer, module)| {
+        module
+          .dependencies
+          .iter()
+          .map(|importee| (importer.clone(), importee.clone()))
+      })
+      .collect()
+  }
+}
@@ -8,6 +8,8 @@ mod bundler;
 pub use bundler::*;
 mod chunk;
 pub use chunk::*;
+mod chunk_mappings;
+pub(crate) use chunk_mappings::*;
 mod normal_module;
 pub use normal_module::*;
 mod external_module;
@@ -19,6 +21,10 @@ pub use graph::*;
 mod module_loader;
 use rolldown_common::{ChunkId, ExportedSpecifier, ModuleId};
 use rolldown_resolver::Resolver;
+pub use rolldown_resolver::{
+  Platform, DEFAULT_CONDITION_NAMES, DEFAULT_EXTENSIONS as DEFAULT_RESOLVE_EXTENSIONS,
+};
+pub use rolldown_common::Loader;
 use rustc_hash::FxHashMap;
 use swc_core::common::{FilePathMapping, Globals, SourceMap};
 mod bundle;
@@ -29,11 +35,27 @@ mod code_splitter;
 pub use code_splitter::*;
 mod chunk_graph;
 pub(crate) use chunk_graph::*;
+mod manual_chunks;
+pub(crate) use manual_chunks::*;
 mod plugin_driver;
 pub(crate) use plugin_driver::*;
 mod utils;
 pub use utils::*;
+mod metrics;
+pub use metrics::*;
+mod graph_diff;
+pub use graph_diff::*;
+mod resolution_report;
+pub use resolution_report::*;
+mod used_symbols;
+pub use used_symbols::*;
+mod module_info;
+pub use module_info::*;
+mod parse_cache;
+pub(crate) use parse_cache::*;
 mod rolldown_output;
+mod transform_cache;
+pub(crate) use transform_cache::*;
 mod treeshake;
 pub use rolldown_output::*;
 
@@ -64,6 +86,7 @@ pub(crate) type UnaryBuildResult<T> = rolldown_error::Result<T>;
 pub type BuildResult<T> = rolldown_error::ResultWithErrors<T>;
 pub type BuildError = rolldown_error::Error;
 pub type WarningHandler = Arc<dyn Fn(BuildError) + Send + Sync>;
+pub type AssetHandler = Arc<dyn Fn(&Asset) -> UnaryBuildResult<()> + Send + Sync>;
 
 // re-exported crates
 
@@ -0,0 +1,197 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use glob::{MatchOptions, Pattern};
+use rolldown_common::{ChunkId, ModuleId};
+use rustc_hash::{FxHashMap, FxHashSet};
+use sugar_path::{AsPath, SugarPath};
+use swc_core::ecma::atoms::{js_word, JsWord};
+
+use crate::{BuildError, Chunk, Graph, UnaryBuildResult};
+
+/// `*` doesn't match `/`, while `**` matches any number of directories.
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+  case_sensitive: true,
+  require_literal_separator: true,
+  require_literal_leading_dot: false,
+};
+
+/// Patterns of `manual_chunks`, sorted by names of the chunks.
+pub(crate) struct ManualChunkPatterns {
+  chunks: Vec<(String, Vec<(String, Pattern)>)>,
+}
+
+impl ManualChunkPatterns {
+  pub(crate) fn new(manual_chunks: &BTreeMap<String, Vec<String>>) -> UnaryBuildResult<Self> {
+    let chunks = manual_chunks
+      .iter()
+      .map(|(name, patterns)| {
+        let patterns = patterns
+          .iter()
+          .map(|pattern| {
+            Pattern::new(pattern)
+              .map(|compiled| (pattern.clone(), compiled))
+              .map_err(|err| BuildError::invalid_manual_chunk_pattern(name, pattern, err))
+          })
+          .collect::<UnaryBuildResult<_>>()?;
+        Ok((name.clone(), patterns))
+      })
+      .collect::<UnaryBuildResult<_>>()?;
+    Ok(Self { chunks })
+  }
+
+  /// Names of the chunks matching the module, either by its id or by the path relative to `cwd`.
+  pub(crate) fn matched_chunks<'a>(
+    &'a self,
+    id: &'a ModuleId,
+    cwd: &'a Path,
+  ) -> impl Iterator<Item = &'a str> {
+    let relative = id.as_path().relative(cwd);
+    self
+      .chunks
+      .iter()
+      .filter(move |(_, patterns)| {
+        patterns.iter().any(|(pattern, compiled)| {
+          pattern == id.as_ref() || compiled.matches_path_with(&relative, MATCH_OPTIONS)
+        })
+      })
+      .map(|(name, _)| name.as_str())
+  }
+}
+
+/// Modules placed by `manual_chunks`, and names their bindings are exported as by their chunks.
+/// Other chunks are only imported through their entries, so they export what their entries export.
+/// A manual chunk is imported through any of its modules, so it exports each binding imported by
+/// other chunks under a name unique in the chunk instead.
+#[derive(Debug, Default)]
+pub(crate) struct ManualChunks {
+  chunk_by_module: FxHashMap<ModuleId, ChunkId>,
+  /// Keyed by owners of bindings and the names they're exported as by the owners.
+  exported_names: FxHashMap<(ModuleId, JsWord), JsWord>,
+  /// Chunks of modules preserved by `preserve_modules`, and exports of the modules kept by
+  /// treeshaking. The files are imported by consumers of the output, so the chunks export them as
+  /// the modules do, like entries.
+  preserved_exports: FxHashMap<ChunkId, FxHashSet<JsWord>>,
+}
+
+impl ManualChunks {
+  pub(crate) fn new(
+    chunk_by_module: FxHashMap<ModuleId, ChunkId>,
+    chunks: &[Chunk],
+    graph: &Graph,
+    preserve_modules: bool,
+  ) -> Self {
+    let mut manual_chunks = Self {
+      chunk_by_module,
+      exported_names: Default::default(),
+      preserved_exports: Default::default(),
+    };
+    if manual_chunks.chunk_by_module.is_empty() {
+      return manual_chunks;
+    }
+
+    // Bindings are visited in sorted order, so names don't rely on the order of chunks.
+    let mut imported = BTreeSet::new();
+    let mut preserved_exports = FxHashMap::default();
+    for chunk in chunks {
+      let is_out_of_chunk =
+        |owner: &ModuleId| matches!(manual_chunks.chunk_of(owner), Some(id) if id != &chunk.id);
+      let modules = chunk
+        .modules
+        .iter()
+        .filter_map(|id| graph.module_by_id.get(id))
+        .filter_map(|module| module.as_norm());
+      for module in modules {
+        module
+          .linked_imports
+          .iter()
+          .filter(|(owner, _)| is_out_of_chunk(owner))
+          .for_each(|(owner, specs)| {
+            imported.extend(
+              specs
+                .iter()
+                .map(|spec| (owner.clone(), spec.imported.clone())),
+            );
+          });
+        // Exports of the entry re-exported from manual chunks.
+        if module.id == chunk.entry && !chunk.is_manual {
+          imported.extend(
+            module
+              .linked_exports
+              .values()
+              .filter(|spec| is_out_of_chunk(&spec.owner))
+              .map(|spec| (spec.owner.clone(), spec.exported_as.clone())),
+          );
+        }
+        if module.id == chunk.entry && chunk.is_manual && preserve_modules {
+          let exports = graph.kept_exports(module).collect::<Vec<_>>();
+          imported.extend(
+            exports
+              .iter()
+              .filter(|(_, spec)| is_out_of_chunk(&spec.owner))
+              .map(|(_, spec)| (spec.owner.clone(), spec.exported_as.clone())),
+          );
+          preserved_exports.insert(
+            chunk.id.clone(),
+            exports.into_iter().map(|(name, _)| name.clone()).collect(),
+          );
+        }
+      }
+    }
+
+    manual_chunks.preserved_exports = preserved_exports;
+
+    let mut used_names: FxHashMap<&ChunkId, FxHashSet<JsWord>> = FxHashMap::default();
+    for (owner, name) in imported {
+      let base_name = if name == js_word!("*") {
+        graph.module_by_id[&owner]
+          .as_norm()
+          .and_then(|module| module.find_exported(&name))
+          .map_or_else(|| name.clone(), |spec| spec.local_id.name().clone())
+      } else {
+        name.clone()
+      };
+      let used_names = used_names
+        .entry(&manual_chunks.chunk_by_module[&owner])
+        .or_default();
+      let mut exported_name = base_name.clone();
+      let mut index = 1;
+      while used_names.contains(&exported_name) {
+        exported_name = format!("{base_name}${index}").into();
+        index += 1;
+      }
+      used_names.insert(exported_name.clone());
+      manual_chunks
+        .exported_names
+        .insert((owner, name), exported_name);
+    }
+    manual_chunks
+  }
+
+  pub(crate) fn chunk_of(&self, module: &ModuleId) -> Option<&ChunkId> {
+    self.chunk_by_module.get(module)
+  }
+
+  /// The name a binding of `owner` is exported as by its manual chunk, given the name it's exported
+  /// as by `owner`.
+  pub(crate) fn exported_name(&self, owner: &ModuleId, name: &JsWord) -> Option<&JsWord> {
+    self.exported_names.get(&(owner.clone(), name.clone()))
+  }
+
+  /// Exports of the module preserved as the chunk, if it's created by `preserve_modules`.
+  pub(crate) fn preserved_exports_of(&self, chunk: &ChunkId) -> Option<&FxHashSet<JsWord>> {
+    self.preserved_exports.get(chunk)
+  }
+
+  /// Bindings exported by the chunk, as `(owner, name in the owner, name in the chunk)`.
+  pub(crate) fn exports_of<'a>(
+    &'a self,
+    chunk: &'a ChunkId,
+  ) -> impl Iterator<Item = (&'a ModuleId, &'a JsWord, &'a JsWord)> {
+    self
+      .exported_names
+      .iter()
+      .filter(move |((owner, _), _)| self.chunk_of(owner) == Some(chunk))
+      .map(|((owner, name), exported_name)| (owner, name, exported_name))
+  }
+}
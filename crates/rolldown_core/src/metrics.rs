@@ -0,0 +1,8 @@
+/// Numbers about the scale of a build, which help to find out where memory goes on huge graphs.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BuildMetrics {
+  /// Top level symbols of all modules, including the ones created by `create_top_level_symbol`.
+  pub symbols: usize,
+  /// Symbols tracked by the union-find, which are the ones linked across modules.
+  pub union_find_size: usize,
+}
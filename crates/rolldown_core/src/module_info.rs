@@ -0,0 +1,36 @@
+use rolldown_common::ModuleId;
+
+use crate::Graph;
+
+/// What's known about a module of a build, like `this.getModuleInfo` of Rollup. It outlives the
+/// build.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModuleInfo {
+  pub id: ModuleId,
+  pub is_entry: bool,
+  /// Directives like `"use client"` in the prologue of the module, which mark it as a boundary
+  /// between the client and the server.
+  pub directives: Vec<String>,
+}
+
+impl ModuleInfo {
+  /// Infos of normal modules of the graph, sorted by ids.
+  pub(crate) fn collect(graph: &Graph) -> Vec<Self> {
+    let mut infos = graph
+      .module_by_id
+      .values()
+      .filter_map(|module| module.as_norm())
+      .map(|module| Self {
+        id: module.id.clone(),
+        is_entry: module.is_user_defined_entry,
+        directives: module
+          .directives
+          .iter()
+          .map(|directive| directive.to_string())
+          .collect(),
+      })
+      .collect::<Vec<_>>();
+    infos.sort_by(|a, b| a.id.as_ref().cmp(b.id.as_ref()));
+    infos
+  }
+}
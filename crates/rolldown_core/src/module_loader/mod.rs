@@ -1,21 +1,28 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, sync::Arc};
 
 use futures::future::join_all;
-use rolldown_common::{ExportedSpecifier, ModuleId};
+use rolldown_common::{ExportedSpecifier, Loader, ModuleId};
 use rolldown_error::Errors;
+use rolldown_plugin::ImportKind;
+use rolldown_runtime_helpers::RuntimeHelpers;
 use rustc_hash::{FxHashMap, FxHashSet};
 use swc_core::common::{Mark, SyntaxContext, GLOBALS};
+use tokio::sync::Semaphore;
 
 pub(crate) mod module_task;
+mod package_side_effects;
+mod tsconfig_discovery;
 
 use module_task::{ModuleTask, TaskResult};
-use swc_core::ecma::atoms::js_word;
+use package_side_effects::PackageSideEffects;
+use swc_core::ecma::atoms::{js_word, JsWord};
 use tracing::instrument;
+use tsconfig_discovery::TsConfigDiscovery;
 
 use crate::{norm_or_ext::NormOrExt, BuildInputOptions, Graph, NormalModule, SWC_GLOBALS};
 use crate::{
-  resolve_id, BuildError, BuildResult, ExternalModule, SharedBuildInputOptions,
-  SharedBuildPluginDriver, SharedResolver, StatementParts,
+  resolve_id, spawn, BuildError, BuildResult, Definitions, ExternalModule, Replacer,
+  SharedBuildInputOptions, SharedBuildPluginDriver, SharedResolver, StatementParts,
 };
 
 pub(crate) struct ModuleLoader<'a> {
@@ -29,6 +36,13 @@ pub(crate) struct ModuleLoader<'a> {
   resolver: SharedResolver,
   errors: Vec<BuildError>,
   dynamic_imported_modules: FxHashSet<ModuleId>,
+  /// Importers of each module and the types they assert, checked once all modules are loaded.
+  asserted_types: FxHashMap<ModuleId, Vec<(ModuleId, Option<JsWord>)>>,
+  transform_semaphore: Option<Arc<Semaphore>>,
+  replacer: Option<Arc<Replacer>>,
+  definitions: Option<Arc<Definitions>>,
+  package_side_effects: PackageSideEffects,
+  tsconfig_discovery: Option<Arc<TsConfigDiscovery>>,
 }
 
 #[derive(Debug)]
@@ -43,8 +57,17 @@ impl<'a> ModuleLoader<'a> {
     resolver: SharedResolver,
     plugin_driver: SharedBuildPluginDriver,
     input_options: SharedBuildInputOptions,
+    replacer: Option<Arc<Replacer>>,
+    definitions: Option<Arc<Definitions>>,
   ) -> Self {
     let (tx, rx) = tokio::sync::mpsc::unbounded_channel::<Msg>();
+    let transform_semaphore = input_options
+      .max_parallel_transforms
+      .map(|limit| Arc::new(Semaphore::new(limit.max(1))));
+    let tsconfig_discovery = input_options
+      .builtins
+      .discover_tsconfig
+      .then(|| Arc::new(TsConfigDiscovery::default()));
     Self {
       graph,
       loaded_modules: Default::default(),
@@ -55,7 +78,13 @@ impl<'a> ModuleLoader<'a> {
       errors: Default::default(),
       build_plugin_driver: plugin_driver,
       dynamic_imported_modules: Default::default(),
+      asserted_types: Default::default(),
       input_options,
+      transform_semaphore,
+      replacer,
+      definitions,
+      package_side_effects: Default::default(),
+      tsconfig_discovery,
     }
   }
 
@@ -64,22 +93,31 @@ impl<'a> ModuleLoader<'a> {
     let futs = input_opts.input.iter().cloned().map(|input_item| {
       let build_plugin_driver = self.build_plugin_driver.clone();
       let resolver = self.resolver.clone();
-      tokio::spawn(async move {
-        let resolve_id = resolve_id(
+      let resolution_report = self.graph.resolution_report.clone();
+      spawn(async move {
+        let resolved = resolve_id(
           &resolver,
           &input_item.import,
           None,
+          ImportKind::Entry,
           false,
           &build_plugin_driver,
         )
-        .await?;
+        .await
+        .and_then(|resolved| {
+          resolved.ok_or_else(|| BuildError::unresolved_entry(&input_item.import).into())
+        });
+
+        if let Some(resolution_report) = &resolution_report {
+          resolution_report.record(&input_item.import, None, &resolved);
+        }
 
-        let Some(resolve_id) = resolve_id else {
-            return Err(BuildError::unresolved_entry(input_item.import))
-          };
+        let (resolve_id, _) = resolved?;
 
         if resolve_id.is_external() {
-          return Err(BuildError::entry_cannot_be_external(resolve_id.as_ref()));
+          return Err(Errors::from(BuildError::entry_cannot_be_external(
+            resolve_id.as_ref(),
+          )));
         }
 
         Ok(resolve_id)
@@ -94,8 +132,8 @@ impl<'a> ModuleLoader<'a> {
       .into_iter()
       .filter_map(|handle| match handle.unwrap() {
         Ok(id) => Some(id),
-        Err(e) => {
-          errors.push(e);
+        Err(errs) => {
+          errors.extend(errs.into_vec());
           None
         }
       })
@@ -121,7 +159,7 @@ impl<'a> ModuleLoader<'a> {
     resolved_entries.into_iter().for_each(|entry_id| {
       self.loaded_modules.insert(entry_id.clone());
       self.graph.entries.push(entry_id.clone());
-      self.spawn_new_module_task(entry_id, true);
+      self.spawn_new_module_task(entry_id, true, None);
     });
 
     while self.remaining_tasks > 0 {
@@ -139,6 +177,7 @@ impl<'a> ModuleLoader<'a> {
       tracing::trace!("remaining: {}", self.remaining_tasks);
     }
 
+    self.check_asserted_types();
     self.mark_dynamic_imported_module();
 
     if self.errors.is_empty() {
@@ -160,7 +199,12 @@ impl<'a> ModuleLoader<'a> {
     });
   }
 
-  fn spawn_new_module_task(&mut self, module_id: ModuleId, is_user_defined_entry: bool) {
+  fn spawn_new_module_task(
+    &mut self,
+    module_id: ModuleId,
+    is_user_defined_entry: bool,
+    asserted_loader: Option<Loader>,
+  ) {
     tracing::trace!("spawning new job for {}", module_id);
     self.remaining_tasks += 1;
     let (top_level_mark, top_level_ctxt) = GLOBALS.set(&SWC_GLOBALS, || {
@@ -175,12 +219,47 @@ impl<'a> ModuleLoader<'a> {
       top_level_ctxt,
       unresolved_ctxt: self.graph.unresolved_ctxt,
       is_user_defined_entry,
+      asserted_loader,
       resolver: self.resolver.clone(),
       plugin_driver: self.build_plugin_driver.clone(),
+      transform_semaphore: self.transform_semaphore.clone(),
+      replacer: self.replacer.clone(),
+      definitions: self.definitions.clone(),
       is_external: self.input_options.is_external.clone(),
       input_options: self.input_options.clone(),
+      resolution_report: self.graph.resolution_report.clone(),
+      transform_cache: self.graph.transform_cache.clone(),
+      parse_cache: self.graph.parse_cache.clone(),
+      tsconfig_discovery: self.tsconfig_discovery.clone(),
     };
-    tokio::spawn(task.run());
+    spawn(task.run());
+  }
+
+  /// A module is only loaded once, so all importers of it must assert the same type. Importers
+  /// are compared in the order of their ids, so the reported ones don't depend on which module
+  /// happens to be scanned first.
+  fn check_asserted_types(&mut self) {
+    let mut asserted_types = std::mem::take(&mut self.asserted_types)
+      .into_iter()
+      .collect::<Vec<_>>();
+    asserted_types.sort_by(|(a, _), (b, _)| a.cmp(b));
+    for (id, mut importers) in asserted_types {
+      importers.sort_by(|(a, _), (b, _)| a.cmp(b));
+      let (first_importer, first_type) = &importers[0];
+      if let Some((importer, asserted_type)) = importers
+        .iter()
+        .find(|(_, asserted_type)| asserted_type != first_type)
+      {
+        self.errors.push(BuildError::inconsistent_import_assertions(
+          id.as_ref(),
+          (first_importer.as_ref().into(), importer.as_ref().into()),
+          (
+            first_type.as_ref().map(ToString::to_string),
+            asserted_type.as_ref().map(ToString::to_string),
+          ),
+        ));
+      }
+    }
   }
 
   #[instrument(skip_all)]
@@ -189,7 +268,13 @@ impl<'a> ModuleLoader<'a> {
     let scan_result = result.scan_result;
     let resolved_ids = result.resolved_ids;
 
-    resolved_ids.values().for_each(|id| {
+    resolved_ids.iter().for_each(|(specifier, id)| {
+      if !id.is_external() {
+        self.asserted_types.entry(id.clone()).or_default().push((
+          module_id.clone(),
+          scan_result.asserted_types.get(specifier).cloned(),
+        ));
+      }
       if self.loaded_modules.contains(id) {
         return;
       }
@@ -207,7 +292,11 @@ impl<'a> ModuleLoader<'a> {
         };
         self.graph.add_module(NormOrExt::External(external_module));
       } else {
-        self.spawn_new_module_task(id.clone(), false);
+        let asserted_loader = scan_result
+          .asserted_types
+          .get(specifier)
+          .and_then(|asserted_type| loader_of_asserted_type(asserted_type));
+        self.spawn_new_module_task(id.clone(), false, asserted_loader);
       }
     });
 
@@ -251,6 +340,11 @@ impl<'a> ModuleLoader<'a> {
       .collect::<FxHashMap<_, _>>();
 
     let top_level_ctxt = result.top_level_ctxt;
+    let side_effects = self.package_side_effects.has_side_effects(&module_id);
+    let runtime_helpers = RuntimeHelpers::new();
+    if result.is_registered {
+      runtime_helpers.module_registry();
+    }
 
     let normal_module = NormalModule {
       dependencies,
@@ -270,6 +364,7 @@ impl<'a> ModuleLoader<'a> {
       visited_global_names: scan_result.visited_global_names,
       external_modules_of_re_export_all: Default::default(),
       is_dynamic_entry: false,
+      has_top_level_await: scan_result.has_top_level_await,
       comments: result.comments,
       imports,
       linked_imports: Default::default(),
@@ -280,10 +375,26 @@ impl<'a> ModuleLoader<'a> {
       resolved_module_ids: resolved_ids,
       declared_scoped_names: scan_result.declared_scoped_names,
       id: module_id,
-      runtime_helpers: Default::default(),
+      runtime_helpers,
       parts: StatementParts::from_parts(scan_result.statement_parts),
       missing_exports: Default::default(),
+      source_hash: result.source_hash,
+      css: result.css,
+      wasm: result.wasm,
+      addon: result.addon,
+      maps_to_source: result.maps_to_source,
+      is_commonjs: result.is_commonjs,
+      side_effects,
+      directives: result.directives,
+      shebang: result.shebang,
     };
     self.graph.add_module(NormOrExt::Normal(normal_module));
   }
 }
+
+fn loader_of_asserted_type(asserted_type: &str) -> Option<Loader> {
+  match asserted_type {
+    "css" => Some(Loader::CssStyleSheet),
+    _ => None,
+  }
+}
@@ -1,15 +1,19 @@
-use std::path::PathBuf;
+use std::hash::{Hash, Hasher};
+use std::time::SystemTime;
+use std::{path::PathBuf, sync::Arc};
 
+use base64::{engine::general_purpose::STANDARD, Engine};
 use derivative::Derivative;
 use futures::future::join_all;
 use rolldown_common::{Loader, ModuleId};
 use rolldown_error::Errors;
-use rolldown_resolver::Resolver;
+use rolldown_plugin::ImportKind;
+use rolldown_resolver::{Platform, Resolver};
 use rolldown_swc_visitors::{clean_ast, ScanResult};
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHasher};
 use sugar_path::AsPath;
 use swc_core::common::pass::Optional;
-use swc_core::common::{chain, Mark, SyntaxContext, GLOBALS};
+use swc_core::common::{chain, Mark, Span, SyntaxContext, GLOBALS};
 use swc_core::ecma::ast;
 use swc_core::ecma::atoms::JsWord;
 use swc_core::ecma::parser::{EsConfig, Syntax, TsConfig};
@@ -17,24 +21,35 @@ use swc_core::ecma::transforms::base::fixer::fixer;
 use swc_core::ecma::transforms::base::helpers::{inject_helpers, HELPERS};
 use swc_core::ecma::transforms::base::hygiene::hygiene;
 use swc_core::ecma::transforms::base::resolver;
-use swc_core::ecma::transforms::proposal::decorators;
+use swc_core::ecma::transforms::proposal::{decorator_2022_03, decorators};
 use swc_core::ecma::transforms::react;
 use swc_core::ecma::transforms::typescript;
 use swc_core::ecma::visit::FoldWith;
 use swc_node_comments::SwcComments;
+use tokio::sync::{Semaphore, SemaphorePermit};
 use tracing::instrument;
 
+use super::tsconfig_discovery::TsConfigDiscovery;
 use super::Msg;
 use crate::{
-  extract_loader_by_path, resolve_id, BuildError, BuildResult, IsExternal, ResolvedModuleIds,
-  SharedBuildInputOptions, SharedBuildPluginDriver, SharedResolver, UnaryBuildResult, COMPILER,
-  SWC_GLOBALS,
+  emitted_file_name, extract_loader_by_path, guess_mime_type, is_legal, node_builtin_name,
+  node_builtin_stub_id, resolve_id, spawn, strip_conditional_blocks, stubbed_node_builtin,
+  BuildError, BuildInputOptions, BuildResult, CachedParse, CachedTransform, DecoratorVersion,
+  Definitions, IsExternal, Jsx, JsxOptions, JsxRuntime, NativeAddon, ParsedModule, Replacer,
+  ResolutionReport, ResolutionSource, ResolvedModuleIds, SharedBuildInputOptions,
+  SharedBuildPluginDriver, SharedParseCache, SharedResolver, SharedTransformCache,
+  UnaryBuildResult, WasmAsset, COMPILER, SWC_GLOBALS,
 };
 
+const NATIVE_ADDON_EXT: &str = ".node";
+const WASM_EXT: &str = ".wasm";
+
 pub(crate) struct ModuleTask {
   pub(crate) input_options: SharedBuildInputOptions,
   pub(crate) id: ModuleId,
   pub(crate) is_user_defined_entry: bool,
+  /// Loader decided by the import assertion of the importer, like `assert { type: 'css' }`
+  pub(crate) asserted_loader: Option<Loader>,
   pub(crate) tx: tokio::sync::mpsc::UnboundedSender<Msg>,
   pub(crate) top_level_mark: Mark,
   pub(crate) top_level_ctxt: SyntaxContext,
@@ -42,41 +57,97 @@ pub(crate) struct ModuleTask {
   pub(crate) unresolved_ctxt: SyntaxContext,
   pub(crate) resolver: SharedResolver,
   pub(crate) plugin_driver: SharedBuildPluginDriver,
+  /// Shared by all tasks to respect `max_parallel_transforms`
+  pub(crate) transform_semaphore: Option<Arc<Semaphore>>,
+  pub(crate) replacer: Option<Arc<Replacer>>,
+  pub(crate) definitions: Option<Arc<Definitions>>,
   pub(crate) is_external: IsExternal,
+  pub(crate) resolution_report: Option<Arc<ResolutionReport>>,
+  pub(crate) transform_cache: Option<SharedTransformCache>,
+  pub(crate) parse_cache: Option<SharedParseCache>,
+  /// Shared by all tasks if `builtins.discover_tsconfig` is enabled
+  pub(crate) tsconfig_discovery: Option<Arc<TsConfigDiscovery>>,
 }
 
 impl ModuleTask {
   // I(hyf0) have no interest in implementing original resolve logic of rollup currently.
   // It's complicated and I doubt the usage of it.
-  pub(crate) async fn resolve_id(
+  async fn resolve_id(
     resolver: &Resolver,
     importer: &ModuleId,
     specifier: &str,
+    kind: ImportKind,
     plugin_driver: &SharedBuildPluginDriver,
     is_external: &IsExternal,
-  ) -> UnaryBuildResult<ModuleId> {
+    input_options: &BuildInputOptions,
+  ) -> BuildResult<(ModuleId, ResolutionSource)> {
     let is_marked_as_external = is_external(specifier, Some(importer.id()), false).await?;
 
     if is_marked_as_external {
-      return Ok(ModuleId::new(specifier, true));
+      return Ok((
+        ModuleId::new(specifier, true),
+        ResolutionSource::ExternalOption,
+      ));
     }
 
-    let resolved_id = resolve_id(resolver, specifier, Some(importer), false, plugin_driver).await?;
+    let resolved_id = resolve_id(
+      resolver,
+      specifier,
+      Some(importer),
+      kind,
+      false,
+      plugin_driver,
+    )
+    .await?;
 
-    if let Some(resolved) = resolved_id {
+    if let Some((resolved, source)) = resolved_id {
+      // Externals returned by `resolve` hooks keep how they are referred to.
+      if resolved.is_external() {
+        return Ok((resolved, source));
+      }
       let is_resolved_marked_as_external =
         is_external(resolved.id(), Some(importer.id()), true).await?;
 
-      Ok(ModuleId::new(
-        resolved.id().clone(),
-        is_resolved_marked_as_external,
+      Ok((
+        ModuleId::new(resolved.id().clone(), is_resolved_marked_as_external),
+        source,
+      ))
+    } else if let Some(name) = node_builtin_name(specifier) {
+      // Plugins and `is_external` take precedence, so builtins could be polyfilled by them.
+      Ok(Self::resolve_node_builtin(
+        resolver,
+        importer,
+        specifier,
+        name,
+        input_options.platform.unwrap_or_default(),
       ))
     } else {
       // TODO: emit warnings like https://rollupjs.org/guide/en#warning-treating-module-as-external-dependency
-      Ok(ModuleId::new(specifier, true))
+      Ok((ModuleId::new(specifier, true), ResolutionSource::Unresolved))
     }
   }
 
+  /// Node builtins, like `node:fs` or `fs`, are kept as they are for `Platform::Node`, which is
+  /// assumed if no platform is set. For `Platform::Browser`, they're resolved to polyfill packages
+  /// of the same name, like `events` for `node:events`, or stubbed by empty modules with virtual
+  /// ids, see `load_node_builtin_stub`.
+  fn resolve_node_builtin(
+    resolver: &Resolver,
+    importer: &ModuleId,
+    specifier: &str,
+    name: &str,
+    platform: Platform,
+  ) -> (ModuleId, ResolutionSource) {
+    let id = match platform {
+      Platform::Node => ModuleId::new(specifier, true),
+      Platform::Browser => match resolver.resolve(Some(importer.as_ref()), name) {
+        Ok(polyfill) => ModuleId::new(polyfill, false),
+        Err(_) => node_builtin_stub_id(name),
+      },
+    };
+    (id, ResolutionSource::NodeBuiltin)
+  }
+
   #[instrument(skip_all)]
   pub(crate) async fn run(self) {
     let tx = self.tx.clone();
@@ -90,31 +161,56 @@ impl ModuleTask {
     }
   }
 
+  /// Imports of wrapped CommonJS modules are all hoisted from `require()` calls, since modules with
+  /// import/export aren't wrapped.
   async fn resolve_dependencies(
     &self,
     result: &ScanResult,
+    is_commonjs: bool,
   ) -> BuildResult<FxHashMap<JsWord, ModuleId>> {
+    let static_kind = if is_commonjs {
+      ImportKind::Require
+    } else {
+      ImportKind::Import
+    };
+    // A module refers to a specifier by a single id, so ones imported both statically and
+    // dynamically are resolved as static imports only.
     let dependencies = result
       .dependencies
       .iter()
-      .chain(result.dyn_dependencies.iter());
+      .map(|specifier| (specifier, static_kind))
+      .chain(
+        result
+          .dyn_dependencies
+          .iter()
+          .filter(|specifier| !result.dependencies.contains(*specifier))
+          .map(|specifier| (specifier, ImportKind::DynamicImport)),
+      );
 
-    let jobs = dependencies.cloned().map(|specifier| {
+    let jobs = dependencies.map(|(specifier, kind)| {
+      let specifier = specifier.clone();
       let resolver = self.resolver.clone();
       let plugin_driver = self.plugin_driver.clone();
       let importer = self.id.clone();
       let is_external = self.is_external.clone();
+      let input_options = self.input_options.clone();
+      let resolution_report = self.resolution_report.clone();
 
-      tokio::spawn(async move {
-        Self::resolve_id(
+      spawn(async move {
+        let resolved = Self::resolve_id(
           &resolver,
           &importer,
           &specifier,
+          kind,
           &plugin_driver,
           &is_external,
+          &input_options,
         )
-        .await
-        .map(|id| (specifier.clone(), id))
+        .await;
+        if let Some(resolution_report) = &resolution_report {
+          resolution_report.record(&specifier, Some(&importer), &resolved);
+        }
+        resolved.map(|(id, _)| (specifier, id))
       })
     });
 
@@ -126,8 +222,8 @@ impl ModuleTask {
       .into_iter()
       .filter_map(|handle| match handle.unwrap() {
         Ok(id) => Some(id),
-        Err(e) => {
-          errors.push(e);
+        Err(errs) => {
+          errors.extend(errs.into_vec());
           None
         }
       })
@@ -140,50 +236,331 @@ impl ModuleTask {
     }
   }
 
-  async fn run_inner(self) -> BuildResult<TaskResult> {
-    let loaded = self.plugin_driver.read().await.load(&self.id).await?;
-    // load hook
-    let (code, loader) = if loaded.is_some() {
-      loaded.map(|l| (l.code, l.loader)).unwrap()
+  async fn acquire_transform_permit(&self) -> Option<SemaphorePermit<'_>> {
+    match &self.transform_semaphore {
+      Some(semaphore) => Some(
+        semaphore
+          .acquire()
+          .await
+          .expect("The semaphore of transforms should never be closed"),
+      ),
+      None => None,
+    }
+  }
+
+  /// Returns `None` if the module needs to be transformed, because it isn't cached, its source has
+  /// changed or a plugin asks for it.
+  async fn cached_transform(
+    &self,
+    loader: Loader,
+    source_hash: u64,
+  ) -> BuildResult<Option<CachedTransform>> {
+    let cached = self
+      .transform_cache
+      .as_ref()
+      .and_then(|cache| cache.get(&self.id, loader, source_hash));
+    let Some(cached) = cached else {
+      return Ok(None);
+    };
+    let plugin_driver = self.plugin_driver.read().await;
+    let should_transform = plugin_driver
+      .should_transform_cached_module(&self.id, &cached.code)
+      .await?;
+    if should_transform {
+      tracing::trace!("{} is transformed again as requested by plugins", self.id);
+      Ok(None)
     } else {
-      let code = tokio::fs::read_to_string(self.id.as_ref())
-        .await
-        .map_err(BuildError::io_error)
-        .map_err(|e| e.context(format!("Read file: {}", self.id.as_ref())))?;
+      plugin_driver.add_bundle_intros(cached.bundle_intros.iter().cloned());
+      Ok(Some(cached))
+    }
+  }
 
-      (code, None)
+  async fn transform(
+    &self,
+    code: String,
+    loaded_as: Loader,
+    source_hash: u64,
+  ) -> BuildResult<(String, Loader, Vec<String>)> {
+    let mut loader = loaded_as;
+
+    let (code, bundle_intros) = {
+      let _permit = self.acquire_transform_permit().await;
+      self
+        .plugin_driver
+        .read()
+        .await
+        .transform(&self.id, code, &mut loader)
+        .await?
     };
 
-    let mut loader = loader.unwrap_or_else(|| {
-      if self.input_options.builtins.detect_loader_by_ext {
-        extract_loader_by_path(self.id.as_path())
+    if let Some(cache) = &self.transform_cache {
+      cache.insert(
+        self.id.clone(),
+        loaded_as,
+        CachedTransform {
+          source_hash,
+          code: code.clone(),
+          loader,
+          bundle_intros: bundle_intros.clone(),
+        },
+      );
+    }
+    Ok((code, loader, bundle_intros))
+  }
+
+  /// The loader of the module if neither the `load` hook nor plugins specify one.
+  fn default_loader(&self) -> Loader {
+    self.asserted_loader.unwrap_or_else(|| {
+      if self.input_options.wasm_esm_integration && self.id.as_ref().ends_with(WASM_EXT) {
+        Loader::Wasm
+      } else if self.id.as_ref().ends_with(NATIVE_ADDON_EXT) {
+        // Native addons can't be bundled, see `Loader::NativeAddon`.
+        Loader::NativeAddon
       } else {
-        Loader::Js
+        extract_loader_by_path(
+          self.id.as_path(),
+          &self.input_options.builtins.loaders,
+          self.input_options.builtins.detect_loader_by_ext,
+        )
       }
-    });
+    })
+  }
+
+  /// The nearest `tsconfig.json` takes precedence over `builtins.tsconfig` if discovered.
+  async fn use_define_for_class_fields(&self, loader: Loader) -> UnaryBuildResult<bool> {
+    let discovered = match (&self.tsconfig_discovery, loader) {
+      (Some(discovery), Loader::Ts | Loader::Tsx) => discovery
+        .nearest(&self.id)
+        .await?
+        .and_then(|config| config.use_define_for_class_fields),
+      _ => None,
+    };
+    let tsconfig = &self.input_options.builtins.tsconfig;
+    Ok(discovered.unwrap_or(tsconfig.use_define_for_class_fields))
+  }
 
-    let code = self
+  /// Returns `None` if the module needs to be read and parsed again, because it isn't cached, its
+  /// file has been modified or a plugin asks for transforming it again.
+  async fn cached_parse(&self, mtime: SystemTime) -> BuildResult<Option<ParsedModule>> {
+    let cached = self
+      .parse_cache
+      .as_ref()
+      .and_then(|cache| cache.get(&self.id, mtime));
+    let Some(cached) = cached else {
+      return Ok(None);
+    };
+    let use_define_for_class_fields = self
+      .use_define_for_class_fields(cached.parsed.loader)
+      .await?;
+    if cached.use_define_for_class_fields != use_define_for_class_fields {
+      return Ok(None);
+    }
+    let plugin_driver = self.plugin_driver.read().await;
+    let should_transform = plugin_driver
+      .should_transform_cached_module(&self.id, &cached.code)
+      .await?;
+    if should_transform {
+      tracing::trace!("{} is parsed again as requested by plugins", self.id);
+      Ok(None)
+    } else {
+      plugin_driver.add_bundle_intros(cached.bundle_intros);
+      Ok(Some(cached.parsed))
+    }
+  }
+
+  /// Stubs of node builtins for `Platform::Browser` are empty modules. Named imports of them are
+  /// shimmed as `undefined` when linking, since exports of the builtin are unknown.
+  fn load_node_builtin_stub(&self) -> Option<(String, Option<Loader>)> {
+    let builtin = stubbed_node_builtin(&self.id)?;
+    (self.input_options.on_warn)(BuildError::node_builtin_stubbed(builtin.to_string()));
+    Some(("export default {};".to_string(), Some(Loader::Js)))
+  }
+
+  /// `mtime` is `Some` if the module is read from the disk and the parse cache is enabled.
+  async fn load_and_parse(
+    &self,
+    loaded: Option<(String, Option<Loader>)>,
+    mtime: Option<SystemTime>,
+  ) -> BuildResult<ParsedModule> {
+    let (code, loader) = match loaded {
+      Some(loaded) => loaded,
+      None if self.default_loader().is_binary() => {
+        let bytes = tokio::fs::read(self.id.as_ref())
+          .await
+          .map_err(BuildError::io_error)
+          .map_err(|e| e.context(format!("Read file: {}", self.id.as_ref())))?;
+        (STANDARD.encode(bytes), None)
+      }
+      None => {
+        let code = tokio::fs::read_to_string(self.id.as_ref())
+          .await
+          .map_err(BuildError::io_error)
+          .map_err(|e| e.context(format!("Read file: {}", self.id.as_ref())))?;
+        (code, None)
+      }
+    };
+    let source_hash = hash_source(&code);
+    let loader = loader.unwrap_or_else(|| self.default_loader());
+    let loaded_as = loader;
+
+    let (code, loader, bundle_intros) = match self.cached_transform(loader, source_hash).await? {
+      Some(cached) => (cached.code, cached.loader, cached.bundle_intros),
+      None => self.transform(code, loader, source_hash).await?,
+    };
+    let transformed = mtime.map(|mtime| (mtime, code.clone(), bundle_intros));
+    // `transform` hooks could switch to a binary loader, which expects base64 encoded sources.
+    let code = if loader.is_binary() && !loaded_as.is_binary() {
+      STANDARD.encode(code)
+    } else {
+      code
+    };
+
+    let code = if self.input_options.builtins.conditional_compilation
+      && syntax_of_loader(loader, &self.input_options).is_some()
+    {
+      let (stripped, tested_names) =
+        strip_conditional_blocks(&self.id, &code, &self.input_options.define)?;
+      if let Some(definitions) = &self.definitions {
+        definitions.mark_used(tested_names);
+      }
+      stripped
+    } else {
+      code
+    };
+
+    let replacer = self
+      .replacer
+      .as_deref()
+      .filter(|_| syntax_of_loader(loader, &self.input_options).is_some());
+    let (code, keys_to_replace) = match replacer {
+      Some(replacer) => replacer.replace(code)?,
+      None => (code, Default::default()),
+    };
+
+    // Source maps point at the files read, which the AST only matches if the code is unchanged.
+    let maps_to_source =
+      syntax_of_loader(loader, &self.input_options).is_some() && hash_source(&code) == source_hash;
+    let css = matches!(loader, Loader::Css).then(|| code.clone());
+    let wasm = matches!(loader, Loader::Wasm)
+      .then(|| WasmAsset::new(&self.id, &code, &self.input_options.cwd))
+      .transpose()?;
+    let addon = matches!(loader, Loader::NativeAddon)
+      .then(|| NativeAddon::new(&self.id, &code, &self.input_options.cwd))
+      .transpose()?;
+
+    let use_define_for_class_fields = self.use_define_for_class_fields(loader).await?;
+    let (mut ast, comments) = parse_to_js_ast(
+      &self.id,
+      code,
+      loader,
+      use_define_for_class_fields,
+      &self.input_options,
+    )?;
+    if let Some(replacer) = replacer {
+      replacer.replace_ast(&mut ast, keys_to_replace);
+    }
+    let parsed = ParsedModule {
+      source_hash,
+      loader,
+      css,
+      wasm,
+      addon,
+      maps_to_source,
+      ast,
+      comments,
+    };
+
+    if let (Some(cache), Some((mtime, code, bundle_intros))) = (&self.parse_cache, transformed) {
+      cache.insert(
+        self.id.clone(),
+        CachedParse {
+          mtime,
+          code,
+          bundle_intros,
+          use_define_for_class_fields,
+          parsed: parsed.clone(),
+        },
+      );
+    }
+    Ok(parsed)
+  }
+
+  async fn run_inner(self) -> BuildResult<TaskResult> {
+    // load hook
+    let loaded = self
       .plugin_driver
       .read()
       .await
-      .transform(&self.id, code, &mut loader)
-      .await?;
+      .load(&self.id)
+      .await?
+      .map(|l| (l.code, l.loader))
+      .or_else(|| self.load_node_builtin_stub());
+    // Only modules read from the disk are parse cached, since the output of `load` hooks can't be
+    // told unchanged without calling them.
+    let mtime = match (&loaded, &self.parse_cache) {
+      (None, Some(_)) => tokio::fs::metadata(self.id.as_ref())
+        .await
+        .and_then(|metadata| metadata.modified())
+        .ok(),
+      _ => None,
+    };
+    let cached = match mtime {
+      Some(mtime) => self.cached_parse(mtime).await?,
+      None => None,
+    };
+    let ParsedModule {
+      source_hash,
+      css,
+      wasm,
+      addon,
+      maps_to_source,
+      mut ast,
+      comments,
+      ..
+    } = match cached {
+      Some(parsed) => parsed,
+      None => self.load_and_parse(loaded, mtime).await?,
+    };
 
-    let (mut ast, comments) = parse_to_js_ast(&self.id, code, loader, &self.input_options)?;
+    {
+      let _permit = self.acquire_transform_permit().await;
+      self
+        .plugin_driver
+        .read()
+        .await
+        .transform_ast(&self.id, &mut ast)
+        .await?;
+    }
 
     // No matter what, the ast should be a pure valid JavaScript in this phrase
     GLOBALS.set(&SWC_GLOBALS, || {
       rolldown_swc_visitors::resolve(&mut ast, self.unresolved_mark, self.top_level_mark);
     });
+    if let Some(definitions) = &self.definitions {
+      definitions.apply(&mut ast, self.unresolved_ctxt);
+    }
+    let directives = rolldown_swc_visitors::take_directives(&mut ast);
+    let shebang = ast.shebang.take();
+    let is_registered = self.input_options.safe_hoisting
+      && rolldown_swc_visitors::wrap_in_registry(
+        &mut ast,
+        self.unresolved_ctxt,
+        self.top_level_ctxt,
+        &self.id.stable_id(&self.input_options.cwd),
+      )
+      .map_err(|hazard| BuildError::unhoistable_module(self.id.as_ref(), hazard))?;
+    let is_commonjs = is_registered
+      || rolldown_swc_visitors::wrap_commonjs(&mut ast, self.unresolved_ctxt, self.top_level_ctxt);
 
     let result = rolldown_swc_visitors::scan(
       &mut ast,
       self.top_level_ctxt,
       self.unresolved_ctxt,
       self.id.clone(),
+      &comments,
     );
 
-    let resolved_ids = self.resolve_dependencies(&result).await?;
+    let resolved_ids = self.resolve_dependencies(&result, is_commonjs).await?;
 
     Ok(TaskResult {
       module_id: self.id,
@@ -194,10 +571,25 @@ impl ModuleTask {
       resolved_ids,
       comments,
       is_user_defined_entry: self.is_user_defined_entry,
+      source_hash,
+      css,
+      wasm,
+      addon,
+      maps_to_source,
+      is_commonjs,
+      is_registered,
+      directives,
+      shebang,
     })
   }
 }
 
+fn hash_source(code: &str) -> u64 {
+  let mut hasher = FxHasher::default();
+  code.hash(&mut hasher);
+  hasher.finish()
+}
+
 #[derive(Derivative)]
 #[derivative(Debug)]
 pub(crate) struct TaskResult {
@@ -210,6 +602,67 @@ pub(crate) struct TaskResult {
   #[derivative(Debug = "ignore")]
   pub comments: SwcComments,
   pub is_user_defined_entry: bool,
+  /// Hash of the loaded source, used to tell whether the module changed between builds.
+  pub source_hash: u64,
+  /// Source of modules loaded by `Loader::Css`.
+  pub css: Option<String>,
+  /// Files of modules loaded by `Loader::Wasm`.
+  pub wasm: Option<WasmAsset>,
+  /// Files of modules loaded by `Loader::NativeAddon`.
+  pub addon: Option<NativeAddon>,
+  /// Whether the module is parsed from the code read, so its source maps point at it.
+  pub maps_to_source: bool,
+  /// Whether the module is wrapped by `wrap_commonjs` or `wrap_in_registry`.
+  pub is_commonjs: bool,
+  /// Whether the module is wrapped by `wrap_in_registry`, which needs the runtime module registry.
+  pub is_registered: bool,
+  /// `MODULE_DIRECTIVES` in the prologue of the module, which are removed from the AST.
+  pub directives: Vec<JsWord>,
+  /// The `#!` line of the module without `#!`, which is removed from the AST.
+  pub shebang: Option<JsWord>,
+}
+
+/// Return `None` for loaders whose source isn't JavaScript or its dialects.
+fn syntax_of_loader(loader: Loader, input_options: &BuildInputOptions) -> Option<Syntax> {
+  let is_jsx_or_tsx = matches!(loader, Loader::Jsx | Loader::Tsx);
+  let tsconfig = &input_options.builtins.tsconfig;
+  match loader {
+    Loader::Ts | Loader::Tsx => Some(Syntax::Typescript(TsConfig {
+      tsx: is_jsx_or_tsx,
+      decorators: true,
+      ..Default::default()
+    })),
+    Loader::Js | Loader::Jsx => Some(Syntax::Es(EsConfig {
+      jsx: is_jsx_or_tsx,
+      import_assertions: true,
+      decorators: tsconfig.decorators && tsconfig.js_decorators,
+      decorators_before_export: tsconfig.decorators_before_export,
+      ..Default::default()
+    })),
+    Loader::CssStyleSheet
+    | Loader::Css
+    | Loader::Text
+    | Loader::Json
+    | Loader::Yaml
+    | Loader::Toml
+    | Loader::DataUrl
+    | Loader::Base64
+    | Loader::Wasm
+    | Loader::NativeAddon => None,
+  }
+}
+
+fn jsx_options_of(options: &JsxOptions) -> react::Options {
+  react::Options {
+    runtime: Some(match options.runtime {
+      JsxRuntime::Classic => react::Runtime::Classic,
+      JsxRuntime::Automatic => react::Runtime::Automatic,
+    }),
+    import_source: options.import_source.clone(),
+    pragma: options.pragma.clone(),
+    pragma_frag: options.pragma_frag.clone(),
+    ..Default::default()
+  }
 }
 
 /// This function should emit valid JavaScript AST(with JSX)
@@ -217,6 +670,7 @@ fn parse_to_js_ast(
   id: &ModuleId,
   source: String,
   loader: Loader,
+  use_define_for_class_fields: bool,
   input_options: &SharedBuildInputOptions,
 ) -> UnaryBuildResult<(ast::Module, SwcComments)> {
   match loader {
@@ -225,26 +679,38 @@ fn parse_to_js_ast(
       let is_ts_or_tsx = matches!(loader, Loader::Ts | Loader::Tsx);
       let is_tsx = matches!(loader, Loader::Tsx);
       let is_ts = matches!(loader, Loader::Ts);
-      let syntax = if is_ts_or_tsx {
-        Syntax::Typescript(TsConfig {
-          tsx: is_jsx_or_tsx,
-          decorators: true,
-          ..Default::default()
-        })
-      } else {
-        Syntax::Es(EsConfig {
-          jsx: is_jsx_or_tsx,
-          ..Default::default()
-        })
+      let jsx_options = match &input_options.builtins.jsx {
+        Jsx::Transform(options) if is_jsx_or_tsx => Some(options),
+        _ => None,
       };
+      let tsconfig = &input_options.builtins.tsconfig;
+      let has_js_decorators = !is_ts_or_tsx && tsconfig.decorators && tsconfig.js_decorators;
+      let need_transform_decorators = tsconfig.decorators && (is_ts_or_tsx || has_js_decorators);
+      let syntax = syntax_of_loader(loader, input_options).unwrap();
       let comments = SwcComments::default();
       let fm = COMPILER.create_source_file(PathBuf::from(id.as_ref().to_string()), source);
-      let ast = COMPILER
-        .parse_with_comments(fm.clone(), syntax, Some(&comments))
-        .map_err(|e| BuildError::parse_js_failed(fm, e).context(format!("{loader:?}")))?;
+      let (ast, mut recovered_errors) = COMPILER
+        .parse_recoverable(fm.clone(), syntax, Some(&comments))
+        .map_err(|e| BuildError::parse_js_failed(fm.clone(), e).context(format!("{loader:?}")))?;
+      if !recovered_errors.is_empty() && !input_options.parse_recovery {
+        let e = recovered_errors.swap_remove(0);
+        return Err(BuildError::parse_js_failed(fm, e).context(format!("{loader:?}")));
+      }
+      recovered_errors.into_iter().for_each(|e| {
+        let loc = COMPILER.cm.lookup_char_pos(e.span().lo);
+        (input_options.on_warn)(BuildError::parse_error_recovered(
+          id.as_ref(),
+          (loc.line, loc.col.0 + 1),
+          e.kind().msg(),
+        ));
+      });
+
+      if is_ts_or_tsx {
+        check_export_default_with_export_assignment(id, &ast)?;
+      }
 
-      let need_resolve = is_ts_or_tsx;
-      let need_inject_helpers = is_ts_or_tsx;
+      let need_resolve = is_ts_or_tsx || has_js_decorators;
+      let need_inject_helpers = need_resolve;
 
       // It's ok to use a new GLOBALS here, since the SyntaxContext information won't be used in bundler.
       // Bundler will resolve SyntaxContext for its own usage.
@@ -253,15 +719,19 @@ fn parse_to_js_ast(
         let top_level_mark = Mark::new();
         let mut folders = chain!(
           Optional {
-            enabled: is_ts_or_tsx,
+            enabled: need_transform_decorators
+              && tsconfig.decorator_version != DecoratorVersion::V2022_03,
             visitor: decorators::decorators(decorators::Config {
-              use_define_for_class_fields: input_options
-                .builtins
-                .tsconfig
-                .use_define_for_class_fields,
+              legacy: tsconfig.decorator_version == DecoratorVersion::Legacy,
+              use_define_for_class_fields,
               ..Default::default()
             }),
           },
+          Optional {
+            enabled: need_transform_decorators
+              && tsconfig.decorator_version == DecoratorVersion::V2022_03,
+            visitor: decorator_2022_03::decorator_2022_03(),
+          },
           Optional {
             enabled: need_resolve,
             visitor: resolver(unresolved_mark, top_level_mark, is_ts_or_tsx),
@@ -287,25 +757,23 @@ fn parse_to_js_ast(
             ),
           },
           Optional {
-            enabled: is_jsx_or_tsx,
+            enabled: jsx_options.is_some(),
             visitor: react::react(
               COMPILER.cm.clone(),
               Some(&comments),
-              react::Options {
-                ..Default::default()
-              },
+              jsx_options.map(jsx_options_of).unwrap_or_default(),
               top_level_mark
             )
           },
           Optional {
-            enabled: is_ts_or_tsx,
+            enabled: need_resolve,
             visitor: // Fix up any identifiers with the same name, but different contexts
             // Notice the resolved SyntaxContext is cleared by hygiene,
             // So we don't need to clear again.
             hygiene(),
           },
           Optional {
-            enabled: is_ts_or_tsx,
+            enabled: need_resolve,
             // Ensure that we have enough parenthesis.
             visitor: fixer(None),
           },
@@ -325,6 +793,316 @@ fn parse_to_js_ast(
 
       Ok((ast, comments))
     }
-    Loader::Json => unimplemented!(),
+    Loader::CssStyleSheet => parse_to_js_ast(
+      id,
+      css_to_constructable_stylesheet(&source),
+      Loader::Js,
+      use_define_for_class_fields,
+      input_options,
+    ),
+    // The css itself is collected in `TaskResult`, the module only exists for its side effects.
+    Loader::Css => parse_to_js_ast(
+      id,
+      String::new(),
+      Loader::Js,
+      use_define_for_class_fields,
+      input_options,
+    ),
+    Loader::Text => parse_to_js_ast(
+      id,
+      format!("export default {};\n", to_js_string_literal(&source)),
+      Loader::Js,
+      use_define_for_class_fields,
+      input_options,
+    ),
+    // The source of binary loaders is already base64 encoded, unless `load` hooks return
+    // something else.
+    Loader::DataUrl => parse_to_js_ast(
+      id,
+      format!(
+        "export default \"data:{};base64,{}\";\n",
+        guess_mime_type(id.as_path()),
+        validate_base64(id, &source, "data URL")?
+      ),
+      Loader::Js,
+      use_define_for_class_fields,
+      input_options,
+    ),
+    Loader::Base64 => parse_to_js_ast(
+      id,
+      format!(
+        "export default \"{}\";\n",
+        validate_base64(id, &source, "base64")?
+      ),
+      Loader::Js,
+      use_define_for_class_fields,
+      input_options,
+    ),
+    Loader::Wasm => parse_to_js_ast(
+      id,
+      wasm_to_esm(
+        &WasmAsset::new(id, &source, &input_options.cwd)?,
+        input_options.platform,
+      ),
+      Loader::Js,
+      use_define_for_class_fields,
+      input_options,
+    ),
+    Loader::NativeAddon => parse_to_js_ast(
+      id,
+      native_addon_to_esm(&emitted_file_name(id, &input_options.cwd)),
+      Loader::Js,
+      use_define_for_class_fields,
+      input_options,
+    ),
+    Loader::Json | Loader::Yaml | Loader::Toml => parse_to_js_ast(
+      id,
+      data_to_esm(&parse_data(id, &source, loader)?),
+      Loader::Js,
+      use_define_for_class_fields,
+      input_options,
+    ),
+  }
+}
+
+fn parse_data(id: &ModuleId, source: &str, loader: Loader) -> UnaryBuildResult<serde_json::Value> {
+  let (format, parsed) = match loader {
+    Loader::Json => (
+      "JSON",
+      serde_json::from_str(source).map_err(|e| e.to_string()),
+    ),
+    Loader::Yaml => ("YAML", parse_yaml(source)),
+    Loader::Toml => ("TOML", parse_toml(source)),
+    _ => unreachable!("{loader:?} doesn't load data"),
+  };
+  parsed.map_err(|reason| BuildError::parse_data_failed(id.as_ref(), format, reason))
+}
+
+#[cfg(feature = "yaml")]
+fn parse_yaml(source: &str) -> Result<serde_json::Value, String> {
+  yaml_to_json(serde_yaml::from_str(source).map_err(|e| e.to_string())?)
+}
+
+#[cfg(not(feature = "yaml"))]
+fn parse_yaml(_source: &str) -> Result<serde_json::Value, String> {
+  Err("the `yaml` feature of rolldown is disabled".to_string())
+}
+
+/// Unlike JSON, keys of YAML mappings could be any value. Scalar keys are converted to strings like
+/// in JavaScript, such as `80: http` => `{ "80": "http" }`.
+#[cfg(feature = "yaml")]
+fn yaml_to_json(value: serde_yaml::Value) -> Result<serde_json::Value, String> {
+  use serde_yaml::Value;
+
+  let key_to_string = |key: Value| match key {
+    Value::Null => Ok("null".to_string()),
+    Value::Bool(boolean) => Ok(boolean.to_string()),
+    Value::Number(number) => Ok(number.to_string()),
+    Value::String(string) => Ok(string),
+    _ => Err(format!(
+      "only scalars could be keys of mappings, but got {}",
+      serde_yaml::to_string(&key).unwrap_or_default().trim_end()
+    )),
+  };
+
+  Ok(match value {
+    Value::Null => serde_json::Value::Null,
+    Value::Bool(boolean) => boolean.into(),
+    Value::Number(number) => serde_json::to_value(number).map_err(|e| e.to_string())?,
+    Value::String(string) => string.into(),
+    Value::Sequence(sequence) => sequence
+      .into_iter()
+      .map(yaml_to_json)
+      .collect::<Result<Vec<_>, _>>()?
+      .into(),
+    Value::Mapping(mapping) => mapping
+      .into_iter()
+      .map(|(key, value)| Ok((key_to_string(key)?, yaml_to_json(value)?)))
+      .collect::<Result<serde_json::Map<_, _>, String>>()?
+      .into(),
+    // Custom tags like `!secret` have no meaning for bundling, so only the values are kept.
+    Value::Tagged(tagged) => yaml_to_json(tagged.value)?,
+  })
+}
+
+#[cfg(feature = "toml")]
+fn parse_toml(source: &str) -> Result<serde_json::Value, String> {
+  toml::from_str(source).map_err(|e| e.to_string())
+}
+
+#[cfg(not(feature = "toml"))]
+fn parse_toml(_source: &str) -> Result<serde_json::Value, String> {
+  Err("the `toml` feature of rolldown is disabled".to_string())
+}
+
+/// ```js
+/// export const name = "rolldown";
+/// export default { name, "not-an-identifier": true };
+/// ```
+fn data_to_esm(data: &serde_json::Value) -> String {
+  let serde_json::Value::Object(entries) = data else {
+    return format!("export default {data};\n");
+  };
+  let mut code = String::new();
+  let properties = entries
+    .iter()
+    .map(|(key, value)| {
+      if is_legal(key) {
+        code.push_str(&format!("export const {key} = {value};\n"));
+        key.clone()
+      } else {
+        format!("{}: {value}", to_js_string_literal(key))
+      }
+    })
+    .collect::<Vec<_>>();
+  code.push_str(&format!(
+    "export default {{ {} }};\n",
+    properties.join(", ")
+  ));
+  code
+}
+
+/// `export =` is stripped to `module.exports =`, which would silently conflict with `export default`.
+fn check_export_default_with_export_assignment(
+  id: &ModuleId,
+  ast: &ast::Module,
+) -> UnaryBuildResult<()> {
+  let mut export_default = None;
+  let mut export_assignment = None;
+  ast.body.iter().for_each(|item| match item {
+    ast::ModuleItem::ModuleDecl(ast::ModuleDecl::ExportDefaultDecl(decl)) => {
+      export_default.get_or_insert(decl.span);
+    }
+    ast::ModuleItem::ModuleDecl(ast::ModuleDecl::ExportDefaultExpr(expr)) => {
+      export_default.get_or_insert(expr.span);
+    }
+    ast::ModuleItem::ModuleDecl(ast::ModuleDecl::TsExportAssignment(assignment)) => {
+      export_assignment.get_or_insert(assignment.span);
+    }
+    _ => {}
+  });
+
+  if let (Some(export_default), Some(export_assignment)) = (export_default, export_assignment) {
+    let location = |span: Span| {
+      let loc = COMPILER.cm.lookup_char_pos(span.lo);
+      (loc.line, loc.col.0 + 1)
+    };
+    return Err(BuildError::default_with_export_assignment(
+      id.as_ref(),
+      location(export_default),
+      location(export_assignment),
+    ));
+  }
+  Ok(())
+}
+
+/// ```js
+/// const sheet = new CSSStyleSheet();
+/// sheet.replaceSync("body { color: red; }");
+/// export { sheet as default };
+/// ```
+fn css_to_constructable_stylesheet(css: &str) -> String {
+  format!(
+    "const sheet = new CSSStyleSheet();\nsheet.replaceSync({});\nexport {{ sheet as default }};\n",
+    to_js_string_literal(css)
+  )
+}
+
+/// ```js
+/// import * as __wasm_import_0 from "./env.js";
+/// const __wasm_url = new URL("math.wasm", import.meta.url);
+/// const __wasm_bytes = await (__wasm_url.protocol === "file:"
+///   ? import("node:fs/promises").then((fs) => fs.readFile(__wasm_url))
+///   : fetch(__wasm_url).then((response) => response.arrayBuffer()));
+/// const { instance: __wasm_instance } = await WebAssembly.instantiate(__wasm_bytes, {
+///   "./env.js": __wasm_import_0,
+/// });
+/// const __wasm_export_0 = __wasm_instance.exports["add"];
+/// export { __wasm_export_0 as add };
+/// ```
+///
+/// The URL is relative to the chunk after `rewrite_wasm_url`. Files are fetched in browsers, so
+/// the branch of Node.js is left out for `Platform::Browser`.
+fn wasm_to_esm(wasm: &WasmAsset, platform: Option<Platform>) -> String {
+  let mut code = String::new();
+  let mut import_object = vec![];
+  for (index, module) in wasm.imports.iter().enumerate() {
+    let module = to_js_string_literal(module);
+    code.push_str(&format!(
+      "import * as __wasm_import_{index} from {module};\n"
+    ));
+    import_object.push(format!("  {module}: __wasm_import_{index},\n"));
+  }
+  code.push_str(&format!(
+    "const __wasm_url = new URL({}, import.meta.url);\n",
+    to_js_string_literal(&wasm.filename)
+  ));
+  let fetch = "fetch(__wasm_url).then((response) => response.arrayBuffer())";
+  if platform == Some(Platform::Browser) {
+    code.push_str(&format!("const __wasm_bytes = await {fetch};\n"));
+  } else {
+    code.push_str(&format!(
+      "const __wasm_bytes = await (__wasm_url.protocol === \"file:\"\n  ? import(\"node:fs/promises\").then((fs) => fs.readFile(__wasm_url))\n  : {fetch});\n"
+    ));
+  }
+  code.push_str(&format!(
+    "const {{ instance: __wasm_instance }} = await WebAssembly.instantiate(__wasm_bytes, {{\n{}}});\n",
+    import_object.concat()
+  ));
+  for (index, name) in wasm.exports.iter().enumerate() {
+    let exported = if is_legal(name) {
+      name.clone()
+    } else {
+      to_js_string_literal(name)
+    };
+    code.push_str(&format!(
+      "const __wasm_export_{index} = __wasm_instance.exports[{}];\nexport {{ __wasm_export_{index} as {exported} }};\n",
+      to_js_string_literal(name)
+    ));
   }
+  code
+}
+
+/// ```js
+/// import { createRequire as __addon_create_require } from "node:module";
+/// export default __addon_create_require(import.meta.url)("addon.node");
+/// ```
+///
+/// ESM can't import addons, so they're required. The path is relative to the chunk after
+/// `rewrite_emitted_file_url`.
+fn native_addon_to_esm(filename: &str) -> String {
+  format!(
+    "import {{ createRequire as __addon_create_require }} from \"node:module\";\nexport default __addon_create_require(import.meta.url)({});\n",
+    to_js_string_literal(filename)
+  )
+}
+
+/// The source of binary loaders, which is safe to put in string literals once it's valid base64.
+fn validate_base64<'s>(
+  id: &ModuleId,
+  source: &'s str,
+  format: &'static str,
+) -> UnaryBuildResult<&'s str> {
+  STANDARD
+    .decode(source)
+    .map_err(|e| BuildError::parse_data_failed(id.as_ref(), format, e))?;
+  Ok(source)
+}
+
+fn to_js_string_literal(value: &str) -> String {
+  let mut ret = String::with_capacity(value.len() + 2);
+  ret.push('"');
+  value.chars().for_each(|c| match c {
+    '"' => ret.push_str("\\\""),
+    '\\' => ret.push_str("\\\\"),
+    '\n' => ret.push_str("\\n"),
+    '\r' => ret.push_str("\\r"),
+    '\t' => ret.push_str("\\t"),
+    '\u{2028}' => ret.push_str("\\u2028"),
+    '\u{2029}' => ret.push_str("\\u2029"),
+    c if c.is_control() => ret.push_str(&format!("\\u{:04x}", c as u32)),
+    c => ret.push(c),
+  });
+  ret.push('"');
+  ret
 }
@@ -0,0 +1,95 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use glob::{MatchOptions, Pattern};
+use rolldown_common::ModuleId;
+use rustc_hash::FxHashMap;
+use sugar_path::AsPath;
+
+/// `sideEffects` of a `package.json`.
+#[derive(Debug)]
+enum SideEffects {
+  Bool(bool),
+  /// Only files matching the globs have side effects. Globs are relative to the package.
+  Globs(Vec<Pattern>),
+}
+
+#[derive(Debug)]
+struct PackageScope {
+  dir: PathBuf,
+  side_effects: Option<SideEffects>,
+}
+
+/// Looks up `sideEffects` of the nearest `package.json` of modules. Parsed `package.json`s are cached
+/// by the directories they're looked up from.
+#[derive(Debug, Default)]
+pub(crate) struct PackageSideEffects {
+  scope_by_dir: FxHashMap<PathBuf, Option<Arc<PackageScope>>>,
+}
+
+impl PackageSideEffects {
+  /// Return `false` only if the nearest `package.json` marks the module as side-effect-free.
+  pub(crate) fn has_side_effects(&mut self, id: &ModuleId) -> bool {
+    if id.is_virtual() {
+      return true;
+    }
+    let path = id.as_ref().as_path();
+    let Some(scope) = path.parent().and_then(|dir| self.scope_of_dir(dir)) else {
+      return true;
+    };
+    match &scope.side_effects {
+      None | Some(SideEffects::Bool(true)) => true,
+      Some(SideEffects::Bool(false)) => false,
+      Some(SideEffects::Globs(globs)) => {
+        let Ok(relative) = path.strip_prefix(&scope.dir) else {
+          return true;
+        };
+        let options = MatchOptions {
+          require_literal_separator: true,
+          ..Default::default()
+        };
+        globs
+          .iter()
+          .any(|glob| glob.matches_path_with(relative, options))
+      }
+    }
+  }
+
+  fn scope_of_dir(&mut self, dir: &Path) -> Option<Arc<PackageScope>> {
+    if let Some(scope) = self.scope_by_dir.get(dir) {
+      return scope.clone();
+    }
+    let scope = match std::fs::read_to_string(dir.join("package.json")) {
+      Ok(content) => Some(Arc::new(PackageScope {
+        dir: dir.to_path_buf(),
+        side_effects: parse_side_effects(&content),
+      })),
+      Err(_) => dir.parent().and_then(|parent| self.scope_of_dir(parent)),
+    };
+    self.scope_by_dir.insert(dir.to_path_buf(), scope.clone());
+    scope
+  }
+}
+
+/// Like webpack, globs without `/` match files in any directory, like `*.css` for `**/*.css`.
+fn parse_side_effects(package_json: &str) -> Option<SideEffects> {
+  let package_json = serde_json::from_str::<serde_json::Value>(package_json).ok()?;
+  match package_json.get("sideEffects")? {
+    serde_json::Value::Bool(value) => Some(SideEffects::Bool(*value)),
+    serde_json::Value::Array(globs) => Some(SideEffects::Globs(
+      globs
+        .iter()
+        .filter_map(|glob| glob.as_str())
+        .filter_map(|glob| {
+          let glob = glob.strip_prefix("./").unwrap_or(glob);
+          if glob.contains('/') {
+            Pattern::new(glob).ok()
+          } else {
+            Pattern::new(&format!("**/{glob}")).ok()
+          }
+        })
+        .collect(),
+    )),
+    _ => None,
+  }
+}
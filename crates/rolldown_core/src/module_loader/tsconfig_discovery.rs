@@ -0,0 +1,50 @@
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use rolldown_common::ModuleId;
+use rustc_hash::FxHashMap;
+use sugar_path::AsPath;
+
+use crate::{is_file, TsConfigFile, UnaryBuildResult};
+
+/// Looks up the nearest `tsconfig.json` of modules. Read configs are cached by the directories
+/// they're looked up from, and shared by all module tasks. The lock is never held while reading
+/// files, so a config could be read by multiple tasks at the same time, but only once afterward.
+#[derive(Debug, Default)]
+pub(crate) struct TsConfigDiscovery {
+  config_by_dir: Mutex<FxHashMap<PathBuf, Option<Arc<TsConfigFile>>>>,
+}
+
+impl TsConfigDiscovery {
+  pub(crate) async fn nearest(&self, id: &ModuleId) -> UnaryBuildResult<Option<Arc<TsConfigFile>>> {
+    if id.is_virtual() {
+      return Ok(None);
+    }
+    let Some(dir) = id.as_ref().as_path().parent() else {
+      return Ok(None);
+    };
+
+    // Directories looked up, which share the config found in the last one.
+    let mut looked_up = vec![];
+    let mut config = None;
+    for dir in dir.ancestors() {
+      let cached = self.config_by_dir.lock().unwrap().get(dir).cloned();
+      if let Some(cached) = cached {
+        config = cached;
+        break;
+      }
+      looked_up.push(dir);
+      let path = dir.join("tsconfig.json");
+      if is_file(&path).await {
+        config = Some(Arc::new(TsConfigFile::read(&path).await?));
+        break;
+      }
+    }
+
+    let mut config_by_dir = self.config_by_dir.lock().unwrap();
+    for dir in looked_up {
+      config_by_dir.insert(dir.to_path_buf(), config.clone());
+    }
+    Ok(config)
+  }
+}
@@ -7,12 +7,12 @@ use rolldown_common::{
 use rolldown_runtime_helpers::RuntimeHelpers;
 use rolldown_swc_visitors::StatementPart;
 use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
-use sugar_path::{AsPath, SugarPath};
+use sugar_path::AsPath;
 use swc_core::{
   common::{
     comments::{Comment, CommentKind, Comments, SingleThreadedComments},
     util::take::Take,
-    Spanned, SyntaxContext,
+    BytePos, LineCol, Spanned, SyntaxContext,
   },
   ecma::{
     ast::{self, Ident},
@@ -23,7 +23,8 @@ use swc_node_comments::SwcComments;
 use tracing::instrument;
 
 use crate::{
-  make_legal, BuildInputOptions, MergedExports, RenderContext, ResolvedModuleIds, COMPILER,
+  make_legal, BuildInputOptions, MergedExports, NativeAddon, OutputComments, RenderContext,
+  ResolvedModuleIds, WasmAsset, COMPILER,
 };
 
 #[derive(Derivative)]
@@ -72,6 +73,7 @@ pub struct NormalModule {
 
   // is imported dynamically
   pub(crate) is_dynamic_entry: bool,
+  pub(crate) has_top_level_await: bool,
 
   /// Comments of the source code
   #[derivative(Debug = "ignore")]
@@ -79,6 +81,28 @@ pub struct NormalModule {
 
   /// Key is missing exported name
   pub(crate) missing_exports: HashMap<JsWord, Symbol>,
+  /// Hash of the loaded source code
+  pub(crate) source_hash: u64,
+  /// Source of the module if it's loaded by `Loader::Css`
+  pub(crate) css: Option<String>,
+  /// The file of the module if it's loaded by `Loader::Wasm`
+  pub(crate) wasm: Option<WasmAsset>,
+  /// The file of the module if it's loaded by `Loader::NativeAddon`
+  pub(crate) addon: Option<NativeAddon>,
+  /// Code generated by loaders or changed by `transform` hooks isn't in the file the module is read
+  /// from, so it's left out of source maps.
+  pub(crate) maps_to_source: bool,
+  /// The module is CommonJS, which only exports `module.exports` as `default`.
+  pub(crate) is_commonjs: bool,
+  /// `false` if `sideEffects` of the nearest `package.json` marks the module as side-effect-free. It's
+  /// dropped by treeshaking if nothing of it is used.
+  pub(crate) side_effects: bool,
+  /// Directives like `"use client"` in the prologue of the module, which are emitted at the top of
+  /// the chunk it's the entry of.
+  pub(crate) directives: Vec<JsWord>,
+  /// The `#!` line of the module without `#!`. It must be the first line of the file, so it's emitted
+  /// at the top of the chunk it's the entry of, instead of the middle of the chunk.
+  pub(crate) shebang: Option<JsWord>,
 }
 
 impl NormalModule {
@@ -233,7 +257,15 @@ impl NormalModule {
 
   pub(crate) fn generate_namespace_export(&mut self) {
     if self.is_facade_namespace_id_referenced {
-      if !self.external_modules_of_re_export_all.is_empty() {
+      // Named exports of a CommonJS module are only known at runtime, so properties of
+      // `module.exports` are merged into its namespace, besides `default`.
+      let commonjs_exports = self
+        .linked_exports
+        .get(&js_word!("default"))
+        .filter(|_| self.is_commonjs)
+        .map(|spec| spec.local_id.clone());
+
+      if !self.external_modules_of_re_export_all.is_empty() || commonjs_exports.is_some() {
         self.runtime_helpers.merge_namespaces();
       };
 
@@ -305,6 +337,7 @@ impl NormalModule {
         external_modules_and_star_symbol
           .iter()
           .map(|(_, id)| id.clone().to_id())
+          .chain(commonjs_exports.map(|symbol| symbol.to_id()))
           .collect(),
       );
 
@@ -312,13 +345,19 @@ impl NormalModule {
     }
   }
 
+  /// Positions of printed tokens are pushed to `mappings` if it's `Some`.
   #[instrument(skip_all)]
-  pub(crate) fn render(&self, _ctx: &RenderContext, options: &BuildInputOptions) -> String {
+  pub(crate) fn render(
+    &self,
+    ctx: &RenderContext,
+    options: &BuildInputOptions,
+    mappings: Option<&mut Vec<(BytePos, LineCol)>>,
+  ) -> String {
     let comments = SingleThreadedComments::default();
 
     let mut text = String::new();
     text.push(' ');
-    text.push_str(&self.id.as_path().relative(&options.cwd).to_string_lossy());
+    text.push_str(&self.id.stable_id(options.display_root()));
     comments.add_leading(
       self.ast.span_lo(),
       Comment {
@@ -327,8 +366,24 @@ impl NormalModule {
         text: text.into(),
       },
     );
+    if ctx.comments == OutputComments::Jsdoc {
+      self.copy_jsdoc_comments(&comments);
+    }
 
-    COMPILER.print(&self.ast, Some(&comments)).unwrap()
+    COMPILER
+      .print_with_mappings(&self.ast, Some(&comments), Default::default(), mappings)
+      .unwrap()
+  }
+
+  /// Comments attached to removed code aren't printed, since their positions aren't visited.
+  fn copy_jsdoc_comments(&self, comments: &SingleThreadedComments) {
+    self.comments.leading.iter().for_each(|entry| {
+      entry
+        .value()
+        .iter()
+        .filter(|comment| comment.kind == CommentKind::Block && comment.text.starts_with('*'))
+        .for_each(|comment| comments.add_leading(*entry.key(), comment.clone()));
+    });
   }
 
   pub(crate) fn suggested_name_for(&self, sym: &JsWord) -> Option<JsWord> {
@@ -0,0 +1,68 @@
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Jsx {
+  /// Transform JSX into calls of the runtime, like `React.createElement` calls
+  Transform(JsxOptions),
+  /// Keep JSX syntax in the output for downstream tools. TypeScript types are still stripped.
+  Preserve,
+}
+
+impl Default for Jsx {
+  fn default() -> Self {
+    Self::Transform(Default::default())
+  }
+}
+
+impl Jsx {
+  pub fn is_preserve(&self) -> bool {
+    matches!(self, Jsx::Preserve)
+  }
+}
+
+/// `transform` is parsed to the transform with default options.
+impl FromStr for Jsx {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "transform" => Ok(Self::Transform(Default::default())),
+      "preserve" => Ok(Self::Preserve),
+      _ => Err(format!("Unknown jsx value \"{}\"", s)),
+    }
+  }
+}
+
+/// How JSX is transformed by `Jsx::Transform`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct JsxOptions {
+  pub runtime: JsxRuntime,
+  /// The package the automatic runtime imports helpers from, like `preact` for
+  /// `preact/jsx-runtime`. None means `react`.
+  pub import_source: Option<String>,
+  /// The function creating elements in the classic runtime. None means `React.createElement`.
+  pub pragma: Option<String>,
+  /// The component of fragments in the classic runtime. None means `React.Fragment`.
+  pub pragma_frag: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum JsxRuntime {
+  /// Call `pragma`, like `React.createElement`, which is expected to be in scope
+  #[default]
+  Classic,
+  /// Import helpers from `<import_source>/jsx-runtime`
+  Automatic,
+}
+
+impl FromStr for JsxRuntime {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "classic" => Ok(Self::Classic),
+      "automatic" => Ok(Self::Automatic),
+      _ => Err(format!("Unknown jsx runtime \"{}\"", s)),
+    }
+  }
+}
@@ -1,21 +1,46 @@
 mod typescript;
+use std::collections::BTreeMap;
+
 use derivative::Derivative;
+use rolldown_common::Loader;
 pub use typescript::*;
+mod jsx;
+pub use jsx::*;
+mod replace;
+pub use replace::*;
 
 #[derive(Derivative)]
 #[derivative(Debug)]
 pub struct BuiltinsOptions {
   /// None means disable the builtin
   pub tsconfig: TsConfig,
+  /// Compile each TypeScript module with options of its nearest `tsconfig.json`, falling back to
+  /// `tsconfig` for options it doesn't set. Useful in monorepos whose packages have different
+  /// tsconfigs.
+  pub discover_tsconfig: bool,
   // TODO: Should come up with a better name before exposing this option.
   pub detect_loader_by_ext: bool,
+  pub jsx: Jsx,
+  /// None means disable the builtin
+  pub replace: Option<ReplaceOptions>,
+  /// Strip code between conditional compilation comments like `// @if DEV` and `// @endif` before
+  /// parsing, unless `DEV` is defined as a truthy value by `define`.
+  pub conditional_compilation: bool,
+  /// Loaders of extensions without the leading dot, like `md` to `Loader::Text`. They take
+  /// precedence over the loaders detected by extensions.
+  pub loaders: BTreeMap<String, Loader>,
 }
 
 impl Default for BuiltinsOptions {
   fn default() -> Self {
     Self {
       tsconfig: Default::default(),
+      discover_tsconfig: false,
       detect_loader_by_ext: true,
+      jsx: Default::default(),
+      replace: None,
+      conditional_compilation: false,
+      loaders: Default::default(),
     }
   }
 }
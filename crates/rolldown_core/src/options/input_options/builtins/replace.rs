@@ -0,0 +1,23 @@
+/// Replace strings in the source code before parsing, like `@rollup/plugin-replace`.
+#[derive(Debug, Clone)]
+pub struct ReplaceOptions {
+  /// Pairs of the string to be replaced and its replacement. Longer keys take precedence.
+  pub values: Vec<(String, String)>,
+  /// Regex sources around the keys. Rust regex has no lookaround, so the default is
+  /// `("\\b", "\\b")` rather than `("\\b", "\\b(?!\\.)")` of rollup.
+  pub delimiters: (String, String),
+  /// Don't replace the key if it's the target of an assignment, like `process.env.DEBUG = true`.
+  /// Keys are replaced on the AST then, so only ones being whole expressions are replaced, and
+  /// values must be single expressions.
+  pub prevent_assignment: bool,
+}
+
+impl Default for ReplaceOptions {
+  fn default() -> Self {
+    Self {
+      values: Default::default(),
+      delimiters: ("\\b".to_string(), "\\b".to_string()),
+      prevent_assignment: false,
+    }
+  }
+}
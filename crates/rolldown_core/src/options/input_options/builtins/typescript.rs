@@ -1,16 +1,61 @@
+use std::path::PathBuf;
+use std::str::FromStr;
+
 use derivative::Derivative;
 
 #[derive(Derivative)]
 #[derivative(Debug)]
 pub struct TsConfig {
   pub use_define_for_class_fields: bool,
+  /// A `tsconfig.json`, relative to `cwd`, whose `compilerOptions.paths` and `baseUrl` are applied
+  /// to bare specifiers before looking them up in `node_modules`. Options inherited via `extends`
+  /// apply as well.
+  pub config_file: Option<PathBuf>,
+  /// Transform decorators. Disable it to skip the pass if decorators aren't used.
+  pub decorators: bool,
+  /// Parse and transform decorators of JavaScript modules as well, which then go through the passes
+  /// of TypeScript modules. Ignored unless `decorators` is enabled.
+  pub js_decorators: bool,
+  /// Whether decorators of exported classes in JavaScript modules are placed before `export`, like
+  /// `@dec export class A {}`, instead of `export @dec class A {}`.
+  pub decorators_before_export: bool,
+  pub decorator_version: DecoratorVersion,
 }
 
-#[allow(clippy::derivable_impls)]
 impl Default for TsConfig {
   fn default() -> Self {
     Self {
       use_define_for_class_fields: false,
+      config_file: None,
+      decorators: true,
+      js_decorators: false,
+      decorators_before_export: false,
+      decorator_version: Default::default(),
+    }
+  }
+}
+
+/// Semantics of transformed decorators.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DecoratorVersion {
+  /// The 2021-12 proposal
+  #[default]
+  V2021_12,
+  /// `experimentalDecorators` of TypeScript
+  Legacy,
+  /// The 2022-03 proposal
+  V2022_03,
+}
+
+impl FromStr for DecoratorVersion {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "2021-12" => Ok(Self::V2021_12),
+      "legacy" => Ok(Self::Legacy),
+      "2022-03" => Ok(Self::V2022_03),
+      _ => Err(format!("Unknown decorator version \"{}\"", s)),
     }
   }
 }
@@ -1,9 +1,17 @@
-use std::{path::PathBuf, pin::Pin, sync::Arc};
+use std::{
+  collections::HashMap,
+  path::{Path, PathBuf},
+  pin::Pin,
+  sync::Arc,
+};
 
 use derivative::Derivative;
 use futures::{future, Future, FutureExt};
+use regex::Regex;
+use rolldown_resolver::{Platform, DEFAULT_CONDITION_NAMES, DEFAULT_EXTENSIONS};
+use rolldown_tracing::LevelFilter;
 
-use crate::{UnaryBuildResult, WarningHandler};
+use crate::{BuildError, UnaryBuildResult, WarningHandler};
 
 mod input_item;
 pub use input_item::*;
@@ -15,19 +23,100 @@ type PinFutureBox<T> = Pin<Box<dyn Future<Output = T> + Send>>;
 pub type IsExternal =
   Arc<dyn Fn(&str, Option<&str>, bool) -> PinFutureBox<UnaryBuildResult<bool>> + Send + Sync>;
 
+/// Build `is_external` from regex patterns, like `^node:`. Like rollup, both specifiers and resolved
+/// ids are tested, so `/node_modules/` externalizes all dependencies resolved into `node_modules`.
+pub fn external_by_patterns(
+  patterns: impl IntoIterator<Item = impl AsRef<str>>,
+) -> UnaryBuildResult<IsExternal> {
+  let patterns = patterns
+    .into_iter()
+    .map(|pattern| {
+      let pattern = pattern.as_ref();
+      Regex::new(pattern).map_err(|e| BuildError::invalid_external_pattern(pattern, e))
+    })
+    .collect::<UnaryBuildResult<Vec<_>>>()?;
+  Ok(Arc::new(move |id, _, _| {
+    let is_external = patterns.iter().any(|pattern| pattern.is_match(id));
+    future::ready(Ok(is_external)).boxed()
+  }))
+}
+
 #[derive(Derivative)]
 #[derivative(Debug)]
 pub struct BuildInputOptions {
   pub input: Vec<InputItem>,
   pub treeshake: bool,
+  /// Treat exports of user-defined entries like any other exports in treeshaking,
+  /// so unused ones get dropped. Useful for apps, but breaks the public API of libraries.
+  pub drop_unused_entry_exports: bool,
   pub cwd: PathBuf,
+  /// Paths in errors, warnings, source maps and comments of the output are displayed relative to it,
+  /// while `cwd` is still used for resolving. Defaults to `cwd`.
+  pub root: Option<PathBuf>,
   #[derivative(Debug = "ignore")]
   pub is_external: IsExternal,
   #[derivative(Debug = "ignore")]
   pub on_warn: WarningHandler,
   pub shim_missing_exports: bool,
   pub preserve_symlinks: bool,
+  /// Adds the condition of the platform, `browser` or `node`, to the default conditions of
+  /// resolving. Conditions set in `condition_names` are kept as they are.
+  pub platform: Option<Platform>,
+  /// Resolve server-absolute imports like `/src/main.js` in this directory first, instead of only in
+  /// the root of the file system.
+  pub absolute_path_root: Option<PathBuf>,
+  /// Replace specifiers before resolving, like `("@", "/project/src")` for `@/utils`. A key matches
+  /// the whole specifier or its leading path segments. Relative replacements are relative to `cwd`.
+  pub alias: Vec<(String, String)>,
+  /// Extensions tried in order for specifiers without one, like `.mjs`. Each must begin with a dot.
+  pub resolve_extensions: Vec<String>,
+  /// Fields of `package.json` tried in order for the entry of packages without `exports`. `None`
+  /// only tries `main`.
+  pub main_fields: Option<Vec<String>>,
+  /// Conditions of `exports`. The default ones follow the one of `platform` if it's set, while
+  /// others are used as they are. The `browser` field of packages is respected if they include
+  /// `browser`.
+  pub condition_names: Vec<String>,
+  /// Resolve subpaths blocked by `exports` of packages in the file system with a warning,
+  /// instead of failing.
+  pub allow_exports_fallback: bool,
+  /// Record how each specifier is resolved, and by which plugin or resolver. Read it via
+  /// `resolution_report` of the bundler after building.
+  pub resolution_diagnostics: bool,
+  /// Load `.wasm` modules like the ESM integration of WebAssembly, which exposes their exports as
+  /// named exports. The `.wasm` files are emitted as assets and instantiated by generated modules
+  /// with top-level await, so only the `es` format is supported.
+  pub wasm_esm_integration: bool,
+  /// Wrap modules that can't be safely hoisted into the scope of the chunk, like CommonJS modules
+  /// and scripts using `eval` or `with`, in functions registered in a runtime module registry.
+  /// Other modules are still hoisted.
+  pub safe_hoisting: bool,
+  /// Keep the transformed code of modules across builds of the same bundler, and reuse it if the
+  /// source is unchanged. Modules read from the disk are kept parsed as well, and reused without
+  /// reading them again if their modified time is unchanged. Plugins could opt out via
+  /// `should_transform_cached_module`.
+  pub cache: bool,
+  /// Warn about parse errors swc could recover from, like a legacy octal literal in strict mode, and
+  /// continue with the recovered AST instead of failing the build. Useful in watch mode.
+  pub parse_recovery: bool,
+  /// Replace free references of globals and their member expressions, like `process.env.NODE_ENV`,
+  /// with expressions in code, like `"\"production\""`. Unlike `builtins.replace`, it works on the
+  /// AST, so locals shadowing the globals are left alone, and replaced conditions are folded by
+  /// treeshaking. Names are tested by `builtins.conditional_compilation` as well.
+  pub define: HashMap<String, String>,
+  /// Fail the build if any key of `define` isn't referenced by any module, instead of warning about
+  /// it.
+  pub strict_define: bool,
   pub builtins: BuiltinsOptions,
+  /// Limit how many modules could be transformed by plugins at the same time, which is
+  /// separate from reading files. `None` means no limit.
+  pub max_parallel_transforms: Option<usize>,
+  /// Fail the build if the module graph has more top-level symbols than it, instead of letting
+  /// memory grow with huge graphs. `None` means no limit. See `BuildMetrics::symbols`.
+  pub max_symbols: Option<usize>,
+  /// Level of the logs emitted by builds of this bundler only, so other bundlers may use other
+  /// levels. `RUST_LOG` takes precedence over it. `None` leaves logs to the global subscriber.
+  pub log_level: Option<LevelFilter>,
 }
 
 impl Default for BuildInputOptions {
@@ -35,14 +124,40 @@ impl Default for BuildInputOptions {
     Self {
       input: Default::default(),
       treeshake: true,
+      drop_unused_entry_exports: false,
       cwd: std::env::current_dir().unwrap(),
+      root: None,
       is_external: Arc::new(|_, _, _| future::ready(Ok(false)).boxed()),
       on_warn: Arc::new(|err| {
         eprintln!("{}", err);
       }),
       shim_missing_exports: false,
       builtins: Default::default(),
+      max_parallel_transforms: None,
+      max_symbols: None,
       preserve_symlinks: true,
+      platform: None,
+      absolute_path_root: None,
+      alias: Default::default(),
+      resolve_extensions: DEFAULT_EXTENSIONS.map(ToString::to_string).to_vec(),
+      main_fields: None,
+      condition_names: DEFAULT_CONDITION_NAMES.map(ToString::to_string).to_vec(),
+      allow_exports_fallback: false,
+      resolution_diagnostics: false,
+      wasm_esm_integration: false,
+      safe_hoisting: false,
+      cache: false,
+      parse_recovery: false,
+      define: Default::default(),
+      strict_define: false,
+      log_level: None,
     }
   }
 }
+
+impl BuildInputOptions {
+  /// The directory paths are displayed relative to, which is `root` or `cwd`.
+  pub fn display_root(&self) -> &Path {
+    self.root.as_deref().unwrap_or(&self.cwd)
+  }
+}
@@ -0,0 +1,38 @@
+/// Options for the AMD output format, aligned with `output.amd` of rollup.
+#[derive(Debug, Clone)]
+pub struct AmdOptions {
+  /// Id of the emitted module, like `define("my-id", [...], factory)`. Takes precedence over `auto_id`.
+  pub id: Option<String>,
+  /// Use the file name of the chunk without `.js` as the id.
+  pub auto_id: bool,
+  /// Prepended to ids generated by `auto_id`.
+  pub base_path: Option<String>,
+  /// Name of the function to call instead of `define`.
+  pub define: String,
+}
+
+impl AmdOptions {
+  pub fn module_id(&self, chunk_filename: &str) -> Option<String> {
+    if let Some(id) = &self.id {
+      return Some(id.clone());
+    }
+    self.auto_id.then(|| {
+      let id = chunk_filename.strip_suffix(".js").unwrap_or(chunk_filename);
+      match &self.base_path {
+        Some(base_path) => format!("{}/{id}", base_path.trim_end_matches('/')),
+        None => id.to_string(),
+      }
+    })
+  }
+}
+
+impl Default for AmdOptions {
+  fn default() -> Self {
+    Self {
+      id: None,
+      auto_id: false,
+      base_path: None,
+      define: "define".to_string(),
+    }
+  }
+}
@@ -0,0 +1,103 @@
+use std::str::FromStr;
+
+use swc_core::ecma::{ast::EsVersion, codegen};
+
+/// ECMAScript version whose syntax generated chunks are printed in. Syntax isn't transpiled, only
+/// how it's printed is affected, like escapes of strings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CodegenTarget {
+  Es3,
+  #[default]
+  Es5,
+  Es2015,
+  Es2016,
+  Es2017,
+  Es2018,
+  Es2019,
+  Es2020,
+  Es2021,
+  Es2022,
+  EsNext,
+}
+
+impl CodegenTarget {
+  /// Whether environments of the target are guaranteed to have `globalThis`.
+  pub(crate) fn has_global_this(&self) -> bool {
+    !matches!(
+      self,
+      CodegenTarget::Es3
+        | CodegenTarget::Es5
+        | CodegenTarget::Es2015
+        | CodegenTarget::Es2016
+        | CodegenTarget::Es2017
+        | CodegenTarget::Es2018
+        | CodegenTarget::Es2019
+    )
+  }
+}
+
+impl From<CodegenTarget> for EsVersion {
+  fn from(target: CodegenTarget) -> Self {
+    match target {
+      CodegenTarget::Es3 => EsVersion::Es3,
+      CodegenTarget::Es5 => EsVersion::Es5,
+      CodegenTarget::Es2015 => EsVersion::Es2015,
+      CodegenTarget::Es2016 => EsVersion::Es2016,
+      CodegenTarget::Es2017 => EsVersion::Es2017,
+      CodegenTarget::Es2018 => EsVersion::Es2018,
+      CodegenTarget::Es2019 => EsVersion::Es2019,
+      CodegenTarget::Es2020 => EsVersion::Es2020,
+      CodegenTarget::Es2021 => EsVersion::Es2021,
+      CodegenTarget::Es2022 => EsVersion::Es2022,
+      CodegenTarget::EsNext => EsVersion::EsNext,
+    }
+  }
+}
+
+impl FromStr for CodegenTarget {
+  type Err = String;
+
+  fn from_str(value: &str) -> Result<Self, Self::Err> {
+    match value {
+      "es3" => Ok(CodegenTarget::Es3),
+      "es5" => Ok(CodegenTarget::Es5),
+      "es2015" => Ok(CodegenTarget::Es2015),
+      "es2016" => Ok(CodegenTarget::Es2016),
+      "es2017" => Ok(CodegenTarget::Es2017),
+      "es2018" => Ok(CodegenTarget::Es2018),
+      "es2019" => Ok(CodegenTarget::Es2019),
+      "es2020" => Ok(CodegenTarget::Es2020),
+      "es2021" => Ok(CodegenTarget::Es2021),
+      "es2022" => Ok(CodegenTarget::Es2022),
+      "esnext" => Ok(CodegenTarget::EsNext),
+      _ => Err(format!("Invalid codegen target: {value}")),
+    }
+  }
+}
+
+/// How generated chunks are printed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CodegenOptions {
+  pub target: CodegenTarget,
+  /// Escape non-ASCII characters in strings and template literals.
+  pub ascii_only: bool,
+  /// Omit whitespaces and line breaks. Names aren't shortened, see `mangle` for that.
+  pub minify: bool,
+}
+
+impl CodegenOptions {
+  /// Modules are printed with default options, so chunks only need to be printed again if any
+  /// option is customized.
+  pub(crate) fn is_customized(&self) -> bool {
+    *self != Self::default()
+  }
+
+  pub(crate) fn to_swc_config(&self) -> codegen::Config {
+    codegen::Config {
+      target: self.target.into(),
+      ascii_only: self.ascii_only,
+      minify: self.minify,
+      ..Default::default()
+    }
+  }
+}
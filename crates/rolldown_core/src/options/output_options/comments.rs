@@ -0,0 +1,24 @@
+use std::str::FromStr;
+
+/// Which comments of modules are kept in generated chunks.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputComments {
+  /// Drop all of them.
+  #[default]
+  None,
+  /// Keep JSDoc comments, like `/** Greets. */`, of code that survives treeshaking, so libraries
+  /// keep their inline docs.
+  Jsdoc,
+}
+
+impl FromStr for OutputComments {
+  type Err = String;
+
+  fn from_str(value: &str) -> Result<Self, Self::Err> {
+    match value {
+      "none" => Ok(OutputComments::None),
+      "jsdoc" => Ok(OutputComments::Jsdoc),
+      _ => Err(format!("Invalid comments: {value}")),
+    }
+  }
+}
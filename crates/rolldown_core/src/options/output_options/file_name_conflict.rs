@@ -0,0 +1,25 @@
+use std::str::FromStr;
+
+/// What to do if multiple chunks are rendered to the same file name by the templates, or plugins emit
+/// files with names taken already.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FileNameConflict {
+  /// Keep the file name of the first chunk in the execution order of their entries, and append a
+  /// number to the others, like `main2.js`.
+  #[default]
+  Suffix,
+  /// Fail the build with `FILE_NAME_CONFLICT`.
+  Error,
+}
+
+impl FromStr for FileNameConflict {
+  type Err = String;
+
+  fn from_str(value: &str) -> Result<Self, Self::Err> {
+    match value {
+      "suffix" => Ok(FileNameConflict::Suffix),
+      "error" => Ok(FileNameConflict::Error),
+      _ => Err(format!("Invalid file name conflict handling: {value}")),
+    }
+  }
+}
@@ -0,0 +1,17 @@
+/// Passes of the minifier run over generated chunks if `minify` is enabled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinifyOptions {
+  /// Shorten local names. Top level names are kept, since they may be exported.
+  pub mangle: bool,
+  /// Simplify the code, like dropping unreachable branches and folding constants.
+  pub compress: bool,
+}
+
+impl Default for MinifyOptions {
+  fn default() -> Self {
+    Self {
+      mangle: true,
+      compress: true,
+    }
+  }
+}
@@ -1,58 +1,140 @@
-use std::str::FromStr;
+use std::collections::BTreeMap;
 
 use derivative::Derivative;
 
+use crate::AssetHandler;
+
 mod export_mode;
 pub use export_mode::*;
+mod amd;
+pub use amd::*;
+mod codegen;
+pub use codegen::*;
+mod minify;
+pub use minify::*;
+mod file_name_conflict;
+pub use file_name_conflict::*;
+mod comments;
+pub use comments::*;
+pub use rolldown_common::{ModuleFormat, SourceMapType};
 
 use self::file_name::FileNameTemplate;
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
-pub enum ModuleFormat {
-  Esm,
-  Cjs,
-  // AMD,
-  // UMD,
-}
-
-impl ModuleFormat {
-  pub fn is_es(self) -> bool {
-    self == ModuleFormat::Esm
-  }
-
-  pub fn is_cjs(self) -> bool {
-    self == ModuleFormat::Cjs
-  }
-}
-
-impl FromStr for ModuleFormat {
-  type Err = String;
-
-  fn from_str(value: &str) -> Result<Self, Self::Err> {
-    match value {
-      "esm" => Ok(ModuleFormat::Esm),
-      "cjs" => Ok(ModuleFormat::Cjs),
-      _ => Err(format!("Invalid module format: {value}")),
-    }
-  }
-}
-
 #[derive(Derivative)]
 #[derivative(Debug)]
 pub struct BuildOutputOptions {
+  /// Where assets are written by the caller, if they are. Relative to `cwd`. Besides informing
+  /// plugins, relative externals are referred to from chunks in it.
+  pub dir: Option<String>,
+  /// File names of entry chunks. Besides `[name]`, `[hash]` is the content hash of the chunk, and
+  /// `[ext]` is `js`.
   pub entry_file_names: FileNameTemplate,
+  /// File names of chunks split from entries, like dynamic imports. Placeholders are the same as
+  /// the ones of `entry_file_names`.
   pub chunk_file_names: FileNameTemplate,
+  /// Put modules into chunks named by the keys, whatever chunks they would be in otherwise, like
+  /// `vendor` for `node_modules`. Modules are matched by their ids, or by globs of their paths
+  /// relative to `cwd`, like `node_modules/**`. Dependencies of matched modules go to the same
+  /// chunk, unless they're matched by other chunks. Entries and dynamically imported modules keep
+  /// their own chunks.
+  pub manual_chunks: Option<BTreeMap<String, Vec<String>>>,
+  /// Give each module with directives like `"use client"` its own chunk, so the directives apply to
+  /// the module only. Otherwise, directives are only emitted by chunks of modules with them as
+  /// entries, and dropped from modules hoisted into other chunks with a warning.
+  pub split_directives: bool,
+  /// Emit a chunk for each module, instead of hoisting modules into chunks of entries. Chunks are
+  /// named by `entry_file_names`, where `[name]` is the path of the module relative to the deepest
+  /// directory containing all of them, so the output keeps the directory structure, like
+  /// `lib/util.js`. It lets consumers of libraries treeshake them by files. `manual_chunks` is
+  /// ignored.
+  pub preserve_modules: bool,
+  /// What to do if multiple chunks are rendered to the same file name, or a file emitted by a plugin
+  /// has a taken name, which would overwrite each other otherwise.
+  pub file_name_conflict: FileNameConflict,
   pub format: ModuleFormat,
   pub export_mode: ExportMode,
+  /// Prepend a UTF-8 BOM to generated chunks. It's skipped for chunks starting with a shebang.
+  pub bom: bool,
+  /// Code prepended to each chunk outside the wrapper of the format, like a license comment.
+  pub banner: Option<String>,
+  /// Code appended to each chunk outside the wrapper of the format. The `//# sourceMappingURL=`
+  /// comment still comes last.
+  pub footer: Option<String>,
+  /// Code prepended inside the wrapper of the format, like the function of `iife`. It follows the
+  /// `"use strict"` directive of `cjs` and `iife`.
+  pub intro: Option<String>,
+  /// Code appended inside the wrapper of the format.
+  pub outro: Option<String>,
+  pub amd: AmdOptions,
+  /// Name of the global variable holding exports of the entry, only used by the `iife` format.
+  pub name: Option<String>,
+  /// Shorten local names of generated chunks. Top level names are kept, since they may be exported.
+  pub mangle: bool,
+  /// Names that are never mangled, such as ones accessed by reflection or strings.
+  pub reserved_names: Vec<String>,
+  /// Run the minifier of swc over generated chunks, after treeshaking and scope hoisting. Unlike
+  /// `codegen.minify`, it rewrites the code as well, see `minify_options`.
+  pub minify: bool,
+  pub minify_options: MinifyOptions,
+  /// Emit `[name].api.json` for each entry chunk, which lists exports of the chunk.
+  pub api_surface: bool,
+  /// Emit `manifest.json`, which maps names of entries in `input` to the files of their chunks, and
+  /// chunks and css they import, like the manifest of Vite.
+  pub manifest: bool,
+  /// Control how generated chunks are printed.
+  pub codegen: CodegenOptions,
+  /// Comments of modules kept in generated chunks. It doesn't apply to `minify`.
+  pub comments: OutputComments,
+  /// Declare `globalThis` at the top of chunks referencing it, if `codegen.target` is older than
+  /// ES2020, which `globalThis` was introduced in.
+  pub global_this_polyfill: bool,
+  /// Replace `import.meta.url` with an equivalent of the format, since only ESM has `import.meta`.
+  /// It's derived from `__filename` for `cjs`, from the `module` dependency for `amd`, and from the
+  /// current script or the page for `iife`, which is best-effort.
+  pub rewrite_import_meta_url: bool,
+  /// URLs of external modules. If set, `[name].importmap.json` is emitted for each chunk of the
+  /// `esm` format, which maps externals imported by the chunk to their URLs.
+  pub import_map: Option<BTreeMap<String, String>>,
+  /// Emit source maps of generated chunks, which map them back to the transformed code of modules.
+  pub sourcemap: Option<SourceMapType>,
+  /// Receive each asset as soon as it's rendered. If set, returned assets only keep their file
+  /// names, so contents of the whole output aren't held in memory at the same time.
+  #[derivative(Debug = "ignore")]
+  pub on_asset: Option<AssetHandler>,
 }
 
 impl Default for BuildOutputOptions {
   fn default() -> Self {
     Self {
+      dir: None,
       entry_file_names: FileNameTemplate::from("[name].js".to_string()),
       chunk_file_names: FileNameTemplate::from("[name]-[hash].js".to_string()),
+      manual_chunks: None,
+      split_directives: false,
+      preserve_modules: false,
+      file_name_conflict: Default::default(),
       format: ModuleFormat::Esm,
       export_mode: ExportMode::Auto,
+      bom: false,
+      banner: None,
+      footer: None,
+      intro: None,
+      outro: None,
+      amd: Default::default(),
+      name: None,
+      mangle: false,
+      reserved_names: Default::default(),
+      minify: false,
+      minify_options: Default::default(),
+      api_surface: false,
+      manifest: false,
+      codegen: Default::default(),
+      comments: Default::default(),
+      global_this_polyfill: false,
+      rewrite_import_meta_url: true,
+      import_map: None,
+      sourcemap: None,
+      on_asset: None,
     }
   }
 }
@@ -67,6 +149,10 @@ pub mod file_name {
     pub fn new(template: String) -> Self {
       Self { template }
     }
+
+    pub fn contains_hash(&self) -> bool {
+      self.template.contains("[hash]")
+    }
   }
 
   impl From<String> for FileNameTemplate {
@@ -78,6 +164,9 @@ pub mod file_name {
   #[derive(Debug, Default)]
   pub struct RenderOptions<'me> {
     pub name: Option<&'me str>,
+    pub hash: Option<&'me str>,
+    /// Extension without the leading dot, like `js`.
+    pub ext: Option<&'me str>,
   }
 
   impl FileNameTemplate {
@@ -86,6 +175,12 @@ pub mod file_name {
       if let Some(name) = options.name {
         tmp = tmp.replace("[name]", name);
       }
+      if let Some(hash) = options.hash {
+        tmp = tmp.replace("[hash]", hash);
+      }
+      if let Some(ext) = options.ext {
+        tmp = tmp.replace("[ext]", ext);
+      }
       tmp
     }
   }
@@ -0,0 +1,72 @@
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use derivative::Derivative;
+use rolldown_common::{Loader, ModuleId};
+use rustc_hash::FxHashMap;
+use swc_core::ecma::ast;
+use swc_node_comments::SwcComments;
+
+use crate::{NativeAddon, WasmAsset};
+
+pub(crate) type SharedParseCache = Arc<ParseCache>;
+
+/// ASTs of modules read from the disk, kept across builds of the same bundler if `cache` is enabled.
+/// Unlike `TransformCache`, a hit skips reading the file as well.
+#[derive(Debug, Default)]
+pub(crate) struct ParseCache {
+  modules: Mutex<FxHashMap<ModuleId, CachedParse>>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct CachedParse {
+  /// Modified time of the file. The cache is stale once it changes.
+  pub(crate) mtime: SystemTime,
+  /// Code after `transform` hooks, passed to `should_transform_cached_module`.
+  pub(crate) code: String,
+  /// Snippets emitted by `transform` hooks via `ctx.emit_bundle_intro`, which are added again on
+  /// hits.
+  pub(crate) bundle_intros: Vec<String>,
+  /// The tsconfig could change between builds if it's discovered.
+  pub(crate) use_define_for_class_fields: bool,
+  pub(crate) parsed: ParsedModule,
+}
+
+/// A module parsed to the AST, before the `transform_ast` hook.
+#[derive(Derivative, Clone)]
+#[derivative(Debug)]
+pub(crate) struct ParsedModule {
+  /// Hash of the loaded source.
+  pub(crate) source_hash: u64,
+  /// Loader after `transform` hooks.
+  pub(crate) loader: Loader,
+  /// Source of modules loaded by `Loader::Css`.
+  pub(crate) css: Option<String>,
+  /// Files of modules loaded by `Loader::Wasm`.
+  pub(crate) wasm: Option<WasmAsset>,
+  /// Files of modules loaded by `Loader::NativeAddon`.
+  pub(crate) addon: Option<NativeAddon>,
+  /// Whether the AST is parsed from the loaded source, unchanged by `transform` hooks and builtins.
+  pub(crate) maps_to_source: bool,
+  pub(crate) ast: ast::Module,
+  /// Shared by clones, which is fine since comments aren't mutated after parsing.
+  #[derivative(Debug = "ignore")]
+  pub(crate) comments: SwcComments,
+}
+
+impl ParseCache {
+  /// Returns `None` if the module isn't cached or its file has been modified.
+  pub(crate) fn get(&self, id: &ModuleId, mtime: SystemTime) -> Option<CachedParse> {
+    self
+      .modules
+      .lock()
+      .unwrap()
+      .get(id)
+      .filter(|cached| cached.mtime == mtime)
+      .cloned()
+  }
+
+  pub(crate) fn insert(&self, id: ModuleId, cached: CachedParse) {
+    self.modules.lock().unwrap().insert(id, cached);
+  }
+}
@@ -1,33 +1,123 @@
-use std::sync::Arc;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
 
+use derivative::Derivative;
 use rolldown_common::{Loader, ModuleId};
+use rolldown_error::Errors;
 use rolldown_plugin::{
-  BuildPlugin, Context, LoadArgs, LoadReturn, ResolveArgs, ResolveReturn, TransformArgs,
+  Asset, BuildPlugin, Context, ExportsUsage, ExportsUsageArgs, LoadArgs, LoadOutput,
+  RenderChunkArgs, RenderOutputOptions, RenderedChunk, ResolveArgs, ResolvedId,
+  ResolvedInputOptions, ShouldTransformCachedModuleArgs, TransformArgs, TransformAstArgs,
 };
+use swc_core::ecma::ast;
 use tokio::sync::RwLock;
 
-use crate::UnaryBuildResult;
+use crate::{BuildError, BuildResult, UnaryBuildResult, WarningHandler};
 
 pub(crate) type SharedBuildPluginDriver = Arc<RwLock<BuildPluginDriver>>;
 
-#[derive(Debug, Default)]
+#[derive(Derivative)]
+#[derivative(Debug)]
 pub(crate) struct BuildPluginDriver {
   pub plugins: Vec<Box<dyn BuildPlugin>>,
+  /// Receives warnings emitted by plugins via `ctx.warn`.
+  #[derivative(Debug = "ignore")]
+  on_warn: WarningHandler,
+  /// Deduplicated snippets emitted via `ctx.emit_bundle_intro` during the build.
+  bundle_intros: Mutex<Vec<String>>,
 }
 
 impl BuildPluginDriver {
-  pub(crate) fn new(plugins: Vec<Box<dyn BuildPlugin>>) -> Self {
-    Self { plugins }
+  pub(crate) fn new(plugins: Vec<Box<dyn BuildPlugin>>, on_warn: WarningHandler) -> Self {
+    Self {
+      plugins,
+      on_warn,
+      bundle_intros: Default::default(),
+    }
   }
 
   pub(crate) fn into_shared(self) -> SharedBuildPluginDriver {
     Arc::new(RwLock::new(self))
   }
 
-  pub(crate) async fn load(&self, id: &ModuleId) -> LoadReturn {
+  /// Snippets to prepend to the chunks evaluated first, in the order they're first emitted.
+  pub(crate) fn bundle_intros(&self) -> Vec<String> {
+    self.bundle_intros.lock().unwrap().clone()
+  }
+
+  /// Add snippets unless they're added already. Cached modules add the ones emitted by their
+  /// `transform` hooks again, since the hooks aren't called.
+  pub(crate) fn add_bundle_intros(&self, intros: impl IntoIterator<Item = String>) {
+    let mut bundle_intros = self.bundle_intros.lock().unwrap();
+    for intro in intros {
+      if !bundle_intros.contains(&intro) {
+        bundle_intros.push(intro);
+      }
+    }
+  }
+
+  /// Hand warnings emitted via `ctx.warn` to `on_warn`, and fail with errors emitted via
+  /// `ctx.error`, followed by the one returned by the hook. Emitted ones are attributed to `plugin`
+  /// and `module`, which is the module handled by the hook if any.
+  fn settle<T, R>(
+    &self,
+    plugin: &dyn BuildPlugin,
+    module: Option<&ModuleId>,
+    ctx: &mut Context<T>,
+    ret: UnaryBuildResult<R>,
+  ) -> BuildResult<R> {
+    self.add_bundle_intros(ctx.take_bundle_intros());
+    let (warnings, errors) = ctx.take_diagnostics();
+    let attribute =
+      |diagnostic| BuildError::plugin_diagnostic(plugin.name().into_owned(), module, diagnostic);
+    warnings
+      .into_iter()
+      .for_each(|warning| (self.on_warn)(attribute(warning)));
+    let mut errors = errors.into_iter().map(attribute).collect::<Vec<_>>();
+    match ret {
+      Ok(output) if errors.is_empty() => Ok(output),
+      Ok(_) => Err(Errors::from_vec(errors)),
+      Err(err) => {
+        errors.push(err);
+        Err(Errors::from_vec(errors))
+      }
+    }
+  }
+
+  pub(crate) async fn build_start(&self, options: &ResolvedInputOptions) -> BuildResult<()> {
+    // Snippets of the previous build are emitted again if they're still wanted.
+    self.bundle_intros.lock().unwrap().clear();
+    for plugin in &self.plugins {
+      let mut ctx = Context::new();
+      let ret = plugin.build_start(&mut ctx, options).await;
+      self.settle(&**plugin, None, &mut ctx, ret)?;
+    }
+    Ok(())
+  }
+
+  /// Every plugin is called even if some of them fail, and all errors are returned.
+  pub(crate) async fn build_end(&self, build_errors: Option<&Errors>) -> BuildResult<()> {
+    let mut errors = vec![];
+    for plugin in &self.plugins {
+      let mut ctx = Context::new();
+      let ret = plugin.build_end(&mut ctx, build_errors).await;
+      if let Err(errs) = self.settle(&**plugin, None, &mut ctx, ret) {
+        errors.extend(errs.into_vec());
+      }
+    }
+    if errors.is_empty() {
+      Ok(())
+    } else {
+      Err(Errors::from_vec(errors))
+    }
+  }
+
+  pub(crate) async fn load(&self, id: &ModuleId) -> BuildResult<Option<LoadOutput>> {
     let mut load_args = LoadArgs { id };
     for plugin in &self.plugins {
-      let output = plugin.load(&mut Context::new(), &mut load_args).await?;
+      let mut ctx = Context::new();
+      let ret = plugin.load(&mut ctx, &mut load_args).await;
+      let output = self.settle(&**plugin, Some(id), &mut ctx, ret)?;
       if output.is_some() {
         return Ok(output);
       }
@@ -35,38 +125,153 @@ impl BuildPluginDriver {
     Ok(None)
   }
 
-  pub(crate) async fn resolve(&self, mut args: ResolveArgs<'_>) -> ResolveReturn {
+  /// Returns the name of the plugin resolving the specifier as well.
+  pub(crate) async fn resolve(
+    &self,
+    mut args: ResolveArgs<'_>,
+  ) -> BuildResult<Option<(String, ResolvedId)>> {
+    let importer = args.importer;
     for plugin in &self.plugins {
-      let output = plugin.resolve(&mut Context::new(), &mut args).await?;
-      if output.is_some() {
-        return Ok(output);
+      let mut ctx = Context::new();
+      let ret = plugin.resolve(&mut ctx, &mut args).await;
+      if let Some(output) = self.settle(&**plugin, importer, &mut ctx, ret)? {
+        return Ok(Some((plugin.name().to_string(), output)));
       }
     }
     Ok(None)
   }
 
+  /// Returns the name of the plugin resolving the specifier as well.
+  pub(crate) async fn resolve_fallback(
+    &self,
+    mut args: ResolveArgs<'_>,
+  ) -> BuildResult<Option<(String, ResolvedId)>> {
+    let importer = args.importer;
+    for plugin in &self.plugins {
+      let mut ctx = Context::new();
+      let ret = plugin.resolve_fallback(&mut ctx, &mut args).await;
+      if let Some(output) = self.settle(&**plugin, importer, &mut ctx, ret)? {
+        return Ok(Some((plugin.name().to_string(), output)));
+      }
+    }
+    Ok(None)
+  }
+
+  /// Returns snippets emitted via `ctx.emit_bundle_intro` as well, which are cached along with the
+  /// code.
   pub(crate) async fn transform(
     &self,
     id: &ModuleId,
     code: String,
     loader: &mut Loader,
-  ) -> UnaryBuildResult<String> {
+  ) -> BuildResult<(String, Vec<String>)> {
+    let mut code = code;
+    let mut bundle_intros = vec![];
+    for plugin in &self.plugins {
+      let mut args = TransformArgs {
+        id,
+        code: &code,
+        loader,
+        stop_further_transforms: false,
+      };
+      let mut ctx = Context::new();
+      let ret = plugin.transform(&mut ctx, &mut args).await;
+      let intros = ctx.take_bundle_intros();
+      self.add_bundle_intros(intros.iter().cloned());
+      bundle_intros.extend(intros);
+      let stop_further_transforms = args.stop_further_transforms;
+      if let Some(output) = self.settle(&**plugin, Some(id), &mut ctx, ret)? {
+        code = output
+      }
+      if stop_further_transforms {
+        tracing::trace!("{} stopped further transforms of {}", plugin.name(), id);
+        break;
+      }
+    }
+    Ok((code, bundle_intros))
+  }
+
+  /// The cached module is transformed again if any plugin returns `true`.
+  pub(crate) async fn should_transform_cached_module(
+    &self,
+    id: &ModuleId,
+    code: &String,
+  ) -> BuildResult<bool> {
+    let args = ShouldTransformCachedModuleArgs { id, code };
+    for plugin in &self.plugins {
+      let mut ctx = Context::new();
+      let ret = plugin.should_transform_cached_module(&mut ctx, &args).await;
+      if self.settle(&**plugin, Some(id), &mut ctx, ret)? {
+        return Ok(true);
+      }
+    }
+    Ok(false)
+  }
+
+  pub(crate) async fn transform_ast(
+    &self,
+    id: &ModuleId,
+    ast: &mut ast::Module,
+  ) -> BuildResult<()> {
+    for plugin in &self.plugins {
+      let mut ctx = Context::new();
+      let ret = plugin
+        .transform_ast(&mut ctx, &mut TransformAstArgs { id, ast })
+        .await;
+      self.settle(&**plugin, Some(id), &mut ctx, ret)?;
+    }
+    Ok(())
+  }
+
+  pub(crate) async fn exports_usage(
+    &self,
+    exports_usage: &BTreeMap<ModuleId, ExportsUsage>,
+  ) -> BuildResult<()> {
+    for plugin in &self.plugins {
+      let mut ctx = Context::new();
+      let ret = plugin
+        .exports_usage(&mut ctx, &ExportsUsageArgs { exports_usage })
+        .await;
+      self.settle(&**plugin, None, &mut ctx, ret)?;
+    }
+    Ok(())
+  }
+
+  pub(crate) async fn render_chunk(
+    &self,
+    chunk: &RenderedChunk,
+    code: String,
+    output_options: &RenderOutputOptions,
+  ) -> BuildResult<String> {
     let mut code = code;
     for plugin in &self.plugins {
-      let output = plugin
-        .transform(
-          &mut Context::new(),
-          &mut TransformArgs {
-            id,
-            code: &code,
-            loader,
-          },
-        )
-        .await?;
-      if let Some(output) = output {
+      let mut ctx = Context::with_context(output_options);
+      let ret = plugin
+        .render_chunk(&mut ctx, &RenderChunkArgs { chunk, code: &code })
+        .await;
+      if let Some(output) = self.settle(&**plugin, None, &mut ctx, ret)? {
         code = output
       }
     }
     Ok(code)
   }
+
+  /// Files emitted by each plugin are passed to `emit` along with the bundle and the name of the
+  /// plugin, before they're appended to the bundle.
+  pub(crate) async fn generate_bundle(
+    &self,
+    bundle: &mut Vec<Asset>,
+    emit: impl Fn(&[Asset], Asset, &str) -> UnaryBuildResult<Asset>,
+  ) -> BuildResult<()> {
+    for plugin in &self.plugins {
+      let mut ctx = Context::with_context(vec![]);
+      let ret = plugin.generate_bundle(&mut ctx, bundle).await;
+      self.settle(&**plugin, None, &mut ctx, ret)?;
+      for asset in std::mem::take(&mut ctx.context) {
+        let asset = emit(bundle, asset, &plugin.name())?;
+        bundle.push(asset);
+      }
+    }
+    Ok(())
+  }
 }
@@ -0,0 +1,76 @@
+use std::sync::Mutex;
+
+use rolldown_common::ModuleId;
+
+use crate::BuildResult;
+
+/// What resolved a specifier.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResolutionSource {
+  /// The `resolve` hook of the plugin with the name.
+  ResolveHook(String),
+  /// The `resolve_fallback` hook of the plugin with the name.
+  ResolveFallbackHook(String),
+  /// The builtin resolver, and the resolved path isn't in `node_modules`.
+  Path,
+  /// The builtin resolver, and the resolved path is in `node_modules`. The field of `package.json`
+  /// that resolved a bare specifier is kept, like `exports` or `main`, which is `None` for files
+  /// found in the package directly, like `pkg/lib/util.js` of packages without `exports`.
+  NodeModules(Option<String>),
+  /// Marked as external by the `external` option.
+  ExternalOption,
+  /// A `node:` builtin, which is external for `Platform::Node`, and resolved to a polyfill package
+  /// or stubbed by an empty module for `Platform::Browser`.
+  NodeBuiltin,
+  /// Not resolved by anything, so it's treated as external.
+  Unresolved,
+}
+
+/// A resolution attempt of a specifier.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolutionRecord {
+  pub specifier: String,
+  /// `None` for entries.
+  pub importer: Option<String>,
+  /// The resolved id, or the message of the error if the resolution failed.
+  pub resolved: Result<String, String>,
+  /// `None` if the resolution failed.
+  pub source: Option<ResolutionSource>,
+}
+
+/// Collects resolution attempts of a build, if `resolution_diagnostics` is enabled.
+#[derive(Debug, Default)]
+pub(crate) struct ResolutionReport {
+  records: Mutex<Vec<ResolutionRecord>>,
+}
+
+impl ResolutionReport {
+  pub(crate) fn record(
+    &self,
+    specifier: &str,
+    importer: Option<&ModuleId>,
+    result: &BuildResult<(ModuleId, ResolutionSource)>,
+  ) {
+    let (resolved, source) = match result {
+      Ok((id, source)) => (Ok(id.to_string()), Some(source.clone())),
+      Err(errors) => (Err(errors.first().to_string()), None),
+    };
+    self.records.lock().unwrap().push(ResolutionRecord {
+      specifier: specifier.to_string(),
+      importer: importer.map(|id| id.to_string()),
+      resolved,
+      source,
+    });
+  }
+
+  /// Records are sorted, since resolutions run in parallel.
+  pub(crate) fn records(&self) -> Vec<ResolutionRecord> {
+    let mut records = self.records.lock().unwrap().clone();
+    records.sort_by(|a, b| {
+      a.importer
+        .cmp(&b.importer)
+        .then_with(|| a.specifier.cmp(&b.specifier))
+    });
+    records
+  }
+}
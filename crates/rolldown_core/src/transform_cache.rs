@@ -0,0 +1,49 @@
+use std::sync::{Arc, Mutex};
+
+use rolldown_common::{Loader, ModuleId};
+use rustc_hash::FxHashMap;
+
+pub(crate) type SharedTransformCache = Arc<TransformCache>;
+
+/// Outputs of `transform` hooks, kept across builds of the same bundler if `cache` is enabled.
+///
+/// Hooks receive the loader along with the id, so outputs are keyed by both. The same module could
+/// be loaded differently, like a `.json` file imported with and without `assert { type: 'json' }`,
+/// or after `loaders` are changed.
+#[derive(Debug, Default)]
+pub(crate) struct TransformCache {
+  modules: Mutex<FxHashMap<(ModuleId, Loader), CachedTransform>>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct CachedTransform {
+  /// Hash of the loaded source. The cache is stale once it changes.
+  pub(crate) source_hash: u64,
+  pub(crate) code: String,
+  /// Loader after `transform` hooks, which could change the one in the key.
+  pub(crate) loader: Loader,
+  /// Snippets emitted by the hooks via `ctx.emit_bundle_intro`, which are added again on hits.
+  pub(crate) bundle_intros: Vec<String>,
+}
+
+impl TransformCache {
+  /// Returns `None` if the module isn't cached or its source has changed.
+  pub(crate) fn get(
+    &self,
+    id: &ModuleId,
+    loader: Loader,
+    source_hash: u64,
+  ) -> Option<CachedTransform> {
+    self
+      .modules
+      .lock()
+      .unwrap()
+      .get(&(id.clone(), loader))
+      .filter(|cached| cached.source_hash == source_hash)
+      .cloned()
+  }
+
+  pub(crate) fn insert(&self, id: ModuleId, loader: Loader, cached: CachedTransform) {
+    self.modules.lock().unwrap().insert((id, loader), cached);
+  }
+}
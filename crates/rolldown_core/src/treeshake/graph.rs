@@ -1,21 +1,29 @@
+use std::collections::BTreeMap;
+
+use itertools::Itertools;
 use rayon::prelude::*;
-use rolldown_common::Symbol;
+use rolldown_common::{ExportedSpecifier, ModuleId, Symbol};
 use rolldown_error::Errors;
+use rolldown_plugin::ExportsUsage;
 use rustc_hash::FxHashSet;
 use swc_core::common::GLOBALS;
+use swc_core::ecma::atoms::JsWord;
 use tracing::instrument;
 
 use super::TreeshakeContext;
-use crate::{treeshake::TreeshakeNormalModule, BuildResult, Graph, COMPILER, SWC_GLOBALS};
+use crate::{
+  normal_module::NormalModule, treeshake::TreeshakeNormalModule, BuildResult, Graph,
+  OutputComments, COMPILER, SWC_GLOBALS,
+};
 
 impl Graph {
   #[instrument(skip_all)]
   pub(crate) fn treeshake(&mut self) -> BuildResult<()> {
-    let used_ids = self
-      .collect_all_used_ids()?
-      .into_iter()
-      .map(|id| id.to_id())
-      .collect();
+    let (used_symbols, included_modules) = self.collect_all_used_ids()?;
+    self.used_symbols = used_symbols;
+    self.exports_usage = self.collect_exports_usage();
+    let used_ids = self.used_symbols.iter().map(|id| id.to_id()).collect();
+    let keeps_comments = self.comments != OutputComments::None;
 
     self
       .module_by_id
@@ -23,6 +31,10 @@ impl Graph {
       .par_bridge()
       .filter_map(|m| m.as_norm_mut())
       .for_each(|module| {
+        if !included_modules.contains(&module.id) {
+          // The module is side-effect-free and nothing of it is used.
+          module.ast.body.clear();
+        }
         GLOBALS.set(&SWC_GLOBALS, || {
           tracing::trace!(
             "[before treeshake]module: {},code: \n{}",
@@ -45,7 +57,10 @@ impl Graph {
             COMPILER.debug_print(&module.ast, None).unwrap()
           );
           // We don't need `export`, because of scope hoisting.
-          rolldown_swc_visitors::remove_export_and_import(&mut module.ast);
+          rolldown_swc_visitors::remove_export_and_import(
+            &mut module.ast,
+            keeps_comments.then_some(&module.comments),
+          );
         });
 
         module
@@ -69,20 +84,63 @@ impl Graph {
     Ok(())
   }
 
+  /// Exports of each module are considered as used if their local symbols are used.
+  /// Exports of the module whose bindings survive treeshaking, which are all of them if it's
+  /// disabled. Bindings of external modules are re-exported from them, so they're always kept.
+  pub(crate) fn kept_exports<'m>(
+    &'m self,
+    module: &'m NormalModule,
+  ) -> impl Iterator<Item = (&'m JsWord, &'m ExportedSpecifier)> {
+    module.linked_exports.iter().filter(|(_, spec)| {
+      !self.input_options.treeshake
+        || spec.owner.is_external()
+        || self.used_symbols.contains(&spec.local_id)
+    })
+  }
+
+  fn collect_exports_usage(&self) -> BTreeMap<ModuleId, ExportsUsage> {
+    self
+      .module_by_id
+      .values()
+      .filter_map(|m| m.as_norm())
+      .filter(|m| !m.linked_exports.is_empty())
+      .map(|module| {
+        let mut usage = ExportsUsage::default();
+        module
+          .linked_exports
+          .iter()
+          .sorted_by_key(|(exported_name, _)| *exported_name)
+          .for_each(|(exported_name, spec)| {
+            if self.used_symbols.contains(&spec.local_id) {
+              usage.used.push(exported_name.clone());
+            } else {
+              usage.unused.push(exported_name.clone());
+            }
+          });
+        (module.id.clone(), usage)
+      })
+      .collect()
+  }
+
+  /// Return used symbols and modules which are included.
   #[instrument(skip_all)]
-  pub(crate) fn collect_all_used_ids(&mut self) -> BuildResult<FxHashSet<Symbol>> {
+  pub(crate) fn collect_all_used_ids(
+    &mut self,
+  ) -> BuildResult<(FxHashSet<Symbol>, FxHashSet<ModuleId>)> {
     let ctx = TreeshakeContext {
       id_to_module: self
         .module_by_id
         .par_iter()
         .filter_map(|(id, m)| m.as_norm().map(|m| (id, TreeshakeNormalModule::new(m))))
         .collect(),
+      drop_unused_entry_exports: self.input_options.drop_unused_entry_exports,
       errors: Default::default(),
     };
     let used_ids = ctx
       .id_to_module
       .values()
       .par_bridge()
+      .filter(|m| m.is_root())
       .map(|m| m.include(&ctx))
       .flatten()
       .collect::<FxHashSet<_>>();
@@ -90,6 +148,12 @@ impl Graph {
     if !errors.is_empty() {
       return Err(Errors::from_vec(errors));
     }
-    Ok(used_ids)
+    let included_modules = ctx
+      .id_to_module
+      .into_iter()
+      .filter(|(_, m)| m.is_included.into_inner())
+      .map(|(id, _)| id.clone())
+      .collect();
+    Ok((used_ids, included_modules))
   }
 }
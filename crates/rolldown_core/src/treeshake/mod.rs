@@ -16,6 +16,7 @@ mod statement_part;
 #[derive(Debug)]
 struct TreeshakeContext<'a> {
   id_to_module: FxHashMap<&'a ModuleId, TreeshakeNormalModule<'a>>,
+  drop_unused_entry_exports: bool,
   pub(crate) errors: Mutex<Vec<rolldown_error::Error>>,
 }
 
@@ -39,8 +40,16 @@ pub(crate) struct TreeshakeNormalModule<'m> {
 }
 
 impl<'m> TreeshakeNormalModule<'m> {
-  fn is_entry(&self) -> bool {
-    self.module.is_dynamic_entry || self.module.is_user_defined_entry
+  /// Exports of dynamic entries are always kept, since we can't know which of them are used by `import()`.
+  fn should_include_exports(&self, ctx: &TreeshakeContext) -> bool {
+    self.module.is_dynamic_entry
+      || (self.module.is_user_defined_entry && !ctx.drop_unused_entry_exports)
+  }
+
+  /// Modules are included from the entries and those having side effects. Side-effect-free modules
+  /// are only included once something of them is used.
+  fn is_root(&self) -> bool {
+    self.module.side_effects || self.module.is_user_defined_entry || self.module.is_dynamic_entry
   }
 
   pub(crate) fn new(module: &'m NormalModule) -> Self {
@@ -69,17 +78,18 @@ impl<'m> TreeshakeNormalModule<'m> {
     ctx: &TreeshakeContext,
     symbol: &Symbol,
   ) -> FxHashSet<Symbol> {
-    self
-      .module
-      .parts
-      .find_parts_where_symbol_declared(symbol)
-      .map(|parts| {
-        parts
-          .into_iter()
-          .flat_map(|p| p.include(ctx, self))
-          .collect()
-      })
-      .expect("Must have declaration")
+    // It's a no-op unless the module is side-effect-free and nothing of it has been used before.
+    let mut included = self.include(ctx);
+    included.extend(
+      self
+        .module
+        .parts
+        .find_parts_where_symbol_declared(symbol)
+        .expect("Must have declaration")
+        .into_iter()
+        .flat_map(|p| p.include(ctx, self)),
+    );
+    included
   }
 
   /// Return `None` if the symbol is not created by import
@@ -248,7 +258,7 @@ impl<'m> TreeshakeNormalModule<'m> {
       };
 
       let include_exports_if_is_entry = || {
-        if self.is_entry() {
+        if self.should_include_exports(ctx) {
           self
             .module
             .linked_exports
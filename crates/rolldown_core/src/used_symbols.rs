@@ -0,0 +1,62 @@
+use std::collections::BTreeSet;
+
+use rolldown_common::ModuleId;
+use rustc_hash::FxHashMap;
+
+use crate::Graph;
+
+/// Top-level symbols kept by treeshaking, which outlives the build. Symbols are named as they're
+/// declared in their modules, before deconflicting renames them in chunks.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct UsedSymbols {
+  symbols: BTreeSet<UsedSymbol>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UsedSymbol {
+  pub module: ModuleId,
+  pub name: String,
+}
+
+impl UsedSymbols {
+  pub(crate) fn new(graph: &Graph) -> Self {
+    let module_by_ctxt = graph
+      .module_by_id
+      .values()
+      .filter_map(|module| module.as_norm())
+      .map(|module| (module.top_level_ctxt, &module.id))
+      .collect::<FxHashMap<_, _>>();
+    // Symbols out of top-level scopes of modules, like globals, aren't declarations to report.
+    let symbols = graph
+      .used_symbols
+      .iter()
+      .filter_map(|symbol| {
+        module_by_ctxt.get(&symbol.ctxt()).map(|module| UsedSymbol {
+          module: (*module).clone(),
+          name: symbol.name().to_string(),
+        })
+      })
+      .collect();
+    Self { symbols }
+  }
+
+  pub fn contains(&self, module: &ModuleId, name: &str) -> bool {
+    self.symbols.contains(&UsedSymbol {
+      module: module.clone(),
+      name: name.to_string(),
+    })
+  }
+
+  /// Sorted by modules, then names, so it's stable across runs.
+  pub fn iter(&self) -> impl Iterator<Item = &UsedSymbol> {
+    self.symbols.iter()
+  }
+
+  pub fn len(&self) -> usize {
+    self.symbols.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.symbols.is_empty()
+  }
+}
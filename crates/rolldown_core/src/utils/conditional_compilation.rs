@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+
+use rolldown_common::ModuleId;
+use rustc_hash::FxHashSet;
+
+use crate::{BuildError, UnaryBuildResult};
+
+enum Directive<'a> {
+  If(&'a str),
+  Else,
+  Endif,
+}
+
+fn parse_directive(line: &str) -> Option<Directive> {
+  let comment = line.trim().strip_prefix("//")?.trim();
+  if let Some(condition) = comment.strip_prefix("@if ") {
+    return Some(Directive::If(condition.trim()));
+  }
+  match comment {
+    "@else" => Some(Directive::Else),
+    "@endif" => Some(Directive::Endif),
+    _ => None,
+  }
+}
+
+/// The name tested by a condition, which could be negated by `!`.
+fn name_of(condition: &str) -> (bool, &str) {
+  match condition.strip_prefix('!') {
+    Some(name) => (true, name.trim()),
+    None => (false, condition),
+  }
+}
+
+/// Like JavaScript, names are falsy if they're undefined or defined as `false`, `0`, `null`,
+/// `undefined` or an empty string.
+fn is_truthy(condition: &str, define: &HashMap<String, String>) -> bool {
+  let (negated, name) = name_of(condition);
+  let truthy = define.get(name).map_or(false, |value| {
+    !matches!(
+      value.trim(),
+      "" | "false" | "0" | "null" | "undefined" | "\"\"" | "''"
+    )
+  });
+  truthy != negated
+}
+
+/// Strip blocks of conditional compilation comments whose conditions are falsy, like
+///
+/// ```js
+/// // @if DEV
+/// console.log('dev only')
+/// // @else
+/// console.log('prod only')
+/// // @endif
+/// ```
+///
+/// Conditions could be negated by `!`, and blocks could be nested. Stripped lines and directives are
+/// replaced with empty lines, so line numbers of the remaining code are unchanged. Names tested by
+/// conditions are returned along with the code.
+pub(crate) fn strip_conditional_blocks<'c>(
+  id: &ModuleId,
+  code: &'c str,
+  define: &HashMap<String, String>,
+) -> UnaryBuildResult<(String, FxHashSet<&'c str>)> {
+  let mut tested_names = FxHashSet::default();
+  if !code.contains("@if") {
+    return Ok((code.to_string(), tested_names));
+  }
+
+  // Whether the enclosing blocks are kept, the innermost one last, along with where they begin.
+  let mut kept_blocks = vec![];
+  let mut output = String::with_capacity(code.len());
+  for (index, line) in code.split_inclusive('\n').enumerate() {
+    let directive = parse_directive(line);
+    let location = || (index + 1, line.len() - line.trim_start().len() + 1);
+    match &directive {
+      Some(Directive::If(condition)) => {
+        tested_names.insert(name_of(condition).1);
+        kept_blocks.push((is_truthy(condition, define), location()));
+      }
+      Some(Directive::Else) => match kept_blocks.last_mut() {
+        Some((kept, _)) => *kept = !*kept,
+        None => {
+          return Err(BuildError::unmatched_conditional_directive(
+            id.as_ref(),
+            location(),
+            "@else",
+          ))
+        }
+      },
+      Some(Directive::Endif) => {
+        if kept_blocks.pop().is_none() {
+          return Err(BuildError::unmatched_conditional_directive(
+            id.as_ref(),
+            location(),
+            "@endif",
+          ));
+        }
+      }
+      None => {}
+    }
+    if directive.is_none() && kept_blocks.iter().all(|(kept, _)| *kept) {
+      output.push_str(line);
+    } else if line.ends_with('\n') {
+      output.push('\n');
+    }
+  }
+  if let Some((_, location)) = kept_blocks.last() {
+    return Err(BuildError::unmatched_conditional_directive(
+      id.as_ref(),
+      *location,
+      "@if",
+    ));
+  }
+  Ok((output, tested_names))
+}
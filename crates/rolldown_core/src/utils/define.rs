@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use rustc_hash::{FxHashMap, FxHashSet};
+use swc_core::common::{Span, SyntaxContext, DUMMY_SP};
+use swc_core::ecma::ast;
+use swc_core::ecma::parser::Syntax;
+use swc_core::ecma::visit::{VisitMut, VisitMutWith};
+
+use crate::{BuildError, UnaryBuildResult, COMPILER};
+
+/// The builtin version of `define` of esbuild, which works on the AST after resolving, unlike
+/// `builtins.replace`. Values are parsed once and shared by all modules.
+pub(crate) struct Definitions {
+  values: FxHashMap<String, ast::Expr>,
+  /// Names replaced or tested by `// @if` in any module, so the rest could be reported as unused.
+  used: Mutex<FxHashSet<String>>,
+}
+
+impl Definitions {
+  pub(crate) fn new(define: &HashMap<String, String>) -> UnaryBuildResult<Self> {
+    let values = define
+      .iter()
+      .map(|(name, value)| Ok((name.clone(), parse_value(name, value)?)))
+      .collect::<UnaryBuildResult<_>>()?;
+    Ok(Self {
+      values,
+      used: Default::default(),
+    })
+  }
+
+  pub(crate) fn apply(&self, ast: &mut ast::Module, unresolved_ctxt: SyntaxContext) {
+    self.mark_used(rolldown_swc_visitors::define(
+      ast,
+      &self.values,
+      unresolved_ctxt,
+    ));
+  }
+
+  /// Names tested by conditional compilation comments are used, although they aren't replaced.
+  pub(crate) fn mark_used<'n>(&self, names: impl IntoIterator<Item = &'n str>) {
+    let mut names = names.into_iter().peekable();
+    if names.peek().is_some() {
+      let mut used = self.used.lock().unwrap();
+      used.extend(names.map(ToString::to_string));
+    }
+  }
+
+  /// Names not used in any module yet, sorted. They're likely typos.
+  pub(crate) fn unused_names(&self) -> Vec<&str> {
+    let used = self.used.lock().unwrap();
+    let mut unused = self
+      .values
+      .keys()
+      .filter(|name| !used.contains(*name))
+      .map(|name| name.as_str())
+      .collect::<Vec<_>>();
+    unused.sort_unstable();
+    unused
+  }
+}
+
+fn parse_value(name: &str, value: &str) -> UnaryBuildResult<ast::Expr> {
+  parse_single_expr(format!("define:{name}"), value)
+    .ok_or_else(|| BuildError::invalid_define_value(name, value))
+}
+
+/// The value is parsed in parentheses, so it must be a single expression to be a single statement.
+/// `file_name` only names the parsed value in the source map.
+pub(crate) fn parse_single_expr(file_name: String, value: &str) -> Option<ast::Expr> {
+  let fm = COMPILER.create_source_file(PathBuf::from(file_name), format!("({value})"));
+  let ast = COMPILER.parse(fm, Syntax::Es(Default::default())).ok()?;
+  let mut body = ast.body.into_iter();
+  let expr = match (body.next(), body.next()) {
+    (Some(ast::ModuleItem::Stmt(ast::Stmt::Expr(stmt))), None) => stmt.expr,
+    _ => return None,
+  };
+  let ast::Expr::Paren(paren) = *expr else {
+    return None;
+  };
+  let mut expr = *paren.expr;
+  // Spans point to the parsed value, which isn't part of any module.
+  expr.visit_mut_with(&mut SpanRemover);
+  Some(expr)
+}
+
+struct SpanRemover;
+
+impl VisitMut for SpanRemover {
+  fn visit_mut_span(&mut self, span: &mut Span) {
+    *span = DUMMY_SP;
+  }
+}
@@ -0,0 +1,45 @@
+use std::path::{Component, Path};
+
+use rolldown_common::ModuleId;
+use swc_core::ecma::{
+  ast,
+  visit::{VisitMut, VisitMutWith},
+};
+
+/// Name of the file emitted for a module, like the ones of `Loader::Wasm` and
+/// `Loader::NativeAddon`, which is the path relative to `cwd`, so it's unique.
+///
+/// `../shared/a.wasm` => `_/shared/a.wasm`, which stays in the output directory.
+pub(crate) fn emitted_file_name(id: &ModuleId, cwd: &Path) -> String {
+  Path::new(&id.stable_id(cwd))
+    .components()
+    .map(|component| match component {
+      Component::Normal(name) => name.to_string_lossy(),
+      _ => "_".into(),
+    })
+    .collect::<Vec<_>>()
+    .join("/")
+}
+
+/// Point the URL of the emitted file in the module loading it to the file relative to the chunk,
+/// which is only known after chunks are named.
+pub(crate) fn rewrite_emitted_file_url(ast: &mut ast::Module, filename: &str, specifier: &str) {
+  struct UrlRewriter<'a> {
+    filename: &'a str,
+    specifier: &'a str,
+  }
+
+  impl VisitMut for UrlRewriter<'_> {
+    fn visit_mut_str(&mut self, node: &mut ast::Str) {
+      if &*node.value == self.filename {
+        node.value = self.specifier.into();
+        node.raw = None;
+      }
+    }
+  }
+
+  ast.visit_mut_with(&mut UrlRewriter {
+    filename,
+    specifier,
+  });
+}
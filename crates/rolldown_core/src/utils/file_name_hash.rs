@@ -0,0 +1,112 @@
+use std::fmt::Write;
+
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use rustc_hash::{FxHashMap, FxHashSet};
+use sha1::{Digest, Sha1};
+
+use crate::{BuildError, UnaryBuildResult};
+
+/// Length of `[hash]` in file names.
+const HASH_LEN: usize = 8;
+
+/// Placeholders are indexed by 4 hex digits.
+const MAX_HASH_PLACEHOLDERS: usize = 0x10000;
+
+/// Placeholders have the same length as hashes, so replacing them keeps columns of source maps.
+static HASH_PLACEHOLDER: Lazy<Regex> = Lazy::new(|| Regex::new(r"!~[0-9a-f]{4}~!").unwrap());
+
+/// `[hash]` is rendered as the placeholder of the chunk at first, since the content is unknown
+/// until the chunk and chunks it imports are rendered. `chunks` is the number of chunks in total.
+pub(crate) fn hash_placeholder(index: usize, chunks: usize) -> UnaryBuildResult<String> {
+  if chunks > MAX_HASH_PLACEHOLDERS {
+    return Err(BuildError::too_many_hashed_chunks(
+      chunks,
+      MAX_HASH_PLACEHOLDERS,
+    ));
+  }
+  Ok(format!("!~{index:04x}~!"))
+}
+
+pub(crate) fn find_hash_placeholder(file_name: &str) -> Option<&str> {
+  HASH_PLACEHOLDER.find(file_name).map(|found| found.as_str())
+}
+
+/// Hashes of chunks by their placeholders. A hash covers the code of the chunk and the ones it
+/// references transitively, so it changes once any file name in its code does. Placeholders are
+/// erased before hashing, since they're assigned in no particular order.
+pub(crate) fn content_hashes(
+  code_by_placeholder: &FxHashMap<String, &str>,
+) -> FxHashMap<String, String> {
+  let erased = code_by_placeholder
+    .iter()
+    .map(|(placeholder, code)| {
+      (
+        placeholder.as_str(),
+        HASH_PLACEHOLDER.replace_all(code, "!~~~~~~!"),
+      )
+    })
+    .collect::<FxHashMap<_, _>>();
+
+  code_by_placeholder
+    .keys()
+    .map(|placeholder| {
+      let mut referenced = FxHashSet::default();
+      let mut stack = vec![placeholder.as_str()];
+      while let Some(current) = stack.pop() {
+        if referenced.insert(current) {
+          stack.extend(
+            HASH_PLACEHOLDER
+              .find_iter(code_by_placeholder[current])
+              .map(|found| found.as_str())
+              .filter(|found| code_by_placeholder.contains_key(*found)),
+          );
+        }
+      }
+      let mut referenced_codes = referenced
+        .into_iter()
+        .filter(|referenced| referenced != placeholder)
+        .map(|referenced| &erased[referenced])
+        .collect::<Vec<_>>();
+      referenced_codes.sort();
+
+      let mut hasher = Sha1::new();
+      hasher.update(erased[placeholder.as_str()].as_bytes());
+      for code in referenced_codes {
+        // Separate codes, so moving code from one to another changes the hash.
+        hasher.update(code.len().to_le_bytes());
+        hasher.update(code.as_bytes());
+      }
+      (placeholder.clone(), hex_hash(hasher))
+    })
+    .collect()
+}
+
+/// Another hash derived from `hash`, for a file name conflicting with another one after hashing.
+pub(crate) fn rehash(hash: &str) -> String {
+  let mut hasher = Sha1::new();
+  hasher.update(hash.as_bytes());
+  hex_hash(hasher)
+}
+
+fn hex_hash(hasher: Sha1) -> String {
+  hasher
+    .finalize()
+    .iter()
+    .fold(String::new(), |mut hex, byte| {
+      write!(hex, "{byte:02x}").unwrap();
+      hex
+    })[..HASH_LEN]
+    .to_string()
+}
+
+pub(crate) fn replace_hash_placeholders(text: &str, hashes: &FxHashMap<String, String>) -> String {
+  HASH_PLACEHOLDER
+    .replace_all(text, |captures: &Captures| {
+      hashes
+        .get(&captures[0])
+        .cloned()
+        .unwrap_or_else(|| captures[0].to_string())
+    })
+    .into_owned()
+}
@@ -0,0 +1,31 @@
+use std::path::Path;
+
+/// Guesses the MIME type of a file by its extension, falling back to `application/octet-stream`.
+pub(crate) fn guess_mime_type(p: &Path) -> &'static str {
+  let ext = p
+    .extension()
+    .and_then(|ext| ext.to_str())
+    .map(|ext| ext.to_ascii_lowercase());
+  match ext.as_deref() {
+    Some("png") => "image/png",
+    Some("jpg" | "jpeg") => "image/jpeg",
+    Some("gif") => "image/gif",
+    Some("svg") => "image/svg+xml",
+    Some("webp") => "image/webp",
+    Some("avif") => "image/avif",
+    Some("ico") => "image/x-icon",
+    Some("bmp") => "image/bmp",
+    Some("woff") => "font/woff",
+    Some("woff2") => "font/woff2",
+    Some("ttf") => "font/ttf",
+    Some("otf") => "font/otf",
+    Some("mp3") => "audio/mpeg",
+    Some("wav") => "audio/wav",
+    Some("mp4") => "video/mp4",
+    Some("webm") => "video/webm",
+    Some("wasm") => "application/wasm",
+    Some("json") => "application/json",
+    Some("txt") => "text/plain",
+    _ => "application/octet-stream",
+  }
+}
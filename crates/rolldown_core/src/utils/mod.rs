@@ -1,18 +1,62 @@
 mod resolve_id;
-use std::{path::Path, str::FromStr};
+use std::{collections::BTreeMap, path::Path, str::FromStr};
 
 pub(crate) use resolve_id::*;
 mod name_helpers;
 pub use name_helpers::*;
 mod preset_of_used_names;
 pub(crate) use preset_of_used_names::*;
+mod replace;
+pub(crate) use replace::*;
+mod define;
+pub(crate) use define::*;
+mod conditional_compilation;
+pub(crate) use conditional_compilation::*;
+mod file_name_hash;
+pub(crate) use file_name_hash::*;
+mod tsconfig;
 use rolldown_common::Loader;
+use tracing::instrument::WithSubscriber;
+pub(crate) use tsconfig::*;
+mod mime_type;
+pub(crate) use mime_type::*;
+mod wasm;
+pub(crate) use wasm::*;
+mod native_addon;
+pub(crate) use native_addon::*;
+mod emitted_file;
+pub(crate) use emitted_file::*;
+mod node_builtins;
+pub(crate) use node_builtins::*;
 
-pub fn extract_loader_by_path(p: &Path) -> Loader {
-  p.extension()
-    .and_then(|ext| ext.to_str())
-    // Unknown extension should treat like JavaScript for Rollup-compatibility
-    .map(Loader::from_str)
-    .map(|l| l.unwrap_or(Loader::Js))
+/// `loaders` are looked up before the builtin mapping of extensions, which is skipped unless
+/// `detect_by_ext` is enabled. Explicit mappings always apply.
+pub fn extract_loader_by_path(
+  p: &Path,
+  loaders: &BTreeMap<String, Loader>,
+  detect_by_ext: bool,
+) -> Loader {
+  let ext = p.extension().and_then(|ext| ext.to_str());
+  if let Some(loader) = ext.and_then(|ext| loaders.get(ext)) {
+    return *loader;
+  }
+  ext
+    .filter(|_| detect_by_ext)
+    .map(|ext| match ext {
+      "mjs" | "cjs" => Loader::Js,
+      "mts" | "cts" => Loader::Ts,
+      // Unknown extension should treat like JavaScript for Rollup-compatibility
+      _ => Loader::from_str(ext).unwrap_or(Loader::Js),
+    })
     .unwrap_or(Loader::Js)
 }
+
+/// `tokio::spawn`, but the task logs to the subscriber of the caller, which is the one of the
+/// bundler during builds.
+pub(crate) fn spawn<F>(future: F) -> tokio::task::JoinHandle<F::Output>
+where
+  F: std::future::Future + Send + 'static,
+  F::Output: Send + 'static,
+{
+  tokio::spawn(future.with_current_subscriber())
+}
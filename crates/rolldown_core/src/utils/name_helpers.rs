@@ -65,6 +65,11 @@ static ILLEGAL_CHARACTERS: Lazy<regex::Regex> = Lazy::new(|| regex::Regex::new(r
 
 pub static CAPTURE_WORD_RE: Lazy<regex::Regex> = Lazy::new(|| regex::Regex::new(r"-(\w)").unwrap());
 
+/// Whether `value` could be used as a binding name as it is.
+pub fn is_legal(value: &str) -> bool {
+  !value.is_empty() && !ILLEGAL_CHARACTERS.is_match(value) && !need_escape(value)
+}
+
 pub fn make_legal(value: &str) -> String {
   let value = ILLEGAL_CHARACTERS.replace_all(value, "_");
 
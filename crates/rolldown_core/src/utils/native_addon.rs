@@ -0,0 +1,26 @@
+use std::path::Path;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use rolldown_common::ModuleId;
+
+use crate::{emitted_file_name, BuildError, UnaryBuildResult};
+
+/// A `.node` file loaded by `Loader::NativeAddon`, which is emitted next to chunks.
+#[derive(Debug, Clone)]
+pub(crate) struct NativeAddon {
+  pub(crate) filename: String,
+  pub(crate) bytes: Vec<u8>,
+}
+
+impl NativeAddon {
+  /// `source` is base64 encoded, like the sources of other binary loaders.
+  pub(crate) fn new(id: &ModuleId, source: &str, cwd: &Path) -> UnaryBuildResult<Self> {
+    let bytes = STANDARD
+      .decode(source)
+      .map_err(|e| BuildError::parse_data_failed(id.as_ref(), "native addon", e.to_string()))?;
+    Ok(Self {
+      filename: emitted_file_name(id, cwd),
+      bytes,
+    })
+  }
+}
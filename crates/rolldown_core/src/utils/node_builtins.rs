@@ -0,0 +1,83 @@
+use rolldown_common::ModuleId;
+
+/// Prefix of node builtins, like `node:fs`. Builtins only available with the prefix, like
+/// `node:test`, aren't in `NODE_BUILTINS`.
+const NODE_BUILTIN_PREFIX: &str = "node:";
+
+/// `require('module').builtinModules` without internal ones, which could be imported without the
+/// prefix, like `fs`.
+const NODE_BUILTINS: [&str; 53] = [
+  "assert",
+  "assert/strict",
+  "async_hooks",
+  "buffer",
+  "child_process",
+  "cluster",
+  "console",
+  "constants",
+  "crypto",
+  "dgram",
+  "diagnostics_channel",
+  "dns",
+  "dns/promises",
+  "domain",
+  "events",
+  "fs",
+  "fs/promises",
+  "http",
+  "http2",
+  "https",
+  "inspector",
+  "module",
+  "net",
+  "os",
+  "path",
+  "path/posix",
+  "path/win32",
+  "perf_hooks",
+  "process",
+  "punycode",
+  "querystring",
+  "readline",
+  "readline/promises",
+  "repl",
+  "stream",
+  "stream/consumers",
+  "stream/promises",
+  "stream/web",
+  "string_decoder",
+  "sys",
+  "timers",
+  "timers/promises",
+  "tls",
+  "trace_events",
+  "tty",
+  "url",
+  "util",
+  "util/types",
+  "v8",
+  "vm",
+  "wasi",
+  "worker_threads",
+  "zlib",
+];
+
+/// Name of the node builtin `specifier` refers to without the prefix, like `fs` of both `node:fs`
+/// and `fs`.
+pub(crate) fn node_builtin_name(specifier: &str) -> Option<&str> {
+  specifier
+    .strip_prefix(NODE_BUILTIN_PREFIX)
+    .or_else(|| NODE_BUILTINS.contains(&specifier).then_some(specifier))
+}
+
+/// Virtual id of the stub replacing the builtin `name` for the browser platform.
+pub(crate) fn node_builtin_stub_id(name: &str) -> ModuleId {
+  ModuleId::new(format!("\0{NODE_BUILTIN_PREFIX}{name}"), false)
+}
+
+/// The builtin stubbed by the module `id`, like `node:fs`.
+pub(crate) fn stubbed_node_builtin(id: &ModuleId) -> Option<&str> {
+  id.as_ref()
+    .strip_prefix('\0')
+    .filter(|builtin| builtin.starts_with(NODE_BUILTIN_PREFIX))
+}
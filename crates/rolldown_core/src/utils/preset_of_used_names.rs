@@ -13,6 +13,14 @@ pub(crate) fn preset_of_used_names(format: &ModuleFormat) -> Vec<JsWord> {
       preset.push("__filename".into());
       preset.push("__dirname".into());
     }
+    ModuleFormat::Amd => {
+      preset.push("define".into());
+      preset.push(js_word!("module"));
+      preset.push(js_word!("require"));
+    }
+    ModuleFormat::Iife => {
+      preset.push("exports".into());
+    }
   }
 
   preset
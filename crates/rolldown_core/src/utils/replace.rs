@@ -0,0 +1,181 @@
+use regex::{Captures, Match, Regex};
+use rustc_hash::FxHashMap;
+use swc_core::common::{BytePos, Spanned};
+use swc_core::ecma::ast;
+use swc_core::ecma::visit::{VisitMut, VisitMutWith};
+
+use crate::{parse_single_expr, BuildError, ReplaceOptions, UnaryBuildResult, COMPILER};
+
+/// Name of the group capturing the key, so groups in user-defined delimiters don't shift it.
+const KEY_GROUP: &str = "key";
+
+/// The builtin version of `@rollup/plugin-replace`.
+///
+/// Keys are replaced in the source code before parsing. With `prevent_assignment`, they're
+/// replaced on the AST instead, so assignment targets are known without parsing twice. Only keys
+/// being whole expressions are replaced then, and values need to be single expressions.
+pub(crate) struct Replacer {
+  regex: Regex,
+  delimiters: (String, String),
+  values: FxHashMap<String, String>,
+  /// Parsed `values`, only if `prevent_assignment` is enabled.
+  exprs: Option<FxHashMap<String, ast::Expr>>,
+}
+
+/// Byte ranges of keys found in the source, which are replaced on the AST by `Replacer::replace_ast`.
+pub(crate) type KeysInSource<'a> = FxHashMap<(usize, usize), &'a str>;
+
+impl Replacer {
+  pub(crate) fn new(options: &ReplaceOptions) -> UnaryBuildResult<Self> {
+    let mut keys = options
+      .values
+      .iter()
+      .map(|(key, _)| key.as_str())
+      .collect::<Vec<_>>();
+    // The regex picks the first matched alternative, so longer keys need to come first.
+    keys.sort_by(|a, b| b.len().cmp(&a.len()).then(a.cmp(b)));
+    keys.dedup();
+
+    let (start, end) = &options.delimiters;
+    let pattern = format!(
+      "(?:{start})(?P<{KEY_GROUP}>{})(?:{end})",
+      keys
+        .iter()
+        .map(|key| regex::escape(key))
+        .collect::<Vec<_>>()
+        .join("|")
+    );
+    let regex = Regex::new(&pattern)
+      .map_err(|e| BuildError::invalid_replace_delimiters(options.delimiters.clone(), e))?;
+
+    let exprs = options
+      .prevent_assignment
+      .then(|| {
+        options
+          .values
+          .iter()
+          .map(|(key, value)| {
+            let expr = parse_single_expr(format!("replace:{key}"), value)
+              .ok_or_else(|| BuildError::invalid_replace_value(key, value))?;
+            Ok((key.clone(), expr))
+          })
+          .collect::<UnaryBuildResult<_>>()
+      })
+      .transpose()?;
+
+    Ok(Self {
+      regex,
+      delimiters: options.delimiters.clone(),
+      values: options.values.iter().cloned().collect(),
+      exprs,
+    })
+  }
+
+  /// The matched key and its value.
+  fn entry_of<'c>(&self, caps: &Captures<'c>) -> UnaryBuildResult<(Match<'c>, &str, &str)> {
+    let key = caps.name(KEY_GROUP).ok_or_else(|| {
+      BuildError::invalid_replace_delimiters(self.delimiters.clone(), "the key isn't captured")
+    })?;
+    let (name, value) = self.values.get_key_value(key.as_str()).ok_or_else(|| {
+      BuildError::invalid_replace_delimiters(
+        self.delimiters.clone(),
+        format!(r#"they capture "{}", which isn't a key"#, key.as_str()),
+      )
+    })?;
+    Ok((key, name, value))
+  }
+
+  /// Replaces keys in the source code. With `prevent_assignment`, the code is returned as it is,
+  /// along with keys found in it, which are left to `replace_ast`.
+  pub(crate) fn replace(&self, code: String) -> UnaryBuildResult<(String, KeysInSource<'_>)> {
+    if !self.regex.is_match(&code) {
+      return Ok((code, Default::default()));
+    }
+
+    if self.exprs.is_some() {
+      let mut keys = KeysInSource::default();
+      for caps in self.regex.captures_iter(&code) {
+        let (key, name, _) = self.entry_of(&caps)?;
+        keys.insert((key.start(), key.end()), name);
+      }
+      return Ok((code, keys));
+    }
+
+    let mut replaced = String::with_capacity(code.len());
+    let mut last_end = 0;
+    for caps in self.regex.captures_iter(&code) {
+      let (_, _, value) = self.entry_of(&caps)?;
+      // Like rollup, delimiters are part of the match and get replaced too.
+      let whole = caps.get(0).unwrap();
+      replaced.push_str(&code[last_end..whole.start()]);
+      replaced.push_str(value);
+      last_end = whole.end();
+    }
+    replaced.push_str(&code[last_end..]);
+
+    Ok((replaced, Default::default()))
+  }
+
+  /// Replaces keys found by `replace` that are whole expressions of the AST parsed from the same
+  /// source, except assignment targets.
+  pub(crate) fn replace_ast<'a>(&'a self, ast: &mut ast::Module, keys: KeysInSource<'a>) {
+    let Some(exprs) = &self.exprs else {
+      return;
+    };
+    if keys.is_empty() {
+      return;
+    }
+    let start_pos = COMPILER.cm.lookup_byte_offset(ast.span.lo).sf.start_pos;
+    ast.visit_mut_with(&mut AstReplacer {
+      exprs,
+      keys,
+      start_pos,
+    });
+  }
+}
+
+struct AstReplacer<'a> {
+  exprs: &'a FxHashMap<String, ast::Expr>,
+  keys: KeysInSource<'a>,
+  /// Start of the source file, which spans are offset by.
+  start_pos: BytePos,
+}
+
+impl AstReplacer<'_> {
+  fn value_of(&self, expr: &ast::Expr) -> Option<ast::Expr> {
+    let span = expr.span();
+    let start = span.lo.0.checked_sub(self.start_pos.0)? as usize;
+    let end = span.hi.0.checked_sub(self.start_pos.0)? as usize;
+    let key = self.keys.get(&(start, end))?;
+    self.exprs.get(*key).cloned()
+  }
+
+  /// Assignment targets are kept. Parts of them are still replaced, like `process.env` in
+  /// `process.env.DEBUG = true`, which is the same as rollup.
+  fn visit_mut_target(&mut self, target: &mut ast::Expr) {
+    target.visit_mut_children_with(self);
+  }
+}
+
+impl VisitMut for AstReplacer<'_> {
+  fn visit_mut_expr(&mut self, node: &mut ast::Expr) {
+    match self.value_of(node) {
+      Some(value) => *node = value,
+      None => node.visit_mut_children_with(self),
+    }
+  }
+
+  fn visit_mut_assign_expr(&mut self, node: &mut ast::AssignExpr) {
+    match &mut node.left {
+      ast::PatOrExpr::Expr(target) | ast::PatOrExpr::Pat(box ast::Pat::Expr(target)) => {
+        self.visit_mut_target(target)
+      }
+      ast::PatOrExpr::Pat(pat) => pat.visit_mut_with(self),
+    }
+    node.right.visit_mut_with(self);
+  }
+
+  fn visit_mut_update_expr(&mut self, node: &mut ast::UpdateExpr) {
+    self.visit_mut_target(&mut node.arg);
+  }
+}
@@ -1,40 +1,88 @@
-use rolldown_common::ModuleId;
-use rolldown_plugin::ResolveArgs;
+use rolldown_common::{ExternalKind, ModuleId};
+use rolldown_plugin::{External, ImportKind, ResolveArgs};
 use rolldown_resolver::Resolver;
 use sugar_path::AsPath;
 
-use crate::{SharedBuildPluginDriver, UnaryBuildResult};
+use crate::{BuildResult, ResolutionSource, SharedBuildPluginDriver};
 
 pub(crate) async fn resolve_id(
   resolver: &Resolver,
   specifier: &str,
   importer: Option<&ModuleId>,
+  kind: ImportKind,
   _preserve_symlinks: bool,
   plugin_driver: &SharedBuildPluginDriver,
-) -> UnaryBuildResult<Option<ModuleId>> {
+) -> BuildResult<Option<(ModuleId, ResolutionSource)>> {
   let plugin_result = plugin_driver
     .read()
     .await
     .resolve(ResolveArgs {
       importer,
       specifier,
+      kind,
     })
     .await?;
 
-  if plugin_result.is_some() {
-    return Ok(
-      plugin_result.map(|plugin_result| ModuleId::new(plugin_result.id, plugin_result.external)),
-    );
+  if let Some((plugin_name, plugin_result)) = plugin_result {
+    return Ok(Some((
+      ModuleId::with_external(plugin_result.id, external_kind(plugin_result.external)),
+      ResolutionSource::ResolveHook(plugin_name),
+    )));
   }
 
-  let importer = importer.map(|id| id.as_ref());
-  // external modules (non-entry modules that start with neither '.' or '/')
+  let importer_path = importer.map(|id| id.as_ref());
+  // external modules (non-entry modules that start with neither '.', '/' or `file://`)
   // are skipped at this stage.
-  if importer.is_some() && !specifier.as_path().is_absolute() && !specifier.starts_with('.') {
-    return Ok(None);
+  let resolved = if importer.is_some()
+    && !specifier.as_path().is_absolute()
+    && !specifier.starts_with('.')
+    && !specifier.starts_with("file://")
+  {
+    Ok(None)
+  } else {
+    resolver.resolve(importer_path, specifier).map(Some)
+  };
+
+  if let Ok(Some(resolved)) = resolved {
+    let is_in_node_modules = resolved
+      .as_path()
+      .components()
+      .any(|c| c.as_os_str() == "node_modules");
+    let source = if is_in_node_modules {
+      ResolutionSource::NodeModules(resolver.package_field(specifier, &resolved))
+    } else {
+      ResolutionSource::Path
+    };
+    return Ok(Some((ModuleId::new(resolved, false), source)));
+  }
+
+  // Nothing resolved the specifier. Catch-all plugins get the last chance before the specifier is
+  // treated as external or reported as unresolved.
+  let fallback_result = plugin_driver
+    .read()
+    .await
+    .resolve_fallback(ResolveArgs {
+      importer,
+      specifier,
+      kind,
+    })
+    .await?;
+
+  if let Some((plugin_name, fallback_result)) = fallback_result {
+    return Ok(Some((
+      ModuleId::with_external(fallback_result.id, external_kind(fallback_result.external)),
+      ResolutionSource::ResolveFallbackHook(plugin_name),
+    )));
   }
 
-  let resolved = resolver.resolve(importer, specifier)?;
+  // Either unresolved or an error of resolving
+  resolved.map(|_| None).map_err(Into::into)
+}
 
-  Ok(Some(ModuleId::new(resolved, false)))
+fn external_kind(external: External) -> Option<ExternalKind> {
+  match external {
+    External::False => None,
+    External::True | External::Absolute => Some(ExternalKind::Absolute),
+    External::Relative => Some(ExternalKind::Relative),
+  }
 }
@@ -0,0 +1,202 @@
+use std::path::{Path, PathBuf};
+
+use futures::future::{BoxFuture, FutureExt};
+use rolldown_resolver::TsConfigPaths;
+use serde_json::Value;
+use sugar_path::SugarPath;
+
+use crate::{BuildError, UnaryBuildResult};
+
+/// Compiler options of a `tsconfig.json` the bundler cares about, including the ones inherited via
+/// `extends`. `None` means unset.
+#[derive(Debug, Default)]
+pub(crate) struct TsConfigFile {
+  pub(crate) use_define_for_class_fields: Option<bool>,
+  /// Absolute path of `baseUrl`.
+  pub(crate) base_url: Option<PathBuf>,
+  /// `paths` and the directory of the `tsconfig.json` setting them, which targets are relative to
+  /// without `baseUrl`.
+  pub(crate) paths: Option<(Vec<(String, Vec<String>)>, PathBuf)>,
+}
+
+impl TsConfigFile {
+  /// Reads the `tsconfig.json` at `path`, which could have comments and trailing commas, along with
+  /// the ones it extends. Options of a config override the ones it extends, and later configs of
+  /// `extends` override earlier ones.
+  ///
+  /// Packages in `extends` are looked up in `node_modules` as files, without reading their
+  /// `package.json`.
+  pub(crate) async fn read(path: &Path) -> UnaryBuildResult<Self> {
+    read_extending(path.to_path_buf(), vec![]).await
+  }
+
+  /// `baseUrl` and `paths` for `Resolver::with_tsconfig_paths`. `path` is the config read.
+  pub(crate) fn into_paths(self, path: &Path) -> TsConfigPaths {
+    let (paths, paths_dir) = self.paths.unwrap_or_else(|| {
+      let dir = path.parent().expect("Should have a parent dir");
+      (vec![], dir.to_path_buf())
+    });
+    TsConfigPaths {
+      has_base_url: self.base_url.is_some(),
+      base_url: self.base_url.unwrap_or(paths_dir),
+      paths,
+    }
+  }
+
+  /// Options set by `config` override the ones of `self`.
+  fn extended_by(self, config: Self) -> Self {
+    Self {
+      use_define_for_class_fields: config
+        .use_define_for_class_fields
+        .or(self.use_define_for_class_fields),
+      base_url: config.base_url.or(self.base_url),
+      paths: config.paths.or(self.paths),
+    }
+  }
+}
+
+/// `extending` are the configs extending the one at `path`, which can't be extended again.
+fn read_extending(
+  path: PathBuf,
+  mut extending: Vec<PathBuf>,
+) -> BoxFuture<'static, UnaryBuildResult<TsConfigFile>> {
+  async move {
+    if extending.contains(&path) {
+      return Err(BuildError::invalid_tsconfig(&path, "`extends` is circular"));
+    }
+    let content = tokio::fs::read_to_string(&path)
+      .await
+      .map_err(|err| BuildError::invalid_tsconfig(&path, err))?;
+    let tsconfig = jsonc_parser::parse_to_serde_value(&content, &Default::default())
+      .map_err(|err| BuildError::invalid_tsconfig(&path, err))?
+      .unwrap_or_default();
+    let dir = path.parent().expect("Should have a parent dir");
+
+    let extends = match tsconfig.get("extends") {
+      Some(Value::String(extends)) => vec![extends.as_str()],
+      Some(Value::Array(extends)) => extends
+        .iter()
+        .map(Value::as_str)
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(|| {
+          BuildError::invalid_tsconfig(&path, "`extends` should be a string or an array of strings")
+        })?,
+      Some(_) => {
+        return Err(BuildError::invalid_tsconfig(
+          &path,
+          "`extends` should be a string or an array of strings",
+        ))
+      }
+      None => vec![],
+    };
+    let own = read_compiler_options(&path, tsconfig.get("compilerOptions"))?;
+
+    let mut config = TsConfigFile::default();
+    extending.push(path.clone());
+    for extends in extends {
+      let extended = find_extended(dir, extends).await.ok_or_else(|| {
+        BuildError::invalid_tsconfig(&path, format!(r#"Cannot find "{extends}" it extends"#))
+      })?;
+      config = config.extended_by(read_extending(extended, extending.clone()).await?);
+    }
+    Ok(config.extended_by(own))
+  }
+  .boxed()
+}
+
+fn read_compiler_options(path: &Path, options: Option<&Value>) -> UnaryBuildResult<TsConfigFile> {
+  let dir = path.parent().expect("Should have a parent dir");
+
+  let use_define_for_class_fields =
+    match options.and_then(|options| options.get("useDefineForClassFields")) {
+      Some(Value::Bool(value)) => Some(*value),
+      Some(_) => {
+        return Err(BuildError::invalid_tsconfig(
+          path,
+          "`useDefineForClassFields` should be a boolean",
+        ))
+      }
+      None => None,
+    };
+
+  let base_url = match options.and_then(|options| options.get("baseUrl")) {
+    Some(Value::String(base_url)) => Some(dir.join(base_url).normalize()),
+    Some(_) => {
+      return Err(BuildError::invalid_tsconfig(
+        path,
+        "`baseUrl` should be a string",
+      ))
+    }
+    None => None,
+  };
+
+  let paths = match options.and_then(|options| options.get("paths")) {
+    Some(Value::Object(paths)) => Some(
+      paths
+        .iter()
+        .map(|(pattern, targets)| {
+          let targets = targets
+            .as_array()
+            .and_then(|targets| {
+              targets
+                .iter()
+                .map(|target| target.as_str().map(ToString::to_string))
+                .collect::<Option<Vec<_>>>()
+            })
+            .ok_or_else(|| {
+              BuildError::invalid_tsconfig(
+                path,
+                format!(r#"`paths` of "{pattern}" should be an array of strings"#),
+              )
+            })?;
+          Ok((pattern.clone(), targets))
+        })
+        .collect::<UnaryBuildResult<Vec<_>>>()?,
+    ),
+    Some(_) => {
+      return Err(BuildError::invalid_tsconfig(
+        path,
+        "`paths` should be an object",
+      ))
+    }
+    None => None,
+  };
+
+  Ok(TsConfigFile {
+    use_define_for_class_fields,
+    base_url,
+    paths: paths.map(|paths| (paths, dir.to_path_buf())),
+  })
+}
+
+/// `extends` is a path relative to `dir`, or a file of a package in `node_modules`. `.json` could
+/// be omitted, and `tsconfig.json` is looked up in the directory of a package.
+async fn find_extended(dir: &Path, extends: &str) -> Option<PathBuf> {
+  let bases = if extends.starts_with('.') || Path::new(extends).is_absolute() {
+    vec![dir.join(extends).normalize()]
+  } else {
+    dir
+      .ancestors()
+      .map(|dir| dir.join("node_modules").join(extends))
+      .collect()
+  };
+  for base in bases {
+    let candidates = [
+      base.clone(),
+      PathBuf::from(format!("{}.json", base.display())),
+      base.join("tsconfig.json"),
+    ];
+    for candidate in candidates {
+      if is_file(&candidate).await {
+        return Some(candidate);
+      }
+    }
+  }
+  None
+}
+
+pub(crate) async fn is_file(path: &Path) -> bool {
+  tokio::fs::metadata(path)
+    .await
+    .map_or(false, |metadata| metadata.is_file())
+}
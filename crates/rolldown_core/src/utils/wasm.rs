@@ -0,0 +1,144 @@
+use std::path::Path;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use rolldown_common::ModuleId;
+
+use crate::{emitted_file_name, BuildError, UnaryBuildResult};
+
+const MAGIC: &[u8] = b"\0asm";
+const IMPORT_SECTION: u8 = 2;
+const EXPORT_SECTION: u8 = 7;
+
+/// A `.wasm` file loaded by `Loader::Wasm`, which is emitted next to chunks.
+#[derive(Debug, Clone)]
+pub(crate) struct WasmAsset {
+  /// Name of the emitted file, which is the path relative to `cwd`, so it's unique.
+  pub(crate) filename: String,
+  pub(crate) bytes: Vec<u8>,
+  /// Modules imported by the file, in order of appearance.
+  pub(crate) imports: Vec<String>,
+  pub(crate) exports: Vec<String>,
+}
+
+impl WasmAsset {
+  /// `source` is base64 encoded, like the sources of other binary loaders.
+  pub(crate) fn new(id: &ModuleId, source: &str, cwd: &Path) -> UnaryBuildResult<Self> {
+    let invalid = |reason: &str| BuildError::parse_data_failed(id.as_ref(), "WebAssembly", reason);
+    let bytes = STANDARD
+      .decode(source)
+      .map_err(|e| invalid(&e.to_string()))?;
+    let (imports, exports) = read_imports_and_exports(&bytes).map_err(invalid)?;
+
+    Ok(Self {
+      filename: emitted_file_name(id, cwd),
+      bytes,
+      imports,
+      exports,
+    })
+  }
+}
+
+/// Names of imported modules and exports, by reading the import and the export section.
+fn read_imports_and_exports(bytes: &[u8]) -> Result<(Vec<String>, Vec<String>), &'static str> {
+  let mut reader = Reader { bytes, pos: 0 };
+  if reader.take(4)? != MAGIC {
+    return Err("it isn't a WebAssembly binary");
+  }
+  reader.take(4)?;
+
+  let mut imports = vec![];
+  let mut exports = vec![];
+  while reader.pos < bytes.len() {
+    let id = reader.byte()?;
+    let size = reader.leb128()? as usize;
+    let mut section = Reader {
+      bytes: reader.take(size)?,
+      pos: 0,
+    };
+    match id {
+      IMPORT_SECTION => {
+        for _ in 0..section.leb128()? {
+          let module = section.name()?;
+          section.name()?;
+          match section.byte()? {
+            // function: type index
+            0 => {
+              section.leb128()?;
+            }
+            // table: reference type and limits
+            1 => {
+              section.byte()?;
+              section.limits()?;
+            }
+            // memory: limits
+            2 => section.limits()?,
+            // global: value type and mutability
+            3 => {
+              section.take(2)?;
+            }
+            _ => return Err("unknown import kind"),
+          }
+          if !imports.contains(&module) {
+            imports.push(module);
+          }
+        }
+      }
+      EXPORT_SECTION => {
+        for _ in 0..section.leb128()? {
+          exports.push(section.name()?);
+          section.byte()?;
+          section.leb128()?;
+        }
+      }
+      _ => {}
+    }
+  }
+  Ok((imports, exports))
+}
+
+struct Reader<'a> {
+  bytes: &'a [u8],
+  pos: usize,
+}
+
+impl<'a> Reader<'a> {
+  fn take(&mut self, len: usize) -> Result<&'a [u8], &'static str> {
+    let taken = self
+      .bytes
+      .get(self.pos..self.pos + len)
+      .ok_or("unexpected end of the binary")?;
+    self.pos += len;
+    Ok(taken)
+  }
+
+  fn byte(&mut self) -> Result<u8, &'static str> {
+    Ok(self.take(1)?[0])
+  }
+
+  /// Unsigned LEB128.
+  fn leb128(&mut self) -> Result<u64, &'static str> {
+    let mut value = 0;
+    for shift in (0..64).step_by(7) {
+      let byte = self.byte()?;
+      value |= u64::from(byte & 0x7f) << shift;
+      if byte & 0x80 == 0 {
+        return Ok(value);
+      }
+    }
+    Err("integer is too long")
+  }
+
+  fn name(&mut self) -> Result<String, &'static str> {
+    let len = self.leb128()? as usize;
+    String::from_utf8(self.take(len)?.to_vec()).map_err(|_| "name isn't valid UTF-8")
+  }
+
+  fn limits(&mut self) -> Result<(), &'static str> {
+    let has_max = self.byte()? & 1 == 1;
+    self.leb128()?;
+    if has_max {
+      self.leb128()?;
+    }
+    Ok(())
+  }
+}
@@ -7,7 +7,7 @@ use std::{
 use rolldown_common::StaticStr;
 use swc_core::common::SourceFile;
 
-use crate::ErrorKind;
+use crate::{ErrorKind, CWD};
 
 #[derive(Debug)]
 pub struct Error {
@@ -126,6 +126,76 @@ impl Error {
     })
   }
 
+  pub fn re_export_all_from_default_only(importer: PathBuf, importee: PathBuf) -> Self {
+    Self::with_kind(ErrorKind::ReExportAllFromDefaultOnly { importer, importee })
+  }
+
+  pub fn bom_with_shebang(chunk: impl Into<StaticStr>) -> Self {
+    Self::with_kind(ErrorKind::BomWithShebang {
+      chunk: chunk.into(),
+    })
+  }
+
+  pub fn exports_fallback(specifier: impl Into<StaticStr>, resolved: impl AsRef<Path>) -> Self {
+    Self::with_kind(ErrorKind::ExportsFallback {
+      specifier: specifier.into(),
+      resolved: resolved.as_ref().to_path_buf(),
+    })
+  }
+
+  pub fn invalid_replace_delimiters(delimiters: (String, String), reason: impl ToString) -> Self {
+    Self::with_kind(ErrorKind::InvalidReplaceDelimiters {
+      delimiters,
+      reason: reason.to_string(),
+    })
+  }
+
+  pub fn invalid_replace_value(key: impl Into<String>, value: impl Into<String>) -> Self {
+    Self::with_kind(ErrorKind::InvalidReplaceValue {
+      key: key.into(),
+      value: value.into(),
+    })
+  }
+
+  pub fn invalid_resolve_extension(extension: impl Into<String>) -> Self {
+    Self::with_kind(ErrorKind::InvalidResolveExtension {
+      extension: extension.into(),
+    })
+  }
+
+  pub fn invalid_define_value(name: impl Into<String>, value: impl Into<String>) -> Self {
+    Self::with_kind(ErrorKind::InvalidDefineValue {
+      name: name.into(),
+      value: value.into(),
+    })
+  }
+
+  pub fn invalid_manual_chunk_pattern(
+    chunk: impl Into<String>,
+    pattern: impl Into<String>,
+    reason: impl ToString,
+  ) -> Self {
+    Self::with_kind(ErrorKind::InvalidManualChunkPattern {
+      chunk: chunk.into(),
+      pattern: pattern.into(),
+      reason: reason.to_string(),
+    })
+  }
+
+  pub fn invalid_external_pattern(pattern: impl Into<String>, reason: impl ToString) -> Self {
+    Self::with_kind(ErrorKind::InvalidExternalPattern {
+      pattern: pattern.into(),
+      reason: reason.to_string(),
+    })
+  }
+
+  pub fn invalid_tsconfig(path: impl AsRef<Path>, reason: impl ToString) -> Self {
+    Self::with_kind(ErrorKind::InvalidTsConfig {
+      path: path.as_ref().to_path_buf(),
+      reason: reason.to_string(),
+    })
+  }
+
   pub fn unresolved_import(specifier: impl Into<StaticStr>, importer: PathBuf) -> Self {
     Self::with_kind(ErrorKind::UnresolvedImport {
       specifier: specifier.into(),
@@ -133,8 +203,83 @@ impl Error {
     })
   }
 
+  pub fn missing_name_option_for_iife_export(entry_module: impl AsRef<Path>) -> Self {
+    Self::with_kind(ErrorKind::MissingNameOptionForIifeExport {
+      entry_module: entry_module.as_ref().to_path_buf(),
+    })
+  }
+
+  pub fn illegal_identifier_as_name(name: impl Into<String>) -> Self {
+    Self::with_kind(ErrorKind::IllegalIdentifierAsName { name: name.into() })
+  }
+
+  pub fn code_splitting_unsupported(format: impl Into<StaticStr>) -> Self {
+    Self::with_kind(ErrorKind::CodeSplittingUnsupported {
+      format: format.into(),
+    })
+  }
+
+  pub fn file_name_conflict(file_name: impl Into<StaticStr>, entries: Vec<PathBuf>) -> Self {
+    Self::with_kind(ErrorKind::FileNameConflict {
+      file_name: file_name.into(),
+      entries,
+    })
+  }
+
+  pub fn emitted_file_name_conflict(
+    file_name: impl Into<StaticStr>,
+    plugin: impl Into<StaticStr>,
+  ) -> Self {
+    Self::with_kind(ErrorKind::EmittedFileNameConflict {
+      file_name: file_name.into(),
+      plugin: plugin.into(),
+    })
+  }
+
+  pub fn manual_chunk_conflict(module: impl AsRef<Path>, chunks: (String, String)) -> Self {
+    Self::with_kind(ErrorKind::ManualChunkConflict {
+      module: module.as_ref().to_path_buf(),
+      chunks,
+    })
+  }
+
+  /// `importers` are the first importer of `importee` and the one disagreeing with it.
+  pub fn inconsistent_import_assertions(
+    importee: impl AsRef<Path>,
+    importers: (PathBuf, PathBuf),
+    asserted_types: (Option<String>, Option<String>),
+  ) -> Self {
+    Self::with_kind(ErrorKind::InconsistentImportAssertions {
+      importee: importee.as_ref().to_path_buf(),
+      importers,
+      asserted_types: (
+        asserted_types.0.map(Into::into),
+        asserted_types.1.map(Into::into),
+      ),
+    })
+  }
+
   // --- rolldown specific
 
+  pub fn default_with_export_assignment(
+    module: impl AsRef<Path>,
+    export_default: (usize, usize),
+    export_assignment: (usize, usize),
+  ) -> Self {
+    Self::with_kind(ErrorKind::DefaultWithExportAssignment {
+      module: module.as_ref().to_path_buf(),
+      export_default,
+      export_assignment,
+    })
+  }
+
+  pub fn invalid_tla_format(format: impl Into<StaticStr>, module: impl AsRef<Path>) -> Self {
+    Self::with_kind(ErrorKind::InvalidTlaFormat {
+      format: format.into(),
+      module: module.as_ref().to_path_buf(),
+    })
+  }
+
   pub fn parse_js_failed(
     fm: Arc<SourceFile>,
     source: swc_core::ecma::parser::error::Error,
@@ -145,6 +290,101 @@ impl Error {
     })
   }
 
+  pub fn parse_error_recovered(
+    module: impl AsRef<Path>,
+    location: (usize, usize),
+    message: impl Into<String>,
+  ) -> Self {
+    Self::with_kind(ErrorKind::ParseErrorRecovered {
+      module: module.as_ref().to_path_buf(),
+      location,
+      message: message.into(),
+    })
+  }
+
+  pub fn unmatched_conditional_directive(
+    module: impl AsRef<Path>,
+    location: (usize, usize),
+    directive: impl Into<StaticStr>,
+  ) -> Self {
+    Self::with_kind(ErrorKind::UnmatchedConditionalDirective {
+      module: module.as_ref().to_path_buf(),
+      location,
+      directive: directive.into(),
+    })
+  }
+
+  pub fn parse_data_failed(
+    module: impl AsRef<Path>,
+    format: impl Into<StaticStr>,
+    reason: impl ToString,
+  ) -> Self {
+    Self::with_kind(ErrorKind::ParseDataFailed {
+      module: module.as_ref().to_path_buf(),
+      format: format.into(),
+      reason: reason.to_string(),
+    })
+  }
+
+  pub fn missing_entry_exports(
+    entry: impl AsRef<Path>,
+    chunk: impl Into<StaticStr>,
+    exports: Vec<impl Into<StaticStr>>,
+  ) -> Self {
+    Self::with_kind(ErrorKind::MissingEntryExports {
+      entry: entry.as_ref().to_path_buf(),
+      chunk: chunk.into(),
+      exports: exports.into_iter().map(|export| export.into()).collect(),
+    })
+  }
+
+  pub fn node_builtin_stubbed(specifier: impl Into<StaticStr>) -> Self {
+    Self::with_kind(ErrorKind::NodeBuiltinStubbed {
+      specifier: specifier.into(),
+    })
+  }
+
+  pub fn unused_define_key(name: impl Into<String>) -> Self {
+    Self::with_kind(ErrorKind::UnusedDefineKey { name: name.into() })
+  }
+
+  pub fn too_many_symbols(symbols: usize, max_symbols: usize) -> Self {
+    Self::with_kind(ErrorKind::TooManySymbols {
+      symbols,
+      max_symbols,
+    })
+  }
+
+  pub fn too_many_hashed_chunks(chunks: usize, max_chunks: usize) -> Self {
+    Self::with_kind(ErrorKind::TooManyHashedChunks { chunks, max_chunks })
+  }
+
+  pub fn module_level_directive(module: impl AsRef<Path>, directive: impl Into<StaticStr>) -> Self {
+    Self::with_kind(ErrorKind::ModuleLevelDirective {
+      module: module.as_ref().to_path_buf(),
+      directive: directive.into(),
+    })
+  }
+
+  pub fn plugin_diagnostic(
+    plugin: impl Into<StaticStr>,
+    module: Option<impl AsRef<Path>>,
+    diagnostic: Error,
+  ) -> Self {
+    Self::with_kind(ErrorKind::PluginDiagnostic {
+      plugin: plugin.into(),
+      module: module.map(|module| module.as_ref().to_path_buf()),
+      source: Box::new(diagnostic),
+    })
+  }
+
+  pub fn unhoistable_module(module: impl AsRef<Path>, reason: impl Into<StaticStr>) -> Self {
+    Self::with_kind(ErrorKind::UnhoistableModule {
+      module: module.as_ref().to_path_buf(),
+      reason: reason.into(),
+    })
+  }
+
   // --- TODO: we should remove following errors
 
   pub fn io_error(e: std::io::Error) -> Self {
@@ -170,11 +410,20 @@ impl std::error::Error for Error {
   fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
     match &self.kind {
       ErrorKind::Panic { source, .. } => Some(source.as_ref()),
+      ErrorKind::PluginDiagnostic { source, .. } => Some(source.as_ref()),
       _ => None,
     }
   }
 }
 
+impl Error {
+  /// Like `ErrorKind::to_readable_string`, but with contexts.
+  pub fn to_readable_string(&self, cwd: impl AsRef<Path>) -> String {
+    let cwd = cwd.as_ref().to_path_buf();
+    CWD.set(&cwd, || self.to_string())
+  }
+}
+
 impl Display for Error {
   fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
     for ctx in self.contexts.iter().rev() {
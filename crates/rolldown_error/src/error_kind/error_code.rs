@@ -71,3 +71,15 @@ pub const VALIDATION_ERROR: &str = "VALIDATION_ERROR";
 
 pub const PANIC: &str = "PANIC";
 pub const IO_ERROR: &str = "IO_ERROR";
+pub const BOM_WITH_SHEBANG: &str = "BOM_WITH_SHEBANG";
+pub const RE_EXPORT_ALL_FROM_DEFAULT_ONLY: &str = "RE_EXPORT_ALL_FROM_DEFAULT_ONLY";
+pub const EXPORTS_FALLBACK: &str = "EXPORTS_FALLBACK";
+pub const DEFAULT_WITH_EXPORT_ASSIGNMENT: &str = "DEFAULT_WITH_EXPORT_ASSIGNMENT";
+pub const PARSE_ERROR_RECOVERED: &str = "PARSE_ERROR_RECOVERED";
+pub const MISSING_ENTRY_EXPORTS: &str = "MISSING_ENTRY_EXPORTS";
+pub const NODE_BUILTIN_STUBBED: &str = "NODE_BUILTIN_STUBBED";
+pub const UNUSED_DEFINE_KEY: &str = "UNUSED_DEFINE_KEY";
+pub const TOO_MANY_SYMBOLS: &str = "TOO_MANY_SYMBOLS";
+pub const TOO_MANY_HASHED_CHUNKS: &str = "TOO_MANY_HASHED_CHUNKS";
+pub const UNMATCHED_CONDITIONAL_DIRECTIVE: &str = "UNMATCHED_CONDITIONAL_DIRECTIVE";
+pub const UNHOISTABLE_MODULE: &str = "UNHOISTABLE_MODULE";
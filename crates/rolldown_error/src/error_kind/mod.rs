@@ -7,8 +7,8 @@ use std::{
 use rolldown_common::StaticStr;
 use swc_core::common::SourceFile;
 
-use crate::utils::{format_quoted_strings, PathExt};
-use crate::CWD;
+use crate::utils::{format_quoted_strings, format_quoted_strings_with_verbs, PathExt};
+use crate::{Error, CWD};
 
 pub mod error_code;
 
@@ -52,12 +52,149 @@ pub enum ErrorKind {
     specifier: StaticStr,
     importer: PathBuf,
   },
+  InvalidTlaFormat {
+    format: StaticStr,
+    module: PathBuf,
+  },
+  MissingNameOptionForIifeExport {
+    entry_module: PathBuf,
+  },
+  IllegalIdentifierAsName {
+    name: String,
+  },
+  /// `format` only supports a single chunk.
+  CodeSplittingUnsupported {
+    format: StaticStr,
+  },
+  /// `entries` are entry modules of the conflicting chunks.
+  FileNameConflict {
+    file_name: StaticStr,
+    entries: Vec<PathBuf>,
+  },
+  /// `plugin` emits the file in `generate_bundle`.
+  EmittedFileNameConflict {
+    file_name: StaticStr,
+    plugin: StaticStr,
+  },
+  /// `chunks` are names of the manual chunks in sorted order, whose patterns both match `module`.
+  ManualChunkConflict {
+    module: PathBuf,
+    chunks: (String, String),
+  },
+  /// `importers` are the first importer of `importee` and the one disagreeing with it. `None` means
+  /// no type is asserted.
+  InconsistentImportAssertions {
+    importee: PathBuf,
+    importers: (PathBuf, PathBuf),
+    asserted_types: (Option<StaticStr>, Option<StaticStr>),
+  },
 
   // --- Rolldown specific
+  ReExportAllFromDefaultOnly {
+    importer: PathBuf,
+    importee: PathBuf,
+  },
+  BomWithShebang {
+    chunk: StaticStr,
+  },
+  ExportsFallback {
+    specifier: StaticStr,
+    resolved: PathBuf,
+  },
+  InvalidReplaceDelimiters {
+    delimiters: (String, String),
+    reason: String,
+  },
+  InvalidReplaceValue {
+    key: String,
+    value: String,
+  },
+  InvalidResolveExtension {
+    extension: String,
+  },
+  InvalidDefineValue {
+    name: String,
+    value: String,
+  },
+  InvalidManualChunkPattern {
+    chunk: String,
+    pattern: String,
+    reason: String,
+  },
+  InvalidExternalPattern {
+    pattern: String,
+    reason: String,
+  },
+  InvalidTsConfig {
+    path: PathBuf,
+    reason: String,
+  },
+  /// Locations are `(line, column)`, both 1-based.
+  DefaultWithExportAssignment {
+    module: PathBuf,
+    export_default: (usize, usize),
+    export_assignment: (usize, usize),
+  },
   ParseJsFailed {
     source_file: Arc<SourceFile>,
     source: swc_core::ecma::parser::error::Error,
   },
+  /// Location is `(line, column)`, both 1-based.
+  ParseErrorRecovered {
+    module: PathBuf,
+    location: (usize, usize),
+    message: String,
+  },
+  /// `directive` is like `@endif`. Location is `(line, column)`, both 1-based.
+  UnmatchedConditionalDirective {
+    module: PathBuf,
+    location: (usize, usize),
+    directive: StaticStr,
+  },
+  /// `format` is the data format like `YAML`, which the module is loaded as.
+  ParseDataFailed {
+    module: PathBuf,
+    format: StaticStr,
+    reason: String,
+  },
+  /// `exports` are exported by the entry module, but not by the rendered chunk of it.
+  MissingEntryExports {
+    entry: PathBuf,
+    chunk: StaticStr,
+    exports: Vec<StaticStr>,
+  },
+  NodeBuiltinStubbed {
+    specifier: StaticStr,
+  },
+  UnusedDefineKey {
+    name: String,
+  },
+  TooManySymbols {
+    symbols: usize,
+    max_symbols: usize,
+  },
+  /// File names of `chunks` contain `[hash]`, but only `max_chunks` placeholders of hashes exist.
+  TooManyHashedChunks {
+    chunks: usize,
+    max_chunks: usize,
+  },
+  /// `directive` of `module` is dropped, since the module is hoisted into a chunk of another entry.
+  ModuleLevelDirective {
+    module: PathBuf,
+    directive: StaticStr,
+  },
+  /// Emitted by `plugin` via `ctx.warn` or `ctx.error` while handling `module`, which is `None` for
+  /// hooks not called with a module.
+  PluginDiagnostic {
+    plugin: StaticStr,
+    module: Option<PathBuf>,
+    source: Box<Error>,
+  },
+  /// `reason` is what makes the ES module unable to be hoisted, like `eval`.
+  UnhoistableModule {
+    module: PathBuf,
+    reason: StaticStr,
+  },
 
   /// This error means that rolldown panics because unrecoverable error happens.
   ///
@@ -113,12 +250,46 @@ impl Display for ErrorKind {
       ErrorKind::ShimmedExport { binding, exporter } => write!(f, r#"Missing export "{binding}" has been shimmed in module "{}"."#, exporter.may_display_relative()),
       ErrorKind::CircularReexport { export_name, exporter } => write!(f, r#""{export_name}" cannot be exported from "{}" as it is a reexport that references itself."#, exporter.may_display_relative()),
       ErrorKind::UnresolvedImport { specifier, importer } => write!(f, r#"Could not resolve "{specifier}" from "{}""#, importer.may_display_relative()),
+      ErrorKind::InvalidTlaFormat { format, module } => write!(f, r#"Module format "{format}" does not support top-level await, which is used in "{}". Use the "esm" output format rather."#, module.may_display_relative()),
+      ErrorKind::MissingNameOptionForIifeExport { entry_module } => write!(f, r#""output.name" is required by the "iife" format to expose exports of "{}"."#, entry_module.may_display_relative()),
+      ErrorKind::IllegalIdentifierAsName { name } => write!(f, r#"Given name "{name}" of "output.name" is not a legal JS identifier."#),
+      ErrorKind::CodeSplittingUnsupported { format } => write!(f, r#"Invalid value "{format}" for option "output.format" - the format is not supported for code-splitting builds."#),
+      ErrorKind::FileNameConflict { file_name, entries } => write!(f, r#""{file_name}" is emitted by chunks of {}, which would overwrite each other."#, format_quoted_strings(&entries.iter().map(|p| p.may_display_relative()).collect::<Vec<_>>())),
+      ErrorKind::EmittedFileNameConflict { file_name, plugin } => write!(f, r#""{file_name}" emitted by plugin "{plugin}" would overwrite another asset of the output."#),
+      ErrorKind::ManualChunkConflict { module, chunks: (first, second) } => write!(f, r#"Cannot assign "{}" to the "{second}" chunk as it is already in the "{first}" chunk."#, module.may_display_relative()),
+      ErrorKind::InconsistentImportAssertions { importee, importers: (first, second), asserted_types: (first_type, second_type) } => {
+        let describe = |asserted_type: &Option<StaticStr>| asserted_type.as_ref().map_or("no type assertion".to_string(), |asserted_type| format!(r#"type "{asserted_type}""#));
+        write!(f, r#""{}" imports "{}" with {}, but it was already imported by "{}" with {}. Import assertions of the same module must be consistent."#, second.may_display_relative(), importee.may_display_relative(), describe(second_type), first.may_display_relative(), describe(first_type))
+      }
       // Rolldown specific
+      ErrorKind::ReExportAllFromDefaultOnly { importer, importee } => write!(f, r#""{}" re-exports all from "{}", which only has a default export. `export *` doesn't re-export `default`, so nothing is re-exported."#, importer.may_display_relative(), importee.may_display_relative()),
+      ErrorKind::BomWithShebang { chunk } => write!(f, r#"BOM is not emitted for "{chunk}", since the chunk starts with a shebang."#),
+      ErrorKind::ExportsFallback { specifier, resolved } => write!(f, r#""{specifier}" is not exported by its package, resolved to "{}" in the file system instead."#, resolved.may_display_relative()),
+      ErrorKind::InvalidReplaceDelimiters { delimiters: (start, end), reason } => write!(f, r#"Invalid "builtins.replace.delimiters" ["{start}", "{end}"]: {reason}"#),
+      ErrorKind::InvalidReplaceValue { key, value } => write!(f, r#"Invalid "builtins.replace.values" value of "{key}": `{value}` isn't a single expression, which is required by "preventAssignment"."#),
+      ErrorKind::InvalidResolveExtension { extension } => write!(f, r#"Invalid "resolveExtensions" entry "{extension}": extensions must begin with a dot, like ".mjs"."#),
+      ErrorKind::InvalidDefineValue { name, value } => write!(f, r#"Invalid "define" value of "{name}": `{value}` isn't a single expression. Strings need to be quoted, like `"production"`."#),
+      ErrorKind::InvalidManualChunkPattern { chunk, pattern, reason } => write!(f, r#"Invalid "manualChunks" pattern "{pattern}" of the "{chunk}" chunk: {reason}"#),
+      ErrorKind::InvalidExternalPattern { pattern, reason } => write!(f, r#"Invalid "external" pattern "{pattern}": {reason}"#),
+      ErrorKind::InvalidTsConfig { path, reason } => write!(f, r#"Failed to read tsconfig "{}": {reason}"#, path.may_display_relative()),
+      ErrorKind::DefaultWithExportAssignment { module, export_default: (default_line, default_column), export_assignment: (assignment_line, assignment_column) } => write!(f, r#""{}" has both `export default` at {default_line}:{default_column} and `export =` at {assignment_line}:{assignment_column}, which can't be used together."#, module.may_display_relative()),
       ErrorKind::Panic { source } => source.fmt(f),
       ErrorKind::Napi { status, reason } => write!(f, "Napi error: {} {}", status, reason),
       ErrorKind::ParseJsFailed { source_file, .. } => {
         write!(f, "Parse failed: {}", source_file.name )
       }
+      ErrorKind::ParseErrorRecovered { module, location: (line, column), message } => write!(f, r#"Recovered from a parse error at {line}:{column} of "{}": {message}"#, module.may_display_relative()),
+      ErrorKind::UnmatchedConditionalDirective { module, location: (line, column), directive } => write!(f, r#"`// {directive}` at {line}:{column} of "{}" has no matching `// {}`."#, module.may_display_relative(), if directive == "@if" { "@endif" } else { "@if" }),
+      ErrorKind::ParseDataFailed { module, format, reason } => write!(f, r#"Failed to parse "{}" as {format}: {reason}"#, module.may_display_relative()),
+      ErrorKind::MissingEntryExports { entry, chunk, exports } => write!(f, r#"{} exported by entry "{}" but missing from the emitted chunk "{chunk}"."#, format_quoted_strings_with_verbs(exports, Some(("is", "are"))), entry.may_display_relative()),
+      ErrorKind::NodeBuiltinStubbed { specifier } => write!(f, r#""{specifier}" is a Node.js builtin, so it's replaced by an empty module for the browser platform. Install a polyfill package named "{}" to bundle it instead."#, specifier.trim_start_matches("node:")),
+      ErrorKind::UnusedDefineKey { name } => write!(f, r#""define" key "{name}" isn't referenced by any module. Is it a typo?"#),
+      ErrorKind::TooManySymbols { symbols, max_symbols } => write!(f, r#"The module graph has {symbols} top-level symbols, which exceeds "maxSymbols" of {max_symbols}."#),
+      ErrorKind::TooManyHashedChunks { chunks, max_chunks } => write!(f, r#"The output has {chunks} chunks, but file names containing "[hash]" only support up to {max_chunks} chunks."#),
+      ErrorKind::ModuleLevelDirective { module, directive } => write!(f, r#"Module level directives cause errors when bundled, "{directive}" in "{}" was ignored."#, module.may_display_relative()),
+      ErrorKind::PluginDiagnostic { plugin, module: Some(module), source } => write!(f, "[plugin {plugin}] {}: {source}", module.may_display_relative()),
+      ErrorKind::PluginDiagnostic { plugin, module: None, source } => write!(f, "[plugin {plugin}] {source}"),
+      ErrorKind::UnhoistableModule { module, reason } => write!(f, r#""{}" can't be safely hoisted since it uses {reason}, but ES modules can't be wrapped by "safeHoisting"."#, module.may_display_relative()),
       ErrorKind::IoError(e) => e.fmt(f),
     }
   }
@@ -144,7 +315,41 @@ impl ErrorKind {
       ErrorKind::ShimmedExport { .. } => error_code::SHIMMED_EXPORT,
       ErrorKind::CircularReexport { .. } => error_code::CIRCULAR_REEXPORT,
       ErrorKind::UnresolvedImport { .. } => error_code::UNRESOLVED_IMPORT,
+      ErrorKind::InvalidTlaFormat { .. } => error_code::INVALID_TLA_FORMAT,
+      ErrorKind::MissingNameOptionForIifeExport { .. } => {
+        error_code::MISSING_NAME_OPTION_FOR_IIFE_EXPORT
+      }
+      ErrorKind::IllegalIdentifierAsName { .. } => error_code::ILLEGAL_IDENTIFIER_AS_NAME,
+      ErrorKind::CodeSplittingUnsupported { .. } => error_code::INVALID_OPTION,
+      ErrorKind::FileNameConflict { .. } => error_code::FILE_NAME_CONFLICT,
+      ErrorKind::EmittedFileNameConflict { .. } => error_code::FILE_NAME_CONFLICT,
+      ErrorKind::ManualChunkConflict { .. } => error_code::INVALID_CHUNK,
+      ErrorKind::InconsistentImportAssertions { .. } => error_code::INCONSISTENT_IMPORT_ASSERTIONS,
       // Rolldown specific
+      ErrorKind::ReExportAllFromDefaultOnly { .. } => error_code::RE_EXPORT_ALL_FROM_DEFAULT_ONLY,
+      ErrorKind::BomWithShebang { .. } => error_code::BOM_WITH_SHEBANG,
+      ErrorKind::ExportsFallback { .. } => error_code::EXPORTS_FALLBACK,
+      ErrorKind::InvalidReplaceDelimiters { .. } => error_code::INVALID_OPTION,
+      ErrorKind::InvalidReplaceValue { .. } => error_code::INVALID_OPTION,
+      ErrorKind::InvalidResolveExtension { .. } => error_code::INVALID_OPTION,
+      ErrorKind::InvalidDefineValue { .. } => error_code::INVALID_OPTION,
+      ErrorKind::InvalidManualChunkPattern { .. } => error_code::INVALID_OPTION,
+      ErrorKind::InvalidExternalPattern { .. } => error_code::INVALID_OPTION,
+      ErrorKind::InvalidTsConfig { .. } => error_code::INVALID_OPTION,
+      ErrorKind::DefaultWithExportAssignment { .. } => error_code::DEFAULT_WITH_EXPORT_ASSIGNMENT,
+      ErrorKind::ParseErrorRecovered { .. } => error_code::PARSE_ERROR_RECOVERED,
+      ErrorKind::ParseDataFailed { .. } => error_code::PARSE_ERROR,
+      ErrorKind::UnmatchedConditionalDirective { .. } => {
+        error_code::UNMATCHED_CONDITIONAL_DIRECTIVE
+      }
+      ErrorKind::MissingEntryExports { .. } => error_code::MISSING_ENTRY_EXPORTS,
+      ErrorKind::NodeBuiltinStubbed { .. } => error_code::NODE_BUILTIN_STUBBED,
+      ErrorKind::UnusedDefineKey { .. } => error_code::UNUSED_DEFINE_KEY,
+      ErrorKind::TooManySymbols { .. } => error_code::TOO_MANY_SYMBOLS,
+      ErrorKind::TooManyHashedChunks { .. } => error_code::TOO_MANY_HASHED_CHUNKS,
+      ErrorKind::ModuleLevelDirective { .. } => error_code::MODULE_LEVEL_DIRECTIVE,
+      ErrorKind::PluginDiagnostic { source, .. } => source.kind.code(),
+      ErrorKind::UnhoistableModule { .. } => error_code::UNHOISTABLE_MODULE,
       ErrorKind::Panic { .. } => error_code::PANIC,
       ErrorKind::IoError(_) => error_code::IO_ERROR,
       ErrorKind::Napi {
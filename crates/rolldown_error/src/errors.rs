@@ -21,6 +21,14 @@ impl Errors {
     self.0
   }
 
+  pub fn first(&self) -> &Error {
+    &self.0[0]
+  }
+
+  pub fn iter(&self) -> std::slice::Iter<'_, Error> {
+    self.0.iter()
+  }
+
   /// This method will panic if passing Vec<Error> is empty.
   pub fn from_vec(vec: Vec<Error>) -> Self {
     assert!(
@@ -7,44 +7,156 @@ export interface ExternalOption {
   function?: (specifier: string, importer: string | undefined, isResolved: boolean) => boolean
   string: Array<string>
 }
+/** A warning passed to `onWarn`. Paths in `message` are relative to `root`. */
+export interface BuildWarning {
+  code: string
+  message: string
+}
 export interface ResolveIdResult {
   id: string
-  external: boolean
+  external: boolean | 'relative' | 'absolute'
 }
 export interface BuildPluginOption {
   name: string
   transform?: (id: string, code: string) => Promise<string | null | undefined>
-  resolveId?: (specifier: string, importer?: string) => Promise<string | null | ResolveIdResult>
+  resolveId?: (specifier: string, importer: string | undefined, kind: 'entry' | 'import' | 'dynamic-import' | 'require') => Promise<string | null | ResolveIdResult>
 }
 export interface TsConfigOptions {
   useDefineForClassFields: boolean
+  configFile?: string
+  decorators?: boolean
+  jsDecorators?: boolean
+  decoratorsBeforeExport?: boolean
+  decoratorVersion?: '2021-12' | 'legacy' | '2022-03'
+}
+export interface JsxOptions {
+  mode?: 'transform' | 'preserve'
+  runtime?: 'classic' | 'automatic'
+  importSource?: string
+  pragma?: string
+  pragmaFrag?: string
+}
+export interface ReplaceOptions {
+  values: Record<string, string>
+  delimiters?: [string, string]
+  preventAssignment?: boolean
 }
 export interface BuiltinsOptions {
   tsconfig?: TsConfigOptions
+  discoverTsconfig?: boolean
+  jsx?: JsxOptions
+  replace?: ReplaceOptions
+  conditionalCompilation?: boolean
+  loaders?: Record<string, 'js' | 'jsx' | 'ts' | 'tsx' | 'css' | 'text' | 'yaml' | 'toml' | 'dataurl' | 'base64'>
 }
 export interface InputOptions {
   external: ExternalOption
   input: Record<string, string>
+  /** Warnings are printed to stderr if it's not provided. */
+  onWarn?: (warning: BuildWarning) => void
   plugins: Array<BuildPluginOption>
   preserveSymlinks: boolean
   shimMissingExports: boolean
   treeshake?: boolean
   cwd: string
+  root?: string
   builtins: BuiltinsOptions
+  dropUnusedEntryExports?: boolean
+  maxParallelTransforms?: number
+  maxSymbols?: number
+  allowExportsFallback?: boolean
+  resolutionDiagnostics?: boolean
+  wasmEsmIntegration?: boolean
+  safeHoisting?: boolean
+  cache?: boolean
+  parseRecovery?: boolean
+  define?: Record<string, string>
+  strictDefine?: boolean
+  platform?: 'node' | 'browser'
+  absolutePathRoot?: string
+  alias?: Array<AliasItem>
+  resolveExtensions?: Array<string>
+  mainFields?: Array<string>
+  conditionNames?: Array<string>
+  logLevel?: 'off' | 'error' | 'warn' | 'info' | 'debug' | 'trace'
+}
+/** Specifiers matching `find` are replaced by `replacement` before resolving. */
+export interface AliasItem {
+  find: string
+  replacement: string
 }
 export interface OutputOptions {
   entryFileNames?: string
   chunkFileNames?: string
+  fileNameConflict?: 'suffix' | 'error'
+  amd?: AmdOptions
+  banner?: string
+  comments?: 'none' | 'jsdoc'
   dir?: string
   exports?: 'default' | 'named' | 'none' | 'auto'
-  format?: 'esm' | 'cjs'
+  footer?: string
+  format?: 'esm' | 'cjs' | 'amd' | 'iife'
+  intro?: string
+  manualChunks?: Record<string, Array<string>>
+  name?: string
+  outro?: string
+  preserveModules?: boolean
+  sourcemap?: 'inline' | 'external' | 'hidden'
+  minify?: boolean
+  minifyOptions?: MinifyOptions
+  bom?: boolean
+  mangle?: boolean
+  reservedNames?: Array<string>
+  apiSurface?: boolean
+  manifest?: boolean
+  codegen?: CodegenOptions
+  globalThisPolyfill?: boolean
+  rewriteImportMetaUrl?: boolean
+  importMap?: Record<string, string>
+  streaming?: boolean
+  splitDirectives?: boolean
+}
+export interface AmdOptions {
+  id?: string
+  autoId?: boolean
+  basePath?: string
+  define?: string
+}
+export interface CodegenOptions {
+  target?: 'es3' | 'es5' | 'es2015' | 'es2016' | 'es2017' | 'es2018' | 'es2019' | 'es2020' | 'es2021' | 'es2022' | 'esnext'
+  asciiOnly?: boolean
+  minify?: boolean
+}
+export interface MinifyOptions {
+  mangle?: boolean
+  compress?: boolean
 }
 export interface OutputChunk {
   code: string
   fileName: string
 }
+/** How a specifier is resolved, reported if `resolutionDiagnostics` is enabled. */
+export interface ResolutionRecord {
+  specifier: string
+  /** Absent for entries. */
+  importer?: string
+  /** Absent if the resolution failed. */
+  resolved?: string
+  /** The message of the error if the resolution failed. */
+  error?: string
+  source?: 'resolveHook' | 'resolveFallbackHook' | 'path' | 'nodeModules' | 'externalOption' | 'nodeBuiltin' | 'unresolved'
+  /** The plugin of `resolveHook` and `resolveFallbackHook`. */
+  plugin?: string
+  /** The field of `package.json` of `nodeModules`, like `exports` or `main`. */
+  packageField?: string
+}
 export class Bundler {
   constructor(inputOpts: InputOptions)
   write(opts: OutputOptions): Promise<Array<OutputChunk>>
   generate(opts: OutputOptions): Promise<Array<OutputChunk>>
+  /**
+   * How each specifier of the latest build is resolved, or `null` if `resolutionDiagnostics` isn't
+   * enabled.
+   */
+  resolutionReport(): Promise<Array<ResolutionRecord> | null>
 }
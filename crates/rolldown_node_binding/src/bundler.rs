@@ -1,3 +1,5 @@
+use std::path::Path;
+
 use napi::{tokio::sync::Mutex, Env};
 use napi_derive::*;
 use rolldown::Bundler as NativeBundler;
@@ -8,6 +10,7 @@ use crate::{
   options::InputOptions,
   options::{resolve_input_options, resolve_output_options, OutputOptions},
   output_chunk::OutputChunk,
+  resolution_record::ResolutionRecord,
   utils::init_custom_trace_subscriber,
   NAPI_ENV,
 };
@@ -21,7 +24,6 @@ pub struct Bundler {
 impl Bundler {
   #[napi(constructor)]
   pub fn new(env: Env, input_opts: InputOptions) -> napi::Result<Self> {
-    init_custom_trace_subscriber(env);
     Self::new_impl(env, input_opts)
   }
 
@@ -34,12 +36,27 @@ impl Bundler {
   pub async fn generate(&self, opts: OutputOptions) -> napi::Result<Vec<OutputChunk>> {
     self.generate_impl(opts).await
   }
+
+  /// How each specifier of the latest build is resolved, or `null` if `resolutionDiagnostics` isn't
+  /// enabled.
+  #[napi]
+  pub async fn resolution_report(&self) -> napi::Result<Option<Vec<ResolutionRecord>>> {
+    let bundler_core = self.inner.try_lock().map_err(|_| {
+      napi::Error::from_reason("Failed to lock the bundler. Is another operation in progress?")
+    })?;
+    Ok(
+      bundler_core
+        .resolution_report()
+        .map(|records| records.iter().map(Into::into).collect()),
+    )
+  }
 }
 
 impl Bundler {
   pub fn new_impl(env: Env, input_opts: InputOptions) -> napi::Result<Self> {
     NAPI_ENV.set(&env, || {
       let (input_opts, plugins) = resolve_input_options(input_opts)?;
+      init_custom_trace_subscriber(env);
       Ok(Bundler {
         inner: Mutex::new(NativeBundler::with_plugins(input_opts, plugins)),
       })
@@ -54,10 +71,8 @@ impl Bundler {
 
     let binding_opts = resolve_output_options(opts)?;
 
-    let outputs = bundler_core
-      .write(binding_opts)
-      .await
-      .map_err(|err| self.handle_errors(err))?;
+    let outputs = bundler_core.write(binding_opts).await;
+    let outputs = outputs.map_err(|err| handle_errors(err, bundler_core.root()))?;
 
     let output_chunks = outputs
       .into_iter()
@@ -77,10 +92,8 @@ impl Bundler {
 
     let binding_opts = resolve_output_options(opts)?;
 
-    let outputs = bundler_core
-      .generate(binding_opts)
-      .await
-      .map_err(|err| self.handle_errors(err))?;
+    let outputs = bundler_core.generate(binding_opts).await;
+    let outputs = outputs.map_err(|err| handle_errors(err, bundler_core.root()))?;
 
     let output_chunks = outputs
       .into_iter()
@@ -91,11 +104,11 @@ impl Bundler {
       .collect::<Vec<_>>();
     Ok(output_chunks)
   }
+}
 
-  fn handle_errors(&self, errors: Errors) -> napi::Error {
-    for error in errors.into_vec().into_iter() {
-      eprintln!("{}", error);
-    }
-    napi::Error::from_reason("Build failed")
+fn handle_errors(errors: Errors, root: &Path) -> napi::Error {
+  for error in errors.into_vec().into_iter() {
+    eprintln!("{}", error.to_readable_string(root));
   }
+  napi::Error::from_reason("Build failed")
 }
@@ -74,14 +74,19 @@ impl BuildPlugin for JsBuildPlugin {
         .call_async((
           args.specifier.to_string(),
           args.importer.map(|s| s.to_string()),
+          args.kind.as_str().to_string(),
         ))
         .await
         .map_err(|e| e.into_bundle_error())?;
 
-      Ok(cb_ret.map(|cb_ret| ResolvedId {
-        id: cb_ret.id,
-        external: cb_ret.external,
-      }))
+      cb_ret
+        .map(|cb_ret| {
+          Ok(ResolvedId {
+            external: cb_ret.external().map_err(|e| e.into_bundle_error())?,
+            id: cb_ret.id,
+          })
+        })
+        .transpose()
     } else {
       Ok(None)
     }
@@ -1,7 +1,11 @@
-use crate::{options::ResolveIdResult, utils::JsCallback};
+use crate::{
+  options::{BuildWarning, ResolveIdResult},
+  utils::JsCallback,
+};
 
 pub type IsExternalCallback = JsCallback<(String, Option<String>, bool), bool>;
+pub type OnWarnCallback = JsCallback<(BuildWarning,), ()>;
 
 // Build hooks
-pub type ResolveIdCallback = JsCallback<(String, Option<String>), Option<ResolveIdResult>>;
+pub type ResolveIdCallback = JsCallback<(String, Option<String>, String), Option<ResolveIdResult>>;
 pub type TransformCallback = JsCallback<(String, String), Option<String>>;
@@ -14,6 +14,7 @@ pub mod js_build_plugin;
 pub mod js_callbacks;
 pub mod options;
 pub mod output_chunk;
+pub mod resolution_record;
 pub mod utils;
 
 scoped_tls::scoped_thread_local!(static NAPI_ENV: napi::Env);
@@ -20,7 +20,7 @@ pub struct BuildPluginOption {
   #[derivative(Debug = "ignore")]
   #[serde(skip_deserializing)]
   #[napi(
-    ts_type = "(specifier: string, importer?: string) => Promise<string | null | ResolveIdResult>"
+    ts_type = "(specifier: string, importer: string | undefined, kind: 'entry' | 'import' | 'dynamic-import' | 'require') => Promise<string | null | ResolveIdResult>"
   )]
   pub resolve_id: Option<JsFunction>,
 }
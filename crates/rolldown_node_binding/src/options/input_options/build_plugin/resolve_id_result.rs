@@ -1,11 +1,28 @@
 use derivative::Derivative;
-use serde::Deserialize;
+use napi::Either;
+use rolldown_plugin::External;
 
 #[napi_derive::napi(object)]
-#[derive(Deserialize, Default, Derivative)]
-#[serde(rename_all = "camelCase")]
+#[derive(Derivative)]
 #[derivative(Debug)]
 pub struct ResolveIdResult {
   pub id: String,
-  pub external: bool,
+  #[napi(ts_type = "boolean | 'relative' | 'absolute'")]
+  pub external: Either<bool, String>,
+}
+
+impl ResolveIdResult {
+  pub fn external(&self) -> napi::Result<External> {
+    match &self.external {
+      Either::A(external) => Ok((*external).into()),
+      Either::B(external) => match external.as_str() {
+        "relative" => Ok(External::Relative),
+        "absolute" => Ok(External::Absolute),
+        _ => Err(napi::Error::new(
+          napi::Status::InvalidArg,
+          format!("Invalid external of resolved id: {external}"),
+        )),
+      },
+    }
+  }
 }
@@ -0,0 +1,16 @@
+use derivative::Derivative;
+use serde::Deserialize;
+
+#[napi_derive::napi(object)]
+#[derive(Deserialize, Default, Derivative)]
+#[serde(rename_all = "camelCase")]
+#[derivative(Debug)]
+pub struct JsxOptions {
+  #[napi(ts_type = "'transform' | 'preserve'")]
+  pub mode: Option<String>,
+  #[napi(ts_type = "'classic' | 'automatic'")]
+  pub runtime: Option<String>,
+  pub import_source: Option<String>,
+  pub pragma: Option<String>,
+  pub pragma_frag: Option<String>,
+}
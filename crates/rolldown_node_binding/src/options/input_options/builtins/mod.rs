@@ -1,8 +1,14 @@
+use std::collections::HashMap;
+
 use derivative::Derivative;
 use serde::Deserialize;
 
 mod tsconfig;
 pub use tsconfig::*;
+mod jsx;
+pub use jsx::*;
+mod replace;
+pub use replace::*;
 
 #[napi_derive::napi(object)]
 #[derive(Deserialize, Default, Derivative)]
@@ -10,4 +16,12 @@ pub use tsconfig::*;
 #[derivative(Debug)]
 pub struct BuiltinsOptions {
   pub tsconfig: Option<TsConfigOptions>,
+  pub discover_tsconfig: Option<bool>,
+  pub jsx: Option<JsxOptions>,
+  pub replace: Option<ReplaceOptions>,
+  pub conditional_compilation: Option<bool>,
+  #[napi(
+    ts_type = "Record<string, 'js' | 'jsx' | 'ts' | 'tsx' | 'css' | 'text' | 'yaml' | 'toml' | 'dataurl' | 'base64'>"
+  )]
+  pub loaders: Option<HashMap<String, String>>,
 }
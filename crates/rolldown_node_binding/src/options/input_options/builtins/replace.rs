@@ -0,0 +1,15 @@
+use std::collections::HashMap;
+
+use derivative::Derivative;
+use serde::Deserialize;
+
+#[napi_derive::napi(object)]
+#[derive(Deserialize, Default, Derivative)]
+#[serde(rename_all = "camelCase")]
+#[derivative(Debug)]
+pub struct ReplaceOptions {
+  pub values: HashMap<String, String>,
+  #[napi(ts_type = "[string, string]")]
+  pub delimiters: Option<Vec<String>>,
+  pub prevent_assignment: Option<bool>,
+}
@@ -7,4 +7,10 @@ use serde::Deserialize;
 #[derivative(Debug)]
 pub struct TsConfigOptions {
   pub use_define_for_class_fields: bool,
+  pub config_file: Option<String>,
+  pub decorators: Option<bool>,
+  pub js_decorators: Option<bool>,
+  pub decorators_before_export: Option<bool>,
+  #[napi(ts_type = "'2021-12' | 'legacy' | '2022-03'")]
+  pub decorator_version: Option<String>,
 }
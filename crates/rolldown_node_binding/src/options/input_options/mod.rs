@@ -1,11 +1,14 @@
-use std::{collections::HashMap, path::PathBuf};
+use std::{collections::HashMap, path::PathBuf, str::FromStr};
 
+use derivative::Derivative;
+use napi::JsFunction;
 use napi_derive::*;
-use rolldown::default_warning_handler;
 use rolldown_plugin::BuildPlugin;
 use serde::Deserialize;
 mod external;
 pub use external::*;
+mod on_warn;
+pub use on_warn::*;
 mod build_plugin;
 pub use build_plugin::*;
 mod builtins;
@@ -14,8 +17,9 @@ pub use builtins::*;
 use crate::js_build_plugin::JsBuildPlugin;
 
 #[napi(object)]
-#[derive(Deserialize, Debug, Default)]
+#[derive(Deserialize, Default, Derivative)]
 #[serde(rename_all = "camelCase")]
+#[derivative(Debug)]
 pub struct InputOptions {
   // Not going to be supported
   // @deprecated Use the "inlineDynamicImports" output option instead.
@@ -23,7 +27,6 @@ pub struct InputOptions {
 
   // acorn?: Record<string, unknown>;
   // acornInjectPlugins?: (() => unknown)[] | (() => unknown);
-  // cache?: false | RollupCache;
   // context?: string;sssssssssss
   // experimentalCacheExpiry?: number;
   pub external: ExternalOption,
@@ -35,7 +38,11 @@ pub struct InputOptions {
   // /** @deprecated Use the "maxParallelFileOps" option instead. */
   // maxParallelFileReads?: number;
   // moduleContext?: ((id: string) => string | null | void) | { [id: string]: string };
-  // onwarn?: WarningHandlerWithDefault;
+  /// Warnings are printed to stderr if it's not provided.
+  #[derivative(Debug = "ignore")]
+  #[serde(skip_deserializing)]
+  #[napi(ts_type = "(warning: BuildWarning) => void")]
+  pub on_warn: Option<JsFunction>,
   // perf?: boolean;
   pub plugins: Vec<BuildPluginOption>,
   // preserveEntrySignatures?: PreserveEntrySignaturesOption;
@@ -49,7 +56,36 @@ pub struct InputOptions {
 
   // extra
   pub cwd: String,
+  pub root: Option<String>,
   pub builtins: BuiltinsOptions,
+  pub drop_unused_entry_exports: Option<bool>,
+  pub max_parallel_transforms: Option<u32>,
+  pub max_symbols: Option<u32>,
+  pub allow_exports_fallback: Option<bool>,
+  pub resolution_diagnostics: Option<bool>,
+  pub wasm_esm_integration: Option<bool>,
+  pub safe_hoisting: Option<bool>,
+  pub cache: Option<bool>,
+  pub parse_recovery: Option<bool>,
+  pub define: Option<HashMap<String, String>>,
+  pub strict_define: Option<bool>,
+  #[napi(ts_type = "'node' | 'browser'")]
+  pub platform: Option<String>,
+  pub absolute_path_root: Option<String>,
+  pub alias: Option<Vec<AliasItem>>,
+  pub resolve_extensions: Option<Vec<String>>,
+  pub main_fields: Option<Vec<String>>,
+  pub condition_names: Option<Vec<String>>,
+  #[napi(ts_type = "'off' | 'error' | 'warn' | 'info' | 'debug' | 'trace'")]
+  pub log_level: Option<String>,
+}
+
+/// Specifiers matching `find` are replaced by `replacement` before resolving.
+#[napi(object)]
+#[derive(Deserialize, Debug)]
+pub struct AliasItem {
+  pub find: String,
+  pub replacement: String,
 }
 
 pub fn resolve_input_options(
@@ -66,6 +102,120 @@ pub fn resolve_input_options(
 
   let is_external = resolve_external(opts.external)?;
 
+  let on_warn = resolve_on_warn(opts.on_warn.as_ref())?;
+
+  let tsconfig = opts
+    .builtins
+    .tsconfig
+    .map(|tsconfig| {
+      let default = rolldown::TsConfig::default();
+      let decorator_version = tsconfig
+        .decorator_version
+        .map(|version| {
+          rolldown::DecoratorVersion::from_str(&version)
+            .map_err(|err| napi::Error::new(napi::Status::InvalidArg, err))
+        })
+        .transpose()?
+        .unwrap_or_default();
+      Ok::<_, napi::Error>(rolldown::TsConfig {
+        use_define_for_class_fields: tsconfig.use_define_for_class_fields,
+        config_file: tsconfig.config_file.map(PathBuf::from),
+        decorators: tsconfig.decorators.unwrap_or(default.decorators),
+        js_decorators: tsconfig.js_decorators.unwrap_or(default.js_decorators),
+        decorators_before_export: tsconfig
+          .decorators_before_export
+          .unwrap_or(default.decorators_before_export),
+        decorator_version,
+      })
+    })
+    .transpose()?;
+
+  let jsx = opts
+    .builtins
+    .jsx
+    .map(|options| {
+      let mut jsx = options
+        .mode
+        .map(|mode| {
+          rolldown::Jsx::from_str(&mode)
+            .map_err(|err| napi::Error::new(napi::Status::InvalidArg, err))
+        })
+        .transpose()?
+        .unwrap_or_default();
+      if let rolldown::Jsx::Transform(transform) = &mut jsx {
+        transform.runtime = options
+          .runtime
+          .map(|runtime| {
+            rolldown::JsxRuntime::from_str(&runtime)
+              .map_err(|err| napi::Error::new(napi::Status::InvalidArg, err))
+          })
+          .transpose()?
+          .unwrap_or_default();
+        transform.import_source = options.import_source;
+        transform.pragma = options.pragma;
+        transform.pragma_frag = options.pragma_frag;
+      }
+      Ok::<_, napi::Error>(jsx)
+    })
+    .transpose()?
+    .unwrap_or_default();
+
+  let replace = opts
+    .builtins
+    .replace
+    .map(|replace| {
+      let default = rolldown::ReplaceOptions::default();
+      let delimiters = match replace.delimiters {
+        Some(delimiters) => <[String; 2]>::try_from(delimiters)
+          .map(|[start, end]| (start, end))
+          .map_err(|_| {
+            napi::Error::new(
+              napi::Status::InvalidArg,
+              "builtins.replace.delimiters should be a pair of strings".to_string(),
+            )
+          })?,
+        None => default.delimiters,
+      };
+      Ok::<_, napi::Error>(rolldown::ReplaceOptions {
+        values: replace.values.into_iter().collect(),
+        delimiters,
+        prevent_assignment: replace.prevent_assignment.unwrap_or(false),
+      })
+    })
+    .transpose()?;
+
+  let loaders = opts
+    .builtins
+    .loaders
+    .unwrap_or_default()
+    .into_iter()
+    .map(|(ext, loader)| {
+      rolldown::Loader::from_str(&loader)
+        .map(|loader| (ext, loader))
+        .map_err(|err| napi::Error::new(napi::Status::InvalidArg, err))
+    })
+    .collect::<napi::Result<_>>()?;
+
+  let platform = opts
+    .platform
+    .map(|platform| {
+      rolldown::Platform::from_str(&platform)
+        .map_err(|err| napi::Error::new(napi::Status::InvalidArg, err))
+    })
+    .transpose()?;
+
+  let log_level = opts
+    .log_level
+    .map(|level| {
+      rolldown::LevelFilter::from_str(&level).map_err(|err| {
+        napi::Error::new(
+          napi::Status::InvalidArg,
+          format!("Invalid log level {}", err),
+        )
+      })
+    })
+    .transpose()?;
+
   Ok((
     rolldown::InputOptions {
       input: opts
@@ -74,16 +224,51 @@ pub fn resolve_input_options(
         .map(|(name, import)| rolldown::InputItem { name, import })
         .collect(),
       cwd,
+      root: opts.root.map(PathBuf::from),
       treeshake: opts.treeshake.unwrap_or(true),
+      drop_unused_entry_exports: opts.drop_unused_entry_exports.unwrap_or(false),
       is_external,
       preserve_symlinks: opts.preserve_symlinks,
+      platform,
+      absolute_path_root: opts.absolute_path_root.map(PathBuf::from),
+      alias: opts
+        .alias
+        .unwrap_or_default()
+        .into_iter()
+        .map(|item| (item.find, item.replacement))
+        .collect(),
+      resolve_extensions: opts.resolve_extensions.unwrap_or_else(|| {
+        rolldown::DEFAULT_RESOLVE_EXTENSIONS
+          .map(ToString::to_string)
+          .to_vec()
+      }),
+      main_fields: opts.main_fields,
+      condition_names: opts.condition_names.unwrap_or_else(|| {
+        rolldown::DEFAULT_CONDITION_NAMES
+          .map(ToString::to_string)
+          .to_vec()
+      }),
+      allow_exports_fallback: opts.allow_exports_fallback.unwrap_or(false),
+      resolution_diagnostics: opts.resolution_diagnostics.unwrap_or(false),
+      wasm_esm_integration: opts.wasm_esm_integration.unwrap_or(false),
+      safe_hoisting: opts.safe_hoisting.unwrap_or(false),
+      cache: opts.cache.unwrap_or(false),
+      parse_recovery: opts.parse_recovery.unwrap_or(false),
+      define: opts.define.unwrap_or_default(),
+      strict_define: opts.strict_define.unwrap_or(false),
       builtins: rolldown::BuiltinsOptions {
-        tsconfig: opts.builtins.tsconfig.map(|opts| rolldown::TsConfig {
-          use_define_for_class_fields: opts.use_define_for_class_fields,
-        }),
+        tsconfig,
+        discover_tsconfig: opts.builtins.discover_tsconfig.unwrap_or(false),
+        jsx,
+        replace,
+        conditional_compilation: opts.builtins.conditional_compilation.unwrap_or(false),
+        loaders,
       },
-      on_warn: default_warning_handler(),
+      on_warn,
       shim_missing_exports: opts.shim_missing_exports,
+      log_level,
+      max_parallel_transforms: opts.max_parallel_transforms.map(|n| n as usize),
+      max_symbols: opts.max_symbols.map(|n| n as usize),
     },
     plugins,
   ))
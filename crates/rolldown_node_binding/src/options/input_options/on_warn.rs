@@ -0,0 +1,27 @@
+use std::sync::Arc;
+
+use napi::JsFunction;
+use rolldown::{default_warning_handler, WarningHandler};
+
+use crate::js_callbacks::OnWarnCallback;
+
+/// A warning passed to `onWarn`. Paths in `message` are relative to `root`.
+#[napi_derive::napi(object)]
+#[derive(Debug)]
+pub struct BuildWarning {
+  pub code: String,
+  pub message: String,
+}
+
+pub fn resolve_on_warn(function: Option<&JsFunction>) -> napi::Result<WarningHandler> {
+  let Some(function) = function else {
+    return Ok(default_warning_handler());
+  };
+  let on_warn_cb = OnWarnCallback::new(function)?;
+  Ok(Arc::new(move |warning| {
+    on_warn_cb.call((BuildWarning {
+      code: warning.kind.code().to_string(),
+      message: warning.to_string(),
+    },));
+  }))
+}
@@ -1,7 +1,7 @@
-use std::str::FromStr;
+use std::{collections::HashMap, str::FromStr};
 
 use napi_derive::*;
-use rolldown::ModuleFormat;
+use rolldown::{FileNameConflict, ModuleFormat, OutputComments, SourceMapType};
 use serde::Deserialize;
 
 #[napi(object)]
@@ -13,11 +13,15 @@ pub struct OutputOptions {
   // dynamicImportFunction: string | undefined;
   pub entry_file_names: Option<String>,
   pub chunk_file_names: Option<String>,
+  #[napi(ts_type = "'suffix' | 'error'")]
+  pub file_name_conflict: Option<String>,
 
-  // amd: NormalizedAmdOptions;
+  pub amd: Option<AmdOptions>,
   // assetFileNames: string | ((chunkInfo: PreRenderedAsset) => string);
-  // banner: () => string | Promise<string>;
+  pub banner: Option<String>,
   // chunkFileNames: string | ((chunkInfo: PreRenderedChunk) => string);
+  #[napi(ts_type = "'none' | 'jsdoc'")]
+  pub comments: Option<String>,
   // compact: boolean;
   pub dir: Option<String>,
   // pub entry_file_names: String, // | ((chunkInfo: PreRenderedChunk) => string)
@@ -26,8 +30,8 @@ pub struct OutputOptions {
   pub exports: Option<String>,
   // extend: boolean;
   // externalLiveBindings: boolean;
-  // footer: () => string | Promise<string>;
-  #[napi(ts_type = "'esm' | 'cjs'")]
+  pub footer: Option<String>,
+  #[napi(ts_type = "'esm' | 'cjs' | 'amd' | 'iife'")]
   pub format: Option<String>,
   // freeze: boolean;
   // generatedCode: NormalizedGeneratedCodeOptions;
@@ -36,20 +40,21 @@ pub struct OutputOptions {
   // indent: true | string;
   // inlineDynamicImports: boolean;
   // interop: GetInterop;
-  // intro: () => string | Promise<string>;
-  // manualChunks: ManualChunksOption;
+  pub intro: Option<String>,
+  pub manual_chunks: Option<HashMap<String, Vec<String>>>,
   // minifyInternalExports: boolean;
-  // name: string | undefined;
+  pub name: Option<String>,
   // namespaceToStringTag: boolean;
   // noConflict: boolean;
-  // outro: () => string | Promise<string>;
+  pub outro: Option<String>,
   // paths: OptionsPaths;
   // plugins: OutputPlugin[];
   // preferConst: boolean;
-  // preserveModules: boolean;
+  pub preserve_modules: Option<bool>,
   // preserveModulesRoot: string | undefined;
   // sanitizeFileName: (fileName: string) => string;
-  // sourcemap: boolean | 'inline' | 'hidden';
+  #[napi(ts_type = "'inline' | 'external' | 'hidden'")]
+  pub sourcemap: Option<String>,
   // sourcemapExcludeSources: boolean;
   // sourcemapFile: string | undefined;
   // sourcemapPathTransform: SourcemapPathTransformOption | undefined;
@@ -57,7 +62,49 @@ pub struct OutputOptions {
   // systemNullSetters: boolean;
   // validate: boolean;
   // --- Enhanced options
-  // pub minify: bool,
+  pub minify: Option<bool>,
+  pub minify_options: Option<MinifyOptions>,
+  pub bom: Option<bool>,
+  pub mangle: Option<bool>,
+  pub reserved_names: Option<Vec<String>>,
+  pub api_surface: Option<bool>,
+  pub manifest: Option<bool>,
+  pub codegen: Option<CodegenOptions>,
+  pub global_this_polyfill: Option<bool>,
+  pub rewrite_import_meta_url: Option<bool>,
+  pub import_map: Option<HashMap<String, String>>,
+  pub streaming: Option<bool>,
+  pub split_directives: Option<bool>,
+}
+
+#[napi(object)]
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AmdOptions {
+  pub id: Option<String>,
+  pub auto_id: Option<bool>,
+  pub base_path: Option<String>,
+  pub define: Option<String>,
+}
+
+#[napi(object)]
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CodegenOptions {
+  #[napi(
+    ts_type = "'es3' | 'es5' | 'es2015' | 'es2016' | 'es2017' | 'es2018' | 'es2019' | 'es2020' | 'es2021' | 'es2022' | 'esnext'"
+  )]
+  pub target: Option<String>,
+  pub ascii_only: Option<bool>,
+  pub minify: Option<bool>,
+}
+
+#[napi(object)]
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MinifyOptions {
+  pub mangle: Option<bool>,
+  pub compress: Option<bool>,
 }
 
 pub fn resolve_output_options(opts: OutputOptions) -> napi::Result<rolldown::OutputOptions> {
@@ -70,6 +117,14 @@ pub fn resolve_output_options(opts: OutputOptions) -> napi::Result<rolldown::Out
   if let Some(chunk_file_names) = opts.chunk_file_names {
     defaults.chunk_file_names = chunk_file_names.into()
   }
+  if let Some(file_name_conflict) = opts.file_name_conflict {
+    defaults.file_name_conflict = FileNameConflict::from_str(&file_name_conflict)
+      .map_err(|err| napi::Error::new(napi::Status::InvalidArg, err))?;
+  }
+  if let Some(comments) = opts.comments {
+    defaults.comments = OutputComments::from_str(&comments)
+      .map_err(|err| napi::Error::new(napi::Status::InvalidArg, err))?;
+  }
   if let Some(format) = opts.format {
     defaults.format = ModuleFormat::from_str(format.as_str()).map_err(|err| {
       napi::Error::new(
@@ -79,7 +134,67 @@ pub fn resolve_output_options(opts: OutputOptions) -> napi::Result<rolldown::Out
     })?;
   }
 
+  if let Some(sourcemap) = opts.sourcemap {
+    defaults.sourcemap = Some(
+      SourceMapType::from_str(&sourcemap)
+        .map_err(|err| napi::Error::new(napi::Status::InvalidArg, err))?,
+    );
+  }
+
   defaults.dir = opts.dir;
+  defaults.name = opts.name;
+  defaults.banner = opts.banner;
+  defaults.footer = opts.footer;
+  defaults.intro = opts.intro;
+  defaults.outro = opts.outro;
+  defaults.manual_chunks = opts
+    .manual_chunks
+    .map(|manual_chunks| manual_chunks.into_iter().collect());
+  defaults.bom = opts.bom.unwrap_or(false);
+  defaults.mangle = opts.mangle.unwrap_or(false);
+  defaults.reserved_names = opts.reserved_names.unwrap_or_default();
+  defaults.minify = opts.minify.unwrap_or(false);
+  defaults.api_surface = opts.api_surface.unwrap_or(false);
+  defaults.manifest = opts.manifest.unwrap_or(false);
+  defaults.global_this_polyfill = opts.global_this_polyfill.unwrap_or(false);
+  defaults.rewrite_import_meta_url = opts.rewrite_import_meta_url.unwrap_or(true);
+  defaults.import_map = opts
+    .import_map
+    .map(|import_map| import_map.into_iter().collect());
+  defaults.streaming = opts.streaming.unwrap_or(false);
+  defaults.split_directives = opts.split_directives.unwrap_or(false);
+  defaults.preserve_modules = opts.preserve_modules.unwrap_or(false);
+
+  if let Some(amd) = opts.amd {
+    defaults.amd = rolldown::AmdOptions {
+      id: amd.id,
+      auto_id: amd.auto_id.unwrap_or(false),
+      base_path: amd.base_path,
+      define: amd.define.unwrap_or(defaults.amd.define),
+    };
+  }
+
+  if let Some(minify_options) = opts.minify_options {
+    defaults.minify_options = rolldown::MinifyOptions {
+      mangle: minify_options.mangle.unwrap_or(true),
+      compress: minify_options.compress.unwrap_or(true),
+    };
+  }
+
+  if let Some(codegen) = opts.codegen {
+    defaults.codegen = rolldown::CodegenOptions {
+      target: codegen
+        .target
+        .map(|target| {
+          rolldown::CodegenTarget::from_str(&target)
+            .map_err(|err| napi::Error::new(napi::Status::InvalidArg, err))
+        })
+        .transpose()?
+        .unwrap_or_default(),
+      ascii_only: codegen.ascii_only.unwrap_or(false),
+      minify: codegen.minify.unwrap_or(false),
+    };
+  }
 
   Ok(defaults)
 }
@@ -0,0 +1,55 @@
+use derivative::Derivative;
+use serde::Deserialize;
+
+/// How a specifier is resolved, reported if `resolutionDiagnostics` is enabled.
+#[napi_derive::napi(object)]
+#[derive(Deserialize, Default, Derivative)]
+#[serde(rename_all = "camelCase")]
+#[derivative(Debug)]
+pub struct ResolutionRecord {
+  pub specifier: String,
+  /// Absent for entries.
+  pub importer: Option<String>,
+  /// Absent if the resolution failed.
+  pub resolved: Option<String>,
+  /// The message of the error if the resolution failed.
+  pub error: Option<String>,
+  #[napi(
+    ts_type = "'resolveHook' | 'resolveFallbackHook' | 'path' | 'nodeModules' | 'externalOption' | 'nodeBuiltin' | 'unresolved'"
+  )]
+  pub source: Option<String>,
+  /// The plugin of `resolveHook` and `resolveFallbackHook`.
+  pub plugin: Option<String>,
+  /// The field of `package.json` of `nodeModules`, like `exports` or `main`.
+  pub package_field: Option<String>,
+}
+
+impl From<&rolldown::ResolutionRecord> for ResolutionRecord {
+  fn from(record: &rolldown::ResolutionRecord) -> Self {
+    let (source, plugin, package_field) = match &record.source {
+      Some(rolldown::ResolutionSource::ResolveHook(plugin)) => {
+        (Some("resolveHook"), Some(plugin.clone()), None)
+      }
+      Some(rolldown::ResolutionSource::ResolveFallbackHook(plugin)) => {
+        (Some("resolveFallbackHook"), Some(plugin.clone()), None)
+      }
+      Some(rolldown::ResolutionSource::Path) => (Some("path"), None, None),
+      Some(rolldown::ResolutionSource::NodeModules(field)) => {
+        (Some("nodeModules"), None, field.clone())
+      }
+      Some(rolldown::ResolutionSource::ExternalOption) => (Some("externalOption"), None, None),
+      Some(rolldown::ResolutionSource::NodeBuiltin) => (Some("nodeBuiltin"), None, None),
+      Some(rolldown::ResolutionSource::Unresolved) => (Some("unresolved"), None, None),
+      None => (None, None, None),
+    };
+    Self {
+      specifier: record.specifier.clone(),
+      importer: record.importer.clone(),
+      resolved: record.resolved.as_ref().ok().cloned(),
+      error: record.resolved.as_ref().err().cloned(),
+      source: source.map(ToString::to_string),
+      plugin,
+      package_field,
+    }
+  }
+}
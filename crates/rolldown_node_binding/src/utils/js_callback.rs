@@ -2,7 +2,9 @@ use std::{fmt::Debug, marker::PhantomData};
 
 use napi::{
   bindgen_prelude::{FromNapiValue, Promise, ValidateNapiValue},
-  threadsafe_function::{ErrorStrategy, ThreadSafeCallContext, ThreadsafeFunction},
+  threadsafe_function::{
+    ErrorStrategy, ThreadSafeCallContext, ThreadsafeFunction, ThreadsafeFunctionCallMode,
+  },
   Either, JsFunction,
 };
 
@@ -43,6 +45,14 @@ impl<Args: JsCallbackArgs + Debug, Ret: JsCallbackRet> JsCallback<Args, Ret> {
       Either::B(promise) => promise.await,
     }
   }
+
+  /// Call the function without waiting for it to return, so it could be called from sync code.
+  /// The returned value is ignored.
+  pub(crate) fn call(&self, args: Args) {
+    self
+      .ts_fn
+      .call(args, ThreadsafeFunctionCallMode::NonBlocking);
+  }
 }
 
 impl<Args: JsCallbackArgs, Ret: JsCallbackRet> Clone for JsCallback<Args, Ret> {
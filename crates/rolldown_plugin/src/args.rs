@@ -1,17 +1,87 @@
+use std::collections::BTreeMap;
+
 use rolldown_common::{Loader, ModuleId};
+use swc_core::ecma::{ast, atoms::JsWord};
 
 #[derive(Debug, Clone)]
 pub struct ResolveArgs<'a> {
   pub importer: Option<&'a ModuleId>,
   pub specifier: &'a str,
+  pub kind: ImportKind,
+}
+
+/// How the specifier of `ResolveArgs` is imported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportKind {
+  /// An entry in `input`, which has no importer.
+  Entry,
+  /// `import` and `export ... from` declarations.
+  Import,
+  /// `import()` expressions. Specifiers also imported statically by the same module are resolved
+  /// as `Import` or `Require` only, since the module refers to a specifier by a single id.
+  DynamicImport,
+  /// `require()` calls of CommonJS modules, which are hoisted to imports of them.
+  Require,
+}
+
+impl ImportKind {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      ImportKind::Entry => "entry",
+      ImportKind::Import => "import",
+      ImportKind::DynamicImport => "dynamic-import",
+      ImportKind::Require => "require",
+    }
+  }
 }
 
 pub struct TransformArgs<'a> {
   pub id: &'a ModuleId,
   pub code: &'a String,
   pub loader: &'a mut Loader,
+  /// Set it to skip `transform` hooks of later plugins, which is useful for plugins fully handling
+  /// the module, like compilers of other languages.
+  pub stop_further_transforms: bool,
+}
+
+pub struct ShouldTransformCachedModuleArgs<'a> {
+  pub id: &'a ModuleId,
+  /// The cached code, which is the output of `transform` hooks of a previous build.
+  pub code: &'a String,
+}
+
+pub struct RenderChunkArgs<'a> {
+  pub chunk: &'a RenderedChunk,
+  pub code: &'a String,
+}
+
+/// A generated chunk, whose code is passed to `render_chunk` hooks.
+#[derive(Debug, Clone)]
+pub struct RenderedChunk {
+  pub filename: String,
+  /// Whether the chunk is of an entry in `input`.
+  pub is_entry: bool,
+  /// Modules assigned to the chunk in execution order, including ones dropped by treeshaking.
+  /// External modules are excluded.
+  pub module_ids: Vec<ModuleId>,
 }
 
 pub struct LoadArgs<'a> {
   pub id: &'a ModuleId,
 }
+
+pub struct TransformAstArgs<'a> {
+  pub id: &'a ModuleId,
+  pub ast: &'a mut ast::Module,
+}
+
+/// Exports of a module, split by whether they are retained after treeshaking.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ExportsUsage {
+  pub used: Vec<JsWord>,
+  pub unused: Vec<JsWord>,
+}
+
+pub struct ExportsUsageArgs<'a> {
+  pub exports_usage: &'a BTreeMap<ModuleId, ExportsUsage>,
+}
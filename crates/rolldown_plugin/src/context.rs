@@ -1,16 +1,89 @@
+use std::path::PathBuf;
+
+use rolldown_common::{ModuleFormat, SourceMapType};
+
+use crate::Asset;
+
 #[derive(Debug, Default)]
 pub struct Context<Ctx = ()> {
   pub context: Ctx,
+  warnings: Vec<rolldown_error::Error>,
+  errors: Vec<rolldown_error::Error>,
+  bundle_intros: Vec<String>,
 }
 
 impl Context {
   pub fn new() -> Self {
     Self::with_context(())
   }
+
+  /// Prepend `code` to the chunk evaluated first when an entry is loaded, before the code of any
+  /// module, like setting up globals. Entries sharing that chunk run it once, and identical snippets
+  /// emitted by any plugins are only added once. Snippets are ordered by when they're first emitted,
+  /// so emit them in `build_start` if the order matters.
+  pub fn emit_bundle_intro(&mut self, code: impl Into<String>) {
+    self.bundle_intros.push(code.into());
+  }
 }
 
 impl<T> Context<T> {
   pub fn with_context(context: T) -> Self {
-    Self { context }
+    Self {
+      context,
+      warnings: vec![],
+      errors: vec![],
+      bundle_intros: vec![],
+    }
+  }
+
+  /// Report a warning via `on_warn` after the hook returns. Like errors emitted via `error`, it's
+  /// attributed to the plugin and the module handled by the hook.
+  pub fn warn(&mut self, warning: rolldown_error::Error) {
+    self.warnings.push(warning);
+  }
+
+  /// Fail the build after the hook returns. Unlike returning an error from the hook, multiple errors
+  /// could be reported, and the hook could still return its output, which is discarded though.
+  pub fn error(&mut self, error: rolldown_error::Error) {
+    self.errors.push(error);
+  }
+
+  /// Take warnings and errors emitted by the hook.
+  pub fn take_diagnostics(&mut self) -> (Vec<rolldown_error::Error>, Vec<rolldown_error::Error>) {
+    (
+      std::mem::take(&mut self.warnings),
+      std::mem::take(&mut self.errors),
+    )
+  }
+
+  /// Take snippets emitted by the hook via `emit_bundle_intro`.
+  pub fn take_bundle_intros(&mut self) -> Vec<String> {
+    std::mem::take(&mut self.bundle_intros)
+  }
+}
+
+/// `generate_bundle` hooks receive assets emitted by `emit_file` via `context`.
+impl Context<Vec<Asset>> {
+  /// Add an asset to the output, which is appended after the hook returns.
+  pub fn emit_file(&mut self, asset: Asset) {
+    self.context.push(asset);
   }
 }
+
+/// Resolved output options of the build, which hooks of the render phase receive via `Context`.
+#[derive(Debug, Clone)]
+pub struct RenderOutputOptions {
+  pub format: ModuleFormat,
+  /// `None` if the output isn't written by rolldown, such as by `generate`.
+  pub dir: Option<String>,
+  pub sourcemap: Option<SourceMapType>,
+}
+
+/// Resolved input options of the build, which `build_start` hooks receive.
+#[derive(Debug, Clone)]
+pub struct ResolvedInputOptions {
+  /// `(name, import)` of entries in order.
+  pub input: Vec<(String, String)>,
+  pub cwd: PathBuf,
+  pub treeshake: bool,
+}
@@ -2,6 +2,24 @@ use rolldown_common::Loader;
 
 pub type TransformOutput = String;
 
+pub type RenderChunkOutput = String;
+
+#[derive(Debug)]
+pub struct Asset {
+  pub filename: String,
+  /// Empty for binary assets.
+  pub content: String,
+  /// Content of binary assets like `.wasm` files, which is written instead of `content`.
+  pub binary: Option<Vec<u8>>,
+}
+
+impl Asset {
+  /// Bytes written to the file.
+  pub fn bytes(&self) -> &[u8] {
+    self.binary.as_deref().unwrap_or(self.content.as_bytes())
+  }
+}
+
 pub struct LoadOutput {
   pub code: String,
   pub loader: Option<Loader>,
@@ -1,22 +1,75 @@
 use std::{borrow::Cow, fmt::Debug};
 
-use crate::{Context, LoadArgs, LoadOutput, ResolveArgs, TransformArgs, TransformOutput};
+use crate::{
+  Asset, Context, ExportsUsageArgs, LoadArgs, LoadOutput, RenderChunkArgs, RenderChunkOutput,
+  RenderOutputOptions, ResolveArgs, ResolvedInputOptions, ShouldTransformCachedModuleArgs,
+  TransformArgs, TransformAstArgs, TransformOutput,
+};
 
 #[derive(Debug)]
 pub struct ResolvedId {
   pub id: String,
-  pub external: bool,
+  pub external: External,
 }
 
+/// Whether a resolved module is external, like `external` returned by `resolveId` hooks of rollup.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum External {
+  #[default]
+  False,
+  /// Like `Absolute`, which keeps the output of plugins returning a plain `true` unchanged.
+  True,
+  /// Absolute ids are referred to by paths relative to the importing chunk.
+  Relative,
+  /// The id is kept as is, even if it's an absolute path.
+  Absolute,
+}
+
+impl From<bool> for External {
+  fn from(value: bool) -> Self {
+    if value {
+      External::True
+    } else {
+      External::False
+    }
+  }
+}
+
+pub type BuildStartReturn = rolldown_error::Result<()>;
+pub type BuildEndReturn = rolldown_error::Result<()>;
 pub type ResolveReturn = rolldown_error::Result<Option<ResolvedId>>;
 pub type TransformReturn = rolldown_error::Result<Option<TransformOutput>>;
 pub type LoadReturn = rolldown_error::Result<Option<LoadOutput>>;
+pub type ShouldTransformCachedModuleReturn = rolldown_error::Result<bool>;
+pub type TransformAstReturn = rolldown_error::Result<()>;
+pub type ExportsUsageReturn = rolldown_error::Result<()>;
+pub type RenderChunkReturn = rolldown_error::Result<Option<RenderChunkOutput>>;
+pub type GenerateBundleReturn = rolldown_error::Result<()>;
 pub type PluginName<'a> = Cow<'a, str>;
 
 #[async_trait::async_trait]
 pub trait BuildPlugin: Debug + Send + Sync {
   fn name(&self) -> PluginName;
 
+  /// Called once before any module is resolved or loaded, like initializing caches.
+  async fn build_start(
+    &self,
+    _ctx: &mut Context,
+    _options: &ResolvedInputOptions,
+  ) -> BuildStartReturn {
+    Ok(())
+  }
+
+  /// Called once the build finishes, or with all errors failing it, whether they come from
+  /// `build_start` hooks, generating the module graph or rendering chunks.
+  async fn build_end(
+    &self,
+    _ctx: &mut Context,
+    _errors: Option<&rolldown_error::Errors>,
+  ) -> BuildEndReturn {
+    Ok(())
+  }
+
   async fn load(&self, _ctx: &mut Context, _args: &mut LoadArgs) -> LoadReturn {
     Ok(None)
   }
@@ -25,7 +78,72 @@ pub trait BuildPlugin: Debug + Send + Sync {
     Ok(None)
   }
 
+  /// A catch-all called only if neither `resolve` hooks nor the builtin resolver could resolve the
+  /// specifier, before it's treated as external or reported as unresolved.
+  async fn resolve_fallback(&self, _ctx: &mut Context, _args: &mut ResolveArgs) -> ResolveReturn {
+    Ok(None)
+  }
+
+  /// `transform` hooks run in the order of plugins, and each one receives the code returned by the
+  /// previous one.
   async fn transform(&self, _ctx: &mut Context, _args: &mut TransformArgs) -> TransformReturn {
     Ok(None)
   }
+
+  /// Only called if `cache` is enabled and the source of a module is unchanged since the previous
+  /// build. Return `true` to transform the module again instead of reusing the cached code, e.g. if
+  /// the result of `transform` depends on something else than the source.
+  async fn should_transform_cached_module(
+    &self,
+    _ctx: &mut Context,
+    _args: &ShouldTransformCachedModuleArgs,
+  ) -> ShouldTransformCachedModuleReturn {
+    Ok(false)
+  }
+
+  /// Mutate the parsed AST in place, which saves the cost of printing and re-parsing in `transform`.
+  /// It's called after all `transform` hooks, and the AST is always valid JavaScript at this point.
+  async fn transform_ast(
+    &self,
+    _ctx: &mut Context,
+    _args: &mut TransformAstArgs,
+  ) -> TransformAstReturn {
+    Ok(())
+  }
+
+  /// Called after treeshaking with which exports of each module are used and which are dropped.
+  /// It helps to find out why a module isn't as tree-shakeable as expected.
+  async fn exports_usage(
+    &self,
+    _ctx: &mut Context,
+    _args: &ExportsUsageArgs,
+  ) -> ExportsUsageReturn {
+    Ok(())
+  }
+
+  /// Called for each generated chunk in order of plugins, and each one receives the code returned by
+  /// the previous one. `ctx.context` is the resolved output options, like the format. Source maps
+  /// of chunks don't follow changes made here.
+  async fn render_chunk(
+    &self,
+    _ctx: &mut Context<&RenderOutputOptions>,
+    _args: &RenderChunkArgs,
+  ) -> RenderChunkReturn {
+    Ok(None)
+  }
+
+  /// Called once all assets are rendered and before they are written, so plugins could inspect,
+  /// change or delete them. Assets added by `ctx.emit_file` are appended to `bundle` after the hook
+  /// returns, and go through the same check of conflicting file names as chunks.
+  ///
+  /// If assets are streamed by `on_asset`, the rendered ones have been handed over already, so only
+  /// their file names are left, and changing them has no effect. Emitted assets are still handed
+  /// over.
+  async fn generate_bundle(
+    &self,
+    _ctx: &mut Context<Vec<Asset>>,
+    _bundle: &mut Vec<Asset>,
+  ) -> GenerateBundleReturn {
+    Ok(())
+  }
 }
@@ -1,7 +1,9 @@
 use std::path::{Path, PathBuf};
 
 use nodejs_resolver::{ResolveResult, Resolver};
-use rolldown_plugin::{async_trait, BuildPlugin, Context, ResolveArgs, ResolveReturn, ResolvedId};
+use rolldown_plugin::{
+  async_trait, BuildPlugin, Context, External, ResolveArgs, ResolveReturn, ResolvedId,
+};
 
 #[derive(Debug)]
 pub struct NodeResolvePlugin {
@@ -47,7 +49,7 @@ impl BuildPlugin for NodeResolvePlugin {
     match s {
       ResolveResult::Info(info) => Ok(Some(ResolvedId {
         id: info.path().to_string_lossy().to_string(),
-        external: false,
+        external: External::False,
       })),
       ResolveResult::Ignored => Ok(None),
     }
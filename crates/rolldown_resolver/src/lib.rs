@@ -1,33 +1,186 @@
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
 
+use derivative::Derivative;
 use nodejs_resolver::{Options, Resolver as EnhancedResolver};
-use sugar_path::AsPath;
+use sugar_path::{AsPath, SugarPath};
+use url::Url;
 
-#[derive(Debug)]
+pub type OnWarn = Arc<dyn Fn(rolldown_error::Error) + Send + Sync>;
+
+#[derive(Derivative)]
+#[derivative(Debug)]
 pub struct Resolver {
   cwd: PathBuf,
   inner: EnhancedResolver,
+  /// Options of `inner`, kept to rebuild it in builders like `with_extensions`
+  #[derivative(Debug = "ignore")]
+  options: Options,
+  /// Set by `with_exports_fallback`
+  #[derivative(Debug = "ignore")]
+  exports_fallback: Option<OnWarn>,
+  /// Set by `with_absolute_path_root`
+  absolute_path_root: Option<PathBuf>,
+  /// Set by `with_alias`
+  alias: Vec<(String, String)>,
+  /// Set by `with_tsconfig_paths`
+  tsconfig_paths: Option<TsConfigPaths>,
+}
+
+/// `compilerOptions.paths` and `baseUrl` of a `tsconfig.json`.
+#[derive(Debug, Clone, Default)]
+pub struct TsConfigPaths {
+  /// `baseUrl`, or the directory of the `tsconfig.json` if only `paths` is set. Targets of `paths`
+  /// are relative to it.
+  pub base_url: PathBuf,
+  /// Whether `baseUrl` is set, so bare specifiers are looked up in it before `node_modules`.
+  pub has_base_url: bool,
+  /// Patterns and their targets, like `("@app/*", ["src/*"])`.
+  pub paths: Vec<(String, Vec<String>)>,
+}
+
+/// Extensions tried in order for specifiers without one, unless set by `with_extensions`.
+pub const DEFAULT_EXTENSIONS: [&str; 4] = [".js", ".jsx", ".ts", ".tsx"];
+
+/// Conditions following the one of the platform by default.
+pub const DEFAULT_CONDITION_NAMES: [&str; 2] = ["import", "default"];
+
+/// The `browser` field of packages is respected if the conditions include it.
+const BROWSER_CONDITION: &str = "browser";
+
+/// `types` points to declaration files, which should never end up in a runtime bundle.
+const TYPES_CONDITION: &str = "types";
+
+/// The environment where the bundle runs, which decides the default conditions of resolving.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Platform {
+  #[default]
+  Node,
+  Browser,
+}
+
+impl Platform {
+  /// `browser` or `node`, followed by `DEFAULT_CONDITION_NAMES`.
+  pub fn condition_names(self) -> Vec<String> {
+    self.condition_names_with(DEFAULT_CONDITION_NAMES.map(ToString::to_string))
+  }
+
+  /// `browser` or `node`, followed by `condition_names`.
+  pub fn condition_names_with(
+    self,
+    condition_names: impl IntoIterator<Item = String>,
+  ) -> Vec<String> {
+    let platform_condition = match self {
+      Platform::Node => "node",
+      Platform::Browser => BROWSER_CONDITION,
+    };
+    std::iter::once(platform_condition.to_string())
+      .chain(condition_names)
+      .collect()
+  }
+}
+
+impl FromStr for Platform {
+  type Err = String;
+
+  fn from_str(value: &str) -> Result<Self, Self::Err> {
+    match value {
+      "node" => Ok(Platform::Node),
+      "browser" => Ok(Platform::Browser),
+      _ => Err(format!("Invalid platform: {value}")),
+    }
+  }
 }
 
 impl Resolver {
   pub fn with_cwd(cwd: PathBuf, preserve_symlinks: bool) -> Self {
+    Self::with_conditions(cwd, preserve_symlinks, Options::default().condition_names)
+  }
+
+  /// Conditions of `platform` are used, and the `browser` field of packages is respected for
+  /// `Platform::Browser`.
+  pub fn with_platform(cwd: PathBuf, preserve_symlinks: bool, platform: Platform) -> Self {
+    Self::with_conditions(cwd, preserve_symlinks, platform.condition_names())
+  }
+
+  /// `types` in `condition_names` is always ignored. The `browser` field of packages is respected if
+  /// `condition_names` includes `browser`.
+  pub fn with_conditions(
+    cwd: PathBuf,
+    preserve_symlinks: bool,
+    condition_names: impl IntoIterator<Item = String>,
+  ) -> Self {
+    let condition_names = condition_names
+      .into_iter()
+      .filter(|name| name != TYPES_CONDITION)
+      .collect::<Vec<_>>();
+    let options = Options {
+      symlinks: !preserve_symlinks,
+      browser_field: condition_names.iter().any(|name| name == BROWSER_CONDITION),
+      condition_names,
+      extensions: DEFAULT_EXTENSIONS.map(ToString::to_string).to_vec(),
+      // TODO(hyf0): Should we set this as default?
+      prefer_relative: true,
+      ..Default::default()
+    };
     Self {
       cwd,
-      inner: EnhancedResolver::new(Options {
-        symlinks: !preserve_symlinks,
-        extensions: vec![
-          ".js".to_string(),
-          ".jsx".to_string(),
-          ".ts".to_string(),
-          ".tsx".to_string(),
-        ],
-        // TODO(hyf0): Should we set this as default?
-        prefer_relative: true,
-        ..Default::default()
-      }),
+      inner: EnhancedResolver::new(options.clone()),
+      options,
+      exports_fallback: None,
+      absolute_path_root: None,
+      alias: vec![],
+      tsconfig_paths: None,
     }
   }
 
+  /// If a subpath of a package is blocked by its `exports`, resolve it in the file system of the
+  /// package instead of failing. Each fallback is reported via `on_warn`.
+  pub fn with_exports_fallback(mut self, on_warn: OnWarn) -> Self {
+    self.exports_fallback = Some(on_warn);
+    self
+  }
+
+  /// Resolve server-absolute specifiers like `/src/main.js` in `root` first, and then in the root of
+  /// the file system.
+  pub fn with_absolute_path_root(mut self, root: PathBuf) -> Self {
+    self.absolute_path_root = Some(root);
+    self
+  }
+
+  /// Replace specifiers by `alias` before resolving, like `@/utils` => `/project/src/utils` for
+  /// `("@", "/project/src")`. A key matches the whole specifier, or its leading segments followed by
+  /// the rest of the path, which is kept. The first matching key wins. Relative replacements are
+  /// relative to `cwd`.
+  pub fn with_alias(mut self, alias: Vec<(String, String)>) -> Self {
+    self.alias = alias;
+    self
+  }
+
+  /// Resolve bare specifiers by `paths` and `baseUrl` of a `tsconfig.json` before looking them up in
+  /// `node_modules`, like `@app/util` => `<baseUrl>/src/util.ts` for `"@app/*": ["src/*"]`.
+  pub fn with_tsconfig_paths(mut self, tsconfig_paths: TsConfigPaths) -> Self {
+    self.tsconfig_paths = Some(tsconfig_paths);
+    self
+  }
+
+  /// Try `extensions` in order for specifiers without one, instead of `DEFAULT_EXTENSIONS`. Each
+  /// extension is expected to begin with a dot, like `.mjs`.
+  pub fn with_extensions(mut self, extensions: Vec<String>) -> Self {
+    self.options.extensions = extensions;
+    self.inner = EnhancedResolver::new(self.options.clone());
+    self
+  }
+
+  /// Try `main_fields` of `package.json` in order for the entry of packages without `exports`,
+  /// instead of only `main`.
+  pub fn with_main_fields(mut self, main_fields: Vec<String>) -> Self {
+    self.options.main_fields = main_fields;
+    self.inner = EnhancedResolver::new(self.options.clone());
+    self
+  }
+
   pub fn cwd(&self) -> &PathBuf {
     &self.cwd
   }
@@ -45,22 +198,220 @@ impl Resolver {
       .map(|s| Path::new(s).parent().expect("Should have a parent dir"))
       .unwrap_or(&self.cwd);
 
-    let resolved = self.inner.resolve(importer_dir, specifier);
+    let requested = specifier;
+    let aliased = self.apply_alias(specifier);
+    let specifier = aliased.as_deref().unwrap_or(specifier);
+
+    // `file:///a/b.js` => `/a/b.js`
+    let file_path = file_url_to_path(specifier);
+    let specifier_to_resolve = file_path.as_deref().unwrap_or(specifier);
+
+    if let (Some(root), Some(path_in_root)) =
+      (&self.absolute_path_root, specifier.strip_prefix('/'))
+    {
+      let rooted = root.join(path_in_root);
+      if let Ok(nodejs_resolver::ResolveResult::Info(info)) =
+        self.inner.resolve(importer_dir, &rooted.to_string_lossy())
+      {
+        return Ok(self.to_id(info.path()));
+      }
+    }
+
+    if file_path.is_none() && is_bare_specifier(specifier) {
+      if let Some(resolved) = self.resolve_tsconfig_paths(specifier) {
+        return Ok(resolved);
+      }
+    }
+
+    let resolved = self.inner.resolve(importer_dir, specifier_to_resolve);
     match resolved {
       Ok(resolved) => match resolved {
-        nodejs_resolver::ResolveResult::Info(info) => Ok(info.path().to_string_lossy().to_string()),
+        nodejs_resolver::ResolveResult::Info(info) => Ok(self.to_id(info.path())),
         nodejs_resolver::ResolveResult::Ignored => unreachable!(),
       },
       Err(_err) => {
+        if let Some(on_warn) = &self.exports_fallback {
+          if let Some(resolved) = self.resolve_bypassing_exports(importer_dir, specifier) {
+            on_warn(rolldown_error::Error::exports_fallback(
+              specifier.to_string(),
+              &resolved,
+            ));
+            return Ok(resolved);
+          }
+        }
         if let Some(importer) = importer {
           Err(rolldown_error::Error::unresolved_import(
-            specifier.to_string(),
+            requested.to_string(),
             importer.as_path().to_path_buf(),
           ))
         } else {
-          Err(rolldown_error::Error::unresolved_entry(specifier.as_path()))
+          Err(rolldown_error::Error::unresolved_entry(requested.as_path()))
+        }
+      }
+    }
+  }
+
+  /// Return `None` if no key of `alias` matches `specifier`.
+  fn apply_alias(&self, specifier: &str) -> Option<String> {
+    self.alias.iter().find_map(|(key, replacement)| {
+      let rest = specifier.strip_prefix(key.as_str())?;
+      if !(rest.is_empty() || rest.starts_with('/') || key.ends_with('/')) {
+        // The key `@/util` doesn't match `@/utils`
+        return None;
+      }
+      let replacement = if replacement.starts_with('.') {
+        self
+          .cwd
+          .join(replacement)
+          .normalize()
+          .to_string_lossy()
+          .to_string()
+      } else {
+        replacement.clone()
+      };
+      Some(format!("{replacement}{rest}"))
+    })
+  }
+
+  /// Targets of the best matching pattern of `paths` are tried in order, and then `baseUrl`. A pattern
+  /// without `*` matches the whole specifier, and is preferred over ones with `*`, among which the
+  /// one with the longest prefix wins, like TypeScript does.
+  fn resolve_tsconfig_paths(&self, specifier: &str) -> Option<String> {
+    let tsconfig_paths = self.tsconfig_paths.as_ref()?;
+    let matched = tsconfig_paths
+      .paths
+      .iter()
+      .filter_map(|(pattern, targets)| match pattern.split_once('*') {
+        None => (pattern == specifier).then_some((usize::MAX, "", targets)),
+        Some((prefix, suffix)) => {
+          let captured = specifier.strip_prefix(prefix)?.strip_suffix(suffix)?;
+          Some((prefix.len(), captured, targets))
         }
+      })
+      .max_by_key(|(rank, ..)| *rank);
+    let mut candidates = matched
+      .into_iter()
+      .flat_map(|(_, captured, targets)| {
+        targets
+          .iter()
+          .map(move |target| target.replacen('*', captured, 1))
+      })
+      .chain(tsconfig_paths.has_base_url.then(|| specifier.to_string()));
+
+    candidates.find_map(|candidate| {
+      let path = tsconfig_paths.base_url.join(candidate);
+      match self
+        .inner
+        .resolve(&tsconfig_paths.base_url, &path.to_string_lossy())
+        .ok()?
+      {
+        nodejs_resolver::ResolveResult::Info(info) => Some(self.to_id(info.path())),
+        nodejs_resolver::ResolveResult::Ignored => None,
       }
+    })
+  }
+
+  /// The field of `package.json` by which the bare `specifier` is resolved to `resolved`, like
+  /// `exports` or `module`. Return `None` if `resolved` isn't in the package of `specifier`, or is
+  /// found in the package directly, like subpaths of packages without `exports`.
+  pub fn package_field(&self, specifier: &str, resolved: &str) -> Option<String> {
+    if !is_bare_specifier(specifier) {
+      return None;
+    }
+    let (package_name, subpath) = split_package_subpath(specifier).unwrap_or((specifier, ""));
+    let package_suffix = Path::new("node_modules").join(package_name);
+    let package_dir = Path::new(resolved)
+      .ancestors()
+      .find(|dir| dir.ends_with(&package_suffix))?;
+    let package_json = std::fs::read_to_string(package_dir.join("package.json")).ok()?;
+    let package_json = serde_json::from_str::<serde_json::Value>(&package_json).ok()?;
+
+    if package_json.get("exports").is_some() {
+      return Some("exports".to_string());
     }
+    if !subpath.is_empty() {
+      return None;
+    }
+    // An entry could omit the extension, or point to a directory with an index file.
+    let resolved = Path::new(resolved);
+    let is_resolved_by = |entry: &str| {
+      let entry = package_dir.join(entry).normalize();
+      resolved == entry || resolved.with_extension("") == entry || resolved.parent() == Some(&entry)
+    };
+    self
+      .options
+      .browser_field
+      .then_some(BROWSER_CONDITION)
+      .into_iter()
+      .chain(self.options.main_fields.iter().map(String::as_str))
+      .find(|field| {
+        package_json
+          .get(field)
+          .and_then(|entry| entry.as_str())
+          .map_or(false, is_resolved_by)
+      })
+      .map(ToString::to_string)
+  }
+
+  /// `pkg/internal/util` => `./internal/util` in the nearest `node_modules/pkg`.
+  fn resolve_bypassing_exports(&self, importer_dir: &Path, specifier: &str) -> Option<String> {
+    let (package_name, subpath) = split_package_subpath(specifier)?;
+    let package_dir = importer_dir
+      .ancestors()
+      .map(|dir| dir.join("node_modules").join(package_name))
+      .find(|dir| dir.join("package.json").is_file())?;
+
+    match self
+      .inner
+      .resolve(&package_dir, &format!("./{subpath}"))
+      .ok()?
+    {
+      nodejs_resolver::ResolveResult::Info(info) => Some(self.to_id(info.path())),
+      nodejs_resolver::ResolveResult::Ignored => None,
+    }
+  }
+
+  /// Unless symlinks are preserved, a module is identified by its real path, so it's the same module
+  /// whether it's reached through symlinks or not. The resolver follows symlinks of directories, but
+  /// could miss ones of the file itself. The path is kept if it can't be canonicalized.
+  fn to_id(&self, path: &Path) -> String {
+    let real_path = self
+      .options
+      .symlinks
+      .then(|| std::fs::canonicalize(path).ok())
+      .flatten();
+    let path = real_path.as_deref().unwrap_or(path).to_string_lossy();
+    // `canonicalize` returns verbatim paths on Windows, like `\\?\C:\a.js`.
+    path.strip_prefix(r"\\?\").unwrap_or(&path).to_string()
+  }
+}
+
+/// Return `None` if `specifier` isn't a `file://` URL, or the URL can't be a path of the platform,
+/// like `file://host/a.js` outside of Windows, where it's a UNC path. Percent-encoded bytes are
+/// decoded, and `file:///C:/a.js` is `C:\a.js` on Windows.
+fn file_url_to_path(specifier: &str) -> Option<String> {
+  if !specifier.starts_with("file:") {
+    return None;
+  }
+  let path = Url::parse(specifier).ok()?.to_file_path().ok()?;
+  path.into_os_string().into_string().ok()
+}
+
+/// Specifiers that are neither relative nor absolute, like `pkg` or `@app/util`.
+fn is_bare_specifier(specifier: &str) -> bool {
+  !specifier.starts_with('.') && !specifier.as_path().is_absolute()
+}
+
+/// `@scope/pkg/a/b` => (`@scope/pkg`, `a/b`). Return `None` if it isn't a subpath of a package.
+fn split_package_subpath(specifier: &str) -> Option<(&str, &str)> {
+  if specifier.starts_with('.') || specifier.as_path().is_absolute() {
+    return None;
   }
+  let name_segments = if specifier.starts_with('@') { 2 } else { 1 };
+  let split_at = specifier
+    .match_indices('/')
+    .nth(name_segments - 1)
+    .map(|(index, _)| index)?;
+  let (package_name, subpath) = (&specifier[..split_at], &specifier[split_at + 1..]);
+  (!subpath.is_empty()).then_some((package_name, subpath))
 }
@@ -0,0 +1,291 @@
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use rolldown_resolver::{Platform, Resolver};
+use url::Url;
+
+fn fixture(name: &str) -> PathBuf {
+  PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+    .join("tests/fixtures")
+    .join(name)
+}
+
+#[test]
+fn types_condition_of_exports_is_ignored() {
+  let cwd = fixture("exports_types_condition");
+  let resolver =
+    Resolver::with_conditions(cwd.clone(), true, ["types".to_string(), "node".to_string()]);
+  let resolved = resolver.resolve(None, "pkg").unwrap();
+  assert_eq!(
+    PathBuf::from(resolved),
+    cwd.join("node_modules/pkg/index.js")
+  );
+}
+
+#[test]
+fn non_exported_subpath_is_unresolved_by_default() {
+  let resolver = Resolver::with_cwd(fixture("exports_fallback"), true);
+  assert!(resolver.resolve(None, "pkg/internal/util").is_err());
+}
+
+#[test]
+fn non_exported_subpath_falls_back_to_file_system() {
+  let cwd = fixture("exports_fallback");
+  let warnings = Arc::new(Mutex::new(vec![]));
+  let resolver = Resolver::with_cwd(cwd.clone(), true).with_exports_fallback({
+    let warnings = warnings.clone();
+    Arc::new(move |warning| warnings.lock().unwrap().push(warning))
+  });
+  let resolved = resolver.resolve(None, "pkg/internal/util").unwrap();
+  assert_eq!(
+    PathBuf::from(resolved),
+    cwd.join("node_modules/pkg/internal/util.js")
+  );
+  let warnings = warnings.lock().unwrap();
+  assert_eq!(warnings.len(), 1);
+  assert_eq!(warnings[0].kind.code(), "EXPORTS_FALLBACK");
+}
+
+#[test]
+fn browser_platform_picks_browser_condition() {
+  let cwd = fixture("platform_conditions");
+  let resolver = Resolver::with_platform(cwd.clone(), true, Platform::Browser);
+  let resolved = resolver.resolve(None, "pkg").unwrap();
+  assert_eq!(
+    PathBuf::from(resolved),
+    cwd.join("node_modules/pkg/browser.js")
+  );
+}
+
+#[test]
+fn node_platform_picks_node_condition() {
+  let cwd = fixture("platform_conditions");
+  let resolver = Resolver::with_platform(cwd.clone(), true, Platform::Node);
+  let resolved = resolver.resolve(None, "pkg").unwrap();
+  assert_eq!(
+    PathBuf::from(resolved),
+    cwd.join("node_modules/pkg/node.js")
+  );
+}
+
+#[test]
+fn absolute_path_is_resolved() {
+  let cwd = fixture("absolute_paths");
+  let resolver = Resolver::with_cwd(cwd.clone(), true);
+  let importer = cwd.join("main.js");
+  let specifier = cwd.join("src/a.js");
+  let resolved = resolver
+    .resolve(
+      Some(&importer.to_string_lossy()),
+      &specifier.to_string_lossy(),
+    )
+    .unwrap();
+  assert_eq!(PathBuf::from(resolved), specifier);
+}
+
+#[test]
+fn file_url_is_resolved() {
+  let cwd = fixture("absolute_paths");
+  let resolver = Resolver::with_cwd(cwd.clone(), true);
+  let importer = cwd.join("main.js");
+  let url = Url::from_file_path(cwd.join("src/b c.js")).unwrap();
+  let resolved = resolver
+    .resolve(Some(&importer.to_string_lossy()), url.as_str())
+    .unwrap();
+  assert_eq!(PathBuf::from(resolved), cwd.join("src/b c.js"));
+}
+
+#[cfg(not(windows))]
+#[test]
+fn file_url_with_localhost_is_resolved() {
+  let cwd = fixture("absolute_paths");
+  let resolver = Resolver::with_cwd(cwd.clone(), true);
+  let mut url = Url::from_file_path(cwd.join("src/a.js")).unwrap();
+  url.set_host(Some("localhost")).unwrap();
+  let resolved = resolver.resolve(None, url.as_str()).unwrap();
+  assert_eq!(PathBuf::from(resolved), cwd.join("src/a.js"));
+}
+
+#[test]
+fn server_absolute_path_is_resolved_in_root() {
+  let cwd = fixture("absolute_paths");
+  let resolver = Resolver::with_cwd(cwd.clone(), true).with_absolute_path_root(cwd.clone());
+  let importer = cwd.join("main.js");
+  let resolved = resolver
+    .resolve(Some(&importer.to_string_lossy()), "/src/a.js")
+    .unwrap();
+  assert_eq!(PathBuf::from(resolved), cwd.join("src/a.js"));
+}
+
+#[test]
+fn subpath_of_scoped_package_is_resolved_via_exports() {
+  let cwd = fixture("scoped_exports");
+  let resolver = Resolver::with_cwd(cwd.clone(), true);
+  let resolved = resolver.resolve(None, "@scope/pkg/feature").unwrap();
+  assert_eq!(
+    PathBuf::from(resolved),
+    cwd.join("node_modules/@scope/pkg/dist/feature.js")
+  );
+}
+
+#[test]
+fn non_exported_subpath_of_scoped_package_falls_back_to_file_system() {
+  let cwd = fixture("scoped_exports");
+  let resolver =
+    Resolver::with_cwd(cwd.clone(), true).with_exports_fallback(Arc::new(|_warning| {}));
+  let resolved = resolver.resolve(None, "@scope/pkg/internal").unwrap();
+  assert_eq!(
+    PathBuf::from(resolved),
+    cwd.join("node_modules/@scope/pkg/internal.js")
+  );
+}
+
+fn alias_resolver(cwd: PathBuf) -> Resolver {
+  Resolver::with_cwd(cwd.clone(), true).with_alias(vec![
+    (
+      "@".to_string(),
+      cwd.join("src").to_string_lossy().to_string(),
+    ),
+    ("utils".to_string(), "./src/utils".to_string()),
+  ])
+}
+
+#[test]
+fn alias_prefix_keeps_rest_of_specifier() {
+  let cwd = fixture("alias");
+  let resolved = alias_resolver(cwd.clone())
+    .resolve(None, "@/utils/format")
+    .unwrap();
+  assert_eq!(PathBuf::from(resolved), cwd.join("src/utils/format.js"));
+}
+
+#[test]
+fn alias_exact_match_with_relative_replacement() {
+  let cwd = fixture("alias");
+  let resolved = alias_resolver(cwd.clone()).resolve(None, "utils").unwrap();
+  assert_eq!(PathBuf::from(resolved), cwd.join("src/utils/index.js"));
+}
+
+#[test]
+fn alias_only_matches_whole_segments() {
+  let cwd = fixture("alias");
+  let importer = cwd.join("main.js");
+  let err = alias_resolver(cwd.clone())
+    .resolve(Some(&importer.to_string_lossy()), "utilsx")
+    .unwrap_err();
+  assert_eq!(err.kind.code(), "UNRESOLVED_IMPORT");
+}
+
+fn extensions_resolver(cwd: PathBuf) -> Resolver {
+  Resolver::with_cwd(cwd, true).with_extensions(vec![
+    ".mjs".to_string(),
+    ".js".to_string(),
+    ".cjs".to_string(),
+  ])
+}
+
+#[test]
+fn extensions_are_tried_in_order() {
+  let cwd = fixture("extensions");
+  let resolved = extensions_resolver(cwd.clone())
+    .resolve(None, "./util")
+    .unwrap();
+  assert_eq!(PathBuf::from(resolved), cwd.join("util.mjs"));
+
+  let resolved = Resolver::with_cwd(cwd.clone(), true)
+    .resolve(None, "./util")
+    .unwrap();
+  assert_eq!(PathBuf::from(resolved), cwd.join("util.js"));
+}
+
+#[test]
+fn extensions_not_configured_are_unresolved() {
+  let cwd = fixture("extensions");
+  let resolved = extensions_resolver(cwd.clone())
+    .resolve(None, "./config")
+    .unwrap();
+  assert_eq!(PathBuf::from(resolved), cwd.join("config.cjs"));
+
+  let importer = cwd.join("main.js");
+  let err = Resolver::with_cwd(cwd.clone(), true)
+    .resolve(Some(&importer.to_string_lossy()), "./config")
+    .unwrap_err();
+  assert_eq!(err.kind.code(), "UNRESOLVED_IMPORT");
+}
+
+#[test]
+fn main_field_is_used_by_default() {
+  let cwd = fixture("main_fields");
+  let resolved = Resolver::with_cwd(cwd.clone(), true)
+    .resolve(None, "pkg")
+    .unwrap();
+  assert_eq!(
+    PathBuf::from(resolved),
+    cwd.join("node_modules/pkg/main.js")
+  );
+}
+
+#[test]
+fn main_fields_are_tried_in_order() {
+  let cwd = fixture("main_fields");
+  let resolved = Resolver::with_cwd(cwd.clone(), true)
+    .with_main_fields(vec!["module".to_string(), "main".to_string()])
+    .resolve(None, "pkg")
+    .unwrap();
+  assert_eq!(
+    PathBuf::from(resolved),
+    cwd.join("node_modules/pkg/module.js")
+  );
+}
+
+#[test]
+fn browser_condition_enables_browser_field() {
+  let cwd = fixture("main_fields");
+  let resolver = Resolver::with_conditions(
+    cwd.clone(),
+    true,
+    Platform::Node.condition_names_with(["browser".to_string()]),
+  )
+  .with_main_fields(vec!["module".to_string(), "main".to_string()]);
+  let resolved = resolver.resolve(None, "pkg").unwrap();
+  assert_eq!(
+    PathBuf::from(resolved),
+    cwd.join("node_modules/pkg/module.browser.js")
+  );
+}
+
+#[test]
+fn directory_subpath_of_package_resolves_to_index() {
+  let cwd = fixture("package_directories");
+  let resolved = Resolver::with_cwd(cwd.clone(), true)
+    .resolve(None, "pkg/feature")
+    .unwrap();
+  assert_eq!(
+    PathBuf::from(resolved),
+    cwd.join("node_modules/pkg/feature/index.js")
+  );
+}
+
+#[test]
+fn directory_subpath_of_package_respects_its_package_json() {
+  let cwd = fixture("package_directories");
+  let resolved = Resolver::with_cwd(cwd.clone(), true)
+    .resolve(None, "pkg/nested")
+    .unwrap();
+  assert_eq!(
+    PathBuf::from(resolved),
+    cwd.join("node_modules/pkg/nested/entry.js")
+  );
+}
+
+#[test]
+fn non_exported_directory_subpath_falls_back_to_index() {
+  let cwd = fixture("exports_fallback");
+  let resolver =
+    Resolver::with_cwd(cwd.clone(), true).with_exports_fallback(Arc::new(|_warning| {}));
+  let resolved = resolver.resolve(None, "pkg/internal/helpers").unwrap();
+  assert_eq!(
+    PathBuf::from(resolved),
+    cwd.join("node_modules/pkg/internal/helpers/index.js")
+  );
+}
@@ -82,6 +82,9 @@ impl RuntimeHelpers {
 
 define_helpers!(Helpers {
     merge_namespaces(_mergeNamespaces): (),
+    dirname_shim(__filename, __dirname, __rolldown_fileURLToPath, __rolldown_dirname): (),
+    global_this_polyfill(globalThis): (),
+    module_registry(__rolldown_modules, __rolldown_define, __rolldown_require): (),
 });
 
 #[test]
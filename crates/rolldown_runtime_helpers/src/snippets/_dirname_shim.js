@@ -0,0 +1,4 @@
+import { fileURLToPath as __rolldown_fileURLToPath } from 'node:url';
+import { dirname as __rolldown_dirname } from 'node:path';
+const __filename = __rolldown_fileURLToPath(import.meta.url);
+const __dirname = __rolldown_dirname(__filename);
\ No newline at end of file
@@ -0,0 +1 @@
+var globalThis = typeof self !== 'undefined' ? self : typeof window !== 'undefined' ? window : typeof global !== 'undefined' ? global : Function('return this')();
\ No newline at end of file
@@ -0,0 +1,15 @@
+var __rolldown_modules = {};
+function __rolldown_define(id, factory) {
+	__rolldown_modules[id] = { factory: factory, module: null, result: null };
+}
+function __rolldown_require(id) {
+	var record = __rolldown_modules[id];
+	if (!record.module) {
+		record.module = { exports: {} };
+		record.result = record.factory.call(record.module.exports, record.module, record.module.exports);
+	}
+	// Factories of modules using top-level await are async, whose exports are ready once they settle.
+	return record.result instanceof Promise
+		? record.result.then(function () { return record.module.exports; })
+		: record.module.exports;
+}
\ No newline at end of file
@@ -0,0 +1,131 @@
+use rustc_hash::{FxHashMap, FxHashSet};
+use swc_core::{
+  common::SyntaxContext,
+  ecma::{
+    ast,
+    visit::{VisitMut, VisitMutWith},
+  },
+};
+
+/// Replace free references of names in `values`, like `process.env.NODE_ENV`, with their values.
+/// Names are dotted paths of member expressions rooted at a global, so locals shadowing the global
+/// are left alone. It should run after `resolve`, and before `treeshake` folds the replaced values.
+///
+/// Identifiers in the values are considered as globals as well. Names replaced in the module are
+/// returned.
+pub fn define<'a>(
+  ast: &mut ast::Module,
+  values: &'a FxHashMap<String, ast::Expr>,
+  unresolved_ctxt: SyntaxContext,
+) -> FxHashSet<&'a str> {
+  let mut definer = Definer {
+    values,
+    unresolved_ctxt,
+    replaced: Default::default(),
+  };
+  ast.visit_mut_with(&mut definer);
+  definer.replaced
+}
+
+struct Definer<'a> {
+  values: &'a FxHashMap<String, ast::Expr>,
+  unresolved_ctxt: SyntaxContext,
+  replaced: FxHashSet<&'a str>,
+}
+
+impl<'a> Definer<'a> {
+  fn value_of(&self, expr: &ast::Expr) -> Option<(&'a str, ast::Expr)> {
+    let (name, value) = self.values.get_key_value(&self.name_of(expr)?)?;
+    let mut value = value.clone();
+    value.visit_mut_with(&mut GlobalBinder {
+      unresolved_ctxt: self.unresolved_ctxt,
+    });
+    Some((name, value))
+  }
+
+  /// Dotted path of `expr` if it's a free identifier or a static member access of one.
+  fn name_of(&self, expr: &ast::Expr) -> Option<String> {
+    match expr {
+      ast::Expr::Ident(ident) if ident.span.ctxt == self.unresolved_ctxt => {
+        Some(ident.sym.to_string())
+      }
+      ast::Expr::Member(ast::MemberExpr { obj, prop, .. }) => {
+        let prop = match prop {
+          ast::MemberProp::Ident(ident) => &ident.sym,
+          ast::MemberProp::Computed(ast::ComputedPropName {
+            expr: box ast::Expr::Lit(ast::Lit::Str(s)),
+            ..
+          }) => &s.value,
+          _ => return None,
+        };
+        Some(format!("{}.{prop}", self.name_of(obj)?))
+      }
+      _ => None,
+    }
+  }
+
+  /// Assignment targets are kept, since replacing them would produce invalid code. Parts of them
+  /// are still replaced, like `process.env.NODE_ENV` in `flags[process.env.NODE_ENV] = true`.
+  fn visit_mut_target(&mut self, target: &mut ast::Expr) {
+    if self.name_of(target).is_none() {
+      target.visit_mut_children_with(self);
+    }
+  }
+}
+
+impl VisitMut for Definer<'_> {
+  fn visit_mut_expr(&mut self, node: &mut ast::Expr) {
+    match self.value_of(node) {
+      Some((name, value)) => {
+        self.replaced.insert(name);
+        *node = value;
+      }
+      None => node.visit_mut_children_with(self),
+    }
+  }
+
+  fn visit_mut_assign_expr(&mut self, node: &mut ast::AssignExpr) {
+    match &mut node.left {
+      ast::PatOrExpr::Expr(target) | ast::PatOrExpr::Pat(box ast::Pat::Expr(target)) => {
+        self.visit_mut_target(target)
+      }
+      ast::PatOrExpr::Pat(pat) => pat.visit_mut_with(self),
+    }
+    node.right.visit_mut_with(self);
+  }
+
+  fn visit_mut_update_expr(&mut self, node: &mut ast::UpdateExpr) {
+    self.visit_mut_target(&mut node.arg);
+  }
+}
+
+/// Bind identifiers of a value to globals of the module it's inlined into. Bindings declared by the
+/// value, like parameters of a function, are bound the same way as references to them, so they
+/// still match. Names of properties aren't identifiers to bind.
+struct GlobalBinder {
+  unresolved_ctxt: SyntaxContext,
+}
+
+impl VisitMut for GlobalBinder {
+  fn visit_mut_ident(&mut self, node: &mut ast::Ident) {
+    node.span.ctxt = self.unresolved_ctxt;
+  }
+
+  fn visit_mut_member_prop(&mut self, node: &mut ast::MemberProp) {
+    if let ast::MemberProp::Computed(computed) = node {
+      computed.visit_mut_with(self);
+    }
+  }
+
+  fn visit_mut_super_prop(&mut self, node: &mut ast::SuperProp) {
+    if let ast::SuperProp::Computed(computed) = node {
+      computed.visit_mut_with(self);
+    }
+  }
+
+  fn visit_mut_prop_name(&mut self, node: &mut ast::PropName) {
+    if let ast::PropName::Computed(computed) = node {
+      computed.visit_mut_with(self);
+    }
+  }
+}
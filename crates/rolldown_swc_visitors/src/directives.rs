@@ -0,0 +1,45 @@
+use swc_core::ecma::{ast, atoms::JsWord};
+
+/// Directives meaningful to frameworks, which mark the module as a boundary between the client and
+/// the server.
+pub const MODULE_DIRECTIVES: [&str; 2] = ["use client", "use server"];
+
+/// Remove `"use strict"` and `MODULE_DIRECTIVES` of a source module, and return the latter. They are
+/// meaningless after hoisting, since they would be in the middle of the chunk. ESM is always strict,
+/// and other formats emit their own `"use strict"`. Module directives are emitted by the chunk
+/// instead, if the module is its entry, and dropped with a warning otherwise. The shebang is kept
+/// apart by the parser, so it still precedes them.
+pub fn take_directives(ast: &mut ast::Module) -> Vec<JsWord> {
+  let prologue_len = ast
+    .body
+    .iter()
+    .take_while(|item| directive_of(item).is_some())
+    .count();
+  let mut directives = vec![];
+  let mut index = 0;
+  ast.body.retain(|item| {
+    let is_in_prologue = index < prologue_len;
+    index += 1;
+    match directive_of(item).filter(|_| is_in_prologue) {
+      Some("use strict") => false,
+      Some(directive) if MODULE_DIRECTIVES.contains(&directive) => {
+        if !directives.iter().any(|taken: &JsWord| taken == directive) {
+          directives.push(directive.into());
+        }
+        false
+      }
+      _ => true,
+    }
+  });
+  directives
+}
+
+fn directive_of(item: &ast::ModuleItem) -> Option<&str> {
+  match item {
+    ast::ModuleItem::Stmt(ast::Stmt::Expr(ast::ExprStmt {
+      expr: box ast::Expr::Lit(ast::Lit::Str(s)),
+      ..
+    })) => Some(&*s.value),
+    _ => None,
+  }
+}
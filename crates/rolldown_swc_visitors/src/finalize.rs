@@ -1,5 +1,5 @@
 use ast::{ExportNamedSpecifier, Id, Ident, PropName};
-use rolldown_common::{ChunkId, ModuleId};
+use rolldown_common::{chunk_import_specifier, ChunkId, ModuleId};
 use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
 use swc_common::{util::take::Take, SyntaxContext, DUMMY_SP};
 use swc_core::{
@@ -48,6 +48,8 @@ pub struct FinalizeContext<'me> {
   /// All declared scoped names in this chunk
   pub declared_scoped_names: &'me HashSet<JsWord>,
   pub unresolved_ctxt: SyntaxContext,
+  /// File name of the chunk being finalized, which specifiers of dynamic imports are relative to
+  pub chunk_filename: &'me str,
   /// Used to rewrite dynamic import
   pub chunk_filename_by_id: &'me HashMap<ChunkId, String>,
   // All top_level_ctxt of modules belong to this chunk
@@ -158,7 +160,7 @@ impl<'a> Finalizer<'a> {
         let module_id = self.resolve_module_id(local_module_id)?;
         let chunk_id = self.ctx.split_point_id_to_chunk_id.get(module_id)?;
         let filename = self.ctx.chunk_filename_by_id.get(chunk_id)?;
-        *local_module_id = format!("./{}", filename.clone()).into();
+        *local_module_id = chunk_import_specifier(self.ctx.chunk_filename, filename).into();
       };
     }
 
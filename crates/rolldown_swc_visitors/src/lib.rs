@@ -19,10 +19,22 @@ use swc_core::{
 pub use treeshake::*;
 mod to_cjs;
 pub use to_cjs::*;
+mod to_amd;
+pub use to_amd::*;
+mod directives;
+pub use directives::*;
+mod wrap_commonjs;
+pub use wrap_commonjs::*;
+mod minify;
+pub use minify::*;
 mod export_mode_shimer;
 pub use export_mode_shimer::*;
 mod clean_ast;
 pub use clean_ast::clean_ast;
+mod rewrite_import_meta_url;
+pub use rewrite_import_meta_url::*;
+mod define;
+pub use define::*;
 
 struct ClearSyntaxContext;
 
@@ -0,0 +1,73 @@
+use std::sync::Arc;
+
+use swc_core::common::{comments::SingleThreadedComments, Mark, SourceMap};
+use swc_core::ecma::minifier::{
+  optimize,
+  option::{CompressOptions, ExtraOptions, MangleOptions, MinifyOptions},
+};
+use swc_core::ecma::{
+  ast,
+  atoms::JsWord,
+  transforms::base::{fixer::fixer, resolver},
+  visit::FoldWith,
+};
+
+/// Minify a generated chunk. Names in `reserved` are kept as-is.
+/// Top level names are neither mangled nor dropped unless `top_level` is set, because they might be
+/// exported. Names renamed by deconflicting are no different from others, since the chunk is
+/// resolved again.
+pub fn minify(
+  ast: ast::Module,
+  cm: Arc<SourceMap>,
+  comments: &SingleThreadedComments,
+  compress: bool,
+  mangle: bool,
+  reserved: Vec<JsWord>,
+  top_level: bool,
+) -> ast::Module {
+  let unresolved_mark = Mark::new();
+  let top_level_mark = Mark::new();
+  let ast = ast.fold_with(&mut resolver(unresolved_mark, top_level_mark, false));
+
+  optimize(
+    ast.into(),
+    cm,
+    Some(comments),
+    None,
+    &MinifyOptions {
+      compress: compress.then(|| CompressOptions {
+        passes: 2,
+        bools: true,
+        collapse_vars: true,
+        comparisons: true,
+        computed_props: true,
+        conditionals: true,
+        dead_code: true,
+        evaluate: true,
+        if_return: true,
+        join_vars: true,
+        loops: true,
+        negate_iife: true,
+        props: true,
+        sequences: 200,
+        side_effects: true,
+        switches: true,
+        unused: true,
+        ..Default::default()
+      }),
+      mangle: mangle.then(|| MangleOptions {
+        top_level: top_level.into(),
+        reserved,
+        ..Default::default()
+      }),
+      ..Default::default()
+    },
+    &ExtraOptions {
+      unresolved_mark,
+      top_level_mark,
+    },
+  )
+  .fold_with(&mut fixer(Some(comments)))
+  .module()
+  .unwrap()
+}
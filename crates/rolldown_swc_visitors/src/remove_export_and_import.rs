@@ -1,20 +1,26 @@
 use ast::{
   ClassDecl, ClassExpr, ExportDefaultDecl, ExprStmt, FnDecl, ModuleDecl, ModuleItem, ParenExpr,
 };
-use swc_common::{util::take::Take, DUMMY_SP};
+use swc_common::{comments::Comments, util::take::Take, Spanned, DUMMY_SP};
 use swc_core::{
   common as swc_common,
   ecma::{ast, visit as swc_ecma_visit},
 };
 use swc_ecma_visit::{VisitMut, VisitMutWith};
 
-struct ExportRemover;
+struct ExportRemover<'a> {
+  comments: Option<&'a dyn Comments>,
+}
 
-pub fn remove_export_and_import(ast: &mut ast::Module) {
-  ast.visit_mut_with(&mut ExportRemover);
+/// With `comments`, leading comments of removed `export` keywords, like JSDoc, are moved to the
+/// declarations. Pass `None` if comments aren't printed, so they're left untouched.
+pub fn remove_export_and_import(ast: &mut ast::Module, comments: Option<&impl Comments>) {
+  ast.visit_mut_with(&mut ExportRemover {
+    comments: comments.map(|comments| comments as &dyn Comments),
+  });
 }
 
-impl ExportRemover {
+impl ExportRemover<'_> {
   fn rewrite_default_export_decl_to_stmt(&self, export_decl: ExportDefaultDecl) -> ModuleItem {
     match export_decl.decl {
       ast::DefaultDecl::Class(cls_decl) => {
@@ -62,26 +68,36 @@ impl ExportRemover {
   }
 }
 
-impl VisitMut for ExportRemover {
+impl VisitMut for ExportRemover<'_> {
   fn visit_mut_module_items(&mut self, items: &mut Vec<ast::ModuleItem>) {
     *items = items
       .take()
       .into_iter()
       .flat_map(|module_item| match module_item {
-        ModuleItem::ModuleDecl(ast::ModuleDecl::ExportDecl(decl)) => match decl.decl {
-          ast::Decl::Class(class) => {
-            vec![ModuleItem::Stmt(ast::Stmt::Decl(ast::Decl::Class(class)))]
-          }
-          ast::Decl::Fn(fn_decl) => {
-            vec![ModuleItem::Stmt(ast::Stmt::Decl(ast::Decl::Fn(fn_decl)))]
+        ModuleItem::ModuleDecl(ast::ModuleDecl::ExportDecl(decl)) => {
+          if let Some(comments) = self.comments {
+            comments.move_leading(decl.span.lo, decl.decl.span_lo());
           }
-          ast::Decl::Var(var) => {
-            vec![ModuleItem::Stmt(ast::Stmt::Decl(ast::Decl::Var(var)))]
+          match decl.decl {
+            ast::Decl::Class(class) => {
+              vec![ModuleItem::Stmt(ast::Stmt::Decl(ast::Decl::Class(class)))]
+            }
+            ast::Decl::Fn(fn_decl) => {
+              vec![ModuleItem::Stmt(ast::Stmt::Decl(ast::Decl::Fn(fn_decl)))]
+            }
+            ast::Decl::Var(var) => {
+              vec![ModuleItem::Stmt(ast::Stmt::Decl(ast::Decl::Var(var)))]
+            }
+            _ => unreachable!(),
           }
-          _ => unreachable!(),
-        },
+        }
         ModuleItem::ModuleDecl(ast::ModuleDecl::ExportDefaultDecl(decl)) => {
-          vec![self.rewrite_default_export_decl_to_stmt(decl)]
+          let export_lo = decl.span.lo;
+          let stmt = self.rewrite_default_export_decl_to_stmt(decl);
+          if let (Some(comments), ModuleItem::Stmt(ast::Stmt::Decl(_))) = (self.comments, &stmt) {
+            comments.move_leading(export_lo, stmt.span_lo());
+          }
+          vec![stmt]
         }
         ModuleItem::ModuleDecl(ast::ModuleDecl::ExportDefaultExpr(default_expr)) => {
           // Remove `export default foo`
@@ -0,0 +1,194 @@
+use rolldown_common::ModuleFormat;
+use rustc_hash::FxHashSet;
+use swc_core::common::{util::take::Take, DUMMY_SP};
+use swc_core::ecma::{
+  ast,
+  atoms::JsWord,
+  utils::{quote_ident, quote_str},
+  visit::{Visit, VisitMut, VisitMutWith, VisitWith},
+};
+
+/// The URL of the script in the iife format, which is only known while it's executed synchronously,
+/// so it's evaluated once by the wrapper of the chunk. It isn't a `<script>` in workers, so the URL
+/// of the page or the worker is used then.
+pub const CURRENT_SCRIPT_URL: &str = concat!(
+  "typeof document !== 'undefined' && document.currentScript && ",
+  "document.currentScript.src || location.href"
+);
+
+/// Replace `import.meta.url` with a variable holding an equivalent of `format`, since
+/// `import.meta` only exists in ESM. Nothing is replaced in the esm format.
+///
+/// The name of the variable is returned if any `import.meta.url` is replaced. It's unused by the
+/// chunk, so free variables the URL is derived from can't be shadowed by bindings of the chunk:
+/// - In the cjs format, it's declared at the top of the chunk, as
+///   `require('url').pathToFileURL(__filename).href`.
+/// - In the amd format, it's declared at the top of the chunk too, as
+///   `new URL(amd_module.uri, document.baseURI).href`. `amd_module` is returned instead, which
+///   needs to be bound to the `module` dependency by the wrapper of the chunk.
+/// - In the iife format, it needs to be declared by the wrapper of the chunk, as
+///   `CURRENT_SCRIPT_URL`.
+pub fn rewrite_import_meta_url(ast: &mut ast::Module, format: ModuleFormat) -> Option<JsWord> {
+  if format.is_es() {
+    return None;
+  }
+  let mut names = NameCollector::default();
+  ast.visit_with(&mut names);
+  let name = names.unused_name("import_meta_url");
+
+  let mut rewriter = ImportMetaUrlRewriter {
+    name: quote_ident!(name.clone()),
+    rewritten: false,
+  };
+  ast.visit_mut_with(&mut rewriter);
+  if !rewriter.rewritten {
+    return None;
+  }
+  let (init, returned) = match format {
+    ModuleFormat::Cjs => (file_url_of_filename(), name.clone()),
+    ModuleFormat::Amd => {
+      let module = names.unused_name("amd_module");
+      (url_of_amd_module(module.clone()), module)
+    }
+    _ => return Some(name),
+  };
+  let declaration =
+    ast::ModuleItem::Stmt(ast::Stmt::Decl(ast::Decl::Var(Box::new(ast::VarDecl {
+      span: DUMMY_SP,
+      kind: ast::VarDeclKind::Var,
+      declare: false,
+      decls: vec![ast::VarDeclarator {
+        span: DUMMY_SP,
+        name: ast::Pat::Ident(quote_ident!(name).into()),
+        init: Some(init),
+        definite: false,
+      }],
+    }))));
+  // After directives like "use strict"
+  let index = ast
+    .body
+    .iter()
+    .take_while(|item| {
+      matches!(
+        item,
+        ast::ModuleItem::Stmt(ast::Stmt::Expr(ast::ExprStmt {
+          expr: box ast::Expr::Lit(ast::Lit::Str(_)),
+          ..
+        }))
+      )
+    })
+    .count();
+  ast.body.insert(index, declaration);
+  Some(returned)
+}
+
+struct ImportMetaUrlRewriter {
+  name: ast::Ident,
+  rewritten: bool,
+}
+
+impl VisitMut for ImportMetaUrlRewriter {
+  fn visit_mut_expr(&mut self, node: &mut ast::Expr) {
+    if is_import_meta_url(node) {
+      *node = ast::Expr::Ident(self.name.clone());
+      self.rewritten = true;
+    } else {
+      node.visit_mut_children_with(self);
+    }
+  }
+}
+
+/// All names of identifiers in the module, no matter whether they're bindings or references.
+#[derive(Default)]
+struct NameCollector {
+  names: FxHashSet<JsWord>,
+}
+
+impl NameCollector {
+  /// `base`, or `base$1`, `base$2` and so on if it's used.
+  fn unused_name(&self, base: &str) -> JsWord {
+    let mut name = JsWord::from(base);
+    let mut count = 1;
+    while self.names.contains(&name) {
+      name = format!("{base}${count}").into();
+      count += 1;
+    }
+    name
+  }
+}
+
+impl Visit for NameCollector {
+  fn visit_ident(&mut self, ident: &ast::Ident) {
+    self.names.insert(ident.sym.clone());
+  }
+}
+
+fn is_import_meta_url(expr: &ast::Expr) -> bool {
+  matches!(
+    expr,
+    ast::Expr::Member(ast::MemberExpr {
+      obj: box ast::Expr::MetaProp(ast::MetaPropExpr {
+        kind: ast::MetaPropKind::ImportMeta,
+        ..
+      }),
+      prop: ast::MemberProp::Ident(prop),
+      ..
+    }) if &*prop.sym == "url"
+  )
+}
+
+/// `require('url').pathToFileURL(__filename).href`
+fn file_url_of_filename() -> Box<ast::Expr> {
+  let require_url = call(
+    Box::new(ast::Expr::Ident(quote_ident!("require"))),
+    Box::new(ast::Expr::Lit(ast::Lit::Str(quote_str!("url")))),
+  );
+  let file_url = call(
+    member(require_url, "pathToFileURL"),
+    Box::new(ast::Expr::Ident(quote_ident!("__filename"))),
+  );
+  member(file_url, "href")
+}
+
+/// `new URL(<module>.uri, document.baseURI).href`, where `<module>` is the `module` dependency of
+/// AMD loaders like RequireJS.
+fn url_of_amd_module(module: JsWord) -> Box<ast::Expr> {
+  let url = Box::new(ast::Expr::New(ast::NewExpr {
+    span: DUMMY_SP,
+    callee: Box::new(ast::Expr::Ident(quote_ident!("URL"))),
+    args: Some(vec![
+      ast::ExprOrSpread {
+        spread: None,
+        expr: member(Box::new(ast::Expr::Ident(quote_ident!(module))), "uri"),
+      },
+      ast::ExprOrSpread {
+        spread: None,
+        expr: member(
+          Box::new(ast::Expr::Ident(quote_ident!("document"))),
+          "baseURI",
+        ),
+      },
+    ]),
+    type_args: None,
+  }));
+  member(url, "href")
+}
+
+fn call(callee: Box<ast::Expr>, arg: Box<ast::Expr>) -> Box<ast::Expr> {
+  Box::new(ast::Expr::Call(ast::CallExpr {
+    callee: ast::Callee::Expr(callee),
+    args: vec![ast::ExprOrSpread {
+      spread: None,
+      expr: arg,
+    }],
+    ..ast::CallExpr::dummy()
+  }))
+}
+
+fn member(obj: Box<ast::Expr>, prop: &str) -> Box<ast::Expr> {
+  Box::new(ast::Expr::Member(ast::MemberExpr {
+    span: DUMMY_SP,
+    obj,
+    prop: ast::MemberProp::Ident(quote_ident!(prop)),
+  }))
+}
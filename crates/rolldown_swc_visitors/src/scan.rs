@@ -9,7 +9,7 @@ use rustc_hash::{FxHashMap as HashMap, FxHashMap, FxHashSet as HashSet};
 use swc_atoms::JsWord;
 use swc_common::SyntaxContext;
 use swc_core::{
-  common::{self as swc_common, util::take::Take},
+  common::{self as swc_common, comments::Comments, util::take::Take},
   ecma::{
     ast,
     atoms::{self as swc_atoms, js_word},
@@ -26,8 +26,9 @@ pub fn scan(
   top_level_ctxt: SyntaxContext,
   unresolved_ctxt: SyntaxContext,
   module_id: ModuleId,
+  comments: &dyn Comments,
 ) -> ScanResult {
-  let mut scanner = Scanner::new(top_level_ctxt, unresolved_ctxt, module_id);
+  let mut scanner = Scanner::new(top_level_ctxt, unresolved_ctxt, module_id, comments);
   ast.visit_mut_with(&mut scanner);
   scanner.result
 }
@@ -53,11 +54,16 @@ pub struct ScanResult {
   pub statement_parts: Vec<StatementPart>,
   pub imports: FxHashMap<JsWord, Vec<ImportedSpecifier>>,
   pub suggested_names: FxHashMap<JsWord, JsWord>,
+  /// `import sheet from './foo.css' assert { type: 'css' }` => "./foo.css" -> "css". Re-exports and
+  /// dynamic imports like `import('./foo.css', { assert: { type: 'css' } })` are included.
+  pub asserted_types: FxHashMap<JsWord, JsWord>,
+  /// Whether `await` or `for await` is used outside of functions.
+  pub has_top_level_await: bool,
 }
 
 /// Notices
 /// 1. Though,the pass is named scan, we will change some AST nodes in this pass.
-struct Scanner {
+struct Scanner<'a> {
   module_id: ModuleId,
   result: ScanResult,
   // Record exported id to check if there are duplicated exports
@@ -66,6 +72,8 @@ struct Scanner {
   top_level_ctxt: SyntaxContext,
   statement_part: StatementPart,
   imported_namespaces: HashMap<Symbol, NamespaceInfo>,
+  /// Used to find calls annotated with `/*#__PURE__*/`.
+  comments: &'a dyn Comments,
 }
 
 struct NamespaceInfo {
@@ -74,11 +82,12 @@ struct NamespaceInfo {
   is_used_dynamically: bool,
 }
 
-impl Scanner {
+impl<'a> Scanner<'a> {
   pub fn new(
     top_level_ctxt: SyntaxContext,
     unresolved_ctxt: SyntaxContext,
     module_id: ModuleId,
+    comments: &'a dyn Comments,
   ) -> Self {
     Self {
       module_id,
@@ -90,6 +99,7 @@ impl Scanner {
       exported_names: Default::default(),
       statement_part: Default::default(),
       imported_namespaces: Default::default(),
+      comments,
     }
   }
 
@@ -149,6 +159,13 @@ impl Scanner {
         if dyn_imported.spread.is_none() {
           if let Expr::Lit(Lit::Str(imported)) = dyn_imported.expr.as_ref() {
             self.result.dyn_dependencies.insert(imported.value.clone());
+            if let Some(asserts) = node
+              .args
+              .get(1)
+              .and_then(|options| asserts_of_dynamic_import(&options.expr))
+            {
+              self.add_asserted_type(&imported.value, asserts);
+            }
           }
         }
       }
@@ -175,10 +192,30 @@ impl Scanner {
     imports_set.push(specifier);
   }
 
+  fn add_asserted_type(&mut self, specifier: &JsWord, asserts: &ast::ObjectLit) {
+    let asserted_type = asserts.props.iter().find_map(|prop| match prop {
+      ast::PropOrSpread::Prop(box ast::Prop::KeyValue(ast::KeyValueProp {
+        key: ast::PropName::Ident(ast::Ident { sym: key, .. })
+          | ast::PropName::Str(ast::Str { value: key, .. }),
+        value: box ast::Expr::Lit(ast::Lit::Str(ast::Str { value, .. })),
+      })) if key == "type" => Some(value.clone()),
+      _ => None,
+    });
+    if let Some(asserted_type) = asserted_type {
+      self
+        .result
+        .asserted_types
+        .insert(specifier.clone(), asserted_type);
+    }
+  }
+
   fn scan_import(&mut self, module_decl: &ModuleDecl) {
     if let ModuleDecl::Import(import_decl) = module_decl {
       let local_module_id = import_decl.src.value.clone();
       self.add_dependency(&local_module_id);
+      if let Some(asserts) = &import_decl.asserts {
+        self.add_asserted_type(&local_module_id, asserts);
+      }
       import_decl.specifiers.iter().for_each(|specifier| {
         let (imported_name, imported_as) = match specifier {
           ast::ImportSpecifier::Named(s) => {
@@ -214,6 +251,9 @@ impl Scanner {
 
         if let Some(source) = &dep_id {
           self.add_dependency(source);
+          if let Some(asserts) = &node.asserts {
+            self.add_asserted_type(source, asserts);
+          }
 
           node.specifiers.iter().for_each(|specifier| {
             match specifier {
@@ -303,6 +343,9 @@ impl Scanner {
       ModuleDecl::ExportAll(node) => {
         // export * from './other'
         let source = node.src.value.clone();
+        if let Some(asserts) = &node.asserts {
+          self.add_asserted_type(&source, asserts);
+        }
         self.add_re_export_all(source);
 
         self.add_dependency(&node.src.value);
@@ -516,7 +559,7 @@ impl Scanner {
   }
 }
 
-impl VisitMut for Scanner {
+impl<'a> VisitMut for Scanner<'a> {
   noop_visit_mut_type!();
 
   fn visit_mut_module_items(&mut self, node: &mut Vec<ModuleItem>) {
@@ -536,15 +579,20 @@ impl VisitMut for Scanner {
     node.visit_mut_children_with(self);
 
     self.add_imported_namespaces_to_imports();
+
+    self.result.has_top_level_await = has_top_level_await(node);
   }
 
   fn visit_mut_module_item(&mut self, node: &mut ModuleItem) {
     self.statement_part.side_effect = match node {
       ModuleItem::ModuleDecl(_) => false,
-      ModuleItem::Stmt(stmt) => stmt.may_have_side_effect(&ExprCtx {
-        unresolved_ctxt: self.unresolved_ctxt,
-        is_unresolved_ref_safe: false,
-      }),
+      ModuleItem::Stmt(stmt) => stmt.may_have_side_effect(
+        &ExprCtx {
+          unresolved_ctxt: self.unresolved_ctxt,
+          is_unresolved_ref_safe: false,
+        },
+        self.comments,
+      ),
     };
     self.collect_declared_id_of_top_level(node);
     node.visit_mut_children_with(self);
@@ -624,6 +672,54 @@ pub struct StatementPart {
   pub side_effect: bool,
 }
 
+/// Whether `await` or `for await` is used outside of functions.
+pub fn has_top_level_await(items: &[ModuleItem]) -> bool {
+  let mut finder = TopLevelAwaitFinder::default();
+  items.iter().for_each(|item| item.visit_with(&mut finder));
+  finder.found
+}
+
+#[derive(Default, Debug)]
+struct TopLevelAwaitFinder {
+  found: bool,
+}
+
+impl Visit for TopLevelAwaitFinder {
+  fn visit_await_expr(&mut self, _n: &ast::AwaitExpr) {
+    self.found = true;
+  }
+
+  fn visit_for_of_stmt(&mut self, n: &ast::ForOfStmt) {
+    self.found |= n.is_await;
+    n.visit_children_with(self);
+  }
+
+  // `await` in bodies and initializers belongs to the function, not the module. Computed keys
+  // are still evaluated in the module, like `[await key]() {}` of a class.
+  fn visit_function(&mut self, _n: &ast::Function) {}
+
+  fn visit_arrow_expr(&mut self, _n: &ast::ArrowExpr) {}
+
+  fn visit_constructor(&mut self, _n: &ast::Constructor) {}
+
+  fn visit_class_prop(&mut self, n: &ast::ClassProp) {
+    n.key.visit_with(self);
+    n.decorators.visit_with(self);
+  }
+
+  fn visit_private_prop(&mut self, _n: &ast::PrivateProp) {}
+
+  fn visit_static_block(&mut self, _n: &ast::StaticBlock) {}
+
+  fn visit_getter_prop(&mut self, n: &ast::GetterProp) {
+    n.key.visit_with(self);
+  }
+
+  fn visit_setter_prop(&mut self, n: &ast::SetterProp) {
+    n.key.visit_with(self);
+  }
+}
+
 #[derive(Default, Debug)]
 struct ParamsCollector {
   pub collected: HashMap<JsWord, Id>,
@@ -642,13 +738,16 @@ impl Visit for ParamsCollector {
 }
 
 trait StmtExt {
-  fn may_have_side_effect(&self, ctx: &ExprCtx) -> bool;
+  fn may_have_side_effect(&self, ctx: &ExprCtx, comments: &dyn Comments) -> bool;
 }
 
 impl StmtExt for ast::Stmt {
-  fn may_have_side_effect(&self, ctx: &ExprCtx) -> bool {
+  fn may_have_side_effect(&self, ctx: &ExprCtx, comments: &dyn Comments) -> bool {
     match self {
-      Stmt::Block(stmt) => stmt.stmts.iter().any(|stmt| stmt.may_have_side_effect(ctx)),
+      Stmt::Block(stmt) => stmt
+        .stmts
+        .iter()
+        .any(|stmt| stmt.may_have_side_effect(ctx, comments)),
       Stmt::Empty(_) | Stmt::Return(_) | Stmt::Labeled(_) | Stmt::Break(_) | Stmt::Continue(_) => {
         false
       }
@@ -656,11 +755,11 @@ impl StmtExt for ast::Stmt {
 
       Stmt::If(stmt) => {
         stmt.test.may_have_side_effects(ctx)
-          || stmt.cons.may_have_side_effect(ctx)
+          || stmt.cons.may_have_side_effect(ctx, comments)
           || stmt
             .alt
             .as_ref()
-            .map(|alt| alt.may_have_side_effect(ctx))
+            .map(|alt| alt.may_have_side_effect(ctx, comments))
             .unwrap_or(false)
       }
       Stmt::Switch(stmt) => {
@@ -671,15 +770,18 @@ impl StmtExt for ast::Stmt {
               .as_ref()
               .map(|test| test.may_have_side_effects(ctx))
               .unwrap_or(false)
-              || case.cons.iter().any(|stmt| stmt.may_have_side_effect(ctx))
+              || case
+                .cons
+                .iter()
+                .any(|stmt| stmt.may_have_side_effect(ctx, comments))
           })
       }
       Stmt::Throw(stmt) => stmt.arg.may_have_side_effects(ctx),
       Stmt::While(stmt) => {
-        stmt.test.may_have_side_effects(ctx) || stmt.body.may_have_side_effect(ctx)
+        stmt.test.may_have_side_effects(ctx) || stmt.body.may_have_side_effect(ctx, comments)
       }
       Stmt::DoWhile(stmt) => {
-        stmt.test.may_have_side_effects(ctx) || stmt.body.may_have_side_effect(ctx)
+        stmt.test.may_have_side_effects(ctx) || stmt.body.may_have_side_effect(ctx, comments)
       }
       Stmt::Decl(stmt) => match stmt {
         ast::Decl::Class(decl) => class_has_side_effect(ctx, &decl.class),
@@ -689,7 +791,7 @@ impl StmtExt for ast::Stmt {
           decl
             .init
             .as_ref()
-            .map(|init| init.may_have_side_effects(ctx))
+            .map(|init| expr_may_have_side_effects(init, ctx, comments))
             .unwrap_or(false)
         }),
         ast::Decl::TsInterface(_) => false,
@@ -697,7 +799,7 @@ impl StmtExt for ast::Stmt {
         ast::Decl::TsEnum(_) => false,
         ast::Decl::TsModule(_) => false,
       },
-      Stmt::Expr(stmt) => stmt.expr.may_have_side_effects(ctx),
+      Stmt::Expr(stmt) => expr_may_have_side_effects(&stmt.expr, ctx, comments),
       // Not decided yet.
       Stmt::With(_) => true,
       Stmt::Try(_) => true,
@@ -707,3 +809,41 @@ impl StmtExt for ast::Stmt {
     }
   }
 }
+
+/// `{ assert: { type: 'css' } }`, the options of `import('./a.css', { assert: { type: 'css' } })` =>
+/// `{ type: 'css' }`
+fn asserts_of_dynamic_import(options: &Expr) -> Option<&ast::ObjectLit> {
+  let Expr::Object(options) = options else {
+    return None;
+  };
+  options.props.iter().find_map(|prop| match prop {
+    ast::PropOrSpread::Prop(box ast::Prop::KeyValue(ast::KeyValueProp {
+      key: ast::PropName::Ident(ast::Ident { sym: key, .. })
+        | ast::PropName::Str(ast::Str { value: key, .. }),
+      value: box Expr::Object(asserts),
+    })) if key == "assert" => Some(asserts),
+    _ => None,
+  })
+}
+
+/// Like `ExprExt::may_have_side_effects`, but calls annotated with `/*#__PURE__*/` are considered
+/// side-effect-free if their arguments are.
+fn expr_may_have_side_effects(expr: &Expr, ctx: &ExprCtx, comments: &dyn Comments) -> bool {
+  let args_may_have_side_effects = |args: &[ast::ExprOrSpread]| {
+    args
+      .iter()
+      .any(|arg| arg.spread.is_some() || expr_may_have_side_effects(&arg.expr, ctx, comments))
+  };
+  match expr {
+    Expr::Paren(expr) => expr_may_have_side_effects(&expr.expr, ctx, comments),
+    Expr::Call(call) if comments.has_flag(call.span.lo, "PURE") => {
+      args_may_have_side_effects(&call.args)
+    }
+    Expr::New(new) if comments.has_flag(new.span.lo, "PURE") => new
+      .args
+      .as_deref()
+      .map(args_may_have_side_effects)
+      .unwrap_or(false),
+    _ => expr.may_have_side_effects(ctx),
+  }
+}
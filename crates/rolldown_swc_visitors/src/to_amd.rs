@@ -0,0 +1,122 @@
+use swc_common::{comments::SingleThreadedComments, Mark, DUMMY_SP};
+use swc_core::common as swc_common;
+use swc_core::ecma::atoms::JsWord;
+use swc_core::ecma::transforms::base::helpers::{self, HELPERS};
+use swc_core::ecma::transforms::base::{
+  fixer::{self, paren_remover},
+  helpers::inject_helpers,
+  hygiene::hygiene,
+};
+use swc_core::ecma::utils::{quote_ident, quote_str};
+use swc_core::ecma::visit::{as_folder, VisitMut};
+use swc_core::ecma::{
+  ast,
+  transforms::{base::resolver, module::amd},
+  visit::FoldWith,
+};
+
+/// Wrap the module with `define(id?, [deps], factory)`. `define` is the name of the function to
+/// call, which could be customized for AMD loaders that don't use the global `define`. If `module`
+/// is given, the `module` dependency is bound to a parameter of the factory named by it.
+pub fn to_amd(
+  ast: ast::Module,
+  unresolved_mark: Mark,
+  comments: &SingleThreadedComments,
+  module_id: Option<String>,
+  define: JsWord,
+  module: Option<JsWord>,
+) -> ast::Module {
+  HELPERS.set(&helpers::Helpers::new(false), || {
+    ast
+      .fold_with(&mut paren_remover(Some(comments)))
+      .fold_with(&mut resolver(unresolved_mark, Mark::new(), false))
+      .fold_with(&mut amd::amd::<SingleThreadedComments>(
+        unresolved_mark,
+        amd::Config {
+          module_id,
+          ..Default::default()
+        },
+        Default::default(),
+        Default::default(),
+      ))
+      .fold_with(&mut hygiene())
+      .fold_with(&mut fixer::fixer(Some(comments)))
+      .fold_with(&mut inject_helpers(unresolved_mark))
+      .fold_with(&mut as_folder(AmdDefineRewriter { define, module }))
+  })
+}
+
+struct AmdDefineRewriter {
+  define: JsWord,
+  module: Option<JsWord>,
+}
+
+impl VisitMut for AmdDefineRewriter {
+  fn visit_mut_module(&mut self, node: &mut ast::Module) {
+    // The wrapper is the only top-level statement after the amd transform.
+    node.body.iter_mut().for_each(|item| {
+      if let ast::ModuleItem::Stmt(ast::Stmt::Expr(ast::ExprStmt {
+        expr:
+          box ast::Expr::Call(ast::CallExpr {
+            callee: ast::Callee::Expr(box ast::Expr::Ident(callee)),
+            args,
+            ..
+          }),
+        ..
+      })) = item
+      {
+        if &*callee.sym == "define" {
+          callee.sym = self.define.clone();
+          if let Some(module) = &self.module {
+            inject_module(args, module.clone());
+          }
+        }
+      }
+    });
+  }
+}
+
+/// Add `"module"` to the dependencies of `define(id?, [deps]?, factory)`, bound to the parameter
+/// `module` of the factory. It's put right after dependencies bound to parameters, since
+/// dependencies like side effect imports may not be bound.
+fn inject_module(args: &mut Vec<ast::ExprOrSpread>, module: JsWord) {
+  let Some(factory) = args.last_mut() else {
+    return;
+  };
+  let index = match &mut *factory.expr {
+    ast::Expr::Fn(ast::FnExpr { function, .. }) => {
+      function.params.push(ast::Param {
+        span: DUMMY_SP,
+        decorators: vec![],
+        pat: ast::Pat::Ident(quote_ident!(module).into()),
+      });
+      function.params.len() - 1
+    }
+    ast::Expr::Arrow(ast::ArrowExpr { params, .. }) => {
+      params.push(ast::Pat::Ident(quote_ident!(module).into()));
+      params.len() - 1
+    }
+    _ => return,
+  };
+  let dependency = Some(ast::ExprOrSpread {
+    spread: None,
+    expr: Box::new(ast::Expr::Lit(ast::Lit::Str(quote_str!("module")))),
+  });
+  let factory_index = args.len() - 1;
+  if let Some(ast::Expr::Array(ast::ArrayLit { elems, .. })) =
+    factory_index.checked_sub(1).map(|i| &mut *args[i].expr)
+  {
+    elems.insert(index.min(elems.len()), dependency);
+    return;
+  }
+  args.insert(
+    factory_index,
+    ast::ExprOrSpread {
+      spread: None,
+      expr: Box::new(ast::Expr::Array(ast::ArrayLit {
+        span: DUMMY_SP,
+        elems: vec![dependency],
+      })),
+    },
+  );
+}
@@ -21,8 +21,9 @@ pub fn to_cjs(
   unresolved_mark: Mark,
   comments: &SingleThreadedComments,
   shim_default_export: bool,
+  es_module_marker: bool,
 ) -> ast::Module {
-  HELPERS.set(&helpers::Helpers::new(false), || {
+  let mut ast = HELPERS.set(&helpers::Helpers::new(false), || {
     ast
       .fold_with(&mut paren_remover(Some(comments)))
       .fold_with(&mut resolver(unresolved_mark, Mark::new(), false))
@@ -41,5 +42,36 @@ pub fn to_cjs(
         visitor: as_folder(default_export_mode_shimer()),
         enabled: shim_default_export,
       })
-  })
+  });
+  if !es_module_marker {
+    ast.body.retain(|item| !is_es_module_marker(item));
+  }
+  ast
+}
+
+/// `Object.defineProperty(exports, "__esModule", { value: true })` emitted by the `cjs` transform.
+fn is_es_module_marker(item: &ast::ModuleItem) -> bool {
+  let ast::ModuleItem::Stmt(ast::Stmt::Expr(ast::ExprStmt {
+    expr: box ast::Expr::Call(call),
+    ..
+  })) = item
+  else {
+    return false;
+  };
+  let is_define_property = matches!(
+    &call.callee,
+    ast::Callee::Expr(box ast::Expr::Member(ast::MemberExpr {
+      obj: box ast::Expr::Ident(obj),
+      prop: ast::MemberProp::Ident(prop),
+      ..
+    })) if &*obj.sym == "Object" && &*prop.sym == "defineProperty"
+  );
+  is_define_property
+    && matches!(
+      call.args.get(1),
+      Some(ast::ExprOrSpread {
+        expr: box ast::Expr::Lit(ast::Lit::Str(s)),
+        ..
+      }) if &*s.value == "__esModule"
+    )
 }
@@ -0,0 +1,328 @@
+use swc_core::{
+  common::{util::take::Take, SyntaxContext, DUMMY_SP},
+  ecma::{
+    ast,
+    atoms::JsWord,
+    utils::{member_expr, quote_ident, quote_str},
+    visit::{Visit, VisitMut, VisitMutWith, VisitWith},
+  },
+};
+
+use crate::has_top_level_await;
+
+/// Turn a CommonJS module into ESM by declaring `module` and `exports` in it and exporting
+/// `module.exports` as `default`. So both `import` and `import()` of the module get the whole
+/// exports as `default`, which is the same as Node.js.
+///
+/// A module is considered as CommonJS if it has no import/export but references free `module` or
+/// `exports`. Returns whether the module is wrapped.
+///
+/// Named exports are never inferred from `exports.foo = ...`, since `module.exports` could be
+/// reassigned later or conditionally, which makes them wrong.
+///
+/// `require("./foo")` with a string literal is replaced by the default import of `./foo`, which is
+/// `module.exports` of a CommonJS module. Like `@rollup/plugin-commonjs`, the imports are hoisted,
+/// so conditional requires run unconditionally. Required ES modules need a default export.
+pub fn wrap_commonjs(
+  ast: &mut ast::Module,
+  unresolved_ctxt: SyntaxContext,
+  top_level_ctxt: SyntaxContext,
+) -> bool {
+  if ast.body.iter().any(|item| item.is_module_decl()) {
+    return false;
+  }
+
+  let mut binder = CommonJsBinder {
+    unresolved_ctxt,
+    top_level_ctxt,
+    is_commonjs: false,
+  };
+  ast.visit_mut_with(&mut binder);
+  let mut requires = RequireHoister {
+    unresolved_ctxt,
+    top_level_ctxt,
+    required: vec![],
+  };
+  ast.visit_mut_with(&mut requires);
+  if !binder.is_commonjs && requires.required.is_empty() {
+    return false;
+  }
+
+  let span = DUMMY_SP.with_ctxt(top_level_ctxt);
+  let imports = requires.into_imports();
+  // var module = { exports: {} };
+  let module_decl = var_decl(
+    quote_ident!(span, "module"),
+    ast::Expr::Object(ast::ObjectLit {
+      span: DUMMY_SP,
+      props: vec![ast::PropOrSpread::Prop(Box::new(ast::Prop::KeyValue(
+        ast::KeyValueProp {
+          key: ast::PropName::Ident(quote_ident!("exports")),
+          value: Box::new(ast::Expr::Object(ast::ObjectLit {
+            span: DUMMY_SP,
+            props: vec![],
+          })),
+        },
+      )))],
+    }),
+  );
+  // var exports = module.exports;
+  let exports_decl = var_decl(
+    quote_ident!(span, "exports"),
+    *member_expr!(span, module.exports),
+  );
+  // export default module.exports;
+  let default_export =
+    ast::ModuleItem::ModuleDecl(ast::ModuleDecl::ExportDefaultExpr(ast::ExportDefaultExpr {
+      span: DUMMY_SP,
+      expr: member_expr!(span, module.exports),
+    }));
+
+  let prologue = imports
+    .chain([module_decl, exports_decl])
+    .collect::<Vec<_>>();
+  ast.body.splice(0..0, prologue);
+  ast.body.push(default_export);
+  true
+}
+
+/// Used instead of `wrap_commonjs` in safe hoisting. Wrap a module that can't be safely hoisted into
+/// the scope of the chunk in a function, which is registered in the runtime module registry by
+/// `key`, and export the result of requiring it as `default`.
+///
+/// ```js
+/// import __require_0 from "./bar.js";
+/// __rolldown_define("foo.js", function (module, exports) { ... });
+/// export default __rolldown_require("foo.js");
+/// ```
+///
+/// A module is wrapped if it references free `module`, `exports` or `eval`, or has `with`
+/// statements, whose references can't be analyzed. Returns whether the module is wrapped. Modules
+/// with import/export can't be put in a function, so what makes them unhoistable is returned as the
+/// error instead.
+///
+/// `require("./bar.js")` is hoisted to the default import of `./bar.js`, the same as
+/// `wrap_commonjs`.
+///
+/// The function of a module using top-level await is async, and the result of requiring it is
+/// awaited, so importers still run after the module finishes.
+pub fn wrap_in_registry(
+  ast: &mut ast::Module,
+  unresolved_ctxt: SyntaxContext,
+  top_level_ctxt: SyntaxContext,
+  key: &str,
+) -> Result<bool, &'static str> {
+  let mut detector = HoistingHazardDetector {
+    unresolved_ctxt,
+    found: None,
+  };
+  ast.visit_with(&mut detector);
+  let Some(hazard) = detector.found else {
+    return Ok(false);
+  };
+  if ast.body.iter().any(|item| item.is_module_decl()) {
+    return Err(hazard);
+  }
+
+  ast.visit_mut_with(&mut CommonJsBinder {
+    unresolved_ctxt,
+    top_level_ctxt,
+    is_commonjs: false,
+  });
+  let mut requires = RequireHoister {
+    unresolved_ctxt,
+    top_level_ctxt,
+    required: vec![],
+  };
+  ast.visit_mut_with(&mut requires);
+
+  let is_async = has_top_level_await(&ast.body);
+  let span = DUMMY_SP.with_ctxt(top_level_ctxt);
+  // function (module, exports) { ... }
+  let factory = ast::Expr::Fn(ast::FnExpr {
+    ident: None,
+    function: Box::new(ast::Function {
+      params: ["module", "exports"]
+        .into_iter()
+        .map(|name| ast::Param {
+          span: DUMMY_SP,
+          decorators: vec![],
+          pat: ast::Pat::Ident(quote_ident!(span, name).into()),
+        })
+        .collect(),
+      body: Some(ast::BlockStmt {
+        span: DUMMY_SP,
+        stmts: ast.body.drain(..).filter_map(|item| item.stmt()).collect(),
+      }),
+      is_async,
+      ..ast::Function::dummy()
+    }),
+  });
+  // __rolldown_define("foo.js", function (module, exports) { ... });
+  let define = ast::ModuleItem::Stmt(ast::Stmt::Expr(ast::ExprStmt {
+    span: DUMMY_SP,
+    expr: Box::new(registry_call("__rolldown_define", key, Some(factory))),
+  }));
+  // export default __rolldown_require("foo.js");
+  let mut required = registry_call("__rolldown_require", key, None);
+  if is_async {
+    // export default await __rolldown_require("foo.js");
+    required = ast::Expr::Await(ast::AwaitExpr {
+      span: DUMMY_SP,
+      arg: Box::new(required),
+    });
+  }
+  let default_export =
+    ast::ModuleItem::ModuleDecl(ast::ModuleDecl::ExportDefaultExpr(ast::ExportDefaultExpr {
+      span: DUMMY_SP,
+      expr: Box::new(required),
+    }));
+
+  ast.body = requires
+    .into_imports()
+    .chain([define, default_export])
+    .collect();
+  Ok(true)
+}
+
+fn registry_call(callee: &str, key: &str, factory: Option<ast::Expr>) -> ast::Expr {
+  ast::Expr::Call(ast::CallExpr {
+    callee: ast::Callee::Expr(quote_ident!(callee).into()),
+    args: std::iter::once(ast::Expr::Lit(quote_str!(key).into()))
+      .chain(factory)
+      .map(|expr| ast::ExprOrSpread {
+        spread: None,
+        expr: Box::new(expr),
+      })
+      .collect(),
+    ..ast::CallExpr::dummy()
+  })
+}
+
+fn var_decl(name: ast::Ident, init: ast::Expr) -> ast::ModuleItem {
+  ast::ModuleItem::Stmt(ast::Stmt::Decl(ast::Decl::Var(Box::new(ast::VarDecl {
+    span: DUMMY_SP,
+    kind: ast::VarDeclKind::Var,
+    declare: false,
+    decls: vec![ast::VarDeclarator {
+      span: DUMMY_SP,
+      name: ast::Pat::Ident(name.into()),
+      init: Some(Box::new(init)),
+      definite: false,
+    }],
+  }))))
+}
+
+/// Bind free `module` and `exports` to the top level ones, which are declared by `wrap_commonjs`.
+struct CommonJsBinder {
+  unresolved_ctxt: SyntaxContext,
+  top_level_ctxt: SyntaxContext,
+  is_commonjs: bool,
+}
+
+impl VisitMut for CommonJsBinder {
+  fn visit_mut_ident(&mut self, ident: &mut ast::Ident) {
+    if ident.span.ctxt == self.unresolved_ctxt && matches!(&*ident.sym, "module" | "exports") {
+      ident.span.ctxt = self.top_level_ctxt;
+      self.is_commonjs = true;
+    }
+  }
+}
+
+/// Replace `require("./foo")` calls of the free `require` with the local names of the default
+/// imports hoisted by `wrap_commonjs` and `wrap_in_registry`. Requiring the same specifier twice
+/// shares the import.
+struct RequireHoister {
+  unresolved_ctxt: SyntaxContext,
+  top_level_ctxt: SyntaxContext,
+  /// Required specifiers and the local names of their imports, in order of appearance.
+  required: Vec<(JsWord, ast::Ident)>,
+}
+
+impl RequireHoister {
+  fn required_specifier<'a>(&self, call: &'a ast::CallExpr) -> Option<&'a JsWord> {
+    let ast::Callee::Expr(box ast::Expr::Ident(callee)) = &call.callee else {
+      return None;
+    };
+    if callee.sym != *"require" || callee.span.ctxt != self.unresolved_ctxt {
+      return None;
+    }
+    match call.args.as_slice() {
+      [ast::ExprOrSpread {
+        spread: None,
+        expr: box ast::Expr::Lit(ast::Lit::Str(specifier)),
+      }] => Some(&specifier.value),
+      _ => None,
+    }
+  }
+
+  /// `import __require_0 from "./foo";` for each required specifier.
+  fn into_imports(self) -> impl Iterator<Item = ast::ModuleItem> {
+    self.required.into_iter().map(|(specifier, local)| {
+      ast::ModuleItem::ModuleDecl(ast::ModuleDecl::Import(ast::ImportDecl {
+        span: DUMMY_SP,
+        specifiers: vec![ast::ImportSpecifier::Default(ast::ImportDefaultSpecifier {
+          span: DUMMY_SP,
+          local,
+        })],
+        src: Box::new(quote_str!(specifier)),
+        type_only: false,
+        asserts: None,
+      }))
+    })
+  }
+}
+
+impl VisitMut for RequireHoister {
+  fn visit_mut_expr(&mut self, expr: &mut ast::Expr) {
+    expr.visit_mut_children_with(self);
+    let ast::Expr::Call(call) = expr else {
+      return;
+    };
+    let Some(specifier) = self.required_specifier(call).cloned() else {
+      return;
+    };
+    let local = match self
+      .required
+      .iter()
+      .find(|(required, _)| *required == specifier)
+    {
+      Some((_, local)) => local.clone(),
+      None => {
+        let local = quote_ident!(
+          DUMMY_SP.with_ctxt(self.top_level_ctxt),
+          format!("__require_{}", self.required.len())
+        );
+        self.required.push((specifier, local.clone()));
+        local
+      }
+    };
+    *expr = ast::Expr::Ident(local);
+  }
+}
+
+/// Find references that make a module unable to be hoisted safely.
+struct HoistingHazardDetector {
+  unresolved_ctxt: SyntaxContext,
+  /// Description of the first one found.
+  found: Option<&'static str>,
+}
+
+impl Visit for HoistingHazardDetector {
+  fn visit_ident(&mut self, ident: &ast::Ident) {
+    if ident.span.ctxt != self.unresolved_ctxt || self.found.is_some() {
+      return;
+    }
+    self.found = match &*ident.sym {
+      "module" => Some("the free `module`"),
+      "exports" => Some("the free `exports`"),
+      "eval" => Some("`eval`"),
+      _ => None,
+    };
+  }
+
+  fn visit_with_stmt(&mut self, stmt: &ast::WithStmt) {
+    self.found.get_or_insert("`with` statements");
+    stmt.visit_children_with(self);
+  }
+}
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use schemars::JsonSchema;
 use serde::Deserialize;
 
@@ -14,6 +16,10 @@ fn true_by_default() -> bool {
   true
 }
 
+fn transform_by_default() -> String {
+  "transform".to_string()
+}
+
 #[derive(Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct InputOptions {
@@ -23,12 +29,53 @@ pub struct InputOptions {
   #[serde(default)]
   pub external: Vec<String>,
 
+  /// Regex patterns of externals, like `^node:`, besides the exact specifiers of `external`.
+  #[serde(default)]
+  pub external_patterns: Vec<String>,
+
   #[serde(default = "true_by_default")]
   pub treeshake: bool,
 
+  #[serde(default)]
+  pub drop_unused_entry_exports: bool,
+
   #[serde(default)]
   pub shim_missing_exports: bool,
 
+  #[serde(default)]
+  pub wasm_esm_integration: bool,
+
+  #[serde(default)]
+  pub safe_hoisting: bool,
+
+  #[serde(default)]
+  pub max_symbols: Option<usize>,
+
+  #[serde(default)]
+  pub preserve_symlinks: bool,
+
+  /// `node` or `browser`.
+  #[serde(default)]
+  pub platform: Option<String>,
+
+  #[serde(default)]
+  pub main_fields: Option<Vec<String>>,
+
+  /// `DEFAULT_CONDITION_NAMES` of rolldown if not set.
+  #[serde(default)]
+  pub condition_names: Option<Vec<String>>,
+
+  /// `DEFAULT_RESOLVE_EXTENSIONS` of rolldown if not set.
+  #[serde(default)]
+  pub resolve_extensions: Option<Vec<String>>,
+
+  /// Globals to the expressions replacing them, like `{ "process.env.NODE_ENV": "\"production\"" }`.
+  #[serde(default)]
+  pub define: BTreeMap<String, String>,
+
+  #[serde(default)]
+  pub strict_define: bool,
+
   #[serde(default)]
   pub builtins: Builtins,
 }
@@ -45,6 +92,40 @@ pub struct InputItem {
 pub struct Builtins {
   #[serde(default)]
   pub tsconfig: TsConfig,
+  #[serde(default)]
+  pub discover_tsconfig: bool,
+  #[serde(default)]
+  pub jsx: Jsx,
+  pub replace: Option<Replace>,
+  /// Strip code of `// @if NAME` comments unless `NAME` is truthy in `define`.
+  #[serde(default)]
+  pub conditional_compilation: bool,
+  /// Extensions without the leading dot to loaders, like `{ "md": "text" }`.
+  #[serde(default)]
+  pub loaders: BTreeMap<String, String>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct Jsx {
+  /// `transform` or `preserve`.
+  #[serde(default = "transform_by_default")]
+  pub mode: String,
+  /// `classic` or `automatic`.
+  pub runtime: Option<String>,
+  pub import_source: Option<String>,
+  pub pragma: Option<String>,
+  pub pragma_frag: Option<String>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct Replace {
+  #[serde(default)]
+  pub values: BTreeMap<String, String>,
+  pub delimiters: Option<(String, String)>,
+  #[serde(default)]
+  pub prevent_assignment: bool,
 }
 
 #[derive(Deserialize, JsonSchema)]
@@ -52,9 +133,19 @@ pub struct Builtins {
 pub struct TsConfig {
   #[serde(default)]
   pub use_define_for_class_fields: bool,
+  #[serde(default = "true_by_default")]
+  pub decorators: bool,
+  #[serde(default)]
+  pub js_decorators: bool,
+  /// `2021-12`, `legacy` or `2022-03`.
+  pub decorator_version: Option<String>,
+  /// Path of the tsconfig whose `paths` and `baseUrl` resolve bare specifiers, relative to the
+  /// fixture.
+  pub config_file: Option<String>,
 }
 
 impl_serde_default!(InputOptions);
 impl_serde_default!(InputItem);
 impl_serde_default!(Builtins);
+impl_serde_default!(Jsx);
 impl_serde_default!(TsConfig);
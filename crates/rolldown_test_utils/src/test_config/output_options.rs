@@ -1,8 +1,18 @@
+use std::collections::BTreeMap;
+
 use schemars::JsonSchema;
 use serde::Deserialize;
 
 use crate::impl_serde_default;
 
+fn name_by_default() -> String {
+  "[name].js".to_string()
+}
+
+fn suffix_by_default() -> String {
+  "suffix".to_string()
+}
+
 fn esm_by_default() -> String {
   "esm".to_string()
 }
@@ -11,13 +21,85 @@ fn auto_by_default() -> String {
   "auto".to_string()
 }
 
+fn define_by_default() -> String {
+  "define".to_string()
+}
+
+fn es5_by_default() -> String {
+  "es5".to_string()
+}
+
+fn true_by_default() -> bool {
+  true
+}
+
 #[derive(Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct OutputOptions {
+  #[serde(default = "name_by_default")]
+  pub entry_file_names: String,
+  /// `suffix` or `error`.
+  #[serde(default = "suffix_by_default")]
+  pub file_name_conflict: String,
   #[serde(default = "esm_by_default")]
   pub format: String,
   #[serde(default = "auto_by_default")]
   pub export_mode: String,
+  #[serde(default)]
+  pub bom: bool,
+  pub banner: Option<String>,
+  pub footer: Option<String>,
+  pub intro: Option<String>,
+  pub outro: Option<String>,
+  #[serde(default)]
+  pub amd: Amd,
+  /// Name of the global variable holding exports of the entry in the `iife` format.
+  pub name: Option<String>,
+  #[serde(default = "true_by_default")]
+  pub rewrite_import_meta_url: bool,
+  #[serde(default)]
+  pub minify: bool,
+  #[serde(default)]
+  pub api_surface: bool,
+  #[serde(default)]
+  pub manifest: bool,
+  #[serde(default)]
+  pub codegen: Codegen,
+  /// Names of chunks to glob patterns of modules in them, like `{ "vendor": ["node_modules/**"] }`.
+  pub manual_chunks: Option<BTreeMap<String, Vec<String>>>,
+  #[serde(default)]
+  pub preserve_modules: bool,
+  #[serde(default)]
+  pub split_directives: bool,
+  #[serde(default)]
+  pub global_this_polyfill: bool,
+  /// Bare specifiers of externals to their URLs, like `{ "react": "https://esm.sh/react@18" }`.
+  pub import_map: Option<BTreeMap<String, String>>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct Amd {
+  pub id: Option<String>,
+  #[serde(default)]
+  pub auto_id: bool,
+  pub base_path: Option<String>,
+  #[serde(default = "define_by_default")]
+  pub define: String,
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct Codegen {
+  /// `es5`, `es2015` and so on, or `esnext`.
+  #[serde(default = "es5_by_default")]
+  pub target: String,
+  #[serde(default)]
+  pub ascii_only: bool,
+  #[serde(default)]
+  pub minify: bool,
 }
 
 impl_serde_default!(OutputOptions);
+impl_serde_default!(Amd);
+impl_serde_default!(Codegen);
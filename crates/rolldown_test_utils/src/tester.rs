@@ -1,6 +1,7 @@
 use std::{
   collections::HashSet,
   path::{Path, PathBuf},
+  str::FromStr,
   sync::{Arc, Mutex},
 };
 
@@ -38,7 +39,9 @@ impl Tester {
         })
         .collect(),
       cwd,
+      root: None,
       treeshake: self.config.input.treeshake,
+      drop_unused_entry_exports: self.config.input.drop_unused_entry_exports,
       is_external: {
         let external = self
           .config
@@ -47,26 +50,163 @@ impl Tester {
           .clone()
           .into_iter()
           .collect::<HashSet<_>>();
-        Arc::new(move |specifier, _importer, _| {
-          let external = external.clone();
-          futures::future::ready(Ok(external.contains(specifier))).boxed()
+        let external_by_patterns =
+          rolldown::external_by_patterns(&self.config.input.external_patterns).unwrap();
+        Arc::new(move |specifier, importer, is_resolved| {
+          if external.contains(specifier) {
+            return futures::future::ready(Ok(true)).boxed();
+          }
+          external_by_patterns(specifier, importer, is_resolved)
         })
       },
       on_warn: Arc::new(move |err| {
         warning_collector.lock().unwrap().push(err);
       }),
-      preserve_symlinks: false,
+      preserve_symlinks: self.config.input.preserve_symlinks,
+      platform: self
+        .config
+        .input
+        .platform
+        .as_deref()
+        .map(|platform| rolldown::Platform::from_str(platform).unwrap()),
+      absolute_path_root: None,
+      alias: Default::default(),
+      resolve_extensions: self
+        .config
+        .input
+        .resolve_extensions
+        .clone()
+        .unwrap_or_else(|| {
+          rolldown::DEFAULT_RESOLVE_EXTENSIONS
+            .map(ToString::to_string)
+            .to_vec()
+        }),
+      main_fields: self.config.input.main_fields.clone(),
+      condition_names: self
+        .config
+        .input
+        .condition_names
+        .clone()
+        .unwrap_or_else(|| {
+          rolldown::DEFAULT_CONDITION_NAMES
+            .map(ToString::to_string)
+            .to_vec()
+        }),
+      allow_exports_fallback: false,
+      resolution_diagnostics: false,
+      wasm_esm_integration: self.config.input.wasm_esm_integration,
+      safe_hoisting: self.config.input.safe_hoisting,
+      cache: false,
+      parse_recovery: false,
+      define: self
+        .config
+        .input
+        .define
+        .iter()
+        .map(|(name, value)| (name.clone(), value.clone()))
+        .collect(),
+      strict_define: self.config.input.strict_define,
       builtins: rolldown::BuiltinsOptions {
-        tsconfig: Some(rolldown::TsConfig {
-          use_define_for_class_fields: self
-            .config
-            .input
-            .builtins
-            .tsconfig
-            .use_define_for_class_fields,
+        tsconfig: Some({
+          let config = &self.config.input.builtins.tsconfig;
+          rolldown::TsConfig {
+            use_define_for_class_fields: config.use_define_for_class_fields,
+            decorators: config.decorators,
+            js_decorators: config.js_decorators,
+            decorator_version: config
+              .decorator_version
+              .as_deref()
+              .map(|version| version.parse().unwrap())
+              .unwrap_or_default(),
+            config_file: config.config_file.as_ref().map(Into::into),
+            ..Default::default()
+          }
+        }),
+        discover_tsconfig: self.config.input.builtins.discover_tsconfig,
+        jsx: {
+          let config = &self.config.input.builtins.jsx;
+          let mut jsx = rolldown::Jsx::from_str(&config.mode).unwrap();
+          if let rolldown::Jsx::Transform(options) = &mut jsx {
+            *options = rolldown::JsxOptions {
+              runtime: config
+                .runtime
+                .as_deref()
+                .map(|runtime| rolldown::JsxRuntime::from_str(runtime).unwrap())
+                .unwrap_or_default(),
+              import_source: config.import_source.clone(),
+              pragma: config.pragma.clone(),
+              pragma_frag: config.pragma_frag.clone(),
+            };
+          }
+          jsx
+        },
+        replace: self.config.input.builtins.replace.as_ref().map(|replace| {
+          let default = rolldown::ReplaceOptions::default();
+          rolldown::ReplaceOptions {
+            values: replace
+              .values
+              .iter()
+              .map(|(key, value)| (key.clone(), value.clone()))
+              .collect(),
+            delimiters: replace.delimiters.clone().unwrap_or(default.delimiters),
+            prevent_assignment: replace.prevent_assignment,
+          }
         }),
+        conditional_compilation: self.config.input.builtins.conditional_compilation,
+        loaders: self
+          .config
+          .input
+          .builtins
+          .loaders
+          .iter()
+          .map(|(ext, loader)| (ext.clone(), rolldown::Loader::from_str(loader).unwrap()))
+          .collect(),
       },
       shim_missing_exports: self.config.input.shim_missing_exports,
+      log_level: None,
+      max_parallel_transforms: None,
+      max_symbols: self.config.input.max_symbols,
+    }
+  }
+
+  pub fn output_options(&self) -> rolldown::OutputOptions {
+    rolldown::OutputOptions {
+      entry_file_names: self.config.output.entry_file_names.clone().into(),
+      file_name_conflict: rolldown::FileNameConflict::from_str(
+        &self.config.output.file_name_conflict,
+      )
+      .unwrap(),
+      format: rolldown::ModuleFormat::from_str(&self.config.output.format).unwrap(),
+      export_mode: rolldown::ExportMode::from_str(&self.config.output.export_mode).unwrap(),
+      bom: self.config.output.bom,
+      banner: self.config.output.banner.clone(),
+      footer: self.config.output.footer.clone(),
+      intro: self.config.output.intro.clone(),
+      outro: self.config.output.outro.clone(),
+      amd: rolldown::AmdOptions {
+        id: self.config.output.amd.id.clone(),
+        auto_id: self.config.output.amd.auto_id,
+        base_path: self.config.output.amd.base_path.clone(),
+        define: self.config.output.amd.define.clone(),
+      },
+      name: self.config.output.name.clone(),
+      rewrite_import_meta_url: self.config.output.rewrite_import_meta_url,
+      minify: self.config.output.minify,
+      api_surface: self.config.output.api_surface,
+      manifest: self.config.output.manifest,
+      codegen: rolldown::CodegenOptions {
+        target: rolldown::CodegenTarget::from_str(&self.config.output.codegen.target).unwrap(),
+        ascii_only: self.config.output.codegen.ascii_only,
+        minify: self.config.output.codegen.minify,
+      },
+      manual_chunks: self.config.output.manual_chunks.clone(),
+      preserve_modules: self.config.output.preserve_modules,
+      split_directives: self.config.output.split_directives,
+      global_this_polyfill: self.config.output.global_this_polyfill,
+      import_map: self.config.output.import_map.clone(),
+      // Hashes would change with any change of the output, which makes snapshots noisy.
+      chunk_file_names: "[name].js".to_string().into(),
+      ..Default::default()
     }
   }
 }
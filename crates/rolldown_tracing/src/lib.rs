@@ -1,6 +1,7 @@
 use std::sync::{atomic::AtomicBool, Arc};
 
-use tracing::{metadata::LevelFilter, Level};
+pub use tracing::metadata::LevelFilter;
+use tracing::{Dispatch, Level};
 use tracing_chrome::{ChromeLayerBuilder, FlushGuard};
 
 static IS_INITIALIZE: AtomicBool = AtomicBool::new(false);
@@ -10,15 +11,17 @@ pub fn enable_tracing_on_demand() -> Option<FlushGuard> {
   if !IS_INITIALIZE.swap(true, std::sync::atomic::Ordering::SeqCst) {
     if std::env::var("TRACING").is_ok() {
       let (chrome_layer, guard) = ChromeLayerBuilder::new().build();
-      tracing_subscriber::registry()
+      let _ = tracing_subscriber::registry()
         .with(chrome_layer)
         .with(
           tracing_subscriber::filter::Targets::new().with_targets(vec![("rolldown", Level::TRACE)]),
         )
-        .init();
+        .try_init();
       Some(guard)
     } else {
-      tracing_subscriber::registry()
+      // Someone else may have already installed a global subscriber. We just ignore it
+      // instead of panicking.
+      let _ = tracing_subscriber::registry()
         .with(fmt::layer())
         .with(
           tracing_subscriber::filter::Targets::new().with_targets(vec![("rolldown", Level::TRACE)]),
@@ -28,7 +31,7 @@ pub fn enable_tracing_on_demand() -> Option<FlushGuard> {
             .with_default_directive(LevelFilter::WARN.into())
             .from_env_lossy(),
         )
-        .init();
+        .try_init();
       None
     }
   } else {
@@ -36,6 +39,22 @@ pub fn enable_tracing_on_demand() -> Option<FlushGuard> {
   }
 }
 
+/// A subscriber printing events at `log_level`, unless `RUST_LOG` is set. It isn't installed
+/// globally, but entered around builds of one bundler, so bundlers in a process could use
+/// different levels.
+pub fn scoped_dispatch(log_level: LevelFilter) -> Dispatch {
+  use tracing_subscriber::{fmt, prelude::*, EnvFilter};
+  tracing_subscriber::registry()
+    .with(fmt::layer())
+    .with(tracing_subscriber::filter::Targets::new().with_targets(vec![("rolldown", Level::TRACE)]))
+    .with(
+      EnvFilter::builder()
+        .with_default_directive(log_level.into())
+        .from_env_lossy(),
+    )
+    .into()
+}
+
 #[derive(Debug, Default, Clone)]
 pub struct ContextedTracer {
   context: Vec<Arc<String>>,
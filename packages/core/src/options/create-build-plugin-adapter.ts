@@ -1,6 +1,5 @@
 import type { Plugin } from '../rollup-types'
 import type { BuildPluginOption } from '@rolldown/node-binding'
-import { unimplemented } from '../utils'
 
 export function createBuildPluginAdapter(plugin: Plugin): BuildPluginOption {
   // TODO: Need to investigate how to pass context to plugin.
@@ -33,7 +32,7 @@ export function createBuildPluginAdapter(plugin: Plugin): BuildPluginOption {
     },
     resolveId: !plugin.resolveId
       ? undefined
-      : async (specifier, importer) => {
+      : async (specifier, importer, kind) => {
           const resolveId = plugin.resolveId
           if (resolveId == null) {
             return null
@@ -49,9 +48,7 @@ export function createBuildPluginAdapter(plugin: Plugin): BuildPluginOption {
 
           const ret = await handler.call(context, specifier, importer, {
             assertions: {},
-            get isEntry() {
-              return unimplemented()
-            },
+            isEntry: kind === 'entry',
           })
           if (typeof ret === 'string') {
             return {
@@ -47,11 +47,6 @@ export interface InputOptions extends RollupInputOptions {
    * deprecated by Rollup
    */
   maxParallelFileReads?: never
-  /**
-   * @deprecated
-   * TODO: Need to investigate.
-   */
-  onwarn?: never
   /**
    * @deprecated
    * TODO: Need to investigate.
@@ -148,6 +143,18 @@ export function normalizeExternal(
   }
 }
 
+function normalizeOnwarn(
+  option: InputOptions['onwarn'],
+): BindingInputOptions['onWarn'] {
+  if (option == null) {
+    return undefined
+  }
+  return (warning) =>
+    option(warning, (warning) =>
+      console.warn(typeof warning === 'string' ? warning : warning.message),
+    )
+}
+
 async function normalizePlugins(
   option: InputOptions['plugins'],
 ): Promise<BindingInputOptions['plugins']> {
@@ -164,6 +171,7 @@ export async function normalizeInputOptions(
     treeshake,
     external,
     plugins,
+    onwarn,
     cwd,
     preserveSymlinks,
     shimMissingExports,
@@ -180,6 +188,7 @@ export async function normalizeInputOptions(
     treeshake: treeshake,
     external: normalizeExternal(external),
     plugins: await normalizePlugins(plugins),
+    onWarn: normalizeOnwarn(onwarn),
     cwd: cwd ?? process.cwd(),
     shimMissingExports: shimMissingExports ?? false,
     builtins: {
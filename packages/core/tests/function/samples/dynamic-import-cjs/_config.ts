@@ -0,0 +1,10 @@
+import { defineTestConfig } from '@/utils'
+import * as t from 'vitest'
+
+export default defineTestConfig({
+  async exports(exports) {
+    const mod = await exports.load()
+    t.expect(mod.default.self).toBe(mod.default)
+    t.expect(mod.default.bar).toEqual({ value: 1 })
+  },
+})
@@ -0,0 +1,4 @@
+const bar = require('./bar')
+
+module.exports = { bar }
+module.exports.self = module.exports
@@ -0,0 +1,3 @@
+export function load() {
+  return import('./foo')
+}